@@ -0,0 +1,43 @@
+//! the time of the max draw down search of a 30 years daily series, run it by `cargo bench --bench max_draw_down`.
+use std::time::Instant;
+
+use mpt_lib::{enums, MPTCalculator};
+
+fn main() {
+    let pattern = [0.3, -0.2, 0.0, 0.1];
+    let data: Vec<f64> = (0..7560)
+        .map(|i| {
+            if i >= 3000 && i < 3250 {
+                -0.1
+            } else {
+                pattern[i % 4] + (i % 7) as f64 * 0.01
+            }
+        })
+        .collect();
+    let dates: Vec<i32> = (0..7560).map(|i| 36526 + i).collect();
+    let mpt = MPTCalculator::from_v(&data);
+
+    let iterations = 1000;
+    let mut max_draw_down = f64::NAN;
+    let (mut peek_date, mut valley_date, mut month, mut recovery_month, mut recovery_date) =
+        (0, 0, 0, 0, 0);
+    let begin = Instant::now();
+    for _ in 0..iterations {
+        mpt.max_draw_down(
+            &dates,
+            enums::ClFrequency::ClFrequencyDaily,
+            &mut max_draw_down,
+            &mut peek_date,
+            &mut valley_date,
+            &mut month,
+            &mut recovery_month,
+            &mut recovery_date,
+        );
+    }
+    println!(
+        "max_draw_down of {} returns: {:.1}us per search, {}",
+        data.len(),
+        begin.elapsed().as_secs_f64() * 1e6 / iterations as f64,
+        max_draw_down
+    );
+}
@@ -1,10 +1,12 @@
 use crate::{
     common::{
-        annualize_return, get_annual_multiplier, is_sorted_array, is_valid_frequency, DataGroup,
+        annualize_return, check_sample_size, get_annual_multiplier,
+        get_annual_multiplier_by_calendar, get_annual_multiplier_by_dates, is_sorted_array,
+        is_valid_frequency, variance_divisor, DataGroup,
     },
     date_util,
     enums::{self, Errors},
-    MPTCalculator,
+    kernels, MPTCalculator,
 };
 use std::ops::ControlFlow;
 
@@ -21,13 +23,7 @@ impl<'a> MPTCalculator<'a> {
     ///assert_eq!(err == Errors::ClErrorCodeNoError && res==20.0,true)
     ///```
     pub fn average(&self, avg: &mut f64) -> Errors {
-        *avg = self
-            .values
-            .iter()
-            .filter(|x| (**x).is_finite())
-            .sum::<f64>()
-            / self.values.iter().filter(|x| (**x).is_finite()).count() as f64;
-        return Errors::ClErrorCodeNoError;
+        return kernels::mean(self.values, avg);
     }
 
     ///calculate the standard deviation value of an array，if the array has NAN/INF values,the result will be NAN
@@ -61,6 +57,11 @@ impl<'a> MPTCalculator<'a> {
         is_annu: bool,
         standard_deviation_result: &mut f64,
     ) -> Errors {
+        *standard_deviation_result = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
         return Self::standard_deviation_internal(
             self.values,
             freq,
@@ -68,6 +69,64 @@ impl<'a> MPTCalculator<'a> {
             standard_deviation_result,
         );
     }
+
+    ///calculate the standard deviation value of an array by the sample or population variance，if the array has NAN/INF values,
+    ///the result will be NAN
+    ///
+    ///# Arguments
+    ///freq: the frequence of source data.
+    ///
+    ///is_annu: the flag of annualize.
+    ///
+    ///mode: the sample variance divides by n - 1 and the population variance divides by n.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.standard_deviation_by_mode(
+    ///    enums::ClFrequency::ClFrequencyMonthly,
+    ///    true,
+    ///    enums::ClVarianceMode::ClVariancePopulation,
+    ///    &mut res,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 15.769477),
+    ///    true
+    ///);
+    ///```
+    pub fn standard_deviation_by_mode(
+        &self,
+        freq: enums::ClFrequency,
+        is_annu: bool,
+        mode: enums::ClVarianceMode,
+        standard_deviation_result: &mut f64,
+    ) -> Errors {
+        *standard_deviation_result = f64::NAN;
+        let min_count = match mode {
+            enums::ClVarianceMode::ClVarianceSample => 2,
+            enums::ClVarianceMode::ClVariancePopulation => 1,
+        };
+        let ret = check_sample_size(self.values.len(), min_count);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        return Self::standard_deviation_by_mode_internal(
+            self.values,
+            freq,
+            is_annu,
+            mode,
+            standard_deviation_result,
+        );
+    }
     ///calculate the harmonic mean value of an array, if the array has NAN/INF values,the result will be NAN
     ///# Examples
     ///```
@@ -434,6 +493,177 @@ impl<'a> MPTCalculator<'a> {
         return Errors::ClErrorCodeNoError;
     }
 
+    ///calculate the annualized return of an irregular frequency array, the annual multiplier is
+    ///estimated by the actual/actual day count between the first and the last date instead of the frequency.
+    ///if the array has NAN/INF values,the result will be NAN
+    ///# Arguments
+    ///dates: the date of value, should be sorted.
+    ///
+    ///is_geometric: the flag of geometric annualize.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![
+    ///   1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016, 1.40278, 1.51232,
+    ///   -1.95588, 1.1185,
+    ///];
+    ///let dates = vec![
+    ///   38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082, 39113, 39141, 39172,
+    ///];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.annualized_return_by_dates(&dates, true, &mut res);
+    ///assert_eq!(
+    ///   err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 11.814662),
+    ///   true
+    ///);
+    ///```
+    pub fn annualized_return_by_dates(
+        &self,
+        dates: &[i32],
+        is_geometric: bool,
+        annu_return: &mut f64,
+    ) -> Errors {
+        if self.values.len() < 2 || dates.len() != self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *annu_return = f64::NAN;
+        if !is_sorted_array(dates) {
+            return Errors::ClErrorCodeUnsortedByDate;
+        }
+        let multiplier = get_annual_multiplier_by_dates(dates);
+        if !multiplier.is_finite() {
+            return Errors::ClErrorCodeInvalidDate;
+        }
+
+        let periods = self.values.len() as f64;
+        if is_geometric {
+            let mut total_return = f64::NAN;
+            Self::total_return_accumulat(self.values, &mut total_return);
+            if total_return.is_finite() {
+                *annu_return =
+                    ((total_return / 100.0 + 1.0).powf(multiplier / periods) - 1.0) * 100.0;
+            }
+        } else {
+            let mut mean = f64::NAN;
+            self.mean_arithmetic(&mut mean);
+            if mean.is_finite() {
+                *annu_return = mean * multiplier;
+            }
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the annualized standard deviation of an irregular frequency array, the annual multiplier is
+    ///estimated by the actual/actual day count between the first and the last date instead of the frequency.
+    ///if the array has NAN/INF values,the result will be NAN
+    ///# Arguments
+    ///dates: the date of value, should be sorted.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![
+    ///   1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016, 1.40278, 1.51232,
+    ///   -1.95588, 1.1185,
+    ///];
+    ///let dates = vec![
+    ///   38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082, 39113, 39141, 39172,
+    ///];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.standard_deviation_by_dates(&dates, &mut res);
+    ///assert_eq!(
+    ///   err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 6.219448),
+    ///   true
+    ///);
+    ///```
+    pub fn standard_deviation_by_dates(
+        &self,
+        dates: &[i32],
+        standard_deviation: &mut f64,
+    ) -> Errors {
+        if self.values.len() < 2 || dates.len() != self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *standard_deviation = f64::NAN;
+        if !is_sorted_array(dates) {
+            return Errors::ClErrorCodeUnsortedByDate;
+        }
+        let multiplier = get_annual_multiplier_by_dates(dates);
+        if !multiplier.is_finite() {
+            return Errors::ClErrorCodeInvalidDate;
+        }
+        let ret = Self::standard_deviation_internal(
+            self.values,
+            enums::ClFrequency::ClFrequencyUnknown,
+            false,
+            standard_deviation,
+        );
+        if ret == Errors::ClErrorCodeNoError && standard_deviation.is_finite() {
+            *standard_deviation *= multiplier.sqrt();
+        }
+        return ret;
+    }
+
+    ///calculate the annualized standard deviation of the daily values by the holiday calendar, the annual multiplier
+    ///is the average business days number of the years from the first to the last date instead of 252.
+    ///if the array has NAN/INF values,the result will be NAN
+    ///# Arguments
+    ///dates: the date of value, should be sorted.
+    ///calendar: the holidays, the weekends are always excluded.
+    ///# Examples
+    ///```
+    ///use mpt_lib::{HolidayList, MPTCalculator};
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![0.5, -0.3, 0.2, 0.8, -0.6];
+    ///let dates = vec![44564, 44565, 44566, 44567, 44568];
+    ///let holidays = HolidayList::from(&[44921, 44925]);
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.standard_deviation_by_calendar(&dates, Some(&holidays), &mut res);
+    ///assert_eq!(
+    ///   err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 9.185097),
+    ///   true
+    ///);
+    ///```
+    pub fn standard_deviation_by_calendar(
+        &self,
+        dates: &[i32],
+        calendar: Option<&dyn date_util::HolidayCalendar>,
+        standard_deviation: &mut f64,
+    ) -> Errors {
+        if self.values.len() < 2 || dates.len() != self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *standard_deviation = f64::NAN;
+        if !is_sorted_array(dates) {
+            return Errors::ClErrorCodeUnsortedByDate;
+        }
+        let multiplier = get_annual_multiplier_by_calendar(
+            enums::ClFrequency::ClFrequencyDaily,
+            dates,
+            calendar,
+        );
+        if !multiplier.is_finite() {
+            return Errors::ClErrorCodeInvalidDate;
+        }
+        let ret = Self::standard_deviation_internal(
+            self.values,
+            enums::ClFrequency::ClFrequencyUnknown,
+            false,
+            standard_deviation,
+        );
+        if ret == Errors::ClErrorCodeNoError && standard_deviation.is_finite() {
+            *standard_deviation *= multiplier.sqrt();
+        }
+        return ret;
+    }
+
     fn loss_gain_standard_deviation(
         &self,
         freq: enums::ClFrequency,
@@ -445,6 +675,10 @@ impl<'a> MPTCalculator<'a> {
             return Errors::ClErrorCodeInvalidPara;
         }
         *loss_standard_deviation = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
         let mut filter_values = Vec::with_capacity(self.values.len());
 
         if self
@@ -571,27 +805,170 @@ impl<'a> MPTCalculator<'a> {
         freq: enums::ClFrequency,
         is_annu: bool,
         dev_res: &mut f64,
+    ) -> Errors {
+        return self.semi_standard_deviation_by_mode(
+            freq,
+            is_annu,
+            enums::ClVarianceMode::ClVarianceSample,
+            dev_res,
+        );
+    }
+
+    ///calculate the semi standard deviation value of an array by the sample or population variance, if the array has NAN/INF values,
+    ///the result will be NAN
+    ///
+    ///# Arguments
+    ///freq: the frequence of source data.
+    ///
+    ///is_annu: the flag of annuize.
+    ///
+    ///mode: the sample variance divides by n - 1 and the population variance divides by n.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.semi_standard_deviation_by_mode(
+    ///    enums::ClFrequency::ClFrequencyMonthly,
+    ///    true,
+    ///    enums::ClVarianceMode::ClVariancePopulation,
+    ///    &mut res,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 13.039018),
+    ///    true
+    ///);
+    ///```
+    pub fn semi_standard_deviation_by_mode(
+        &self,
+        freq: enums::ClFrequency,
+        is_annu: bool,
+        mode: enums::ClVarianceMode,
+        dev_res: &mut f64,
+    ) -> Errors {
+        return self.semi_standard_deviation_internal(
+            freq,
+            is_annu,
+            enums::ClSemiDeviationTarget::ClSemiDeviationMean,
+            mode,
+            dev_res,
+        );
+    }
+
+    ///calculate the semi standard deviation value of an array below the selected target, if the array has NAN/INF values,
+    ///the result will be NAN
+    ///
+    ///# Arguments
+    ///freq: the frequence of source data.
+    ///
+    ///is_annu: the flag of annuize.
+    ///
+    ///target: the mean of the values, zero, a fixed value or the risk free return of the same period.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.semi_standard_deviation_by_target(
+    ///    enums::ClFrequency::ClFrequencyMonthly,
+    ///    true,
+    ///    enums::ClSemiDeviationTarget::ClSemiDeviationZero,
+    ///    &mut res,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 14.63827),
+    ///    true
+    ///);
+    ///```
+    pub fn semi_standard_deviation_by_target(
+        &self,
+        freq: enums::ClFrequency,
+        is_annu: bool,
+        target: enums::ClSemiDeviationTarget,
+        dev_res: &mut f64,
+    ) -> Errors {
+        return self.semi_standard_deviation_internal(
+            freq,
+            is_annu,
+            target,
+            enums::ClVarianceMode::ClVarianceSample,
+            dev_res,
+        );
+    }
+
+    fn semi_standard_deviation_internal(
+        &self,
+        freq: enums::ClFrequency,
+        is_annu: bool,
+        target: enums::ClSemiDeviationTarget,
+        mode: enums::ClVarianceMode,
+        dev_res: &mut f64,
     ) -> Errors {
         if self.values.len() == 0 || is_annu && !is_valid_frequency(freq) {
             return Errors::ClErrorCodeInvalidPara;
         }
+        match target {
+            enums::ClSemiDeviationTarget::ClSemiDeviationValue(v) if !v.is_finite() => {
+                return Errors::ClErrorCodeInvalidPara;
+            }
+            enums::ClSemiDeviationTarget::ClSemiDeviationRiskFree
+                if self.riskfree.len() != self.values.len() =>
+            {
+                return Errors::ClErrorCodeInvalidPara;
+            }
+            _ => {}
+        }
         *dev_res = f64::NAN;
+        let ret = check_sample_size(
+            self.values.len(),
+            match mode {
+                enums::ClVarianceMode::ClVarianceSample => 2,
+                enums::ClVarianceMode::ClVariancePopulation => 1,
+            },
+        );
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
         let mut mean_res = f64::NAN;
         let ret = self.mean_arithmetic(&mut mean_res);
         if ret != Errors::ClErrorCodeNoError {
             return Errors::ClErrorCodeNoError;
         }
+        let threshold = |i: usize| match target {
+            enums::ClSemiDeviationTarget::ClSemiDeviationMean => mean_res,
+            enums::ClSemiDeviationTarget::ClSemiDeviationZero => 0.0,
+            enums::ClSemiDeviationTarget::ClSemiDeviationValue(v) => v,
+            enums::ClSemiDeviationTarget::ClSemiDeviationRiskFree => self.riskfree[i],
+        };
         let mut sum_return = 0.0;
 
         if self
             .values
             .iter()
+            .enumerate()
             .try_for_each(|x| {
-                if !x.is_finite() {
+                let t = threshold(x.0);
+                if !x.1.is_finite() || !t.is_finite() {
                     return ControlFlow::Break(());
                 }
-                if *x < mean_res {
-                    sum_return += (*x - mean_res) * (*x - mean_res);
+                if *x.1 < t {
+                    sum_return += (*x.1 - t) * (*x.1 - t);
                 }
                 ControlFlow::Continue(())
             })
@@ -600,7 +977,7 @@ impl<'a> MPTCalculator<'a> {
             return Errors::ClErrorCodeNoError;
         }
 
-        *dev_res = (sum_return / (self.values.len() - 1) as f64).sqrt();
+        *dev_res = (sum_return / variance_divisor(self.values.len(), mode)).sqrt();
         if is_annu {
             *dev_res *= (get_annual_multiplier(freq, false)).sqrt();
         }
@@ -641,26 +1018,78 @@ impl<'a> MPTCalculator<'a> {
     ///);
     ///```
     pub fn weighted_standard_deviation(&self, weights: &[f64], dev_res: &mut f64) -> Errors {
-        if self.values.len() == 0 || weights.len() == 0 || self.values.len() != weights.len() {
-            return Errors::ClErrorCodeInvalidPara;
-        }
-        *dev_res = f64::NAN;
-
-        let sum_weight: f64 = weights.iter().filter(|x| (**x).is_finite()).sum();
-
-        let mut mean_res = 0.0;
-        let res = self.weighted_mean_arithmetic(weights, &mut mean_res);
-        if res != Errors::ClErrorCodeNoError || !mean_res.is_finite() {
-            return Errors::ClErrorCodeNoError;
-        }
-
-        let excess_sum = self.values.iter().enumerate().fold(0.0, |acc, v| {
-            acc + weights[v.0] * (v.1 - mean_res) * (v.1 - mean_res)
-        });
+        return self.weighted_standard_deviation_by_mode(
+            weights,
+            enums::ClVarianceMode::ClVariancePopulation,
+            dev_res,
+        );
+    }
 
-        if sum_weight != 0.0 {
-            *dev_res = (excess_sum / sum_weight).sqrt();
-        }
+    ///calculate the weighted standard deviation value of an array by the sample or population variance，if the array or weights
+    ///has NAN/INF values,the result will be NAN
+    ///# Arguments
+    ///weights: the weights for values
+    ///
+    ///mode: the population variance divides by the sum of weights V1, and the sample variance divides by V1 - V2 / V1
+    ///as the reliability weights, V2 is the sum of the squared weights.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![1.0, 2.0, 3.0, 4.0];
+    ///let weighting = vec![1.0, 2.0, 3.0, 4.0];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.weighted_standard_deviation_by_mode(
+    ///    &weighting,
+    ///    enums::ClVarianceMode::ClVarianceSample,
+    ///    &mut res,
+    ///);
+    ///assert_eq!(
+    ///   err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 1.195229),
+    ///   true
+    ///);
+    ///```
+    pub fn weighted_standard_deviation_by_mode(
+        &self,
+        weights: &[f64],
+        mode: enums::ClVarianceMode,
+        dev_res: &mut f64,
+    ) -> Errors {
+        if self.values.len() == 0 || weights.len() == 0 || self.values.len() != weights.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *dev_res = f64::NAN;
+
+        let sum_weight: f64 = weights.iter().filter(|x| (**x).is_finite()).sum();
+
+        let mut mean_res = 0.0;
+        let res = self.weighted_mean_arithmetic(weights, &mut mean_res);
+        if res != Errors::ClErrorCodeNoError || !mean_res.is_finite() {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let excess_sum = self.values.iter().enumerate().fold(0.0, |acc, v| {
+            acc + weights[v.0] * (v.1 - mean_res) * (v.1 - mean_res)
+        });
+
+        let divisor = match mode {
+            enums::ClVarianceMode::ClVarianceSample => {
+                sum_weight
+                    - weights
+                        .iter()
+                        .filter(|x| (**x).is_finite())
+                        .map(|x| x * x)
+                        .sum::<f64>()
+                        / sum_weight
+            }
+            enums::ClVarianceMode::ClVariancePopulation => sum_weight,
+        };
+        if divisor != 0.0 {
+            *dev_res = (excess_sum / divisor).sqrt();
+        }
         return Errors::ClErrorCodeNoError;
     }
     ///calculate the skewness value of an array, if the array has NAN/INF values,the result will be NAN
@@ -690,10 +1119,11 @@ impl<'a> MPTCalculator<'a> {
     ///);
     ///```
     pub fn skewness(&self, skewness: &mut f64) -> Errors {
-        if self.values.len() == 0 {
-            return Errors::ClErrorCodeInvalidPara;
-        }
         *skewness = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 3);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
 
         let mut mean_res = 0.0;
         let res = self.average(&mut mean_res);
@@ -768,10 +1198,11 @@ impl<'a> MPTCalculator<'a> {
     ///);
     ///```
     pub fn kurtosis(&self, kurtosis: &mut f64) -> Errors {
-        if self.values.len() == 0 {
-            return Errors::ClErrorCodeInvalidPara;
-        }
         *kurtosis = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 4);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
 
         let mut mean_res = 0.0;
         let res = self.average(&mut mean_res);
@@ -864,6 +1295,10 @@ impl<'a> MPTCalculator<'a> {
         sharpe_ratio_result: &mut f64,
     ) -> Errors {
         *sharpe_ratio_result = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
 
         let mut avg_excess_return = f64::NAN;
         self.calc_avg_excess_return(&mut avg_excess_return);
@@ -983,6 +1418,10 @@ impl<'a> MPTCalculator<'a> {
             return Errors::ClErrorCodeInvalidPara;
         }
         *sharpe_ratio_arithmetic = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
 
         if is_annu {
             let mut annu_total_return = f64::NAN;
@@ -1109,6 +1548,10 @@ impl<'a> MPTCalculator<'a> {
             return Errors::ClErrorCodeInvalidPara;
         }
         *sharpe_ratio_result = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
 
         let mut total_return = f64::NAN;
         let mut rf_total_return = f64::NAN;
@@ -1190,6 +1633,10 @@ impl<'a> MPTCalculator<'a> {
         }
 
         *downside_deviation = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
         let mut sum_return = 0.0;
         let mut count = 0;
         if self
@@ -1300,6 +1747,10 @@ impl<'a> MPTCalculator<'a> {
         sortino_ratio_result: &mut f64,
     ) -> Errors {
         *sortino_ratio_result = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
 
         let mut avg_excess_return = f64::NAN;
         self.calc_avg_excess_return(&mut avg_excess_return);
@@ -1367,6 +1818,10 @@ impl<'a> MPTCalculator<'a> {
             return Errors::ClErrorCodeInvalidPara;
         }
         *sortino_ratio_res = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
 
         if is_annu {
             let mut annu_total_return = f64::NAN;
@@ -1450,6 +1905,10 @@ impl<'a> MPTCalculator<'a> {
             return Errors::ClErrorCodeInvalidPara;
         }
         *sortino_ratio_result = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
 
         let mut total_return = f64::NAN;
         let mut rf_total_return = f64::NAN;
@@ -1571,9 +2030,13 @@ impl<'a> MPTCalculator<'a> {
         {
             return Errors::ClErrorCodeInvalidPara;
         }
+        *omega_res = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
 
         let lpm = Self::calc_lpm(self.values, self.riskfree, 1.0);
-        *omega_res = f64::NAN;
 
         if !lpm.is_finite() || lpm == 0.0 {
             return Errors::ClErrorCodeNoError;
@@ -1653,9 +2116,13 @@ impl<'a> MPTCalculator<'a> {
         {
             return Errors::ClErrorCodeInvalidPara;
         }
+        *kappa3_res = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
 
         let lpm = Self::calc_lpm(self.values, self.riskfree, 3.0);
-        *kappa3_res = f64::NAN;
 
         if !lpm.is_finite() || MPTCalculator::is_eq_double(lpm, 0.0) {
             return Errors::ClErrorCodeNoError;
@@ -1745,7 +2212,130 @@ impl<'a> MPTCalculator<'a> {
 
         return Errors::ClErrorCodeNoError;
     }
-    ///calculate the coefficeient viaiantion value of an array, if the array has NAN/INF values,the result will be NAN
+
+    ///calculate the gain to pain ratio value of an array, it is the sum of the returns divided by the absolute sum of
+    ///the negative returns by schwager, the returns are not compounded unlike the gain loss ratio. it is NAN if there
+    ///is no negative return. if the array has NAN/INF values,the result will be NAN
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.gain_to_pain_ratio(&mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, -0.411233),
+    ///    true
+    ///);
+    ///```
+    pub fn gain_to_pain_ratio(&self, gain_to_pain_res: &mut f64) -> Errors {
+        if self.values.len() == 0 {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *gain_to_pain_res = f64::NAN;
+        if self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let sum: f64 = self.values.iter().sum();
+        let pain: f64 = self.values.iter().filter(|x| **x < 0.0).map(|x| -x).sum();
+        if pain > 0.0 {
+            *gain_to_pain_res = sum / pain;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the tail ratio value of an array, it is the 1 - q quantile of the returns divided by the absolute q
+    ///quantile of the returns, the quantiles are linear interpolated. it is larger than 1 if the right tail is fatter
+    ///than the left tail, and it is NAN if the q quantile is 0. if the array has NAN/INF values,the result will be NAN
+    ///
+    ///# Arguments
+    ///q: the probability of the tail, it is larger than 0 and less than 0.5, e.g. 0.05.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.tail_ratio(0.1, &mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 0.520596),
+    ///    true
+    ///);
+    ///```
+    pub fn tail_ratio(&self, q: f64, tail_ratio_res: &mut f64) -> Errors {
+        if self.values.len() == 0 || !(q > 0.0 && q < 0.5) {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *tail_ratio_res = f64::NAN;
+        if self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let mut sorted = self.values.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let left = kernels::sorted_quantile(&sorted, q).abs();
+        if left > 0.0 {
+            *tail_ratio_res = kernels::sorted_quantile(&sorted, 1.0 - q) / left;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the common sense ratio value of an array, it is the tail ratio multiplied by the gain to pain ratio.
+    ///if the array has NAN/INF values,the result will be NAN
+    ///
+    ///# Arguments
+    ///q: the probability of the tail of the tail ratio, it is larger than 0 and less than 0.5, e.g. 0.05.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.common_sense_ratio(0.1, &mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, -0.214086),
+    ///    true
+    ///);
+    ///```
+    pub fn common_sense_ratio(&self, q: f64, common_sense_res: &mut f64) -> Errors {
+        *common_sense_res = f64::NAN;
+        let mut tail_ratio = f64::NAN;
+        let ret = self.tail_ratio(q, &mut tail_ratio);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        let mut gain_to_pain = f64::NAN;
+        let ret = self.gain_to_pain_ratio(&mut gain_to_pain);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        *common_sense_res = tail_ratio * gain_to_pain;
+        return Errors::ClErrorCodeNoError;
+    }
+    ///calculate the coefficient variation value of an array, it is the standard deviation divided by the arithmetic mean,
+    ///if the array has NAN/INF values,the result will be NAN
     ///
     ///# Examples
     ///```
@@ -1759,18 +2349,73 @@ impl<'a> MPTCalculator<'a> {
     ///];
     ///let mut res = 0.0;
     ///let mpt = MPTCalculator::from_v(&data);
-    ///let err = mpt.coefficeient_viaiantion(&mut res);
+    ///let err = mpt.coefficient_variation(&mut res);
     ///assert_eq!(
     ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, -5.41921),
     ///    true
     ///);
     ///```
+    pub fn coefficient_variation(&self, coefficient_variation_res: &mut f64) -> Errors {
+        return self.coefficient_variation_internal(
+            enums::ClFrequency::ClFrequencyUnknown,
+            false,
+            coefficient_variation_res,
+        );
+    }
+
+    ///calculate the annualized coefficient variation value of an array, it is the annualized standard deviation divided by
+    ///the annualized arithmetic mean, if the array has NAN/INF values,the result will be NAN
+    ///
+    ///# Arguments
+    ///freq: the frequence of source data.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err =
+    ///    mpt.coefficient_variation_annualized(enums::ClFrequency::ClFrequencyMonthly, &mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, -1.56439),
+    ///    true
+    ///);
+    ///```
+    pub fn coefficient_variation_annualized(
+        &self,
+        freq: enums::ClFrequency,
+        coefficient_variation_res: &mut f64,
+    ) -> Errors {
+        if !is_valid_frequency(freq) {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        return self.coefficient_variation_internal(freq, true, coefficient_variation_res);
+    }
+
+    ///the deprecated misspelled name of coefficient_variation.
+    #[deprecated(note = "use coefficient_variation instead")]
     pub fn coefficeient_viaiantion(&self, coefficeient_viaiantion_res: &mut f64) -> Errors {
+        return self.coefficient_variation(coefficeient_viaiantion_res);
+    }
+
+    fn coefficient_variation_internal(
+        &self,
+        freq: enums::ClFrequency,
+        is_annu: bool,
+        coefficient_variation_res: &mut f64,
+    ) -> Errors {
         if self.values.len() == 0 {
             return Errors::ClErrorCodeInvalidPara;
         }
 
-        *coefficeient_viaiantion_res = f64::NAN;
+        *coefficient_variation_res = f64::NAN;
         let mut mean_res = 0.0;
         let mut res = self.mean_arithmetic(&mut mean_res);
         if res != Errors::ClErrorCodeNoError {
@@ -1778,22 +2423,25 @@ impl<'a> MPTCalculator<'a> {
         }
 
         let mut std_dev = f64::NAN;
-        res = self.standard_deviation(enums::ClFrequency::ClFrequencyMonthly, false, &mut std_dev);
+        res = self.standard_deviation(freq, is_annu, &mut std_dev);
         if res != Errors::ClErrorCodeNoError {
             return res;
         }
+        if is_annu {
+            mean_res *= get_annual_multiplier(freq, false);
+        }
 
         if !std_dev.is_finite()
             || !mean_res.is_finite()
             || MPTCalculator::is_eq_double(mean_res, 0.0)
         {
-            *coefficeient_viaiantion_res = f64::NAN;
+            *coefficient_variation_res = f64::NAN;
         } else {
-            *coefficeient_viaiantion_res = std_dev / mean_res;
+            *coefficient_variation_res = std_dev / mean_res;
         }
         return Errors::ClErrorCodeNoError;
     }
-    ///calculate the efficiency ratio arthmetic value of an array, if the array has NAN/INF values,the result will be NAN
+    ///calculate the efficiency ratio arithmetic value of an array, if the array has NAN/INF values,the result will be NAN
     ///
     ///# Arguments
     ///freq: the frequence of source data.
@@ -1813,13 +2461,13 @@ impl<'a> MPTCalculator<'a> {
     ///let mut res = 0.0;
     ///let mpt = MPTCalculator::from_v(&data);
     ///let err =
-    ///    mpt.efficiency_ratio_arthmetic(enums::ClFrequency::ClFrequencyMonthly, false, &mut res);
+    ///    mpt.efficiency_ratio_arithmetic(enums::ClFrequency::ClFrequencyMonthly, false, &mut res);
     ///assert_eq!(
     ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, -0.020986),
     ///    true
     ///);
     ///```
-    pub fn efficiency_ratio_arthmetic(
+    pub fn efficiency_ratio_arithmetic(
         &self,
         freq: enums::ClFrequency,
         is_annu: bool,
@@ -1837,7 +2485,7 @@ impl<'a> MPTCalculator<'a> {
         }
 
         let mut std_dev = f64::NAN;
-        res = self.standard_deviation(enums::ClFrequency::ClFrequencyMonthly, false, &mut std_dev);
+        res = self.standard_deviation(freq, false, &mut std_dev);
         if res != Errors::ClErrorCodeNoError {
             return res;
         }
@@ -1854,6 +2502,17 @@ impl<'a> MPTCalculator<'a> {
 
         return Errors::ClErrorCodeNoError;
     }
+
+    ///the deprecated misspelled name of efficiency_ratio_arithmetic.
+    #[deprecated(note = "use efficiency_ratio_arithmetic instead")]
+    pub fn efficiency_ratio_arthmetic(
+        &self,
+        freq: enums::ClFrequency,
+        is_annu: bool,
+        result: &mut f64,
+    ) -> Errors {
+        return self.efficiency_ratio_arithmetic(freq, is_annu, result);
+    }
     ///calculate the jarque_bera value of an array, if the array has NAN/INF values,the result will be NAN
     ///
     ///# Examples
@@ -1875,9 +2534,6 @@ impl<'a> MPTCalculator<'a> {
     ///);
     ///```
     pub fn jarque_bera(&self, jarque_bera: &mut f64) -> Errors {
-        if self.values.len() == 0 {
-            return Errors::ClErrorCodeInvalidPara;
-        }
         *jarque_bera = f64::NAN;
         let mut skewness = f64::NAN;
         let mut kurtosis = f64::NAN;
@@ -2141,104 +2797,162 @@ impl<'a> MPTCalculator<'a> {
         return Errors::ClErrorCodeNoError;
     }
 
-    fn get_max_draw_down(values: &[f64], start: usize, end: usize, dg: &mut DataGroup) -> Errors {
-        if values.len() == 0 || end >= values.len() {
-            return Errors::ClErrorCodeInvalidPara;
-        }
-        let mut start = start;
-        for i in start..end + 1 {
-            if values[i] != 0.0 {
-                start = if i > 0 { i - 1 } else { i };
-                break;
-            }
-        }
-
-        let mut total_max_index = start;
-        let mut total_min_index = start;
+    ///skip the leading zero values of the accumulated series, the search starts from the value before the first non
+    ///zero value.
+    fn skip_leading_zeros(v: &dyn Fn(usize) -> f64, start: usize, end: usize) -> usize {
         for i in start..end + 1 {
-            if values[i] > values[total_max_index] {
-                total_max_index = i;
+            if v(i) != 0.0 {
+                return if i > 0 { i - 1 } else { i };
             }
-            if values[i] < values[total_min_index] {
-                total_min_index = i;
-            }
-        }
-
-        if total_max_index < total_min_index {
-            dg.start = total_max_index;
-            dg.end = total_min_index;
-            dg.data = values[dg.start] - values[dg.end];
-            return Errors::ClErrorCodeInvalidPara;
         }
+        return start;
+    }
 
-        if total_max_index == total_min_index {
-            dg.start = 0;
-            dg.end = 0;
-            dg.data = 0.0;
+    ///search the max draw down segment of the accumulated series between start and end, the sign 1.0 searches the max
+    ///decrease and the sign -1.0 searches the max increase, it is shared by the max draw down and max gain.
+    ///it walks the ranges of the recursive search in a loop: in each range the segment ending at the min is kept before
+    ///the segment starting at the max, then the range between them is searched, and a segment of an inner range is kept
+    ///only when its decrease is larger. the min and max of a range are found through the next smaller index and the
+    ///previous not smaller index of each value, so each value is visited a few times. the search stops at the max
+    ///decrease of the one pass, and it is kept when the ranges stop getting smaller before it.
+    pub(crate) fn get_max_segment(
+        values: &[f64],
+        start: usize,
+        end: usize,
+        sign: f64,
+        dg: &mut DataGroup,
+    ) -> Errors {
+        if values.len() == 0 || end >= values.len() {
             return Errors::ClErrorCodeInvalidPara;
         }
-
-        let mut maxindex_before_min = start;
-        for i in start..total_min_index {
-            if values[i] > values[maxindex_before_min] {
-                maxindex_before_min = i;
+        let v = |i: usize| sign * values[i];
+        let none = values.len();
+        let mut next_smaller = vec![none; end + 1];
+        let mut prev_not_smaller = vec![none; end + 1];
+        let mut stack: Vec<usize> = Vec::new();
+        for i in 0..end + 1 {
+            while let Some(&j) = stack.last() {
+                if v(i) < v(j) {
+                    next_smaller[j] = i;
+                    stack.pop();
+                } else {
+                    break;
+                }
             }
+            stack.push(i);
         }
-        let down_value_befor_min = values[maxindex_before_min] - values[total_min_index];
-
-        let mut minindex_after_max = total_max_index;
-        for i in total_max_index..end + 1 {
-            if values[i] < values[minindex_after_max] {
-                minindex_after_max = i;
+        stack.clear();
+        for i in 0..end + 1 {
+            while let Some(&j) = stack.last() {
+                if v(j) < v(i) {
+                    stack.pop();
+                } else {
+                    break;
+                }
+            }
+            if let Some(&j) = stack.last() {
+                prev_not_smaller[i] = j;
+            }
+            stack.push(i);
+        }
+
+        //the max decrease in one pass, the peak is the first position of the running max.
+        let mut max = DataGroup::new();
+        let mut peak_index = Self::skip_leading_zeros(&v, start, end);
+        for i in peak_index..end + 1 {
+            if v(i) > v(peak_index) {
+                peak_index = i;
+            } else if v(peak_index) - v(i) > max.data {
+                max.start = peak_index;
+                max.end = i;
+                max.data = v(peak_index) - v(i);
             }
         }
-        let down_value_after_max = values[total_max_index] - values[minindex_after_max];
 
-        let mut first_inflexion_after_min = total_min_index;
-        for i in total_min_index..total_max_index {
-            if values[i + 1] > values[i] {
-                first_inflexion_after_min = i + 1;
-            } else {
-                break;
+        *dg = DataGroup::new();
+        let (mut range_start, mut range_end) = (start, end);
+        let mut is_outer = true;
+        loop {
+            let start = Self::skip_leading_zeros(&v, range_start, range_end);
+            let end = range_end;
+            //the first min follows the smaller values from the start, the first max follows the not smaller values from the end.
+            let mut total_min_index = start;
+            while next_smaller[total_min_index] <= end {
+                total_min_index = next_smaller[total_min_index];
+            }
+            let mut total_max_index = end;
+            while prev_not_smaller[total_max_index] != none
+                && prev_not_smaller[total_max_index] >= start
+            {
+                total_max_index = prev_not_smaller[total_max_index];
             }
-        }
 
-        let mut first_inflexion_before_max = total_max_index;
-        for i in (total_min_index..total_max_index).rev() {
-            if values[i - 1] < values[i] {
-                first_inflexion_before_max = i - 1;
-            } else {
+            let mut segment = DataGroup::new();
+            if total_max_index < total_min_index {
+                segment.start = total_max_index;
+                segment.end = total_min_index;
+                segment.data = v(total_max_index) - v(total_min_index);
+            } else if total_max_index > total_min_index {
+                let mut maxindex_before_min = start;
+                for i in start..total_min_index {
+                    if v(i) > v(maxindex_before_min) {
+                        maxindex_before_min = i;
+                    }
+                }
+                let mut minindex_after_max = total_max_index;
+                for i in total_max_index..end + 1 {
+                    if v(i) < v(minindex_after_max) {
+                        minindex_after_max = i;
+                    }
+                }
+                let down_value_befor_min = v(maxindex_before_min) - v(total_min_index);
+                let down_value_after_max = v(total_max_index) - v(minindex_after_max);
+                if down_value_befor_min < down_value_after_max {
+                    segment.start = total_max_index;
+                    segment.end = minindex_after_max;
+                    segment.data = down_value_after_max;
+                } else {
+                    segment.start = maxindex_before_min;
+                    segment.end = total_min_index;
+                    segment.data = down_value_befor_min;
+                }
+            }
+            if is_outer || segment.data > dg.data {
+                *dg = segment;
+            }
+            is_outer = false;
+            if total_max_index <= total_min_index || dg.data == max.data {
                 break;
             }
-        }
-
-        let mut max_down_extern = DataGroup::new();
-        if down_value_befor_min < down_value_after_max {
-            max_down_extern.start = total_max_index;
-            max_down_extern.end = minindex_after_max;
-            max_down_extern.data = down_value_after_max;
-        } else {
-            max_down_extern.start = maxindex_before_min;
-            max_down_extern.end = total_min_index;
-            max_down_extern.data = down_value_befor_min;
-        }
 
-        if first_inflexion_after_min > first_inflexion_before_max {
-            *dg = max_down_extern;
-            return Errors::ClErrorCodeNoError;
+            let mut first_inflexion_after_min = total_min_index;
+            for i in total_min_index..total_max_index {
+                if v(i + 1) > v(i) {
+                    first_inflexion_after_min = i + 1;
+                } else {
+                    break;
+                }
+            }
+            let mut first_inflexion_before_max = total_max_index;
+            for i in (total_min_index..total_max_index).rev() {
+                if i > 0 && v(i - 1) < v(i) {
+                    first_inflexion_before_max = i - 1;
+                } else {
+                    break;
+                }
+            }
+            if first_inflexion_after_min > first_inflexion_before_max
+                || (first_inflexion_after_min, first_inflexion_before_max)
+                    == (range_start, range_end)
+            {
+                break;
+            }
+            range_start = first_inflexion_after_min;
+            range_end = first_inflexion_before_max;
         }
-        let mut max_down_between = DataGroup::new();
-        Self::get_max_draw_down(
-            values,
-            first_inflexion_after_min,
-            first_inflexion_before_max,
-            &mut max_down_between,
-        );
-
-        if max_down_between.data > max_down_extern.data {
-            *dg = max_down_between;
-        } else {
-            *dg = max_down_extern;
+        //the range between them is not smaller, the recursive search never ended before the max decrease.
+        if dg.data < max.data {
+            *dg = max;
         }
         return Errors::ClErrorCodeNoError;
     }
@@ -2343,10 +3057,11 @@ impl<'a> MPTCalculator<'a> {
         }
 
         let mut max_draw_down_dg = DataGroup::new();
-        Self::get_max_draw_down(
+        Self::get_max_segment(
             &log_accum_series,
             0,
             log_accum_series.len() - 1,
+            1.0,
             &mut max_draw_down_dg,
         );
 
@@ -2373,107 +3088,6 @@ impl<'a> MPTCalculator<'a> {
         return Errors::ClErrorCodeNoError;
     }
 
-    fn get_max_gain(values: &[f64], start: usize, end: usize, dg: &mut DataGroup) -> Errors {
-        if values.len() == 0 || end >= values.len() {
-            return Errors::ClErrorCodeInvalidPara;
-        }
-        let mut start = start;
-        for i in start..end + 1 {
-            if values[i] != 0.0 {
-                start = if i > 0 { i - 1 } else { i };
-                break;
-            }
-        }
-
-        let mut total_max_index = start;
-        let mut total_min_index = start;
-        for i in start..end + 1 {
-            if values[i] > values[total_max_index] {
-                total_max_index = i;
-            }
-            if values[i] < values[total_min_index] {
-                total_min_index = i;
-            }
-        }
-        //the max is at right, min is at left, mean it is a increase series.
-        if total_max_index > total_min_index {
-            dg.start = total_min_index;
-            dg.end = total_max_index;
-            dg.data = values[total_max_index] - values[total_min_index];
-            return Errors::ClErrorCodeInvalidPara;
-        }
-
-        if total_max_index == total_min_index {
-            dg.start = 0;
-            dg.end = 0;
-            dg.data = 0.0;
-            return Errors::ClErrorCodeInvalidPara;
-        }
-        //the max is at left, min is at right, mean it is a decrease series.
-        let mut minindex_before_max = start;
-        for i in start..total_max_index {
-            if values[i] < values[minindex_before_max] {
-                minindex_before_max = i;
-            }
-        }
-        let gain_value_befor_max = values[total_max_index] - values[minindex_before_max];
-
-        let mut maxindex_after_min = total_min_index;
-        for i in total_min_index..end + 1 {
-            if values[i] > values[maxindex_after_min] {
-                maxindex_after_min = i;
-            }
-        }
-        let gain_value_after_min = values[maxindex_after_min] - values[total_min_index];
-
-        let mut first_inflexion_after_max = total_max_index;
-        for i in total_max_index..total_min_index {
-            if values[i + 1] < values[i] {
-                first_inflexion_after_max = i + 1;
-            } else {
-                break;
-            }
-        }
-
-        let mut first_inflexion_before_min = total_min_index;
-        for i in (total_max_index..total_min_index).rev() {
-            if values[i - 1] > values[i] {
-                first_inflexion_before_min = i - 1;
-            } else {
-                break;
-            }
-        }
-
-        let mut max_gain_extern = DataGroup::new();
-        if gain_value_befor_max < gain_value_after_min {
-            max_gain_extern.start = total_min_index;
-            max_gain_extern.end = maxindex_after_min;
-            max_gain_extern.data = gain_value_after_min;
-        } else {
-            max_gain_extern.start = minindex_before_max;
-            max_gain_extern.end = total_max_index;
-            max_gain_extern.data = gain_value_befor_max;
-        }
-
-        if first_inflexion_after_max > first_inflexion_before_min {
-            *dg = max_gain_extern;
-            return Errors::ClErrorCodeNoError;
-        }
-        let mut max_gain_between = DataGroup::new();
-        Self::get_max_gain(
-            values,
-            first_inflexion_after_max,
-            first_inflexion_before_min,
-            &mut max_gain_between,
-        );
-
-        if max_gain_between.data > max_gain_extern.data {
-            *dg = max_gain_between;
-        } else {
-            *dg = max_gain_extern;
-        }
-        return Errors::ClErrorCodeNoError;
-    }
     ///calculate the max gain value,start date,end date,max gain month of an array, if the array has NAN/INF values,the result will be NAN
     ///freq: the frequence of source data.
     ///
@@ -2566,10 +3180,11 @@ impl<'a> MPTCalculator<'a> {
         }
 
         let mut max_gain_dg = DataGroup::new();
-        Self::get_max_gain(
+        Self::get_max_segment(
             &log_accum_series,
             0,
             log_accum_series.len() - 1,
+            -1.0,
             &mut max_gain_dg,
         );
         *max_gain = (max_gain_dg.data.exp() - 1.0) * 100.0;
@@ -2764,6 +3379,134 @@ impl<'a> MPTCalculator<'a> {
         *avg_draw_down *= annu_mutiplier / self.values.len() as f64;
         return Errors::ClErrorCodeNoError;
     }
+    ///calculate the average draw down value of an array by the selected blocks, the input data should sort by date,
+    ///and should has not NA/INF,otherwrise the result will be NAN.
+    ///the annual blocks start from the first date as average_draw_down, the calendar year blocks are split at the year ends,
+    ///both are the sum of the max draw down of each block divided by the number of years. the rolling blocks are all windows of
+    ///the periods and the episode blocks are all draw down episodes of max_draw_down_table, both are the simple average.
+    ///
+    ///# Arguments
+    ///dates: the date of value
+    ///
+    ///freq: the frequence of source data.
+    ///
+    ///block: the blocks to calculate the max draw down.
+    ///
+    ///periods: the number of periods of the rolling blocks, it is ignored by the other blocks.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![
+    ///    2.1, -3.4, 1.2, -0.8, -2.5, 3.1, 1.7, -1.1, 0.6, -4.2, 2.8, 1.5, -0.9, 2.2, -1.6, -2.9,
+    ///    0.4, 3.3, -1.2, 1.9, -2.6, 0.7, 1.4, -0.5,
+    ///];
+    ///let dates = vec![
+    ///    44773, 44804, 44834, 44865, 44895, 44926, 44957, 44985, 45016, 45046, 45077, 45107,
+    ///    45138, 45169, 45199, 45230, 45260, 45291, 45322, 45351, 45382, 45412, 45443, 45473,
+    ///];
+    ///let mut result = f64::NAN;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.average_draw_down_by_block(
+    ///    &dates,
+    ///    enums::ClFrequency::ClFrequencyMonthly,
+    ///    enums::ClDrawDownBlock::ClDrawDownBlockCalendarYear,
+    ///    0,
+    ///    &mut result,
+    ///);
+    ///assert_eq!(
+    ///   err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(result, -6.366312),
+    ///   true
+    ///);
+    ///```
+    pub fn average_draw_down_by_block(
+        &self,
+        dates: &[i32],
+        freq: enums::ClFrequency,
+        block: enums::ClDrawDownBlock,
+        periods: usize,
+        avg_draw_down: &mut f64,
+    ) -> Errors {
+        if self.values.len() == 0
+            || !is_valid_frequency(freq)
+            || dates.len() != self.values.len()
+            || (block == enums::ClDrawDownBlock::ClDrawDownBlockRolling
+                && (periods == 0 || periods > self.values.len()))
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        if !is_sorted_array(dates) {
+            return Errors::ClErrorCodeUnsortedByDate;
+        }
+        *avg_draw_down = f64::NAN;
+        if self.values.iter().find(|x| !x.is_finite()) != None {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        //the max draw down of the values between start and end, it is 0 if there is no draw down.
+        let block_draw_down = |start: usize, end: usize| -> f64 {
+            let mut log_accum_series = vec![0.0; end - start + 2];
+            for i in start..end + 1 {
+                log_accum_series[i - start + 1] =
+                    (1.0 + self.values[i] / 100.0).ln() + log_accum_series[i - start];
+            }
+            let mut dg = DataGroup::new();
+            Self::get_max_segment(
+                &log_accum_series,
+                0,
+                log_accum_series.len() - 1,
+                1.0,
+                &mut dg,
+            );
+            ((-dg.data).exp() - 1.0) * 100.0
+        };
+
+        match block {
+            enums::ClDrawDownBlock::ClDrawDownBlockAnnual => {
+                return self.average_draw_down(dates, freq, avg_draw_down);
+            }
+            enums::ClDrawDownBlock::ClDrawDownBlockCalendarYear => {
+                let mut sum = 0.0;
+                let mut start_pos = 0;
+                for i in 1..self.values.len() + 1 {
+                    if i == self.values.len()
+                        || date_util::period_index(
+                            enums::ClFrequency::ClFrequencyAnnually,
+                            dates[i] as u64,
+                        ) != date_util::period_index(
+                            enums::ClFrequency::ClFrequencyAnnually,
+                            dates[start_pos] as u64,
+                        )
+                    {
+                        sum += block_draw_down(start_pos, i - 1);
+                        start_pos = i;
+                    }
+                }
+                *avg_draw_down =
+                    sum * get_annual_multiplier(freq, false) / self.values.len() as f64;
+            }
+            enums::ClDrawDownBlock::ClDrawDownBlockRolling => {
+                let count = self.values.len() - periods + 1;
+                *avg_draw_down = (0..count)
+                    .map(|i| block_draw_down(i, i + periods - 1))
+                    .sum::<f64>()
+                    / count as f64;
+            }
+            enums::ClDrawDownBlock::ClDrawDownBlockEpisode => {
+                let mut table = Vec::new();
+                let ret = self.max_draw_down_table(dates, freq, &mut table);
+                if ret != Errors::ClErrorCodeNoError {
+                    return ret;
+                }
+                *avg_draw_down = if table.len() == 0 {
+                    0.0
+                } else {
+                    table.iter().map(|x| x.value).sum::<f64>() / table.len() as f64
+                };
+            }
+        }
+        return Errors::ClErrorCodeNoError;
+    }
     ///calculate the sterling ratio value of an array, the input data should sort by date,and should has not NA/INF,otherwrise the result will be NAN
     ///
     ///# Arguments
@@ -2804,7 +3547,44 @@ impl<'a> MPTCalculator<'a> {
         freq: enums::ClFrequency,
         sterling_ration: &mut f64,
     ) -> Errors {
-        if self.values.len() == 0 || !is_valid_frequency(freq) {
+        return self.sterling_ratio_by_constant(dates, freq, 10.0, sterling_ration);
+    }
+    ///calculate the sterling ratio value of an array with the constant subtracted from the average draw down,
+    ///sterling_ratio uses 10. the input data should sort by date,and should has not NA/INF,otherwrise the result
+    ///will be NAN
+    ///
+    ///# Arguments
+    ///freq: the frequence of source data.
+    ///
+    ///dates: the date of value.
+    ///
+    ///constant: the constant of the average draw down in percent.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![1.5, -2.0, 3.0, -4.0, 2.5, 1.0];
+    ///let dates = vec![38291, 38321, 38352, 38383, 38411, 38442];
+    ///let (mut result, mut expected) = (f64::NAN, f64::NAN);
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///mpt.sterling_ratio(&dates, enums::ClFrequency::ClFrequencyMonthly, &mut expected);
+    ///let err = mpt.sterling_ratio_by_constant(
+    ///    &dates,
+    ///    enums::ClFrequency::ClFrequencyMonthly,
+    ///    10.0,
+    ///    &mut result,
+    ///);
+    ///assert_eq!(err == Errors::ClErrorCodeNoError && result == expected, true);
+    ///```
+    pub fn sterling_ratio_by_constant(
+        &self,
+        dates: &[i32],
+        freq: enums::ClFrequency,
+        constant: f64,
+        sterling_ration: &mut f64,
+    ) -> Errors {
+        if self.values.len() == 0 || !is_valid_frequency(freq) || !constant.is_finite() {
             return Errors::ClErrorCodeInvalidPara;
         }
         *sterling_ration = f64::NAN;
@@ -2812,7 +3592,7 @@ impl<'a> MPTCalculator<'a> {
 
         self.average_draw_down(dates, freq, &mut avg_draw_down);
 
-        if avg_draw_down - 10.0 != 0.0 {
+        if avg_draw_down - constant != 0.0 {
             let total_return = (self
                 .values
                 .iter()
@@ -2824,7 +3604,7 @@ impl<'a> MPTCalculator<'a> {
                 annualize_return(total_return, freq, self.values.len() as f64, true);
 
             if annu_total_return.is_finite() {
-                *sterling_ration = annu_total_return / (avg_draw_down - 10.0).abs();
+                *sterling_ration = annu_total_return / (avg_draw_down - constant).abs();
             }
         }
         return Errors::ClErrorCodeNoError;
@@ -2834,7 +3614,7 @@ impl<'a> MPTCalculator<'a> {
         &self,
         dates: &[i32],
         best_months_num: i32,
-        cmp_fn: fn(f64, f64) -> bool,
+        is_best: bool,
         best_rolling_month_date: &mut i32,
         best_rolling_month_value: &mut f64,
     ) -> Errors {
@@ -2846,40 +3626,20 @@ impl<'a> MPTCalculator<'a> {
             return Errors::ClErrorCodeInvalidOutput;
         }
 
-        *best_rolling_month_date = 0;
-        *best_rolling_month_value = f64::NAN;
-        if self
-            .values
-            .iter()
-            .enumerate()
-            .try_for_each(|x| {
-                if !x.1.is_finite() {
-                    return ControlFlow::Break(());
-                }
-                if x.0 >= (best_months_num - 1) as usize {
-                    let mut f = 1.0;
-                    let mut pos = 0;
-                    while pos < best_months_num {
-                        f *= 1.0 + self.values[x.0 - pos as usize] / 100.0;
-                        pos += 1;
-                    }
-                    f = (f - 1.0) * 100.0;
-
-                    if !(*best_rolling_month_value).is_finite()
-                        || cmp_fn(f, *best_rolling_month_value)
-                    {
-                        *best_rolling_month_date = dates[x.0];
-                        *best_rolling_month_value = f;
-                    }
-                }
-                ControlFlow::Continue(())
-            })
-            .is_break()
-        {
-            return Errors::ClErrorCodeNoError;
+        if is_best {
+            return self.best_rolling_period(
+                dates,
+                best_months_num as usize,
+                best_rolling_month_date,
+                best_rolling_month_value,
+            );
         }
-
-        return Errors::ClErrorCodeNoError;
+        return self.worst_rolling_period(
+            dates,
+            best_months_num as usize,
+            best_rolling_month_date,
+            best_rolling_month_value,
+        );
     }
     ///calculate the best rolling month value of an array, the input data should sort by date,and should has not NA/INF,the result will be NAN
     ///
@@ -2944,7 +3704,7 @@ impl<'a> MPTCalculator<'a> {
         return self.best_worth_rolling_month(
             dates,
             best_months_num,
-            |a, b| a > b,
+            true,
             best_rolling_month_date,
             best_rolling_month_value,
         );
@@ -3012,49 +3772,418 @@ impl<'a> MPTCalculator<'a> {
         return self.best_worth_rolling_month(
             dates,
             worst_months_num,
-            |a, b| a < b,
+            false,
             worst_rolling_month_date,
             worst_rolling_month_value,
         );
     }
 
-    fn get_last_up_down_streak(
-        values: &[f64],
-        start: usize,
-        end: usize,
-        cmp_fn: fn(f64, f64) -> bool,
-    ) -> DataGroup {
-        let mut final_streak = DataGroup {
-            start: start,
-            end: start,
-            data: 1.0,
-        };
-        for mut i in start..end {
-            if !values[i].is_finite() {
-                continue;
-            }
-
-            let mut streak = DataGroup {
-                start: i,
-                end: i,
-                data: 1.0,
-            };
-            while i < end {
-                if !values[i].is_finite() {
-                    i += 1;
-                    continue;
-                } else if cmp_fn(values[i], 0.0) {
-                    streak.data *= values[i] / 100.0 + 1.0;
-                    streak.end = i;
-                } else {
-                    break;
-                }
-                i += 1;
+    ///calculate the compound return of each rolling window of an array, the output is the end date of the window and
+    ///the return of the window, the windows which have NAN/INF values are skipped.
+    ///the input data should sort by date.
+    ///
+    ///# Arguments
+    ///dates: the date of value
+    ///
+    ///window: the number of periods of each rolling window, e.g. 12 for rolling 1 year on monthly data
+    ///
+    ///output: the end date and the compound return of each rolling window
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![
+    ///    -2.57909, 0.0353, 3.56387, -3.88416, 0.0, -9.81106, -7.70466, -0.04348, -9.65637,
+    ///    3.37025, 7.68514, -6.79066, -1.76334, -3.7317, -0.49068, 11.83432, 9.08289, 3.39531,
+    ///    0.70368, 0.89286, -0.76953, 6.39783, 1.38484, 2.33645, 2.80998, 0.5808, -0.61141,
+    ///    -0.20506, -0.47945, -0.13765, -3.4459, -0.85653, 1.83585, 0.84836, 3.61024, 3.99188,
+    ///    -1.7892, 2.02054, -0.81169, -1.40753, 3.02125, -0.67676, 1.07073, -2.21509, 0.29547,
+    ///    -2.65139, 2.62273, -0.65557, 0.76463, -1.22072, -0.0668, 2.20588, -0.91563, -0.76766,
+    ///    -1.21429, 3.43456, 4.99825, 3.89481, 1.59564, 0.86793, 2.41477, -1.80305, 0.6709,
+    ///    3.57769, 4.77481, -0.37317, -3.52713, 1.88831, 1.73502, 1.20155, -3.36542, -2.03551,
+    ///    -5.6145, -2.71663, -0.04815, 3.99807, 1.66744, -9.68658, -0.46681, 4.22095, -6.7,
+    ///    -15.27331, -8.46123, 0.76369,
+    ///];
+    ///let dates = vec![
+    ///    37287, 37315, 37346, 37376, 37407, 37437, 37468, 37499, 37529, 37560, 37590, 37621,
+    ///    37652, 37680, 37711, 37741, 37772, 37802, 37833, 37864, 37894, 37925, 37955, 37986,
+    ///    38017, 38046, 38077, 38107, 38138, 38168, 38199, 38230, 38260, 38291, 38321, 38352,
+    ///    38383, 38411, 38442, 38472, 38503, 38533, 38564, 38595, 38625, 38656, 38686, 38717,
+    ///    38748, 38776, 38807, 38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082,
+    ///    39113, 39141, 39172, 39202, 39233, 39263, 39294, 39325, 39355, 39386, 39416, 39447,
+    ///    39478, 39507, 39538, 39568, 39599, 39629, 39660, 39691, 39721, 39752, 39782, 39813,
+    ///];
+    ///let mut output = Vec::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.rolling_returns(&dates, 12, &mut output);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && output.len() == 73
+    ///        && output[0].0 == 37621
+    ///        && MPTCalculator::is_eq_double(output[0].1, -24.340896),
+    ///    true
+    ///);
+    ///```
+    pub fn rolling_returns(
+        &self,
+        dates: &[i32],
+        window: usize,
+        output: &mut Vec<(i32, f64)>,
+    ) -> Errors {
+        instrument!("rolling_returns", rows = self.values.len(), window = window);
+        if window == 0 || dates.len() != self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        if dates.len() > 1 && !is_sorted_array(dates) {
+            return Errors::ClErrorCodeUnsortedByDate;
+        }
+        output.clear();
+        let mut invalid_pos: Option<usize> = None;
+        for i in 0..self.values.len() {
+            if !self.values[i].is_finite() {
+                invalid_pos = Some(i);
             }
-            if streak.data != 1.0
-                && (streak.end - streak.start) >= (final_streak.end - final_streak.start)
-            {
-                final_streak = streak;
+            if i + 1 < window || invalid_pos.map_or(false, |x| x + window > i) {
+                continue;
+            }
+            let f = self.values[i + 1 - window..=i]
+                .iter()
+                .fold(1.0, |acc, x| acc * (1.0 + x / 100.0));
+            output.push((dates[i], (f - 1.0) * 100.0));
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the quantile of the rolling compound returns of an array, the window is the number of periods
+    ///of the data frequency, e.g. 63 for rolling quarter on daily data. the quantile is linear interpolated between
+    ///the sorted rolling returns, the windows which have NAN/INF values are skipped.
+    ///the input data should sort by date.
+    ///
+    ///# Arguments
+    ///dates: the date of value
+    ///
+    ///window: the number of periods of each rolling window
+    ///
+    ///q: the quantile between 0 and 1, 0 is the worst rolling return and 1 is the best rolling return
+    ///
+    ///quantile_date: the end date of the window whose return is the nearest to the quantile
+    ///
+    ///quantile_value: the quantile of the rolling returns
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![
+    ///    -2.57909, 0.0353, 3.56387, -3.88416, 0.0, -9.81106, -7.70466, -0.04348, -9.65637,
+    ///    3.37025, 7.68514, -6.79066, -1.76334, -3.7317, -0.49068, 11.83432, 9.08289, 3.39531,
+    ///    0.70368, 0.89286, -0.76953, 6.39783, 1.38484, 2.33645, 2.80998, 0.5808, -0.61141,
+    ///    -0.20506, -0.47945, -0.13765, -3.4459, -0.85653, 1.83585, 0.84836, 3.61024, 3.99188,
+    ///    -1.7892, 2.02054, -0.81169, -1.40753, 3.02125, -0.67676, 1.07073, -2.21509, 0.29547,
+    ///    -2.65139, 2.62273, -0.65557, 0.76463, -1.22072, -0.0668, 2.20588, -0.91563, -0.76766,
+    ///    -1.21429, 3.43456, 4.99825, 3.89481, 1.59564, 0.86793, 2.41477, -1.80305, 0.6709,
+    ///    3.57769, 4.77481, -0.37317, -3.52713, 1.88831, 1.73502, 1.20155, -3.36542, -2.03551,
+    ///    -5.6145, -2.71663, -0.04815, 3.99807, 1.66744, -9.68658, -0.46681, 4.22095, -6.7,
+    ///    -15.27331, -8.46123, 0.76369,
+    ///];
+    ///let dates = vec![
+    ///    37287, 37315, 37346, 37376, 37407, 37437, 37468, 37499, 37529, 37560, 37590, 37621,
+    ///    37652, 37680, 37711, 37741, 37772, 37802, 37833, 37864, 37894, 37925, 37955, 37986,
+    ///    38017, 38046, 38077, 38107, 38138, 38168, 38199, 38230, 38260, 38291, 38321, 38352,
+    ///    38383, 38411, 38442, 38472, 38503, 38533, 38564, 38595, 38625, 38656, 38686, 38717,
+    ///    38748, 38776, 38807, 38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082,
+    ///    39113, 39141, 39172, 39202, 39233, 39263, 39294, 39325, 39355, 39386, 39416, 39447,
+    ///    39478, 39507, 39538, 39568, 39599, 39629, 39660, 39691, 39721, 39752, 39782, 39813,
+    ///];
+    ///let mut quantile_date = 0;
+    ///let mut quantile_value = f64::NAN;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.rolling_period_quantile(&dates, 3, 0.05, &mut quantile_date, &mut quantile_value);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(quantile_value, -16.754485)
+    ///        && quantile_date == 37468,
+    ///    true
+    ///);
+    ///```
+    pub fn rolling_period_quantile(
+        &self,
+        dates: &[i32],
+        window: usize,
+        q: f64,
+        quantile_date: &mut i32,
+        quantile_value: &mut f64,
+    ) -> Errors {
+        instrument!(
+            "rolling_period_quantile",
+            rows = self.values.len(),
+            window = window
+        );
+        if self.values.len() == 0
+            || window == 0
+            || window > self.values.len()
+            || dates.len() != self.values.len()
+            || !(q >= 0.0 && q <= 1.0)
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *quantile_date = 0;
+        *quantile_value = f64::NAN;
+
+        let mut rolling = Vec::new();
+        let ret = self.rolling_returns(dates, window, &mut rolling);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        if rolling.len() == 0 {
+            return Errors::ClErrorCodeNoError;
+        }
+        let mut sorted: Vec<f64> = rolling.iter().map(|x| x.1).collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        *quantile_value = kernels::sorted_quantile(&sorted, q);
+
+        let mut distance = f64::INFINITY;
+        rolling.iter().for_each(|x| {
+            if (x.1 - *quantile_value).abs() < distance {
+                distance = (x.1 - *quantile_value).abs();
+                *quantile_date = x.0;
+            }
+        });
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the quantile of the rolling compound returns as rolling_period_quantile, the window is given by its
+    ///frequency, e.g. rolling quarter, and the number of the periods of the window is the number of the data
+    ///periods in one window period by the business day count, e.g. 63 for rolling quarter on daily data and 3 for
+    ///rolling quarter on monthly data.
+    ///
+    ///# Arguments
+    ///dates: the date of value
+    ///
+    ///freq: the frequence of source data.
+    ///
+    ///window_freq: the frequence of the rolling window, it should not be shorter than the frequence of the data
+    ///
+    ///q: the quantile between 0 and 1, 0 is the worst rolling return and 1 is the best rolling return
+    ///
+    ///quantile_date: the end date of the window whose return is the nearest to the quantile
+    ///
+    ///quantile_value: the quantile of the rolling returns
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![
+    ///    -2.57909, 0.0353, 3.56387, -3.88416, 0.0, -9.81106, -7.70466, -0.04348, -9.65637,
+    ///    3.37025, 7.68514, -6.79066, -1.76334, -3.7317, -0.49068, 11.83432, 9.08289, 3.39531,
+    ///    0.70368, 0.89286, -0.76953, 6.39783, 1.38484, 2.33645, 2.80998, 0.5808, -0.61141,
+    ///    -0.20506, -0.47945, -0.13765, -3.4459, -0.85653, 1.83585, 0.84836, 3.61024, 3.99188,
+    ///    -1.7892, 2.02054, -0.81169, -1.40753, 3.02125, -0.67676, 1.07073, -2.21509, 0.29547,
+    ///    -2.65139, 2.62273, -0.65557, 0.76463, -1.22072, -0.0668, 2.20588, -0.91563, -0.76766,
+    ///    -1.21429, 3.43456, 4.99825, 3.89481, 1.59564, 0.86793, 2.41477, -1.80305, 0.6709,
+    ///    3.57769, 4.77481, -0.37317, -3.52713, 1.88831, 1.73502, 1.20155, -3.36542, -2.03551,
+    ///    -5.6145, -2.71663, -0.04815, 3.99807, 1.66744, -9.68658, -0.46681, 4.22095, -6.7,
+    ///    -15.27331, -8.46123, 0.76369,
+    ///];
+    ///let dates = vec![
+    ///    37287, 37315, 37346, 37376, 37407, 37437, 37468, 37499, 37529, 37560, 37590, 37621,
+    ///    37652, 37680, 37711, 37741, 37772, 37802, 37833, 37864, 37894, 37925, 37955, 37986,
+    ///    38017, 38046, 38077, 38107, 38138, 38168, 38199, 38230, 38260, 38291, 38321, 38352,
+    ///    38383, 38411, 38442, 38472, 38503, 38533, 38564, 38595, 38625, 38656, 38686, 38717,
+    ///    38748, 38776, 38807, 38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082,
+    ///    39113, 39141, 39172, 39202, 39233, 39263, 39294, 39325, 39355, 39386, 39416, 39447,
+    ///    39478, 39507, 39538, 39568, 39599, 39629, 39660, 39691, 39721, 39752, 39782, 39813,
+    ///];
+    ///let mut quantile_date = 0;
+    ///let mut quantile_value = f64::NAN;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.rolling_period_quantile_by_frequency(
+    ///    &dates,
+    ///    enums::ClFrequency::ClFrequencyMonthly,
+    ///    enums::ClFrequency::ClFrequencyQuarterly,
+    ///    0.05,
+    ///    &mut quantile_date,
+    ///    &mut quantile_value,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(quantile_value, -16.754485)
+    ///        && quantile_date == 37468,
+    ///    true
+    ///);
+    ///```
+    pub fn rolling_period_quantile_by_frequency(
+        &self,
+        dates: &[i32],
+        freq: enums::ClFrequency,
+        window_freq: enums::ClFrequency,
+        q: f64,
+        quantile_date: &mut i32,
+        quantile_value: &mut f64,
+    ) -> Errors {
+        *quantile_date = 0;
+        *quantile_value = f64::NAN;
+        if !is_valid_frequency(freq) || !is_valid_frequency(window_freq) {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let window =
+            (get_annual_multiplier(freq, true) / get_annual_multiplier(window_freq, true)).round();
+        if window < 1.0 {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        return self.rolling_period_quantile(
+            dates,
+            window as usize,
+            q,
+            quantile_date,
+            quantile_value,
+        );
+    }
+
+    ///calculate the best rolling period return of an array and the end date of the window, the window is the number
+    ///of periods of the data frequency, e.g. days, weeks or quarters. the windows which have NAN/INF values are skipped.
+    ///
+    ///# Arguments
+    ///dates: the date of value
+    ///
+    ///window: the number of periods of each rolling window
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![
+    ///    -2.57909, 0.0353, 3.56387, -3.88416, 0.0, -9.81106, -7.70466, -0.04348, -9.65637,
+    ///    3.37025, 7.68514, -6.79066, -1.76334, -3.7317, -0.49068, 11.83432, 9.08289, 3.39531,
+    ///    0.70368, 0.89286, -0.76953, 6.39783, 1.38484, 2.33645, 2.80998, 0.5808, -0.61141,
+    ///    -0.20506, -0.47945, -0.13765, -3.4459, -0.85653, 1.83585, 0.84836, 3.61024, 3.99188,
+    ///    -1.7892, 2.02054, -0.81169, -1.40753, 3.02125, -0.67676, 1.07073, -2.21509, 0.29547,
+    ///    -2.65139, 2.62273, -0.65557, 0.76463, -1.22072, -0.0668, 2.20588, -0.91563, -0.76766,
+    ///    -1.21429, 3.43456, 4.99825, 3.89481, 1.59564, 0.86793, 2.41477, -1.80305, 0.6709,
+    ///    3.57769, 4.77481, -0.37317, -3.52713, 1.88831, 1.73502, 1.20155, -3.36542, -2.03551,
+    ///    -5.6145, -2.71663, -0.04815, 3.99807, 1.66744, -9.68658, -0.46681, 4.22095, -6.7,
+    ///    -15.27331, -8.46123, 0.76369,
+    ///];
+    ///let dates = vec![
+    ///    37287, 37315, 37346, 37376, 37407, 37437, 37468, 37499, 37529, 37560, 37590, 37621,
+    ///    37652, 37680, 37711, 37741, 37772, 37802, 37833, 37864, 37894, 37925, 37955, 37986,
+    ///    38017, 38046, 38077, 38107, 38138, 38168, 38199, 38230, 38260, 38291, 38321, 38352,
+    ///    38383, 38411, 38442, 38472, 38503, 38533, 38564, 38595, 38625, 38656, 38686, 38717,
+    ///    38748, 38776, 38807, 38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082,
+    ///    39113, 39141, 39172, 39202, 39233, 39263, 39294, 39325, 39355, 39386, 39416, 39447,
+    ///    39478, 39507, 39538, 39568, 39599, 39629, 39660, 39691, 39721, 39752, 39782, 39813,
+    ///];
+    ///let mut best_date = 0;
+    ///let mut best_value = f64::NAN;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.best_rolling_period(&dates, 3, &mut best_date, &mut best_value);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(best_value, 26.13411852)
+    ///        && best_date == 37802,
+    ///    true
+    ///);
+    ///```
+    pub fn best_rolling_period(
+        &self,
+        dates: &[i32],
+        window: usize,
+        best_date: &mut i32,
+        best_value: &mut f64,
+    ) -> Errors {
+        return self.rolling_period_quantile(dates, window, 1.0, best_date, best_value);
+    }
+
+    ///calculate the worst rolling period return of an array and the end date of the window, the window is the number
+    ///of periods of the data frequency, e.g. days, weeks or quarters. the windows which have NAN/INF values are skipped.
+    ///
+    ///# Arguments
+    ///dates: the date of value
+    ///
+    ///window: the number of periods of each rolling window
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![
+    ///    -2.57909, 0.0353, 3.56387, -3.88416, 0.0, -9.81106, -7.70466, -0.04348, -9.65637,
+    ///    3.37025, 7.68514, -6.79066, -1.76334, -3.7317, -0.49068, 11.83432, 9.08289, 3.39531,
+    ///    0.70368, 0.89286, -0.76953, 6.39783, 1.38484, 2.33645, 2.80998, 0.5808, -0.61141,
+    ///    -0.20506, -0.47945, -0.13765, -3.4459, -0.85653, 1.83585, 0.84836, 3.61024, 3.99188,
+    ///    -1.7892, 2.02054, -0.81169, -1.40753, 3.02125, -0.67676, 1.07073, -2.21509, 0.29547,
+    ///    -2.65139, 2.62273, -0.65557, 0.76463, -1.22072, -0.0668, 2.20588, -0.91563, -0.76766,
+    ///    -1.21429, 3.43456, 4.99825, 3.89481, 1.59564, 0.86793, 2.41477, -1.80305, 0.6709,
+    ///    3.57769, 4.77481, -0.37317, -3.52713, 1.88831, 1.73502, 1.20155, -3.36542, -2.03551,
+    ///    -5.6145, -2.71663, -0.04815, 3.99807, 1.66744, -9.68658, -0.46681, 4.22095, -6.7,
+    ///    -15.27331, -8.46123, 0.76369,
+    ///];
+    ///let dates = vec![
+    ///    37287, 37315, 37346, 37376, 37407, 37437, 37468, 37499, 37529, 37560, 37590, 37621,
+    ///    37652, 37680, 37711, 37741, 37772, 37802, 37833, 37864, 37894, 37925, 37955, 37986,
+    ///    38017, 38046, 38077, 38107, 38138, 38168, 38199, 38230, 38260, 38291, 38321, 38352,
+    ///    38383, 38411, 38442, 38472, 38503, 38533, 38564, 38595, 38625, 38656, 38686, 38717,
+    ///    38748, 38776, 38807, 38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082,
+    ///    39113, 39141, 39172, 39202, 39233, 39263, 39294, 39325, 39355, 39386, 39416, 39447,
+    ///    39478, 39507, 39538, 39568, 39599, 39629, 39660, 39691, 39721, 39752, 39782, 39813,
+    ///];
+    ///let mut worst_date = 0;
+    ///let mut worst_value = f64::NAN;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.worst_rolling_period(&dates, 3, &mut worst_date, &mut worst_value);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(worst_value, -27.63860069)
+    ///        && worst_date == 39782,
+    ///    true
+    ///);
+    ///```
+    pub fn worst_rolling_period(
+        &self,
+        dates: &[i32],
+        window: usize,
+        worst_date: &mut i32,
+        worst_value: &mut f64,
+    ) -> Errors {
+        return self.rolling_period_quantile(dates, window, 0.0, worst_date, worst_value);
+    }
+
+    fn get_last_up_down_streak(
+        values: &[f64],
+        start: usize,
+        end: usize,
+        cmp_fn: fn(f64, f64) -> bool,
+    ) -> DataGroup {
+        let mut final_streak = DataGroup {
+            start: start,
+            end: start,
+            data: 1.0,
+        };
+        for mut i in start..end {
+            if !values[i].is_finite() {
+                continue;
+            }
+
+            let mut streak = DataGroup {
+                start: i,
+                end: i,
+                data: 1.0,
+            };
+            while i < end {
+                if !values[i].is_finite() {
+                    i += 1;
+                    continue;
+                } else if cmp_fn(values[i], 0.0) {
+                    streak.data *= values[i] / 100.0 + 1.0;
+                    streak.end = i;
+                } else {
+                    break;
+                }
+                i += 1;
+            }
+            if streak.data != 1.0
+                && (streak.end - streak.start) >= (final_streak.end - final_streak.start)
+            {
+                final_streak = streak;
             }
         }
 
@@ -3270,7 +4399,7 @@ impl<'a> MPTCalculator<'a> {
             longest_up_down_periods,
         );
     }
-    ///calculate the volatity value of an array, if the array has NAN/INF values,the result will be NAN
+    ///calculate the volatility value of an array, if the array has NAN/INF values,the result will be NAN
     ///
     ///# Arguments
     ///freq: the frequence of source data.
@@ -3286,15 +4415,16 @@ impl<'a> MPTCalculator<'a> {
     ///];
     ///let mut res = 0.0;
     ///let mpt = MPTCalculator::from_v(&data);
-    ///let err = mpt.volatity(enums::ClFrequency::ClFrequencyDaily, &mut res);
+    ///let err = mpt.volatility(enums::ClFrequency::ClFrequencyDaily, &mut res);
     ///assert_eq!(
     ///   err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 83.388666),
     ///   true
     ///);
     ///```
-    pub fn volatity(&self, freq: enums::ClFrequency, result: &mut f64) -> Errors {
-        if self.values.len() == 0 {
-            return Errors::ClErrorCodeInvalidPara;
+    pub fn volatility(&self, freq: enums::ClFrequency, result: &mut f64) -> Errors {
+        let ret = check_sample_size(self.values.len(), 3);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
         }
         if self.values.iter().find(|x| !x.is_finite()) != None {
             return Errors::ClErrorCodeNoError;
@@ -3337,7 +4467,13 @@ impl<'a> MPTCalculator<'a> {
         return Errors::ClErrorCodeNoError;
     }
 
-    ///calculate the volatity value of an array, if the array has NAN/INF values,the result will be NAN
+    ///the deprecated misspelled name of volatility.
+    #[deprecated(note = "use volatility instead")]
+    pub fn volatity(&self, freq: enums::ClFrequency, result: &mut f64) -> Errors {
+        return self.volatility(freq, result);
+    }
+
+    ///calculate the zscore value of the observerd value, it is the distance from the arithmetic mean in standard deviations
     ///
     ///# Arguments
     ///freq: the frequence of source data.
@@ -3382,13 +4518,59 @@ impl<'a> MPTCalculator<'a> {
         *result = (observerd_value - mean_res) / stddev;
         return Errors::ClErrorCodeNoError;
     }
+
+    ///calculate the rolling zscore of an array, it is the zscore of the return of each period against the returns
+    ///of its trailing window, the window does not include the period itself, so the result is the standardized
+    ///surprise of the period. the results of the first window periods are NAN, and the result is NAN if the period
+    ///or its window has NAN/INF values or the window has no dispersion.
+    ///
+    ///# Arguments
+    ///window: the number of the trailing periods, it should be 2 at least.
+    ///
+    ///result: the rolling zscore of each period, its length should not be less than the array.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let data = vec![1.0, 2.0, 3.0, 2.0, 10.0];
+    ///let mut res = vec![0.0; 5];
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.rolling_zscore(3, &mut res);
+    ///assert_eq!(
+    ///   err == Errors::ClErrorCodeNoError
+    ///       && res[2].is_nan()
+    ///       && MPTCalculator::is_eq_double_array(&res[3..], &[0.0, 13.279056]),
+    ///   true
+    ///);
+    ///```
+    pub fn rolling_zscore(&self, window: usize, result: &mut [f64]) -> Errors {
+        if self.values.len() == 0 || window < 2 || result.len() < self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        instrument!("rolling_zscore", rows = self.values.len(), window = window);
+        result.iter_mut().for_each(|x| *x = f64::NAN);
+
+        for i in window..self.values.len() {
+            if !self.values[i].is_finite() {
+                continue;
+            }
+            let trailing = MPTCalculator::from_v(&self.values[i - window..i]);
+            let ret = trailing.zscore(self.values[i], &mut result[i]);
+            if ret != Errors::ClErrorCodeNoError {
+                return ret;
+            }
+        }
+        return Errors::ClErrorCodeNoError;
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::{
+        common::DataGroup,
         enums::{self, Errors},
-        MPTCalculator,
+        HolidayList, MPTCalculator,
     };
 
     #[test]
@@ -3416,6 +4598,38 @@ mod test {
         );
     }
 
+    #[test]
+    fn should_correct_stddev_by_mode() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.standard_deviation_by_mode(
+            enums::ClFrequency::ClFrequencyMonthly,
+            true,
+            enums::ClVarianceMode::ClVarianceSample,
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 15.99317),
+            true
+        );
+        let err = mpt.standard_deviation_by_mode(
+            enums::ClFrequency::ClFrequencyMonthly,
+            true,
+            enums::ClVarianceMode::ClVariancePopulation,
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 15.769477),
+            true
+        );
+    }
+
     #[test]
     fn should_correct_gain_stddev() {
         let data = vec![
@@ -3468,6 +4682,74 @@ mod test {
             true
         );
     }
+    #[test]
+    fn should_correct_semi_stddev_by_mode() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.semi_standard_deviation_by_mode(
+            enums::ClFrequency::ClFrequencyMonthly,
+            true,
+            enums::ClVarianceMode::ClVariancePopulation,
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 13.039018),
+            true
+        );
+    }
+    #[test]
+    fn should_correct_semi_stddev_by_target() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let riskfree: Vec<f64> = (0..36).map(|i| 0.1 + 0.01 * i as f64).collect();
+        let mpt = MPTCalculator::from_v_r(&data, &riskfree);
+        let targets = [
+            (enums::ClSemiDeviationTarget::ClSemiDeviationMean, 13.22398),
+            (enums::ClSemiDeviationTarget::ClSemiDeviationZero, 14.63827),
+            (
+                enums::ClSemiDeviationTarget::ClSemiDeviationValue(1.0),
+                16.492304,
+            ),
+            (
+                enums::ClSemiDeviationTarget::ClSemiDeviationRiskFree,
+                15.295775,
+            ),
+        ];
+        for (target, expected) in targets {
+            let mut res = 0.0;
+            let err = mpt.semi_standard_deviation_by_target(
+                enums::ClFrequency::ClFrequencyMonthly,
+                true,
+                target,
+                &mut res,
+            );
+            assert_eq!(
+                err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, expected),
+                true
+            );
+        }
+
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.semi_standard_deviation_by_target(
+            enums::ClFrequency::ClFrequencyMonthly,
+            true,
+            enums::ClSemiDeviationTarget::ClSemiDeviationRiskFree,
+            &mut res,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+
     #[test]
     fn should_correct_mean_harmonic() {
         let data = vec![-1.5, 2.3, 4.5];
@@ -3709,6 +4991,75 @@ mod test {
         );
     }
 
+    #[test]
+    fn should_correct_weighted_standard_deviation_by_mode() {
+        let data = vec![
+            1.22072, 0.0668, 2.20588, 0.91563, 0.76766, 1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, 1.80305, 0.6709, 3.57769, 4.77481, 0.37317, 3.52713,
+            1.88831, 1.73502, 1.20155, 3.36542, 2.03551, 5.6145, 2.71663, 0.04815, 3.99807,
+            1.66744, 9.68658, 0.46681, 4.22095, 6.7, 15.27331, 8.46123, 0.76369, 10.32347,
+        ];
+
+        let weighting = vec![
+            3.683070486,
+            2.698835031,
+            2.615091784,
+            2.829245119,
+            4.197477687,
+            3.747731115,
+            1.428980992,
+            1.490970258,
+            3.776323531,
+            1.126182408,
+            4.589706355,
+            2.213203472,
+            3.290841193,
+            1.574023637,
+            2.7073515,
+            2.067657476,
+            2.715387407,
+            3.782522676,
+            4.737767273,
+            3.587905857,
+            1.00234693,
+            3.598129659,
+            2.182956354,
+            2.399354298,
+            0.893462788,
+            1.636175797,
+            1.182474797,
+            4.58802791,
+            3.983018253,
+            4.741795995,
+            2.837587798,
+            2.613364024,
+            4.084667264,
+            0.443121313,
+            1.119531868,
+            3.833709695,
+        ];
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.weighted_standard_deviation_by_mode(
+            &weighting,
+            enums::ClVarianceMode::ClVariancePopulation,
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 3.586653428),
+            true
+        );
+        let err = mpt.weighted_standard_deviation_by_mode(
+            &weighting,
+            enums::ClVarianceMode::ClVarianceSample,
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 3.64762),
+            true
+        );
+    }
+
     #[test]
     fn should_correct_skewness() {
         let data = vec![
@@ -3982,6 +5333,7 @@ mod test {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn should_correct_coefficeient_viaiantion() {
         let data = vec![
             -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
@@ -3999,6 +5351,51 @@ mod test {
     }
 
     #[test]
+    fn should_correct_coefficient_variation() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.coefficient_variation(&mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, -5.41921),
+            true
+        );
+        let err = mpt
+            .coefficient_variation_annualized(enums::ClFrequency::ClFrequencyQuarterly, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, -2.709603),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_efficiency_ratio_arithmetic() {
+        let data = vec![
+            2.8709, -1.6506, 0.8281, 4.8182, 4.0484, -0.4246, -1.8230, 1.1619, 6.2151, 5.3158,
+            -3.7904, 0.3500, -8.9486, -1.6029, -2.1879, 6.5159, 3.0498, -8.3762, -3.9341, -0.0780,
+            -17.9807, -21.5895, -11.3292, 4.8884, -7.5447, -7.5943, 13.9102, 13.6679, 6.2313,
+            -1.3755, 8.7637, 2.1660, 5.3087, -5.4276, 5.4496, 4.3492,
+        ];
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.efficiency_ratio_arithmetic(
+            enums::ClFrequency::ClFrequencyQuarterly,
+            true,
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, -0.041972),
+            true
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
     fn should_correct_efficiency_ratio_arthmetic() {
         let data = vec![
             2.8709, -1.6506, 0.8281, 4.8182, 4.0484, -0.4246, -1.8230, 1.1619, 6.2151, 5.3158,
@@ -4177,7 +5574,68 @@ mod test {
             true
         );
         assert_eq!(
-            err == Errors::ClErrorCodeNoError && recovery_date == 0,
+            err == Errors::ClErrorCodeNoError && recovery_date == 0,
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_max_segment_of_equal_segments() {
+        //the decreases from 10 to 0 and from 20 to 10 are equal, the segment from the max is kept
+        let mut dg = DataGroup::new();
+        MPTCalculator::get_max_segment(&[0.0, -5.0, 10.0, 0.0, 20.0, 10.0], 0, 5, 1.0, &mut dg);
+        assert_eq!(dg.start == 4 && dg.end == 5 && dg.data == 10.0, true);
+
+        //the equal decrease to the min is kept before the decrease from the max
+        let values = [5.0, 12.0, 5.0, 8.0, 2.0, 14.0, 24.0, 14.0];
+        MPTCalculator::get_max_segment(&values, 0, 7, 1.0, &mut dg);
+        assert_eq!(dg.start == 1 && dg.end == 4 && dg.data == 10.0, true);
+
+        //the peak is the last one of the equal zero values of a range
+        let values = [-3.0, -1.0, 0.0, 0.0, -3.0, 1.0, 0.0, 0.0, 2.0];
+        MPTCalculator::get_max_segment(&values, 0, 8, 1.0, &mut dg);
+        assert_eq!(dg.start == 3 && dg.end == 4 && dg.data == 3.0, true);
+    }
+
+    #[test]
+    fn should_correct_max_draw_down_of_long_daily_series() {
+        //30 years of daily returns with flat days and a crash block.
+        let pattern = [0.3, -0.2, 0.0, 0.1];
+        let data: Vec<f64> = (0..7560)
+            .map(|i| {
+                if i >= 3000 && i < 3250 {
+                    -0.1
+                } else {
+                    pattern[i % 4]
+                }
+            })
+            .collect();
+        let dates: Vec<i32> = (0..7560).map(|i| 36526 + i).collect();
+        let mut max_draw_down = f64::NAN;
+        let mut max_draw_down_peek_date = 0;
+        let mut max_draw_down_valley_date = 0;
+        let mut max_draw_down_month = 0;
+        let mut recovery_month = 0;
+        let mut recovery_date = 0;
+
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.max_draw_down(
+            &dates,
+            enums::ClFrequency::ClFrequencyDaily,
+            &mut max_draw_down,
+            &mut max_draw_down_peek_date,
+            &mut max_draw_down_valley_date,
+            &mut max_draw_down_month,
+            &mut recovery_month,
+            &mut recovery_date,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(max_draw_down, -22.207689)
+                && max_draw_down_valley_date == 39775
+                && max_draw_down_month == 253
+                && recovery_month == 499
+                && recovery_date == 40274,
             true
         );
     }
@@ -4235,6 +5693,42 @@ mod test {
         );
     }
     #[test]
+    fn should_correct_max_gain_of_equal_segments() {
+        //the gains ending at 38986 and at 39136 are equal, the segment of the outer range is kept
+        let data = vec![
+            -50.0, -75.0, 100.0, -75.0, 300.0, 100.0, 100.0, 100.0, -75.0, -75.0, 100.0, 100.0,
+            300.0, 0.0, -75.0,
+        ];
+        let dates: Vec<i32> = (0..data.len() as i32).map(|i| 38776 + 30 * i).collect();
+        let mut max_gain = f64::NAN;
+        let mut start_date = 0;
+        let mut end_date = 0;
+        let mut max_gain_month = 0;
+
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.max_gain(
+            &dates,
+            enums::ClFrequency::ClFrequencyMonthly,
+            &mut max_gain,
+            &mut start_date,
+            &mut end_date,
+            &mut max_gain_month,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(max_gain, 3100.0),
+            true
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && start_date == 38869,
+            true
+        );
+        assert_eq!(err == Errors::ClErrorCodeNoError && end_date == 39136, true);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && max_gain_month == 9,
+            true
+        );
+    }
+    #[test]
     fn should_correct_calmar_ratio() {
         let data = vec![
             1.52768, 4.04616, 3.40287, -2.43748, 2.1044, -1.7708, -1.89656, 3.18186, 0.14197,
@@ -4290,6 +5784,52 @@ mod test {
         );
     }
 
+    #[test]
+    fn should_correct_average_draw_down_by_block() {
+        let data = vec![
+            2.1, -3.4, 1.2, -0.8, -2.5, 3.1, 1.7, -1.1, 0.6, -4.2, 2.8, 1.5, -0.9, 2.2, -1.6, -2.9,
+            0.4, 3.3, -1.2, 1.9, -2.6, 0.7, 1.4, -0.5,
+        ];
+        let dates = vec![
+            44773, 44804, 44834, 44865, 44895, 44926, 44957, 44985, 45016, 45046, 45077, 45107,
+            45138, 45169, 45199, 45230, 45260, 45291, 45322, 45351, 45382, 45412, 45443, 45473,
+        ];
+        let mpt = MPTCalculator::from_v(&data);
+        let blocks = [
+            (enums::ClDrawDownBlock::ClDrawDownBlockAnnual, -4.978811),
+            (
+                enums::ClDrawDownBlock::ClDrawDownBlockCalendarYear,
+                -6.366312,
+            ),
+            (enums::ClDrawDownBlock::ClDrawDownBlockRolling, -4.08231),
+            (enums::ClDrawDownBlock::ClDrawDownBlockEpisode, -2.52627),
+        ];
+        for (block, expected) in blocks {
+            let mut result = f64::NAN;
+            let err = mpt.average_draw_down_by_block(
+                &dates,
+                enums::ClFrequency::ClFrequencyMonthly,
+                block,
+                6,
+                &mut result,
+            );
+            assert_eq!(
+                err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(result, expected),
+                true
+            );
+        }
+
+        let mut result = f64::NAN;
+        let err = mpt.average_draw_down_by_block(
+            &dates,
+            enums::ClFrequency::ClFrequencyMonthly,
+            enums::ClDrawDownBlock::ClDrawDownBlockRolling,
+            25,
+            &mut result,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+
     #[test]
     fn should_correct_sterling_ratio() {
         let data = vec![
@@ -4360,6 +5900,13 @@ mod test {
             err == Errors::ClErrorCodeNoError && best_rolling_month_date == 37802,
             true
         );
+
+        let mut rolling = Vec::new();
+        mpt.rolling_returns(&dates, 3, &mut rolling);
+        assert_eq!(
+            rolling.iter().all(|x| x.1 <= best_rolling_month_value),
+            true
+        );
     }
 
     #[test]
@@ -4522,6 +6069,23 @@ mod test {
     }
 
     #[test]
+    fn should_correct_volatility() {
+        let data = vec![
+            210.69, 195.58, 190.08, 179.72, 179.72, 165.24, 163.12, 160.8, 148.96, 153.29, 169.47,
+            181.52, 174.86, 184.9, 174.12, 166.82, 167.46, 165.24, 150.86, 143.88, 151.07, 150.65,
+            141.13,
+        ];
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.volatility(enums::ClFrequency::ClFrequencyDaily, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 83.388666),
+            true
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
     fn should_correct_volatity() {
         let data = vec![
             210.69, 195.58, 190.08, 179.72, 179.72, 165.24, 163.12, 160.8, 148.96, 153.29, 169.47,
@@ -4553,4 +6117,387 @@ mod test {
             true
         );
     }
+
+    #[test]
+    fn should_correct_annualize_by_dates() {
+        let data = vec![
+            1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016, 1.40278, 1.51232,
+            -1.95588, 1.1185,
+        ];
+        let dates = vec![
+            38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082, 39113, 39141, 39172,
+        ];
+        let mpt = MPTCalculator::from_v(&data);
+        let mut res = 0.0;
+        let err = mpt.annualized_return_by_dates(&dates, true, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 11.814662),
+            true
+        );
+        let err = mpt.standard_deviation_by_dates(&dates, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 6.219448),
+            true
+        );
+        let err = mpt.standard_deviation_by_dates(&dates[1..], &mut res);
+        assert_eq!(err == Errors::ClErrorCodeInvalidPara, true);
+    }
+
+    #[test]
+    fn should_correct_standard_deviation_by_calendar() {
+        //2022-12-26 and 2022-12-30 are the holidays of 2022, 2023-01-02 is the holiday of 2023
+        let holidays = HolidayList::from(&[44921, 44925, 44928]);
+        let data = vec![0.5, -0.3, 0.2, 0.8];
+        let dates = vec![44924, 44929, 44930, 44931];
+        let mpt = MPTCalculator::from_v(&data);
+        let mut res = 0.0;
+        let err = mpt.standard_deviation_by_calendar(&dates, Some(&holidays), &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 7.541220),
+            true
+        );
+        let err = mpt.standard_deviation_by_calendar(&dates, None, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 7.563068),
+            true
+        );
+        let err = mpt.standard_deviation_by_calendar(&[44929, 44924, 44930, 44931], None, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeUnsortedByDate && res.is_nan(),
+            true
+        );
+        let err = mpt.standard_deviation_by_calendar(&dates[1..], None, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeInvalidPara, true);
+    }
+
+    #[test]
+    fn should_correct_rolling_period_quantile() {
+        let data = vec![
+            -2.57909, 0.0353, 3.56387, -3.88416, 0.0, -9.81106, -7.70466, -0.04348, -9.65637,
+            3.37025, 7.68514, -6.79066, -1.76334, -3.7317, -0.49068, 11.83432, 9.08289, 3.39531,
+            0.70368, 0.89286, -0.76953, 6.39783, 1.38484, 2.33645, 2.80998, 0.5808, -0.61141,
+            -0.20506, -0.47945, -0.13765, -3.4459, -0.85653, 1.83585, 0.84836, 3.61024, 3.99188,
+            -1.7892, 2.02054, -0.81169, -1.40753, 3.02125, -0.67676, 1.07073, -2.21509, 0.29547,
+            -2.65139, 2.62273, -0.65557, 0.76463, -1.22072, -0.0668, 2.20588, -0.91563, -0.76766,
+            -1.21429, 3.43456, 4.99825, 3.89481, 1.59564, 0.86793, 2.41477, -1.80305, 0.6709,
+            3.57769, 4.77481, -0.37317, -3.52713, 1.88831, 1.73502, 1.20155, -3.36542, -2.03551,
+            -5.6145, -2.71663, -0.04815, 3.99807, 1.66744, -9.68658, -0.46681, 4.22095, -6.7,
+            -15.27331, -8.46123, 0.76369,
+        ];
+        let dates = vec![
+            37287, 37315, 37346, 37376, 37407, 37437, 37468, 37499, 37529, 37560, 37590, 37621,
+            37652, 37680, 37711, 37741, 37772, 37802, 37833, 37864, 37894, 37925, 37955, 37986,
+            38017, 38046, 38077, 38107, 38138, 38168, 38199, 38230, 38260, 38291, 38321, 38352,
+            38383, 38411, 38442, 38472, 38503, 38533, 38564, 38595, 38625, 38656, 38686, 38717,
+            38748, 38776, 38807, 38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082,
+            39113, 39141, 39172, 39202, 39233, 39263, 39294, 39325, 39355, 39386, 39416, 39447,
+            39478, 39507, 39538, 39568, 39599, 39629, 39660, 39691, 39721, 39752, 39782, 39813,
+        ];
+        let mut quantile_date = 0;
+        let mut quantile_value = f64::NAN;
+        let mpt = MPTCalculator::from_v(&data);
+        let err =
+            mpt.rolling_period_quantile(&dates, 3, 0.5, &mut quantile_date, &mut quantile_value);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(quantile_value, 0.529055)
+                && quantile_date == 37590,
+            true
+        );
+
+        let err =
+            mpt.rolling_period_quantile(&dates, 3, 0.95, &mut quantile_date, &mut quantile_value);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(quantile_value, 10.806542)
+                && quantile_date == 39051,
+            true
+        );
+
+        let err = mpt.worst_rolling_period(&dates, 3, &mut quantile_date, &mut quantile_value);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(quantile_value, -27.63860069)
+                && quantile_date == 39782,
+            true
+        );
+
+        let err = mpt.rolling_period_quantile_by_frequency(
+            &dates,
+            enums::ClFrequency::ClFrequencyMonthly,
+            enums::ClFrequency::ClFrequencyQuarterly,
+            0.95,
+            &mut quantile_date,
+            &mut quantile_value,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(quantile_value, 10.806542)
+                && quantile_date == 39051,
+            true
+        );
+        let err = mpt.rolling_period_quantile_by_frequency(
+            &dates,
+            enums::ClFrequency::ClFrequencyMonthly,
+            enums::ClFrequency::ClFrequencyAnnually,
+            1.0,
+            &mut quantile_date,
+            &mut quantile_value,
+        );
+        let mut expected = Vec::new();
+        mpt.rolling_returns(&dates, 12, &mut expected);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && expected.iter().all(|x| x.1 <= quantile_value)
+                && expected
+                    .iter()
+                    .any(|x| x == &(quantile_date, quantile_value)),
+            true
+        );
+        let err = mpt.rolling_period_quantile_by_frequency(
+            &dates,
+            enums::ClFrequency::ClFrequencyMonthly,
+            enums::ClFrequency::ClFrequencyWeekly,
+            0.5,
+            &mut quantile_date,
+            &mut quantile_value,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && quantile_value.is_nan(),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_rolling_returns() {
+        let data = vec![
+            -2.57909, 0.0353, 3.56387, -3.88416, 0.0, -9.81106, -7.70466, -0.04348, -9.65637,
+            3.37025, 7.68514, -6.79066, -1.76334, -3.7317, -0.49068, 11.83432, 9.08289, 3.39531,
+            0.70368, 0.89286, -0.76953, 6.39783, 1.38484, 2.33645, 2.80998, 0.5808, -0.61141,
+            -0.20506, -0.47945, -0.13765, -3.4459, -0.85653, 1.83585, 0.84836, 3.61024, 3.99188,
+            -1.7892, 2.02054, -0.81169, -1.40753, 3.02125, -0.67676, 1.07073, -2.21509, 0.29547,
+            -2.65139, 2.62273, -0.65557, 0.76463, -1.22072, -0.0668, 2.20588, -0.91563, -0.76766,
+            -1.21429, 3.43456, 4.99825, 3.89481, 1.59564, 0.86793, 2.41477, -1.80305, 0.6709,
+            3.57769, 4.77481, -0.37317, -3.52713, 1.88831, 1.73502, 1.20155, -3.36542, -2.03551,
+            -5.6145, -2.71663, -0.04815, 3.99807, 1.66744, -9.68658, -0.46681, 4.22095, -6.7,
+            -15.27331, -8.46123, 0.76369,
+        ];
+        let dates = vec![
+            37287, 37315, 37346, 37376, 37407, 37437, 37468, 37499, 37529, 37560, 37590, 37621,
+            37652, 37680, 37711, 37741, 37772, 37802, 37833, 37864, 37894, 37925, 37955, 37986,
+            38017, 38046, 38077, 38107, 38138, 38168, 38199, 38230, 38260, 38291, 38321, 38352,
+            38383, 38411, 38442, 38472, 38503, 38533, 38564, 38595, 38625, 38656, 38686, 38717,
+            38748, 38776, 38807, 38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082,
+            39113, 39141, 39172, 39202, 39233, 39263, 39294, 39325, 39355, 39386, 39416, 39447,
+            39478, 39507, 39538, 39568, 39599, 39629, 39660, 39691, 39721, 39752, 39782, 39813,
+        ];
+        let mut output = Vec::new();
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.rolling_returns(&dates, 12, &mut output);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && output.len() == 73
+                && output[1].0 == 37652
+                && MPTCalculator::is_eq_double(output[1].1, -23.707367)
+                && output[72].0 == 39813
+                && MPTCalculator::is_eq_double(output[72].1, -33.713033),
+            true
+        );
+
+        let mut data_with_nan = data.clone();
+        data_with_nan[20] = f64::NAN;
+        let mpt = MPTCalculator::from_v(&data_with_nan);
+        let err = mpt.rolling_returns(&dates, 12, &mut output);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && output.len() == 61,
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_insufficient_data() {
+        let data = vec![1.5, -2.0, 3.0, 0.5];
+        let bmk_data = vec![1.0, -1.5, 2.0, 0.8];
+        let rf_data = vec![0.1; 4];
+        let freq = enums::ClFrequency::ClFrequencyMonthly;
+        for n in 0..4 {
+            let expected = |min_count: usize| {
+                if n == 0 {
+                    Errors::ClErrorCodeInvalidPara
+                } else if n < min_count {
+                    Errors::ClErrorCodeInsufficientData
+                } else {
+                    Errors::ClErrorCodeNoError
+                }
+            };
+            let mpt = MPTCalculator::from(&data[..n], &bmk_data[..n], &rf_data[..n]);
+            let mut res = 0.0;
+            assert_eq!(mpt.standard_deviation(freq, true, &mut res), expected(2));
+            assert_eq!(n >= 2 || res.is_nan(), true);
+            assert_eq!(mpt.sharpe_ratio(freq, true, &mut res), expected(2));
+            assert_eq!(mpt.skewness(&mut res), expected(3));
+            assert_eq!(mpt.kurtosis(&mut res), expected(4));
+            assert_eq!(mpt.jarque_bera(&mut res), expected(4));
+            assert_eq!(mpt.volatility(freq, &mut res), expected(3));
+            assert_eq!(mpt.beta(&mut res), expected(2));
+            assert_eq!(mpt.alpha(freq, true, &mut res), expected(2));
+            assert_eq!(mpt.r_squared(&mut res), expected(2));
+            assert_eq!(mpt.tracking_error(freq, true, &mut res), expected(2));
+            assert_eq!(
+                mpt.standard_deviation_by_mode(
+                    freq,
+                    false,
+                    enums::ClVarianceMode::ClVariancePopulation,
+                    &mut res
+                ),
+                expected(1)
+            );
+            assert_eq!(
+                mpt.semi_standard_deviation(freq, true, &mut res),
+                expected(2)
+            );
+            assert_eq!(
+                mpt.gain_standard_deviation(freq, true, &mut res),
+                expected(2)
+            );
+            assert_eq!(
+                mpt.sharpe_ratio_arithmetic(freq, true, &mut res),
+                expected(2)
+            );
+            assert_eq!(
+                mpt.sharpe_ratio_geometric(freq, true, &mut res),
+                expected(2)
+            );
+            assert_eq!(mpt.sortino_ratio(freq, true, &mut res), expected(2));
+            assert_eq!(
+                mpt.sortino_ratio_arithmetic(freq, true, &mut res),
+                expected(2)
+            );
+            assert_eq!(
+                mpt.sortino_ratio_geometric(freq, true, &mut res),
+                expected(2)
+            );
+            assert_eq!(mpt.downside_deviation(freq, true, &mut res), expected(2));
+            assert_eq!(mpt.omega(freq, true, &mut res), expected(2));
+            assert_eq!(mpt.kappa3(freq, true, &mut res), expected(2));
+            assert_eq!(
+                mpt.information_ratio_arithmetic(freq, true, &mut res),
+                expected(2)
+            );
+            assert_eq!(
+                mpt.information_ratio_geometric(freq, true, &mut res),
+                expected(2)
+            );
+            assert_eq!(
+                mpt.treynor_ratio_arithmetic(freq, true, &mut res),
+                expected(2)
+            );
+            assert_eq!(
+                mpt.treynor_ratio_geometric(freq, true, &mut res),
+                expected(2)
+            );
+            assert_eq!(
+                mpt.downside_standard_deviation(freq, true, &mut res),
+                expected(2)
+            );
+            assert_eq!(mpt.m_squared(freq, true, &mut res), expected(2));
+            assert_eq!(mpt.correlation(&mut res), expected(2));
+            assert_eq!(mpt.covariance(&mut res), expected(2));
+            let mut p_value = 0.0;
+            assert_eq!(
+                mpt.excess_return_t_test(&mut res, &mut p_value),
+                expected(2)
+            );
+            assert_eq!(mpt.standard_error_alpha(&mut res), expected(3));
+            assert_eq!(n >= 3 || res.is_nan(), true);
+            assert_eq!(mpt.standard_error_beta(&mut res), expected(3));
+            assert_eq!(mpt.appraisal_ratio(&mut res), expected(3));
+        }
+    }
+
+    #[test]
+    fn should_correct_gain_to_pain_ratio() {
+        let data = vec![1.0, -2.0, 3.0, 0.5, -0.5];
+        let mut res = 0.0;
+        let err = MPTCalculator::from_v(&data).gain_to_pain_ratio(&mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 0.8),
+            true
+        );
+
+        let mut gain_loss = 0.0;
+        MPTCalculator::from_v(&data).gain_loss_ratio(&mut gain_loss);
+        assert_eq!(!MPTCalculator::is_eq_double(res, gain_loss), true);
+
+        let err = MPTCalculator::from_v(&[1.0, 2.0]).gain_to_pain_ratio(&mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res.is_nan(), true);
+        let err = MPTCalculator::from_v(&[1.0, f64::NAN, -2.0]).gain_to_pain_ratio(&mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res.is_nan(), true);
+    }
+
+    #[test]
+    fn should_correct_tail_ratio() {
+        let data = vec![-4.0, -2.0, -1.0, 0.0, 1.0, 2.0, 3.0, 6.0, 8.0];
+        let mpt = MPTCalculator::from_v(&data);
+        let mut res = 0.0;
+        let err = mpt.tail_ratio(0.25, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 3.0),
+            true
+        );
+        let err = mpt.tail_ratio(0.0625, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 7.0 / 3.0),
+            true
+        );
+
+        let mut gain_to_pain = 0.0;
+        mpt.gain_to_pain_ratio(&mut gain_to_pain);
+        let err = mpt.common_sense_ratio(0.25, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(res, 3.0 * gain_to_pain),
+            true
+        );
+
+        let err = mpt.tail_ratio(0.5, &mut res);
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+        let err = MPTCalculator::from_v(&[1.0, f64::NAN, -1.0]).common_sense_ratio(0.1, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res.is_nan(), true);
+    }
+
+    #[test]
+    fn should_correct_rolling_zscore() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+        ];
+        let mut res = vec![0.0; data.len()];
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.rolling_zscore(12, &mut res);
+        let mut expected = f64::NAN;
+        MPTCalculator::from_v(&data[..12]).zscore(data[12], &mut expected);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && res[..12].iter().all(|x| x.is_nan())
+                && MPTCalculator::is_eq_double(res[12], expected)
+                && res[17] < -1.0,
+            true
+        );
+
+        //the windows with the NAN value are skipped
+        let mut nan_data = data.clone();
+        nan_data[8] = f64::NAN;
+        let err = MPTCalculator::from_v(&nan_data).rolling_zscore(3, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && res[7].is_finite()
+                && res[8..12].iter().all(|x| x.is_nan())
+                && res[12].is_finite(),
+            true
+        );
+
+        let err = mpt.rolling_zscore(1, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeInvalidPara, true);
+    }
 }
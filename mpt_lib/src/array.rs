@@ -3,9 +3,10 @@ use std::cmp::Ordering;
 use chrono::NaiveDate;
 
 use crate::{
+    common::{get_annual_multiplier, is_valid_frequency},
     date_util,
     enums::{self, Errors},
-    MPTCalculator,
+    kernels, MPTCalculator,
 };
 //use chrono::{Duration, NaiveDate};
 use std::ops::ControlFlow;
@@ -891,6 +892,182 @@ impl<'a> MPTCalculator<'a> {
         return Errors::ClErrorCodeNoError;
     }
 
+    ///normalize the values of a universe to a common scale, so the statistics with the different scales can be
+    ///combined to a composite score. the z-score is the distance to the mean in the sample standard deviations,
+    ///the min-max score is from 0 to 1, the percentile score is from 0 to 100 and the equal values have the
+    ///average percentile. the NAN/INF values are excluded and their scores are NAN, if all the valid values are
+    ///equal, the scores are 0, 0.5 and 50.
+    ///
+    ///# Arguments
+    ///method: the normalization method.
+    ///
+    ///output: the score of each value, it should have the same size as the values.
+    ///
+    ///# Examples
+    ///```
+    /// use mpt_lib::MPTCalculator;
+    /// use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![4.0, 2.0, f64::NAN, 8.0, 6.0];
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let mut res = [f64::NAN; 5];
+    ///let err = mpt.normalize(enums::ClNormalizeMethod::ClNormalizeMinMax, &mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(
+    ///            &[res[0], res[1], res[3], res[4]],
+    ///            &[1.0 / 3.0, 0.0, 1.0, 2.0 / 3.0]
+    ///        )
+    ///        && res[2].is_nan(),
+    ///    true
+    ///);
+    ///```
+    pub fn normalize(&self, method: enums::ClNormalizeMethod, output: &mut [f64]) -> Errors {
+        if output.len() != self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        output.fill(f64::NAN);
+        let valid: Vec<f64> = self
+            .values
+            .iter()
+            .filter(|x| x.is_finite())
+            .cloned()
+            .collect();
+        if valid.len() == 0 {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let count = valid.len() as f64;
+        let min = valid.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = valid.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let mean = valid.iter().sum::<f64>() / count;
+        let mut std_dev = 0.0;
+        if valid.len() > 1 {
+            kernels::standard_deviation(
+                &valid,
+                enums::ClFrequency::ClFrequencyMonthly,
+                false,
+                enums::ClVarianceMode::ClVarianceSample,
+                &mut std_dev,
+            );
+        }
+        let mut sorted = valid.clone();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        self.values
+            .iter()
+            .zip(output.iter_mut())
+            .filter(|x| x.0.is_finite())
+            .for_each(|(x, score)| {
+                *score = match method {
+                    enums::ClNormalizeMethod::ClNormalizeZScore if std_dev > 0.0 => {
+                        (x - mean) / std_dev
+                    }
+                    enums::ClNormalizeMethod::ClNormalizeZScore => 0.0,
+                    enums::ClNormalizeMethod::ClNormalizeMinMax if max > min => {
+                        (x - min) / (max - min)
+                    }
+                    enums::ClNormalizeMethod::ClNormalizeMinMax => 0.5,
+                    enums::ClNormalizeMethod::ClNormalizePercentile if valid.len() > 1 => {
+                        let less = sorted.partition_point(|v| v < x) as f64;
+                        let equal = sorted.partition_point(|v| v <= x) as f64 - less;
+                        (less + (equal - 1.0) / 2.0) / (count - 1.0) * 100.0
+                    }
+                    enums::ClNormalizeMethod::ClNormalizePercentile => 50.0,
+                };
+            });
+        return Errors::ClErrorCodeNoError;
+    }
+
+    fn fee_per_period(annual_fee_bps: f64, freq: enums::ClFrequency) -> f64 {
+        let multiplier = get_annual_multiplier(freq, false);
+        return 1.0 - (1.0 - annual_fee_bps / 10000.0).powf(1.0 / multiplier);
+    }
+
+    ///deduct an annual fee from a gross return array, the fee of each period is compounded so that
+    ///the fee deducted in one year is equal to the annual fee, the output is an same size array
+    ///#Arguments:
+    ///annual_fee_bps: the annual fee in basis points
+    ///
+    ///freq: the frequence of source data.
+    ///
+    ///# Examples
+    ///```
+    /// use mpt_lib::MPTCalculator;
+    /// use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577];
+    ///const EXPECTED_RES: [f64; 6] = [1.257938, -2.959448, 0.051739, 0.532616, 2.2936, 2.491125];
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let mut res = [f64::NAN; 6];
+    ///let err = mpt.apply_fee(100.0, enums::ClFrequency::ClFrequencyMonthly, &mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double_array(&res, &EXPECTED_RES),
+    ///    true
+    ///);
+    ///```
+    pub fn apply_fee(
+        &self,
+        annual_fee_bps: f64,
+        freq: enums::ClFrequency,
+        output: &mut [f64],
+    ) -> Errors {
+        if !is_valid_frequency(freq)
+            || !annual_fee_bps.is_finite()
+            || annual_fee_bps >= 10000.0
+            || output.len() < self.values.len()
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let fee = Self::fee_per_period(annual_fee_bps, freq);
+        self.values.iter().enumerate().for_each(|x| {
+            output[x.0] = ((1.0 + x.1 / 100.0) * (1.0 - fee) - 1.0) * 100.0;
+        });
+
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///add an annual fee back to a net return array, it is the reverse of apply_fee, the output is an same size array
+    ///#Arguments:
+    ///annual_fee_bps: the annual fee in basis points
+    ///
+    ///freq: the frequence of source data.
+    ///
+    ///# Examples
+    ///```
+    /// use mpt_lib::MPTCalculator;
+    /// use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![1.257938, -2.959448, 0.051739, 0.532616, 2.2936, 2.491125];
+    ///const EXPECTED_RES: [f64; 6] = [1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577];
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let mut res = [f64::NAN; 6];
+    ///let err = mpt.remove_fee(100.0, enums::ClFrequency::ClFrequencyMonthly, &mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double_array(&res, &EXPECTED_RES),
+    ///    true
+    ///);
+    ///```
+    pub fn remove_fee(
+        &self,
+        annual_fee_bps: f64,
+        freq: enums::ClFrequency,
+        output: &mut [f64],
+    ) -> Errors {
+        if !is_valid_frequency(freq)
+            || !annual_fee_bps.is_finite()
+            || annual_fee_bps >= 10000.0
+            || output.len() < self.values.len()
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let fee = Self::fee_per_period(annual_fee_bps, freq);
+        self.values.iter().enumerate().for_each(|x| {
+            output[x.0] = ((1.0 + x.1 / 100.0) / (1.0 - fee) - 1.0) * 100.0;
+        });
+
+        return Errors::ClErrorCodeNoError;
+    }
+
     fn top_or_botoom_n_vec(
         &self,
         in_n: i32,
@@ -1091,43 +1268,14 @@ impl<'a> MPTCalculator<'a> {
     ///assert_eq!(MPTCalculator::is_eq_double(res, 0.14355), true);
     ///```
     pub fn percentile(&self, nth: i32, percentile: &mut f64) -> Errors {
-        let mut values_sort = vec![f64::NAN; self.values.len()];
-        values_sort.copy_from_slice(self.values);
-        values_sort.sort_by(|a, b| {
-            if a.is_finite() && b.is_finite() {
-                a.total_cmp(&b)
-            } else if !b.is_finite() {
-                Ordering::Less
-            } else {
-                Ordering::Equal
-            }
-        });
-
-        let mut count = 0;
-        Self::count(
-            self.values,
-            &[f64::NAN; 0],
-            &[f64::NAN; 0],
-            self.values.len() as i32,
-            &mut count,
-        );
-
-        let remain = nth as f64 * (count as f64 - 1.0) / 100.0;
-        let n = remain.floor() as i32;
-        let remain = remain.fract();
-
-        *percentile = values_sort[n as usize];
-        if n + 1 < count {
-            *percentile += remain * (values_sort[(n + 1) as usize] - values_sort[n as usize]);
-        }
-        return Errors::ClErrorCodeNoError;
+        return kernels::percentile(self.values, nth, percentile);
     }
 }
 
 #[cfg(test)]
 mod test {
     use crate::{
-        enums::{ClFrequency, Errors},
+        enums::{ClFrequency, ClNormalizeMethod, Errors},
         MPTCalculator,
     };
 
@@ -1405,6 +1553,38 @@ mod test {
         );
     }
 
+    #[test]
+    fn should_correct_apply_and_remove_fee() {
+        let data = vec![1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577];
+        const EXPECTED_RES: [f64; 6] = [1.257938, -2.959448, 0.051739, 0.532616, 2.2936, 2.491125];
+        let mpt = MPTCalculator::from_v(&data);
+        let mut res = [f64::NAN; 6];
+        let err = mpt.apply_fee(100.0, ClFrequency::ClFrequencyMonthly, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&res, &EXPECTED_RES),
+            true
+        );
+        let mut gross = [f64::NAN; 6];
+        let err = MPTCalculator::from_v(&res).remove_fee(
+            100.0,
+            ClFrequency::ClFrequencyMonthly,
+            &mut gross,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double_array(&gross, &data),
+            true
+        );
+
+        let data = vec![0.0; 12];
+        let mpt = MPTCalculator::from_v(&data);
+        let mut res = [f64::NAN; 12];
+        mpt.apply_fee(100.0, ClFrequency::ClFrequencyMonthly, &mut res);
+        let mut total = 0.0;
+        MPTCalculator::total_return_accumulat(&res, &mut total);
+        assert_eq!(MPTCalculator::is_eq_double(total, -1.0), true);
+    }
+
     #[test]
     fn should_correct_max() {
         let data = vec![
@@ -1848,4 +2028,42 @@ mod test {
             true
         );
     }
+
+    #[test]
+    fn should_correct_normalize() {
+        let data = vec![1.0, 3.0, f64::INFINITY, 3.0, 5.0, f64::NAN];
+        let mpt = MPTCalculator::from_v(&data);
+        let mut res = [0.0; 6];
+        let err = mpt.normalize(ClNormalizeMethod::ClNormalizeZScore, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(
+                    &[res[0], res[1], res[3], res[4]],
+                    &[-1.224745, 0.0, 0.0, 1.224745]
+                )
+                && res[2].is_nan()
+                && res[5].is_nan(),
+            true
+        );
+
+        let err = mpt.normalize(ClNormalizeMethod::ClNormalizePercentile, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && [res[0], res[1], res[3], res[4]] == [0.0, 50.0, 50.0, 100.0],
+            true
+        );
+
+        let data = vec![2.0; 3];
+        let mpt = MPTCalculator::from_v(&data);
+        let mut res = [f64::NAN; 3];
+        mpt.normalize(ClNormalizeMethod::ClNormalizeZScore, &mut res);
+        assert_eq!(res, [0.0; 3]);
+        mpt.normalize(ClNormalizeMethod::ClNormalizeMinMax, &mut res);
+        assert_eq!(res, [0.5; 3]);
+        mpt.normalize(ClNormalizeMethod::ClNormalizePercentile, &mut res);
+        assert_eq!(res, [50.0; 3]);
+
+        let err = mpt.normalize(ClNormalizeMethod::ClNormalizeMinMax, &mut res[1..]);
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
 }
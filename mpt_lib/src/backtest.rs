@@ -0,0 +1,766 @@
+use crate::{
+    common::is_sorted_array,
+    date_util::period_index,
+    enums::{self, Errors},
+    kernels, MPTCalculator,
+};
+
+///the result of a rebalancing backtest, the returns are in percent and the weights are fractions.
+#[derive(Clone, Debug)]
+pub struct BacktestResult {
+    ///the portfolio return of each period before the transaction costs.
+    pub returns: Vec<f64>,
+    ///the portfolio return of each period after the transaction costs.
+    pub net_returns: Vec<f64>,
+    ///the sum of the transaction costs of all rebalances, in percent of the portfolio value.
+    pub total_cost: f64,
+    ///the sum of the one way turnover of all rebalances.
+    pub turnover: f64,
+    ///the number of rebalances.
+    pub rebalance_count: i32,
+    ///the average of the max absolute drift from the target weights at the end of each period.
+    pub average_drift: f64,
+    ///the max absolute drift from the target weights.
+    pub max_drift: f64,
+}
+
+impl BacktestResult {
+    pub fn new() -> BacktestResult {
+        BacktestResult {
+            returns: Vec::new(),
+            net_returns: Vec::new(),
+            total_cost: f64::NAN,
+            turnover: f64::NAN,
+            rebalance_count: 0,
+            average_drift: f64::NAN,
+            max_drift: f64::NAN,
+        }
+    }
+}
+
+impl Default for BacktestResult {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///the transaction cost assumptions of the rebalancing, both are charged on the traded value.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TransactionCost {
+    ///the commission and fee of each trade, in basis points.
+    pub cost_bps: f64,
+    ///the slippage of each trade against the period end price, in basis points.
+    pub slippage_bps: f64,
+}
+
+///one phase of a glide path, the target weights apply from the start date until the start date of the next phase.
+#[derive(Clone, Debug)]
+pub struct GlidePathPhase {
+    pub start_date: i32,
+    pub target_weights: Vec<f64>,
+}
+
+///the performance and risk of the portfolio in one glide path phase, the returns are in percent.
+#[derive(Clone, Debug)]
+pub struct PhaseReport {
+    pub start_date: i32,
+    pub end_date: i32,
+    pub periods: i32,
+    pub total_return: f64,
+    pub standard_deviation: f64,
+    pub max_draw_down: f64,
+}
+
+///the turnover, drift and rebalancing need of each period of a weight history, the weights are fractions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeightHistoryReport {
+    ///the one way turnover from the weights of the previous period, it is NAN for the first period.
+    pub turnovers: Vec<f64>,
+    ///the max absolute drift from the target weights.
+    pub drifts: Vec<f64>,
+    ///a weight of the period is outside its band.
+    pub rebalance_needed: Vec<bool>,
+    ///the suggested trade of each asset back to the target weights, it is 0 if the period needs no rebalancing.
+    pub trades: Vec<Vec<f64>>,
+}
+
+impl WeightHistoryReport {
+    pub fn new() -> WeightHistoryReport {
+        WeightHistoryReport {
+            turnovers: Vec::new(),
+            drifts: Vec::new(),
+            rebalance_needed: Vec::new(),
+            trades: Vec::new(),
+        }
+    }
+}
+
+impl Default for WeightHistoryReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn phase_report(dates: &[i32], returns: &[f64]) -> PhaseReport {
+    let mut report = PhaseReport {
+        start_date: dates[0],
+        end_date: dates[dates.len() - 1],
+        periods: dates.len() as i32,
+        total_return: f64::NAN,
+        standard_deviation: f64::NAN,
+        max_draw_down: f64::NAN,
+    };
+    MPTCalculator::total_return_accumulat(returns, &mut report.total_return);
+    if !report.total_return.is_finite() {
+        return report;
+    }
+    if returns.len() > 1 {
+        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+        let variance = returns.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>()
+            / (returns.len() - 1) as f64;
+        report.standard_deviation = variance.sqrt();
+    }
+    report.max_draw_down = 0.0
+        - kernels::draw_down_depths(returns)
+            .into_iter()
+            .fold(0.0, f64::max);
+    return report;
+}
+
+///run the backtest with the target weights of each period, the weights are reset to the target weights of the
+///next period at the end of each rebalance period, when the drift exceeds the threshold or the target changes.
+fn run_backtest(
+    returns_matrix: &[&[f64]],
+    dates: &[i32],
+    targets: &[&[f64]],
+    rebalance_freq: enums::ClFrequency,
+    threshold: f64,
+    cost_rate: f64,
+    result: &mut BacktestResult,
+) {
+    let n = returns_matrix.len();
+    let mut weights = targets[0].to_vec();
+    let mut turnover = 0.0;
+    let mut total_cost = 0.0;
+    let mut drift_sum = 0.0;
+    let mut max_drift: f64 = 0.0;
+    for t in 0..dates.len() {
+        if returns_matrix.iter().all(|x| x[t].is_finite()) {
+            let portfolio: f64 = (0..n).map(|i| weights[i] * returns_matrix[i][t]).sum();
+            for i in 0..n {
+                weights[i] *= (1.0 + returns_matrix[i][t] / 100.0) / (1.0 + portfolio / 100.0);
+            }
+            result.returns.push(portfolio);
+        } else {
+            result.returns.push(f64::NAN);
+        }
+
+        let drift = (0..n)
+            .map(|i| (weights[i] - targets[t][i]).abs())
+            .fold(0.0, f64::max);
+        drift_sum += drift;
+        max_drift = max_drift.max(drift);
+
+        let is_last = t + 1 == dates.len();
+        let is_calendar = rebalance_freq != enums::ClFrequency::ClFrequencyUnknown
+            && !is_last
+            && period_index(rebalance_freq, dates[t + 1] as u64)
+                != period_index(rebalance_freq, dates[t] as u64);
+        let is_threshold = threshold > 0.0 && drift > threshold;
+        let is_new_target = !is_last && targets[t + 1] != targets[t];
+        let mut cost = 0.0;
+        if !is_last && (is_calendar || is_threshold || is_new_target) {
+            let traded: f64 = (0..n).map(|i| (weights[i] - targets[t + 1][i]).abs()).sum();
+            turnover += traded / 2.0;
+            cost = traded * cost_rate;
+            total_cost += cost * 100.0;
+            result.rebalance_count += 1;
+            weights.copy_from_slice(targets[t + 1]);
+        }
+        let gross = result.returns[t];
+        result
+            .net_returns
+            .push(((1.0 + gross / 100.0) * (1.0 - cost) - 1.0) * 100.0);
+    }
+    result.turnover = turnover;
+    result.total_cost = total_cost;
+    result.average_drift = drift_sum / dates.len() as f64;
+    result.max_drift = max_drift;
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///backtest a static weight portfolio, the portfolio begins at the target weights, the weights drift with the
+    ///asset returns and are reset to the target weights at the end of each rebalance period or when the drift
+    ///exceeds the threshold. the periods which have NAN/INF values in any asset have NAN portfolio return and
+    ///the weights do not drift in them.
+    ///# Arguments
+    ///returns_matrix: the return array of each asset, should have the same length as the dates.
+    ///
+    ///dates: the end date of each period, sorted ascending.
+    ///
+    ///target_weights: the target weight of each asset, the sum should be 1.
+    ///
+    ///rebalance_freq: the calendar rebalance frequency, ClFrequencyUnknown means no calendar rebalance.
+    ///
+    ///threshold: rebalance when the max absolute drift of the weights exceeds it, 0 means no threshold rebalance.
+    ///
+    ///result: the portfolio returns, turnover and drift statistics.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::backtest::BacktestResult;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let equity = vec![2.0, -1.0, 3.0, 1.5, -2.0, 1.0];
+    ///let bond = vec![0.5, 0.3, -0.2, 0.4, 0.6, 0.1];
+    ///let dates = vec![44957, 44985, 45016, 45046, 45077, 45107];
+    ///let mut result = BacktestResult::new();
+    ///let err = MPTCalculator::rebalance_backtest(
+    ///    &[&equity, &bond],
+    ///    &dates,
+    ///    &[0.6, 0.4],
+    ///    enums::ClFrequency::ClFrequencyQuarterly,
+    ///    0.0,
+    ///    &mut result,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(
+    ///            &result.returns,
+    ///            &[1.4, -0.484615, 1.721359, 1.06, -0.966792, 0.636693]
+    ///        )
+    ///        && MPTCalculator::is_eq_double(result.turnover, 0.007972)
+    ///        && result.rebalance_count == 1,
+    ///    true
+    ///);
+    ///```
+    pub fn rebalance_backtest(
+        returns_matrix: &[&[f64]],
+        dates: &[i32],
+        target_weights: &[f64],
+        rebalance_freq: enums::ClFrequency,
+        threshold: f64,
+        result: &mut BacktestResult,
+    ) -> Errors {
+        return MPTCalculator::rebalance_backtest_with_cost(
+            returns_matrix,
+            dates,
+            target_weights,
+            rebalance_freq,
+            threshold,
+            &TransactionCost::default(),
+            result,
+        );
+    }
+
+    ///backtest a static weight portfolio as rebalance_backtest, the transaction costs of each rebalance are
+    ///deducted from the return of the period in which the rebalance happens, the frictionless returns are kept
+    ///in the returns of the result and the net of cost returns are in the net_returns.
+    ///# Arguments
+    ///returns_matrix: the return array of each asset, should have the same length as the dates.
+    ///
+    ///dates: the end date of each period, sorted ascending.
+    ///
+    ///target_weights: the target weight of each asset, the sum should be 1.
+    ///
+    ///rebalance_freq: the calendar rebalance frequency, ClFrequencyUnknown means no calendar rebalance.
+    ///
+    ///threshold: rebalance when the max absolute drift of the weights exceeds it, 0 means no threshold rebalance.
+    ///
+    ///costs: the per trade cost and slippage in basis points.
+    ///
+    ///result: the gross and net portfolio returns, costs, turnover and drift statistics.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::backtest::{BacktestResult, TransactionCost};
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let equity = vec![2.0, -1.0, 3.0, 1.5, -2.0, 1.0];
+    ///let bond = vec![0.5, 0.3, -0.2, 0.4, 0.6, 0.1];
+    ///let dates = vec![44957, 44985, 45016, 45046, 45077, 45107];
+    ///let costs = TransactionCost {
+    ///    cost_bps: 25.0,
+    ///    slippage_bps: 5.0,
+    ///};
+    ///let mut result = BacktestResult::new();
+    ///let err = MPTCalculator::rebalance_backtest_with_cost(
+    ///    &[&equity, &bond],
+    ///    &dates,
+    ///    &[0.6, 0.4],
+    ///    enums::ClFrequency::ClFrequencyUnknown,
+    ///    0.01,
+    ///    &costs,
+    ///    &mut result,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(
+    ///            &result.net_returns,
+    ///            &[1.4, -0.484615, 1.721359, 1.062362, -0.96, 0.63433]
+    ///        )
+    ///        && MPTCalculator::is_eq_double(result.total_cost, 0.00634),
+    ///    true
+    ///);
+    ///```
+    pub fn rebalance_backtest_with_cost(
+        returns_matrix: &[&[f64]],
+        dates: &[i32],
+        target_weights: &[f64],
+        rebalance_freq: enums::ClFrequency,
+        threshold: f64,
+        costs: &TransactionCost,
+        result: &mut BacktestResult,
+    ) -> Errors {
+        instrument!(
+            "rebalance_backtest",
+            rows = dates.len(),
+            cols = returns_matrix.len()
+        );
+        if returns_matrix.len() == 0
+            || target_weights.len() != returns_matrix.len()
+            || returns_matrix.iter().any(|x| x.len() != dates.len())
+            || target_weights.iter().any(|x| !x.is_finite())
+            || !(threshold >= 0.0)
+            || !(costs.cost_bps >= 0.0)
+            || !(costs.slippage_bps >= 0.0)
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        if dates.len() > 1 && (!is_sorted_array(dates) || dates[0] > dates[dates.len() - 1]) {
+            return Errors::ClErrorCodeUnsortedByDate;
+        }
+        *result = BacktestResult::new();
+        if dates.len() == 0 {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let targets = vec![target_weights; dates.len()];
+        run_backtest(
+            returns_matrix,
+            dates,
+            &targets,
+            rebalance_freq,
+            threshold,
+            (costs.cost_bps + costs.slippage_bps) / 10000.0,
+            result,
+        );
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///backtest a portfolio which follows a glide path, the weights are reset to the target weights of the new
+    ///phase at the end of the last period of the previous phase, and between the phase changes the portfolio is
+    ///rebalanced as rebalance_backtest_with_cost. the statistics of each phase are based on the net returns.
+    ///# Arguments
+    ///returns_matrix: the return array of each asset, should have the same length as the dates.
+    ///
+    ///dates: the end date of each period, sorted ascending.
+    ///
+    ///phases: the phases of the glide path sorted by the start date, the first phase should start not later than
+    ///the first date.
+    ///
+    ///rebalance_freq: the calendar rebalance frequency, ClFrequencyUnknown means no calendar rebalance.
+    ///
+    ///threshold: rebalance when the max absolute drift of the weights exceeds it, 0 means no threshold rebalance.
+    ///
+    ///costs: the per trade cost and slippage in basis points.
+    ///
+    ///result: the gross and net portfolio returns, costs, turnover and drift statistics.
+    ///
+    ///reports: the return and risk statistics of each phase which has periods.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::backtest::{BacktestResult, GlidePathPhase, TransactionCost};
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let equity = vec![2.0, -1.0, 3.0, 1.5, -2.0, 1.0];
+    ///let bond = vec![0.5, 0.3, -0.2, 0.4, 0.6, 0.1];
+    ///let dates = vec![44957, 44985, 45016, 45046, 45077, 45107];
+    ///let phases = vec![
+    ///    GlidePathPhase {
+    ///        start_date: 44927,
+    ///        target_weights: vec![0.8, 0.2],
+    ///    },
+    ///    GlidePathPhase {
+    ///        start_date: 45017,
+    ///        target_weights: vec![0.4, 0.6],
+    ///    },
+    ///];
+    ///let mut result = BacktestResult::new();
+    ///let mut reports = Vec::new();
+    ///let err = MPTCalculator::glide_path_backtest(
+    ///    &[&equity, &bond],
+    ///    &dates,
+    ///    &phases,
+    ///    enums::ClFrequency::ClFrequencyUnknown,
+    ///    0.0,
+    ///    &TransactionCost::default(),
+    ///    &mut result,
+    ///    &mut reports,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(
+    ///            &result.returns,
+    ///            &[1.7, -0.743068, 2.360905, 0.84, -0.446807, 0.456703]
+    ///        )
+    ///        && MPTCalculator::is_eq_double(reports[0].total_return, 3.327499)
+    ///        && MPTCalculator::is_eq_double(reports[1].total_return, 0.847921),
+    ///    true
+    ///);
+    ///```
+    pub fn glide_path_backtest(
+        returns_matrix: &[&[f64]],
+        dates: &[i32],
+        phases: &[GlidePathPhase],
+        rebalance_freq: enums::ClFrequency,
+        threshold: f64,
+        costs: &TransactionCost,
+        result: &mut BacktestResult,
+        reports: &mut Vec<PhaseReport>,
+    ) -> Errors {
+        instrument!(
+            "glide_path_backtest",
+            rows = dates.len(),
+            cols = returns_matrix.len()
+        );
+        if returns_matrix.len() == 0
+            || phases.len() == 0
+            || returns_matrix.iter().any(|x| x.len() != dates.len())
+            || phases.iter().any(|x| {
+                x.target_weights.len() != returns_matrix.len()
+                    || x.target_weights.iter().any(|w| !w.is_finite())
+            })
+            || phases
+                .windows(2)
+                .any(|x| x[0].start_date >= x[1].start_date)
+            || !(threshold >= 0.0)
+            || !(costs.cost_bps >= 0.0)
+            || !(costs.slippage_bps >= 0.0)
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        if dates.len() > 1 && (!is_sorted_array(dates) || dates[0] > dates[dates.len() - 1]) {
+            return Errors::ClErrorCodeUnsortedByDate;
+        }
+        *result = BacktestResult::new();
+        reports.clear();
+        if dates.len() == 0 {
+            return Errors::ClErrorCodeNoError;
+        }
+        if phases[0].start_date > dates[0] {
+            return Errors::ClErrorCodeInvalidDate;
+        }
+
+        let phase_of: Vec<usize> = dates
+            .iter()
+            .map(|d| phases.partition_point(|x| x.start_date <= *d) - 1)
+            .collect();
+        let targets: Vec<&[f64]> = phase_of
+            .iter()
+            .map(|i| phases[*i].target_weights.as_slice())
+            .collect();
+        run_backtest(
+            returns_matrix,
+            dates,
+            &targets,
+            rebalance_freq,
+            threshold,
+            (costs.cost_bps + costs.slippage_bps) / 10000.0,
+            result,
+        );
+
+        let mut start = 0;
+        for t in 1..=dates.len() {
+            if t == dates.len() || phase_of[t] != phase_of[start] {
+                reports.push(phase_report(
+                    &dates[start..t],
+                    &result.net_returns[start..t],
+                ));
+                start = t;
+            }
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///analyze a history of the portfolio weights against the target weights without the asset returns, e.g. the
+    ///weights of the holdings reports. a period needs rebalancing if the absolute drift of a weight exceeds the
+    ///absolute band, or exceeds the relative band times its target weight, the suggested trades of the period
+    ///reset all the weights to the target weights. the turnover, drift and trades of the periods with NAN/INF weights
+    ///are NAN.
+    ///# Arguments
+    ///weights_history: the weights of each period, each should have the same size as the targets.
+    ///
+    ///targets: the target weights.
+    ///
+    ///absolute_band: the max absolute drift of a weight, e.g. 0.05, 0 is no absolute band.
+    ///
+    ///relative_band: the max drift of a weight relative to its target weight, e.g. 0.25, 0 is no relative band.
+    ///
+    ///report: the turnover, drift, rebalancing need and suggested trades of each period.
+    ///# Examples
+    ///```
+    ///use mpt_lib::backtest::WeightHistoryReport;
+    ///use mpt_lib::enums::Errors;
+    ///use mpt_lib::MPTCalculator;
+    ///
+    ///let history = [[0.6, 0.4], [0.63, 0.37], [0.7, 0.3]];
+    ///let history: Vec<&[f64]> = history.iter().map(|x| x.as_slice()).collect();
+    ///let mut report = WeightHistoryReport::new();
+    ///let err = MPTCalculator::weight_history_analytics(&history, &[0.6, 0.4], 0.05, 0.0, &mut report);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && report.rebalance_needed == vec![false, false, true]
+    ///        && MPTCalculator::is_eq_double_array(&report.trades[2], &[-0.1, 0.1])
+    ///        && MPTCalculator::is_eq_double(report.turnovers[2], 0.07),
+    ///    true
+    ///);
+    ///```
+    pub fn weight_history_analytics(
+        weights_history: &[&[f64]],
+        targets: &[f64],
+        absolute_band: f64,
+        relative_band: f64,
+        report: &mut WeightHistoryReport,
+    ) -> Errors {
+        *report = WeightHistoryReport::new();
+        if weights_history.len() == 0
+            || targets.len() == 0
+            || weights_history.iter().any(|x| x.len() != targets.len())
+            || targets.iter().any(|x| !x.is_finite())
+            || !(absolute_band.is_finite() && absolute_band >= 0.0)
+            || !(relative_band.is_finite() && relative_band >= 0.0)
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+
+        for (t, weights) in weights_history.iter().enumerate() {
+            report.turnovers.push(if t == 0 {
+                f64::NAN
+            } else {
+                weights
+                    .iter()
+                    .zip(weights_history[t - 1].iter())
+                    .map(|x| (x.0 - x.1).abs())
+                    .sum::<f64>()
+                    / 2.0
+            });
+            let drifts: Vec<f64> = weights
+                .iter()
+                .zip(targets.iter())
+                .map(|x| x.0 - x.1)
+                .collect();
+            if drifts.iter().any(|x| !x.is_finite()) {
+                report.drifts.push(f64::NAN);
+                report.rebalance_needed.push(false);
+                report.trades.push(vec![f64::NAN; targets.len()]);
+                continue;
+            }
+            report
+                .drifts
+                .push(drifts.iter().fold(0.0, |acc, x| f64::max(acc, x.abs())));
+            let needed = drifts.iter().zip(targets.iter()).any(|(drift, target)| {
+                (absolute_band > 0.0 && drift.abs() > absolute_band)
+                    || (relative_band > 0.0 && drift.abs() > relative_band * target.abs())
+            });
+            report.rebalance_needed.push(needed);
+            report.trades.push(if needed {
+                drifts.iter().map(|x| -x).collect()
+            } else {
+                vec![0.0; targets.len()]
+            });
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BacktestResult, GlidePathPhase, TransactionCost, WeightHistoryReport};
+    use crate::{
+        enums::{self, Errors},
+        MPTCalculator,
+    };
+
+    const EQUITY: [f64; 6] = [2.0, -1.0, 3.0, 1.5, -2.0, 1.0];
+    const BOND: [f64; 6] = [0.5, 0.3, -0.2, 0.4, 0.6, 0.1];
+    const DATES: [i32; 6] = [44957, 44985, 45016, 45046, 45077, 45107];
+
+    #[test]
+    fn should_correct_rebalance_backtest() {
+        let mut result = BacktestResult::new();
+        let err = MPTCalculator::rebalance_backtest(
+            &[&EQUITY, &BOND],
+            &DATES,
+            &[0.6, 0.4],
+            enums::ClFrequency::ClFrequencyQuarterly,
+            0.0,
+            &mut result,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(
+                    &result.returns,
+                    &[1.4, -0.484615, 1.721359, 1.06, -0.966792, 0.636693]
+                )
+                && MPTCalculator::is_eq_double(result.turnover, 0.007972)
+                && result.rebalance_count == 1
+                && MPTCalculator::is_eq_double(result.average_drift, 0.003293)
+                && MPTCalculator::is_eq_double(result.max_drift, 0.007972),
+            true
+        );
+
+        let err = MPTCalculator::rebalance_backtest(
+            &[&EQUITY, &BOND],
+            &DATES,
+            &[0.6, 0.4],
+            enums::ClFrequency::ClFrequencyUnknown,
+            0.01,
+            &mut result,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(
+                    &result.returns,
+                    &[1.4, -0.484615, 1.721359, 1.068769, -0.96, 0.63433]
+                )
+                && MPTCalculator::is_eq_double(result.turnover, 0.010566)
+                && result.rebalance_count == 1
+                && MPTCalculator::is_eq_double(result.average_drift, 0.005493),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_rebalance_backtest_with_cost() {
+        let costs = TransactionCost {
+            cost_bps: 25.0,
+            slippage_bps: 5.0,
+        };
+        let mut result = BacktestResult::new();
+        let err = MPTCalculator::rebalance_backtest_with_cost(
+            &[&EQUITY, &BOND],
+            &DATES,
+            &[0.6, 0.4],
+            enums::ClFrequency::ClFrequencyUnknown,
+            0.01,
+            &costs,
+            &mut result,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(
+                    &result.returns,
+                    &[1.4, -0.484615, 1.721359, 1.068769, -0.96, 0.63433]
+                )
+                && MPTCalculator::is_eq_double_array(
+                    &result.net_returns,
+                    &[1.4, -0.484615, 1.721359, 1.062362, -0.96, 0.63433]
+                )
+                && MPTCalculator::is_eq_double(result.total_cost, 0.00634),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_glide_path_backtest() {
+        let phases = vec![
+            GlidePathPhase {
+                start_date: 44927,
+                target_weights: vec![0.8, 0.2],
+            },
+            GlidePathPhase {
+                start_date: 45017,
+                target_weights: vec![0.4, 0.6],
+            },
+        ];
+        let mut result = BacktestResult::new();
+        let mut reports = Vec::new();
+        let err = MPTCalculator::glide_path_backtest(
+            &[&EQUITY, &BOND],
+            &DATES,
+            &phases,
+            enums::ClFrequency::ClFrequencyUnknown,
+            0.0,
+            &TransactionCost::default(),
+            &mut result,
+            &mut reports,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(
+                    &result.returns,
+                    &[1.7, -0.743068, 2.360905, 0.84, -0.446807, 0.456703]
+                )
+                && MPTCalculator::is_eq_double(result.turnover, 0.40528)
+                && result.rebalance_count == 1
+                && reports.len() == 2
+                && reports[0].end_date == 45016
+                && reports[1].periods == 3
+                && MPTCalculator::is_eq_double(reports[0].total_return, 3.327499)
+                && MPTCalculator::is_eq_double(reports[0].standard_deviation, 1.635034)
+                && MPTCalculator::is_eq_double(reports[0].max_draw_down, -0.743068)
+                && MPTCalculator::is_eq_double(reports[1].total_return, 0.847921)
+                && MPTCalculator::is_eq_double(reports[1].standard_deviation, 0.660696)
+                && MPTCalculator::is_eq_double(reports[1].max_draw_down, -0.446807),
+            true
+        );
+
+        let err = MPTCalculator::glide_path_backtest(
+            &[&EQUITY, &BOND],
+            &DATES,
+            &phases[1..],
+            enums::ClFrequency::ClFrequencyUnknown,
+            0.0,
+            &TransactionCost::default(),
+            &mut result,
+            &mut reports,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidDate);
+    }
+
+    #[test]
+    fn should_correct_weight_history_analytics() {
+        let history = [
+            [0.6, 0.3, 0.1],
+            [0.62, 0.28, 0.1],
+            [0.6, 0.28, 0.12],
+            [0.7, 0.25, 0.05],
+            [f64::NAN, 0.3, 0.1],
+        ];
+        let history: Vec<&[f64]> = history.iter().map(|x| x.as_slice()).collect();
+        let targets = [0.6, 0.3, 0.1];
+        let mut report = WeightHistoryReport::new();
+        let err =
+            MPTCalculator::weight_history_analytics(&history, &targets, 0.0, 0.15, &mut report);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && report.turnovers[0].is_nan()
+                && MPTCalculator::is_eq_double_array(&report.turnovers[1..4], &[0.02, 0.02, 0.1])
+                && MPTCalculator::is_eq_double_array(&report.drifts[..4], &[0.0, 0.02, 0.02, 0.1])
+                && report.rebalance_needed[..4] == [false, false, true, true]
+                && MPTCalculator::is_eq_double_array(&report.trades[2], &[0.0, 0.02, -0.02])
+                && MPTCalculator::is_eq_double_array(&report.trades[1], &[0.0, 0.0, 0.0]),
+            true
+        );
+
+        MPTCalculator::weight_history_analytics(&history, &targets, 0.05, 0.0, &mut report);
+        assert_eq!(
+            report.rebalance_needed[..4] == [false, false, false, true],
+            true
+        );
+
+        let err = MPTCalculator::weight_history_analytics(
+            &history,
+            &targets[..2],
+            0.05,
+            0.0,
+            &mut report,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && report.drifts.len() == 0,
+            true
+        );
+    }
+}
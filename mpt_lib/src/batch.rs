@@ -0,0 +1,561 @@
+use crate::{
+    cancel::CancellationToken,
+    common::check_sample_size,
+    enums::{self, Errors},
+    kernels,
+    linalg::covariance_matrix_by_column,
+    MPTCalculator,
+};
+
+#[cfg(feature = "gpu")]
+use crate::gpu;
+
+///the gpu kernels without the gpu feature, the batch statistics always fall back to the cpu.
+#[cfg(not(feature = "gpu"))]
+mod gpu {
+    pub(super) fn covariance_matrix(_columns: &[&[f64]]) -> Option<Vec<Vec<f64>>> {
+        None
+    }
+
+    pub(super) fn rolling_standard_deviation(
+        _columns: &[&[f64]],
+        _window: usize,
+    ) -> Option<Vec<Vec<f64>>> {
+        None
+    }
+}
+
+///check the returns matrix of the batch statistics, all the columns should have the same length of min_count at least.
+fn check_returns_matrix(returns_matrix: &[&[f64]], min_count: usize) -> Errors {
+    if returns_matrix.len() == 0
+        || returns_matrix
+            .iter()
+            .any(|x| x.len() != returns_matrix[0].len())
+    {
+        return Errors::ClErrorCodeInvalidPara;
+    }
+    return check_sample_size(returns_matrix[0].len(), min_count);
+}
+
+fn is_finite_matrix(returns_matrix: &[&[f64]]) -> bool {
+    return returns_matrix
+        .iter()
+        .all(|x| x.iter().all(|v| v.is_finite()));
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///calculate the sample covariance matrix of the columns of the returns matrix.
+    ///ClComputeGpu offloads the calculation to the gpu if the lib is built with the gpu feature and there is a gpu,
+    ///otherwise it falls back to the cpu, used_backend is the backend which calculated the matrix. the gpu uses f32,
+    ///so its results have about 6 significant digits. the covariances of a column with NAN/INF values are NAN.
+    ///
+    ///# Arguments
+    ///returns_matrix: the returns of each asset, all the columns should have the same length.
+    ///
+    ///backend: the preferred backend.
+    ///
+    ///matrix: the covariance matrix, matrix[i][j] is the covariance of the column i and the column j.
+    ///
+    ///used_backend: the backend which calculated the matrix.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{ClComputeBackend, Errors};
+    ///
+    ///let fund = vec![1.0, 2.0, 3.0];
+    ///let index = vec![2.0, 4.0, 6.0];
+    ///let mut matrix = Vec::new();
+    ///let mut used_backend = ClComputeBackend::ClComputeGpu;
+    ///let err = MPTCalculator::covariance_matrix_batch(
+    ///    &[&fund, &index],
+    ///    ClComputeBackend::ClComputeCpu,
+    ///    &mut matrix,
+    ///    &mut used_backend,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && matrix == vec![vec![1.0, 2.0], vec![2.0, 4.0]]
+    ///        && used_backend == ClComputeBackend::ClComputeCpu,
+    ///    true
+    ///);
+    ///```
+    pub fn covariance_matrix_batch(
+        returns_matrix: &[&[f64]],
+        backend: enums::ClComputeBackend,
+        matrix: &mut Vec<Vec<f64>>,
+        used_backend: &mut enums::ClComputeBackend,
+    ) -> Errors {
+        return MPTCalculator::covariance_matrix_batch_with_cancel(
+            returns_matrix,
+            backend,
+            &CancellationToken::new(),
+            matrix,
+            used_backend,
+        );
+    }
+
+    ///calculate the covariance matrix as covariance_matrix_batch, the cpu checks the token for each column and
+    ///returns ClErrorCodeCancelled after it is cancelled, the gpu is not interrupted.
+    pub fn covariance_matrix_batch_with_cancel(
+        returns_matrix: &[&[f64]],
+        backend: enums::ClComputeBackend,
+        cancel: &CancellationToken,
+        matrix: &mut Vec<Vec<f64>>,
+        used_backend: &mut enums::ClComputeBackend,
+    ) -> Errors {
+        return MPTCalculator::covariance_matrix_batch_with_progress(
+            returns_matrix,
+            backend,
+            cancel,
+            |_, _| {},
+            matrix,
+            used_backend,
+        );
+    }
+
+    ///calculate the covariance matrix as covariance_matrix_batch_with_cancel and report the progress by
+    ///progress(done, total), the done is the number of the finished columns and the total is the number of the
+    ///columns. the gpu reports the progress only when it finishes.
+    ///# Examples
+    ///```
+    ///use mpt_lib::cancel::CancellationToken;
+    ///use mpt_lib::enums::{ClComputeBackend, Errors};
+    ///use mpt_lib::MPTCalculator;
+    ///
+    ///let fund = vec![1.0, 2.0, 3.0];
+    ///let index = vec![2.0, 4.0, 6.0];
+    ///let mut matrix = Vec::new();
+    ///let mut used_backend = ClComputeBackend::ClComputeCpu;
+    ///let mut reports = Vec::new();
+    ///let err = MPTCalculator::covariance_matrix_batch_with_progress(
+    ///    &[&fund, &index],
+    ///    ClComputeBackend::ClComputeCpu,
+    ///    &CancellationToken::new(),
+    ///    |done, total| reports.push((done, total)),
+    ///    &mut matrix,
+    ///    &mut used_backend,
+    ///);
+    ///assert_eq!(err == Errors::ClErrorCodeNoError && reports == vec![(1, 2), (2, 2)], true);
+    ///```
+    pub fn covariance_matrix_batch_with_progress<F: FnMut(usize, usize)>(
+        returns_matrix: &[&[f64]],
+        backend: enums::ClComputeBackend,
+        cancel: &CancellationToken,
+        mut progress: F,
+        matrix: &mut Vec<Vec<f64>>,
+        used_backend: &mut enums::ClComputeBackend,
+    ) -> Errors {
+        instrument!(
+            "covariance_matrix_batch",
+            rows = returns_matrix.first().map_or(0, |x| x.len()),
+            cols = returns_matrix.len()
+        );
+        matrix.clear();
+        *used_backend = enums::ClComputeBackend::ClComputeCpu;
+        let ret = check_returns_matrix(returns_matrix, 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+
+        if backend == enums::ClComputeBackend::ClComputeGpu && is_finite_matrix(returns_matrix) {
+            if let Some(cov) = gpu::covariance_matrix(returns_matrix) {
+                *matrix = cov;
+                progress(returns_matrix.len(), returns_matrix.len());
+                *used_backend = enums::ClComputeBackend::ClComputeGpu;
+                return Errors::ClErrorCodeNoError;
+            }
+        }
+
+        match covariance_matrix_by_column(returns_matrix, cancel, &mut progress) {
+            Some(cov) => *matrix = cov,
+            None => return Errors::ClErrorCodeCancelled,
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the sample standard deviation of each rolling window of each column of the returns matrix, it is not
+    ///annualized. the value of a period is the standard deviation of the window ending at the period, the periods
+    ///before the first full window and the windows with NAN/INF values are NAN.
+    ///ClComputeGpu offloads the calculation to the gpu if the lib is built with the gpu feature and there is a gpu,
+    ///otherwise it falls back to the cpu, the gpu uses f32, so its results have about 6 significant digits.
+    ///
+    ///# Arguments
+    ///returns_matrix: the returns of each asset, all the columns should have the same length.
+    ///
+    ///window: the number of periods of the window, it is 2 at least.
+    ///
+    ///backend: the preferred backend.
+    ///
+    ///results: the rolling standard deviations of each column, the same size as the column.
+    ///
+    ///used_backend: the backend which calculated the results.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{ClComputeBackend, Errors};
+    ///
+    ///let fund = vec![1.0, 3.0, 5.0, 5.0];
+    ///let mut results = Vec::new();
+    ///let mut used_backend = ClComputeBackend::ClComputeGpu;
+    ///let err = MPTCalculator::rolling_standard_deviation_batch(
+    ///    &[&fund],
+    ///    2,
+    ///    ClComputeBackend::ClComputeCpu,
+    ///    &mut results,
+    ///    &mut used_backend,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && results[0][0].is_nan()
+    ///        && MPTCalculator::is_eq_double_array(&results[0][1..], &[1.414214, 1.414214, 0.0]),
+    ///    true
+    ///);
+    ///```
+    pub fn rolling_standard_deviation_batch(
+        returns_matrix: &[&[f64]],
+        window: usize,
+        backend: enums::ClComputeBackend,
+        results: &mut Vec<Vec<f64>>,
+        used_backend: &mut enums::ClComputeBackend,
+    ) -> Errors {
+        return MPTCalculator::rolling_standard_deviation_batch_with_cancel(
+            returns_matrix,
+            window,
+            backend,
+            &CancellationToken::new(),
+            results,
+            used_backend,
+        );
+    }
+
+    ///calculate the rolling standard deviations as rolling_standard_deviation_batch, the cpu checks the token for
+    ///each column and returns ClErrorCodeCancelled with empty results after it is cancelled, the gpu is not
+    ///interrupted.
+    pub fn rolling_standard_deviation_batch_with_cancel(
+        returns_matrix: &[&[f64]],
+        window: usize,
+        backend: enums::ClComputeBackend,
+        cancel: &CancellationToken,
+        results: &mut Vec<Vec<f64>>,
+        used_backend: &mut enums::ClComputeBackend,
+    ) -> Errors {
+        return MPTCalculator::rolling_standard_deviation_batch_with_progress(
+            returns_matrix,
+            window,
+            backend,
+            cancel,
+            |_, _| {},
+            results,
+            used_backend,
+        );
+    }
+
+    ///calculate the rolling standard deviations as rolling_standard_deviation_batch_with_cancel and report the
+    ///progress by progress(done, total), the done is the number of the finished columns and the total is the number
+    ///of the columns. the gpu reports the progress only when it finishes.
+    pub fn rolling_standard_deviation_batch_with_progress<F: FnMut(usize, usize)>(
+        returns_matrix: &[&[f64]],
+        window: usize,
+        backend: enums::ClComputeBackend,
+        cancel: &CancellationToken,
+        mut progress: F,
+        results: &mut Vec<Vec<f64>>,
+        used_backend: &mut enums::ClComputeBackend,
+    ) -> Errors {
+        instrument!(
+            "rolling_standard_deviation_batch",
+            rows = returns_matrix.first().map_or(0, |x| x.len()),
+            cols = returns_matrix.len(),
+            window = window
+        );
+        results.clear();
+        *used_backend = enums::ClComputeBackend::ClComputeCpu;
+        if window < 2 {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let ret = check_returns_matrix(returns_matrix, window);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+
+        if backend == enums::ClComputeBackend::ClComputeGpu && is_finite_matrix(returns_matrix) {
+            if let Some(rolling) = gpu::rolling_standard_deviation(returns_matrix, window) {
+                *results = rolling;
+                progress(returns_matrix.len(), returns_matrix.len());
+                *used_backend = enums::ClComputeBackend::ClComputeGpu;
+                return Errors::ClErrorCodeNoError;
+            }
+        }
+
+        for column in returns_matrix.iter() {
+            if cancel.is_cancelled() {
+                results.clear();
+                return Errors::ClErrorCodeCancelled;
+            }
+            let mut rolling = vec![f64::NAN; column.len()];
+            for t in window - 1..column.len() {
+                kernels::standard_deviation(
+                    &column[t + 1 - window..=t],
+                    enums::ClFrequency::ClFrequencyMonthly,
+                    false,
+                    enums::ClVarianceMode::ClVarianceSample,
+                    &mut rolling[t],
+                );
+            }
+            results.push(rolling);
+            progress(results.len(), returns_matrix.len());
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the statistics of each column of the returns matrix against the same benchmark and risk free,
+    ///results[i][j] is the statistic i of the column j. the statistic of a column which returns an error is NAN,
+    ///e.g. the funds with insufficient history, so one fund does not fail the universe.
+    ///
+    ///# Arguments
+    ///returns_matrix: the returns of each fund, all the columns should have the same length.
+    ///
+    ///benchmark: the benchmark of the funds, it can be empty if no statistic needs it.
+    ///
+    ///riskfree: the risk free of the funds, it can be empty if no statistic needs it.
+    ///
+    ///statistics: the statistics of each fund.
+    ///
+    ///results: the value of each statistic of each fund.
+    ///# Examples
+    ///```
+    ///use mpt_lib::enums::{ClFrequency, Errors};
+    ///use mpt_lib::MPTCalculator;
+    ///
+    ///let fund1 = vec![1.0, 2.0, 3.0];
+    ///let fund2 = vec![2.0, 4.0, 6.0];
+    ///let index = vec![1.0, 1.5, 2.0];
+    ///let mut results = Vec::new();
+    ///let err = MPTCalculator::statistics_batch(
+    ///    &[&fund1, &fund2],
+    ///    &index,
+    ///    &[],
+    ///    &[|calc, res| calc.average(res), |calc, res| calc.beta(res)],
+    ///    &mut results,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && results == vec![vec![2.0, 4.0], vec![2.0, 4.0]],
+    ///    true
+    ///);
+    ///```
+    pub fn statistics_batch(
+        returns_matrix: &[&[f64]],
+        benchmark: &[f64],
+        riskfree: &[f64],
+        statistics: &[fn(&MPTCalculator, &mut f64) -> Errors],
+        results: &mut Vec<Vec<f64>>,
+    ) -> Errors {
+        results.clear();
+        let ret = check_returns_matrix(returns_matrix, 1);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        let rows = returns_matrix[0].len();
+        if (benchmark.len() != 0 && benchmark.len() != rows)
+            || (riskfree.len() != 0 && riskfree.len() != rows)
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        instrument!(
+            "statistics_batch",
+            rows = rows,
+            cols = returns_matrix.len(),
+            statistics = statistics.len()
+        );
+
+        *results = vec![vec![f64::NAN; returns_matrix.len()]; statistics.len()];
+        for (j, column) in returns_matrix.iter().enumerate() {
+            let calc = MPTCalculator::from(column, benchmark, riskfree);
+            for (i, statistic) in statistics.iter().enumerate() {
+                let mut value = f64::NAN;
+                if statistic(&calc, &mut value) == Errors::ClErrorCodeNoError {
+                    results[i][j] = value;
+                }
+            }
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        cancel::CancellationToken,
+        enums::{ClComputeBackend, ClFrequency, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_batch_on_both_backends() {
+        let fund = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477,
+        ];
+        let index = vec![
+            1.22072, -2.0668, 0.20588, 0.91563, 1.76766, 2.21429, 2.43456, 1.99825, 0.89481,
+            1.59564, -1.86793, 0.41477,
+        ];
+        let returns_matrix: [&[f64]; 2] = [&fund, &index];
+        let mut cpu = Vec::new();
+        let mut used_backend = ClComputeBackend::ClComputeGpu;
+        let err = MPTCalculator::covariance_matrix_batch(
+            &returns_matrix,
+            ClComputeBackend::ClComputeCpu,
+            &mut cpu,
+            &mut used_backend,
+        );
+        let mut covariance = f64::NAN;
+        MPTCalculator::from_v_b(&fund, &index).covariance(&mut covariance);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && used_backend == ClComputeBackend::ClComputeCpu
+                && MPTCalculator::is_eq_double(cpu[0][1], covariance),
+            true
+        );
+
+        //the gpu is used if there is one, otherwise the cpu, both should agree in f32 precision.
+        let mut gpu = Vec::new();
+        let err = MPTCalculator::covariance_matrix_batch(
+            &returns_matrix,
+            ClComputeBackend::ClComputeGpu,
+            &mut gpu,
+            &mut used_backend,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && (used_backend == ClComputeBackend::ClComputeGpu || gpu == cpu)
+                && MPTCalculator::approx_eq_array(&gpu.concat(), &cpu.concat(), 1e-4),
+            true
+        );
+
+        let (mut cpu, mut gpu) = (Vec::new(), Vec::new());
+        MPTCalculator::rolling_standard_deviation_batch(
+            &returns_matrix,
+            3,
+            ClComputeBackend::ClComputeCpu,
+            &mut cpu,
+            &mut used_backend,
+        );
+        let err = MPTCalculator::rolling_standard_deviation_batch(
+            &returns_matrix,
+            3,
+            ClComputeBackend::ClComputeGpu,
+            &mut gpu,
+            &mut used_backend,
+        );
+        let mut expected = f64::NAN;
+        MPTCalculator::from_v(&index[3..6]).standard_deviation(
+            crate::enums::ClFrequency::ClFrequencyMonthly,
+            false,
+            &mut expected,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && cpu[0][1].is_nan()
+                && MPTCalculator::is_eq_double(cpu[1][5], expected)
+                && MPTCalculator::approx_eq_array(&gpu.concat(), &cpu.concat(), 1e-4),
+            true
+        );
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let err = MPTCalculator::covariance_matrix_batch_with_cancel(
+            &returns_matrix,
+            ClComputeBackend::ClComputeCpu,
+            &cancel,
+            &mut cpu,
+            &mut used_backend,
+        );
+        assert_eq!(err == Errors::ClErrorCodeCancelled && cpu.len() == 0, true);
+
+        let cancel = CancellationToken::new();
+        let mut reports = Vec::new();
+        let err = MPTCalculator::rolling_standard_deviation_batch_with_progress(
+            &returns_matrix,
+            3,
+            ClComputeBackend::ClComputeCpu,
+            &cancel,
+            |done, total| {
+                reports.push((done, total));
+                cancel.cancel();
+            },
+            &mut cpu,
+            &mut used_backend,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeCancelled && reports == vec![(1, 2)] && cpu.len() == 0,
+            true
+        );
+
+        let short = vec![1.0; 11];
+        assert_eq!(
+            MPTCalculator::covariance_matrix_batch(
+                &[&fund, &short],
+                ClComputeBackend::ClComputeCpu,
+                &mut cpu,
+                &mut used_backend,
+            ),
+            Errors::ClErrorCodeInvalidPara
+        );
+        assert_eq!(
+            MPTCalculator::rolling_standard_deviation_batch(
+                &[&fund[..2]],
+                3,
+                ClComputeBackend::ClComputeCpu,
+                &mut cpu,
+                &mut used_backend,
+            ),
+            Errors::ClErrorCodeInsufficientData
+        );
+    }
+
+    #[test]
+    fn should_correct_statistics_batch() {
+        let fund = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477,
+        ];
+        let short_history = vec![f64::NAN; 12];
+        let riskfree = vec![0.1; 12];
+        let index = vec![
+            1.22072, -2.0668, 0.20588, 0.91563, 1.76766, 2.21429, 2.43456, 1.99825, 0.89481,
+            1.59564, -1.86793, 0.41477,
+        ];
+        let mut results = Vec::new();
+        let err = MPTCalculator::statistics_batch(
+            &[&fund, &short_history],
+            &index,
+            &riskfree,
+            &[
+                |calc, res| calc.standard_deviation(ClFrequency::ClFrequencyMonthly, true, res),
+                |calc, res| calc.sharpe_ratio(ClFrequency::ClFrequencyMonthly, true, res),
+            ],
+            &mut results,
+        );
+        let mut expected = f64::NAN;
+        MPTCalculator::from_v(&fund).standard_deviation(
+            ClFrequency::ClFrequencyMonthly,
+            true,
+            &mut expected,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && results[0][0] == expected
+                && results[0][1].is_nan()
+                && results[1][0].is_finite()
+                && results[1][1].is_nan(),
+            true
+        );
+
+        let err = MPTCalculator::statistics_batch(&[&fund], &index[1..], &[], &[], &mut results);
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && results.len() == 0,
+            true
+        );
+    }
+}
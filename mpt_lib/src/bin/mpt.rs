@@ -0,0 +1,237 @@
+//! the command line tool of the standard statistics pack.
+//!
+//! usage: mpt <csv file> [--json] [--freq <frequency>] [--stats <name,name,...>]
+//!
+//! the csv file has a header line, the first column is the date, the following columns are the fund,
+//! the benchmark and the risk free returns in percent, the benchmark and the risk free columns are optional.
+//! the frequency is detected from the dates if --freq is not set, it can be daily, weekly, semimonthly, monthly,
+//! bimonthly, quarterly, semiannually or annually.
+use std::{fs::File, io::BufReader, process::exit};
+
+use mpt_lib::{
+    date_util,
+    enums::{ClFrequency, Errors},
+    ingest::CsvChunkReader,
+    report::{self, StandardPack},
+    MPTCalculator,
+};
+
+const USAGE: &str = "usage: mpt <csv file> [--json] [--freq <frequency>] [--stats <name,name,...>]";
+
+struct Options {
+    path: String,
+    json: bool,
+    freq: Option<ClFrequency>,
+    stats: Vec<String>,
+}
+
+fn parse_frequency(text: &str) -> Option<ClFrequency> {
+    match text.to_lowercase().as_str() {
+        "daily" => Some(ClFrequency::ClFrequencyDaily),
+        "weekly" => Some(ClFrequency::ClFrequencyWeekly),
+        "semimonthly" => Some(ClFrequency::ClFrequencySemimonthly),
+        "monthly" => Some(ClFrequency::ClFrequencyMonthly),
+        "bimonthly" => Some(ClFrequency::ClFrequencyBimonthly),
+        "quarterly" => Some(ClFrequency::ClFrequencyQuarterly),
+        "semiannually" => Some(ClFrequency::ClFrequencySemiannually),
+        "annually" => Some(ClFrequency::ClFrequencyAnnually),
+        _ => None,
+    }
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        path: String::new(),
+        json: false,
+        freq: None,
+        stats: Vec::new(),
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => options.json = true,
+            "--freq" => {
+                let text = args.next().ok_or("--freq needs a frequency")?;
+                options.freq =
+                    Some(parse_frequency(text).ok_or(format!("unknown frequency {}", text))?);
+            }
+            "--stats" => {
+                let text = args.next().ok_or("--stats needs the statistic names")?;
+                options.stats = text.split(',').map(|x| x.trim().to_string()).collect();
+            }
+            _ if options.path.len() == 0 && !arg.starts_with("--") => options.path = arg.clone(),
+            _ => return Err(format!("unknown argument {}", arg)),
+        }
+    }
+    if options.path.len() == 0 {
+        return Err(String::from("missing csv file"));
+    }
+    return Ok(options);
+}
+
+fn run(options: &Options) -> Result<String, String> {
+    let file = File::open(&options.path).map_err(|e| format!("{}: {}", options.path, e))?;
+    let reader = CsvChunkReader::new(BufReader::new(file), 4096)
+        .map_err(|e| format!("{}: {:?}", options.path, e))?;
+    let mut dates = Vec::new();
+    let mut columns: Vec<Vec<f64>> = vec![Vec::new(); reader.names().len().min(3)];
+    for chunk in reader {
+        let chunk = chunk.map_err(|e| format!("{}: {:?}", options.path, e))?;
+        dates.extend(chunk.dates);
+        columns
+            .iter_mut()
+            .zip(chunk.columns)
+            .for_each(|(column, values)| column.extend(values));
+    }
+
+    let freq = options
+        .freq
+        .unwrap_or_else(|| date_util::infer_frequency(&dates));
+    if freq == ClFrequency::ClFrequencyUnknown {
+        return Err(String::from(
+            "can not detect the frequency, set it by --freq",
+        ));
+    }
+    let empty = Vec::new();
+    let calc = MPTCalculator::from(
+        &columns[0],
+        columns.get(1).unwrap_or(&empty),
+        columns.get(2).unwrap_or(&empty),
+    );
+    let mut pack = StandardPack::new();
+    let err = report::standard_pack(&calc, &dates, freq, &mut pack);
+    if err != Errors::ClErrorCodeNoError {
+        return Err(format!("{:?}", err));
+    }
+
+    let values: Vec<(&str, f64)> = pack
+        .values()
+        .into_iter()
+        .filter(|(name, _)| options.stats.len() == 0 || options.stats.iter().any(|x| x == name))
+        .collect();
+    if let Some(name) = options
+        .stats
+        .iter()
+        .find(|x| values.iter().all(|(name, _)| name != x))
+    {
+        return Err(format!("unknown statistic {}", name));
+    }
+    if options.json {
+        return Ok(report::values_to_json(&values));
+    }
+    let lines: Vec<String> = values
+        .iter()
+        .map(|(name, v)| {
+            if name.ends_with("_date") {
+                format!("{:<28}{:>14}", name, v)
+            } else {
+                format!("{:<28}{:>14.6}", name, v)
+            }
+        })
+        .collect();
+    return Ok(lines.join("\n"));
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match parse_options(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}\n{}", message, USAGE);
+            exit(2);
+        }
+    };
+    match run(&options) {
+        Ok(output) => println!("{}", output),
+        Err(message) => {
+            eprintln!("{}", message);
+            exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(text: &str) -> Vec<String> {
+        text.split_whitespace().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn should_correct_parse_options() {
+        let options =
+            parse_options(&args("a.csv --json --freq Monthly --stats beta,alpha")).unwrap();
+        assert_eq!(
+            options.path == "a.csv"
+                && options.json
+                && options.freq == Some(ClFrequency::ClFrequencyMonthly)
+                && options.stats == vec!["beta", "alpha"],
+            true
+        );
+        let options = parse_options(&args("a.csv")).unwrap();
+        assert_eq!(
+            options.json == false && options.freq.is_none() && options.stats.len() == 0,
+            true
+        );
+
+        let err = |text: &str| parse_options(&args(text)).err().unwrap();
+        assert_eq!(err("a.csv --freq"), "--freq needs a frequency");
+        assert_eq!(err("a.csv --freq hourly"), "unknown frequency hourly");
+        assert_eq!(err("a.csv --stats"), "--stats needs the statistic names");
+        assert_eq!(err("a.csv b.csv"), "unknown argument b.csv");
+        assert_eq!(err("a.csv --csv"), "unknown argument --csv");
+        assert_eq!(err("--json"), "missing csv file");
+    }
+
+    #[test]
+    fn should_correct_run() {
+        let fund = [
+            1.2, -0.8, 2.1, 0.5, -1.4, 3.0, 0.7, -0.3, 1.8, -2.2, 0.9, 1.1,
+        ];
+        let index = [
+            0.9, -1.1, 1.7, 0.8, -0.9, 2.4, 0.2, -0.6, 1.5, -1.8, 1.2, 0.6,
+        ];
+        let ends = [31, 29, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31];
+        let mut text = String::from("date,fund,index\n");
+        for i in 0..12 {
+            text += &format!("2024-{:02}-{},{},{}\n", i + 1, ends[i], fund[i], index[i]);
+        }
+        let path = std::env::temp_dir().join(format!(
+            "mpt_should_correct_run_{}_{}.csv",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos()
+        ));
+        std::fs::write(&path, text).unwrap();
+        let mut options = parse_options(&[
+            path.to_string_lossy().to_string(),
+            String::from("--json"),
+            String::from("--stats"),
+            String::from("cumulative_return,beta"),
+        ])
+        .unwrap();
+        let json = run(&options);
+        options.stats = vec![String::from("sharpe")];
+        let unknown = run(&options);
+        std::fs::remove_file(&path).unwrap();
+
+        let dates: Vec<i32> = (0..12)
+            .map(|i| {
+                let date = chrono::NaiveDate::from_ymd_opt(2024, i + 1, ends[i as usize]).unwrap();
+                date_util::to_int(&date) as i32
+            })
+            .collect();
+        let calc = MPTCalculator::from(&fund, &index, &[]);
+        let mut pack = StandardPack::new();
+        report::standard_pack(&calc, &dates, ClFrequency::ClFrequencyMonthly, &mut pack);
+        let expected = report::values_to_json(&[
+            ("cumulative_return", pack.cumulative_return),
+            ("beta", pack.beta),
+        ]);
+        assert_eq!(json, Ok(expected));
+        assert_eq!(unknown, Err(String::from("unknown statistic sharpe")));
+    }
+}
@@ -0,0 +1,333 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{enums::Errors, MPTCalculator};
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+fn fnv_update(hash: u64, values: &[f64]) -> u64 {
+    let mut hash = values
+        .len()
+        .to_le_bytes()
+        .iter()
+        .fold(hash, |acc, x| (acc ^ *x as u64).wrapping_mul(FNV_PRIME));
+    for v in values.iter() {
+        for x in v.to_bits().to_le_bytes() {
+            hash = (hash ^ x as u64).wrapping_mul(FNV_PRIME);
+        }
+    }
+    return hash;
+}
+
+///the hash of the values, it is the FNV-1a hash of the bits of the values, so it is the same in all the processes
+///and it can be stored with the reports.
+pub fn series_hash(values: &[f64]) -> u64 {
+    return fnv_update(FNV_OFFSET, values);
+}
+
+///the hash of the dates, it is the FNV-1a hash of the bytes of the dates as series_hash.
+pub fn dates_hash(dates: &[i32]) -> u64 {
+    let mut hash = dates.len().to_le_bytes().iter().fold(FNV_OFFSET, |acc, x| {
+        (acc ^ *x as u64).wrapping_mul(FNV_PRIME)
+    });
+    for d in dates.iter() {
+        for x in d.to_le_bytes() {
+            hash = (hash ^ x as u64).wrapping_mul(FNV_PRIME);
+        }
+    }
+    return hash;
+}
+
+///the hash of the values, the benchmark and the risk free of the calculator.
+pub fn calculator_hash(calc: &MPTCalculator) -> u64 {
+    let hash = fnv_update(FNV_OFFSET, calc.values);
+    let hash = fnv_update(hash, calc.benchmark);
+    return fnv_update(hash, calc.riskfree);
+}
+
+///the key of a cached statistic, the params are the arguments of the statistic besides the datasets and the dates,
+///e.g. "freq=monthly, is_annu=true".
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    pub calculator_hash: u64,
+    pub dates_hash: u64,
+    pub statistic: String,
+    pub params: String,
+}
+
+///the default max number of the cached statistics.
+pub const DEFAULT_CACHE_CAPACITY: usize = 10_000;
+
+///the cache of the statistics keyed by the hash of the inputs, the dates, the statistic and the params, so a report
+///regeneration only recomputes the statistics whose inputs changed. the cache keeps at most capacity statistics,
+///the least recently used statistic is removed to add a new one, the statistics are ordered by their last use so
+///the removal does not search all the statistics.
+///# Examples
+///```
+///use mpt_lib::cache::StatisticsCache;
+///use mpt_lib::enums::{self, Errors};
+///use mpt_lib::MPTCalculator;
+///
+///let data = vec![1.0, -2.0, 3.0, 0.5, -1.5, 2.0];
+///let mpt = MPTCalculator::from_v(&data);
+///let mut cache = StatisticsCache::new();
+///let mut res = f64::NAN;
+///for _ in 0..2 {
+///    cache.get_or_compute(
+///        &mpt,
+///        &[],
+///        "standard_deviation",
+///        "freq=monthly, is_annu=true",
+///        &mut res,
+///        |calc, res| calc.standard_deviation(enums::ClFrequency::ClFrequencyMonthly, true, res),
+///    );
+///}
+///assert_eq!(
+///    MPTCalculator::is_eq_double(res, 6.752777) && cache.hits() == 1 && cache.misses() == 1,
+///    true
+///);
+///```
+#[derive(Clone, Debug)]
+pub struct StatisticsCache {
+    ///the error code, the result and the last use of each statistic.
+    entries: HashMap<CacheKey, (Errors, f64, u64)>,
+    ///the key of each statistic by its last use, the first one is the least recently used.
+    recency: BTreeMap<u64, CacheKey>,
+    capacity: usize,
+    uses: u64,
+    hits: usize,
+    misses: usize,
+}
+
+impl Default for StatisticsCache {
+    fn default() -> Self {
+        StatisticsCache::new()
+    }
+}
+
+impl StatisticsCache {
+    pub fn new() -> StatisticsCache {
+        StatisticsCache::with_capacity(DEFAULT_CACHE_CAPACITY)
+    }
+
+    ///create the cache which keeps at most capacity statistics, the capacity is at least 1.
+    pub fn with_capacity(capacity: usize) -> StatisticsCache {
+        StatisticsCache {
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            capacity: capacity.max(1),
+            uses: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    ///return the cached statistic of the calculator, or compute it by compute and cache the error code and the result.
+    ///
+    ///# Arguments
+    ///calc: the calculator of the statistic.
+    ///
+    ///dates: the dates of the statistic, it is empty if the statistic does not use the dates.
+    ///
+    ///statistic: the name of the statistic.
+    ///
+    ///params: the arguments of the statistic besides the datasets.
+    ///
+    ///result: the value of the statistic.
+    ///
+    ///compute: the calculation of the statistic, it is called only if the statistic is not cached.
+    pub fn get_or_compute<F>(
+        &mut self,
+        calc: &MPTCalculator,
+        dates: &[i32],
+        statistic: &str,
+        params: &str,
+        result: &mut f64,
+        compute: F,
+    ) -> Errors
+    where
+        F: FnOnce(&MPTCalculator, &mut f64) -> Errors,
+    {
+        let key = CacheKey {
+            calculator_hash: calculator_hash(calc),
+            dates_hash: dates_hash(dates),
+            statistic: statistic.to_string(),
+            params: params.to_string(),
+        };
+        self.uses += 1;
+        if let Some((err, value, last_use)) = self.entries.get_mut(&key) {
+            self.hits += 1;
+            if let Some(key) = self.recency.remove(last_use) {
+                self.recency.insert(self.uses, key);
+            }
+            *last_use = self.uses;
+            *result = *value;
+            return *err;
+        }
+        self.misses += 1;
+        *result = f64::NAN;
+        let err = compute(calc, result);
+        if self.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = self.recency.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.recency.insert(self.uses, key.clone());
+        self.entries.insert(key, (err, *result, self.uses));
+        return err;
+    }
+
+    ///remove the statistics of the calculator of the hash, it is the calculator_hash of the values, the benchmark and
+    ///the risk free. return the number of the removed statistics.
+    pub fn invalidate_calculator(&mut self, calculator_hash: u64) -> usize {
+        let count = self.entries.len();
+        self.entries
+            .retain(|key, _| key.calculator_hash != calculator_hash);
+        self.recency
+            .retain(|_, key| key.calculator_hash != calculator_hash);
+        return count - self.entries.len();
+    }
+
+    ///remove the statistic of all the inputs, e.g. after its methodology changed, return the number of the removed
+    ///statistics.
+    pub fn invalidate_statistic(&mut self, statistic: &str) -> usize {
+        let count = self.entries.len();
+        self.entries.retain(|key, _| key.statistic != statistic);
+        self.recency.retain(|_, key| key.statistic != statistic);
+        return count - self.entries.len();
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{calculator_hash, dates_hash, series_hash, StatisticsCache};
+    use crate::{
+        enums::{self, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_statistics_cache() {
+        let data = vec![1.0, -2.0, 3.0, 0.5, -1.5, 2.0];
+        let bmk_data = vec![0.8, -1.5, 2.5, 0.2, -1.0, 1.5];
+        let mut changed = data.clone();
+        changed[5] = 2.5;
+        let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+        let changed_mpt = MPTCalculator::from_v_b(&changed, &bmk_data);
+        let mut cache = StatisticsCache::new();
+        let mut calls = 0;
+        let mut beta = |cache: &mut StatisticsCache, calc: &MPTCalculator, res: &mut f64| {
+            cache.get_or_compute(calc, &[], "beta", "", res, |calc, res| {
+                calls += 1;
+                calc.beta(res)
+            })
+        };
+
+        let (mut res, mut expected) = (f64::NAN, f64::NAN);
+        mpt.beta(&mut expected);
+        beta(&mut cache, &mpt, &mut res);
+        let err = beta(&mut cache, &mpt, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res == expected, true);
+        beta(&mut cache, &changed_mpt, &mut res);
+        assert_eq!(res != expected && cache.len() == 2, true);
+
+        cache.get_or_compute(&mpt, &[], "beta", "", &mut res, |calc, res| {
+            calc.standard_deviation(enums::ClFrequency::ClFrequencyMonthly, true, res)
+        });
+        assert_eq!(res, expected);
+        assert_eq!(cache.invalidate_calculator(calculator_hash(&mpt)), 1);
+        beta(&mut cache, &mpt, &mut res);
+        assert_eq!(calls, 3);
+        assert_eq!(
+            cache.hits() == 2 && cache.misses() == 3 && cache.invalidate_statistic("beta") == 2,
+            true
+        );
+
+        let err = cache.get_or_compute(&mpt, &[], "beta", "", &mut res, |_, _| {
+            Errors::ClErrorCodeInvalidPara
+        });
+        let cached =
+            cache.get_or_compute(&mpt, &[], "beta", "", &mut res, |calc, res| calc.beta(res));
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && cached == err && res.is_nan(),
+            true
+        );
+
+        assert_eq!(
+            series_hash(&data) == series_hash(&data.clone())
+                && series_hash(&data) != series_hash(&changed)
+                && series_hash(&[]) != series_hash(&[0.0])
+                && calculator_hash(&mpt) != series_hash(&data),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_statistics_cache_by_dates_and_capacity() {
+        let data = vec![1.0, -2.0, 3.0, 0.5, -1.5, 2.0];
+        let mpt = MPTCalculator::from_v(&data);
+        let dates = vec![45322, 45351, 45382, 45412, 45443, 45473];
+        let shifted: Vec<i32> = dates.iter().map(|x| x + 365).collect();
+        let mut cache = StatisticsCache::with_capacity(2);
+        assert_eq!(cache.is_empty(), true);
+        let mut res = f64::NAN;
+        let annualized = |cache: &mut StatisticsCache, dates: &[i32], res: &mut f64| {
+            cache.get_or_compute(&mpt, dates, "annualized_return", "", res, |calc, res| {
+                calc.annualized_return_by_dates(dates, true, res)
+            })
+        };
+        annualized(&mut cache, &dates, &mut res);
+        annualized(&mut cache, &shifted, &mut res);
+        annualized(&mut cache, &dates, &mut res);
+        assert_eq!(
+            cache.len() == 2 && cache.hits() == 1 && cache.misses() == 2,
+            true
+        );
+
+        cache.get_or_compute(&mpt, &[], "average", "", &mut res, |calc, res| {
+            calc.average(res)
+        });
+        annualized(&mut cache, &dates, &mut res);
+        annualized(&mut cache, &shifted, &mut res);
+        assert_eq!(
+            cache.len() == 2
+                && cache.capacity() == 2
+                && cache.hits() == 2
+                && cache.misses() == 4
+                && dates_hash(&dates) != dates_hash(&shifted)
+                && dates_hash(&[]) != dates_hash(&[0]),
+            true
+        );
+        assert_eq!(
+            cache.invalidate_calculator(calculator_hash(&mpt)) == 2 && cache.is_empty(),
+            true
+        );
+        annualized(&mut cache, &dates, &mut res);
+        assert_eq!(cache.len() == 1 && cache.misses() == 5, true);
+    }
+}
@@ -0,0 +1,55 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+///the cancellation token of the long running computations, e.g. the optimizers and the batch statistics.
+///the computation checks the token periodically and returns ClErrorCodeCancelled after it is cancelled,
+///so a web service can abort the work when its client disconnects. the clones share the same flag.
+///# Examples
+///```
+///use mpt_lib::cancel::CancellationToken;
+///use mpt_lib::enums::{ClComputeBackend, Errors};
+///use mpt_lib::MPTCalculator;
+///
+///let token = CancellationToken::new();
+///let handle = token.clone();
+///handle.cancel();
+///let fund = vec![1.0, 3.0, 5.0, 5.0];
+///let mut results = Vec::new();
+///let mut used_backend = ClComputeBackend::ClComputeCpu;
+///let err = MPTCalculator::rolling_standard_deviation_batch_with_cancel(
+///    &[&fund],
+///    2,
+///    ClComputeBackend::ClComputeCpu,
+///    &token,
+///    &mut results,
+///    &mut used_backend,
+///);
+///assert_eq!(err == Errors::ClErrorCodeCancelled && results.len() == 0, true);
+///```
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> CancellationToken {
+        CancellationToken {
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    ///the token of an existing flag, the computation is cancelled when the flag is set to true.
+    pub fn from_flag(cancelled: Arc<AtomicBool>) -> CancellationToken {
+        CancellationToken { cancelled }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
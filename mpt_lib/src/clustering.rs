@@ -0,0 +1,309 @@
+use crate::{
+    correlation::pairwise_correlation,
+    enums::{ClClusterCut, ClLinkage, Errors},
+};
+
+///a merge of the dendrogram, the nodes 0..n are the series and the node n + k is the cluster of the merge k.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClusterMerge {
+    pub left: usize,
+    pub right: usize,
+    ///the linkage distance of the two nodes.
+    pub distance: f64,
+    ///the number of the series of the merged cluster.
+    pub size: usize,
+}
+
+///the clusters of the series.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Clusters {
+    ///the cluster of each series, the clusters are numbered by the first series of each cluster.
+    pub labels: Vec<usize>,
+    ///the n - 1 merges of the full dendrogram in the merge order, the distances are not decreasing.
+    pub merges: Vec<ClusterMerge>,
+}
+
+impl Clusters {
+    pub fn new() -> Clusters {
+        Clusters {
+            labels: Vec::new(),
+            merges: Vec::new(),
+        }
+    }
+}
+
+impl Default for Clusters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///the correlation distance of the series, a pair without the correlation has the maximum distance 1.
+fn distance_matrix(series: &[&[f64]]) -> Vec<Vec<f64>> {
+    let n = series.len();
+    let mut distances = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in i + 1..n {
+            let (correlation, _) = pairwise_correlation(series[i], series[j], 2);
+            distances[i][j] = if correlation.is_finite() {
+                ((1.0 - correlation) / 2.0).max(0.0).sqrt()
+            } else {
+                1.0
+            };
+            distances[j][i] = distances[i][j];
+        }
+    }
+    distances
+}
+
+///the agglomerative clustering of the distances, return the n - 1 merges.
+fn agglomerate(mut distances: Vec<Vec<f64>>, linkage: ClLinkage) -> Vec<ClusterMerge> {
+    let n = distances.len();
+    let mut nodes: Vec<usize> = (0..n).collect();
+    let mut sizes = vec![1; n];
+    let mut active = vec![true; n];
+    let mut merges = Vec::with_capacity(n.saturating_sub(1));
+    for step in 0..n.saturating_sub(1) {
+        let mut best = (usize::MAX, usize::MAX, f64::INFINITY);
+        for i in (0..n).filter(|x| active[*x]) {
+            for j in (i + 1..n).filter(|x| active[*x]) {
+                if distances[i][j] < best.2 || best.0 == usize::MAX {
+                    best = (i, j, distances[i][j]);
+                }
+            }
+        }
+        let (i, j, distance) = best;
+        for k in (0..n).filter(|x| active[*x] && *x != i && *x != j) {
+            distances[i][k] = match linkage {
+                ClLinkage::ClLinkageSingle => distances[i][k].min(distances[j][k]),
+                ClLinkage::ClLinkageComplete => distances[i][k].max(distances[j][k]),
+                ClLinkage::ClLinkageAverage => {
+                    (distances[i][k] * sizes[i] as f64 + distances[j][k] * sizes[j] as f64)
+                        / (sizes[i] + sizes[j]) as f64
+                }
+            };
+            distances[k][i] = distances[i][k];
+        }
+        merges.push(ClusterMerge {
+            left: nodes[i],
+            right: nodes[j],
+            distance,
+            size: sizes[i] + sizes[j],
+        });
+        nodes[i] = n + step;
+        sizes[i] += sizes[j];
+        active[j] = false;
+    }
+    merges
+}
+
+///the cluster of each series after the first count merges.
+fn cut_labels(n: usize, merges: &[ClusterMerge], count: usize) -> Vec<usize> {
+    fn find(parents: &mut [usize], x: usize) -> usize {
+        let mut root = x;
+        while parents[root] != root {
+            root = parents[root];
+        }
+        parents[x] = root;
+        root
+    }
+    let mut parents: Vec<usize> = (0..n).collect();
+    let mut leaves: Vec<usize> = (0..n).collect();
+    for merge in merges.iter() {
+        leaves.push(leaves[merge.left]);
+    }
+    for merge in merges[..count].iter() {
+        let left = find(&mut parents, leaves[merge.left]);
+        let right = find(&mut parents, leaves[merge.right]);
+        parents[right] = left;
+    }
+
+    let mut roots: Vec<usize> = Vec::new();
+    (0..n)
+        .map(|x| {
+            let root = find(&mut parents, x);
+            match roots.iter().position(|r| *r == root) {
+                Some(label) => label,
+                None => {
+                    roots.push(root);
+                    roots.len() - 1
+                }
+            }
+        })
+        .collect()
+}
+
+///cluster the series by the agglomerative hierarchical clustering of the correlation distance
+///sqrt((1 - correlation) / 2), the correlation of a pair uses the periods which have finite values in both series.
+///the clusters can de-duplicate the highly similar funds of a screen, and the merge order of the dendrogram is the
+///quasi diagonal order of the hierarchical risk parity.
+///
+///# Arguments
+///series: the return array of each series, should have the same length.
+///
+///linkage: the distance of two clusters, it is the minimum, the maximum or the average distance of their series.
+///
+///cut: the number of the clusters, or the maximum merge distance of a cluster.
+///
+///clusters: the cluster labels and the dendrogram.
+///# Examples
+///```
+///use mpt_lib::clustering::{self, Clusters};
+///use mpt_lib::enums::{ClClusterCut, ClLinkage, Errors};
+///
+///let fund1 = vec![1.0, -2.0, 3.0, 0.5, -1.5, 2.0];
+///let fund2 = vec![1.1, -1.9, 3.2, 0.4, -1.6, 2.1];
+///let fund3 = vec![-0.5, 1.0, 0.2, 2.0, 1.5, -1.0];
+///let mut clusters = Clusters::new();
+///let err = clustering::cluster_by_correlation(
+///    &[&fund1, &fund2, &fund3],
+///    ClLinkage::ClLinkageAverage,
+///    ClClusterCut::ClClusterCount(2),
+///    &mut clusters,
+///);
+///assert_eq!(
+///    err == Errors::ClErrorCodeNoError
+///        && clusters.labels == vec![0, 0, 1]
+///        && clusters.merges[0].left == 0
+///        && clusters.merges[0].right == 1
+///        && clusters.merges[1].size == 3,
+///    true
+///);
+///```
+pub fn cluster_by_correlation(
+    series: &[&[f64]],
+    linkage: ClLinkage,
+    cut: ClClusterCut,
+    clusters: &mut Clusters,
+) -> Errors {
+    *clusters = Clusters::new();
+    let n = series.len();
+    if n == 0 || series.iter().any(|x| x.len() != series[0].len()) {
+        return Errors::ClErrorCodeInvalidPara;
+    }
+    match cut {
+        ClClusterCut::ClClusterCount(count) if count == 0 || count > n => {
+            return Errors::ClErrorCodeInvalidPara
+        }
+        ClClusterCut::ClClusterThreshold(threshold)
+            if !threshold.is_finite() || threshold < 0.0 =>
+        {
+            return Errors::ClErrorCodeInvalidPara
+        }
+        _ => {}
+    }
+    instrument!("cluster_by_correlation", rows = series[0].len(), cols = n);
+
+    clusters.merges = agglomerate(distance_matrix(series), linkage);
+    let count = match cut {
+        ClClusterCut::ClClusterCount(count) => n - count,
+        ClClusterCut::ClClusterThreshold(threshold) => clusters
+            .merges
+            .iter()
+            .take_while(|x| x.distance <= threshold)
+            .count(),
+    };
+    clusters.labels = cut_labels(n, &clusters.merges, count);
+    return Errors::ClErrorCodeNoError;
+}
+
+#[cfg(test)]
+mod test {
+    use super::{cluster_by_correlation, Clusters};
+    use crate::{
+        enums::{ClClusterCut, ClLinkage, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_cluster_by_correlation() {
+        let fund1 = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477,
+        ];
+        let fund2 = vec![
+            0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+            1.40278, 1.51232,
+        ];
+        let clone1: Vec<f64> = fund1.iter().map(|x| x * 1.1 + 0.05).collect();
+        let mut clone2: Vec<f64> = fund2.iter().map(|x| x * 0.9 - 0.1).collect();
+        clone2[3] = f64::NAN;
+        let funds = [&fund1[..], &fund2, &clone1, &clone2];
+
+        for linkage in [
+            ClLinkage::ClLinkageSingle,
+            ClLinkage::ClLinkageComplete,
+            ClLinkage::ClLinkageAverage,
+        ] {
+            let mut clusters = Clusters::new();
+            let err = cluster_by_correlation(
+                &funds,
+                linkage,
+                ClClusterCut::ClClusterCount(2),
+                &mut clusters,
+            );
+            assert_eq!(
+                err == Errors::ClErrorCodeNoError
+                    && clusters.labels == vec![0, 1, 0, 1]
+                    && clusters.merges.len() == 3
+                    && clusters.merges[2].size == 4
+                    && clusters.merges[2].left == 4
+                    && clusters.merges[2].right == 5
+                    && clusters
+                        .merges
+                        .windows(2)
+                        .all(|x| x[0].distance <= x[1].distance),
+                true
+            );
+        }
+
+        let correlation = [(0, 1), (0, 3), (2, 1), (2, 3)]
+            .iter()
+            .map(|(i, j)| {
+                let mut res = f64::NAN;
+                MPTCalculator::from_v_b(funds[*i], funds[*j]).correlation(&mut res);
+                res
+            })
+            .fold(f64::NEG_INFINITY, f64::max);
+        let mut clusters = Clusters::new();
+        cluster_by_correlation(
+            &funds,
+            ClLinkage::ClLinkageSingle,
+            ClClusterCut::ClClusterThreshold(0.01),
+            &mut clusters,
+        );
+        assert_eq!(
+            clusters.labels == vec![0, 1, 0, 1]
+                && MPTCalculator::is_eq_double(
+                    clusters.merges[2].distance,
+                    ((1.0 - correlation) / 2.0).sqrt()
+                ),
+            true
+        );
+        cluster_by_correlation(
+            &funds,
+            ClLinkage::ClLinkageComplete,
+            ClClusterCut::ClClusterThreshold(1.0),
+            &mut clusters,
+        );
+        assert_eq!(clusters.labels, vec![0, 0, 0, 0]);
+        cluster_by_correlation(
+            &funds,
+            ClLinkage::ClLinkageSingle,
+            ClClusterCut::ClClusterCount(4),
+            &mut clusters,
+        );
+        assert_eq!(clusters.labels, vec![0, 1, 2, 3]);
+
+        let err = cluster_by_correlation(
+            &funds,
+            ClLinkage::ClLinkageSingle,
+            ClClusterCut::ClClusterCount(5),
+            &mut clusters,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && clusters.labels.len() == 0,
+            true
+        );
+    }
+}
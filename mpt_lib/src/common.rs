@@ -5,9 +5,11 @@ use float_cmp::approx_eq;
 use crate::{
     date_util,
     enums::{self, Errors},
-    MPTCalculator,
+    kernels, MPTCalculator,
 };
 
+pub(crate) use crate::kernels::{check_sample_size, variance_divisor};
+
 pub struct AvgCreditQualityCalculator {
     pub a0: [f64; 3],
     pub a1: [f64; 3],
@@ -84,25 +86,40 @@ pub(crate) struct RatioData {
 }
 
 pub fn get_annual_multiplier(freq: enums::ClFrequency, is_fd: bool) -> f64 {
-    let mut multiplier = f64::NAN;
-    if freq == enums::ClFrequency::ClFrequencyDaily {
-        if is_fd {
-            multiplier = 250.0;
-        } else {
-            multiplier = 365.25;
-        }
-    } else if freq == enums::ClFrequency::ClFrequencyWeekly {
-        multiplier = 52.0;
-    } else if freq == enums::ClFrequency::ClFrequencyMonthly {
-        multiplier = 12.0;
-    } else if freq == enums::ClFrequency::ClFrequencyQuarterly {
-        multiplier = 4.0;
-    } else if freq == enums::ClFrequency::ClFrequencySemiannually {
-        multiplier = 2.0;
-    } else if freq == enums::ClFrequency::ClFrequencyAnnually {
-        multiplier = 1.0;
-    }
-    return multiplier;
+    return kernels::annual_multiplier(freq, is_fd);
+}
+
+///the observation number per year estimated by the actual dates of the data, the dates should be sorted
+pub fn get_annual_multiplier_by_dates(dates: &[i32]) -> f64 {
+    if dates.len() < 2 {
+        return f64::NAN;
+    }
+    let years = date_util::year_fraction(dates[0] as u64, dates[dates.len() - 1] as u64);
+    if years <= 0.0 {
+        return f64::NAN;
+    }
+    return (dates.len() - 1) as f64 / years;
+}
+
+///the annual multiplier of the frequency by the calendar, the daily multiplier is the average business days number
+///of the years from the first to the last date, so the weekends and the holidays of the calendar are not counted.
+///the other frequencies are not affected by the holidays. the dates should be sorted
+pub fn get_annual_multiplier_by_calendar(
+    freq: enums::ClFrequency,
+    dates: &[i32],
+    calendar: Option<&dyn date_util::HolidayCalendar>,
+) -> f64 {
+    if freq != enums::ClFrequency::ClFrequencyDaily {
+        return get_annual_multiplier(freq, false);
+    }
+    if dates.is_empty() || dates[0] < 2 {
+        return f64::NAN;
+    }
+    return date_util::annual_business_days(
+        dates[0] as u64,
+        dates[dates.len() - 1] as u64,
+        calendar,
+    );
 }
 
 pub(crate) fn is_valid_frequency(freq: enums::ClFrequency) -> bool {
@@ -112,6 +129,8 @@ pub(crate) fn is_valid_frequency(freq: enums::ClFrequency) -> bool {
         || freq == enums::ClFrequency::ClFrequencyQuarterly
         || freq == enums::ClFrequency::ClFrequencySemiannually
         || freq == enums::ClFrequency::ClFrequencyAnnually
+        || freq == enums::ClFrequency::ClFrequencySemimonthly
+        || freq == enums::ClFrequency::ClFrequencyBimonthly
     {
         return true;
     } else {
@@ -139,6 +158,179 @@ pub(crate) fn annualize_return(
         }
     }
 }
+///the inverse cumulative distribution function of the standard normal distribution(acklam's algorithm).
+pub(crate) fn normal_inverse_cdf(p: f64) -> f64 {
+    if !(p > 0.0 && p < 1.0) {
+        return f64::NAN;
+    }
+    const A: [f64; 6] = [
+        -3.969683028665376e+01,
+        2.209460984245205e+02,
+        -2.759285104469687e+02,
+        1.383577518672690e+02,
+        -3.066479806614716e+01,
+        2.506628277459239e+00,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e+01,
+        1.615858368580409e+02,
+        -1.556989798598866e+02,
+        6.680131188771972e+01,
+        -1.328068155288572e+01,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-03,
+        -3.223964580411365e-01,
+        -2.400758277161838e+00,
+        -2.549732539343734e+00,
+        4.374664141464968e+00,
+        2.938163982698783e+00,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-03,
+        3.224671290700398e-01,
+        2.445134137142996e+00,
+        3.754408661907416e+00,
+    ];
+    let p_low = 0.02425;
+    if p < p_low {
+        let q = (-2.0 * p.ln()).sqrt();
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - p_low {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        -normal_inverse_cdf(1.0 - p)
+    }
+}
+
+///the cumulative distribution function of the standard normal distribution(chebyshev approximation of erfc).
+pub(crate) fn normal_cdf(x: f64) -> f64 {
+    if x.is_nan() {
+        return f64::NAN;
+    }
+    if x.is_infinite() {
+        return if x > 0.0 { 1.0 } else { 0.0 };
+    }
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let erfc = t
+        * (-z * z - 1.26551223
+            + t * (1.00002368
+                + t * (0.37409196
+                    + t * (0.09678418
+                        + t * (-0.18628806
+                            + t * (0.27886807
+                                + t * (-1.13520398
+                                    + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277)))))))))
+            .exp();
+    if x >= 0.0 {
+        1.0 - 0.5 * erfc
+    } else {
+        0.5 * erfc
+    }
+}
+
+///the natural logarithm of the gamma function(lanczos approximation).
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    const G: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        return (std::f64::consts::PI / (std::f64::consts::PI * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let mut sum = G[0];
+    for (i, g) in G.iter().enumerate().skip(1) {
+        sum += g / (x + i as f64);
+    }
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+///the continued fraction of the incomplete beta function(modified lentz's method).
+fn incomplete_beta_fraction(a: f64, b: f64, x: f64) -> f64 {
+    let tiny = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < tiny {
+        d = tiny;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+    for m in 1..300 {
+        let m = m as f64;
+        let aa = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        d = 1.0 + aa * d;
+        if d.abs() < tiny {
+            d = tiny;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < tiny {
+            c = tiny;
+        }
+        d = 1.0 / d;
+        h *= d * c;
+        let aa = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        d = 1.0 + aa * d;
+        if d.abs() < tiny {
+            d = tiny;
+        }
+        c = 1.0 + aa / c;
+        if c.abs() < tiny {
+            c = tiny;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < 1e-15 {
+            break;
+        }
+    }
+    h
+}
+
+///the regularized incomplete beta function i_x(a, b).
+pub(crate) fn regularized_incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if !(x >= 0.0 && x <= 1.0) || !(a > 0.0 && b > 0.0) {
+        return f64::NAN;
+    }
+    if x == 0.0 || x == 1.0 {
+        return x;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * incomplete_beta_fraction(a, b, x) / a
+    } else {
+        1.0 - front * incomplete_beta_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+///the cumulative distribution function of the student's t distribution with df degrees of freedom.
+pub(crate) fn student_t_cdf(t: f64, df: f64) -> f64 {
+    if !t.is_finite() || !(df > 0.0) {
+        return f64::NAN;
+    }
+    let tail = 0.5 * regularized_incomplete_beta(df / 2.0, 0.5, df / (df + t * t));
+    if t > 0.0 {
+        1.0 - tail
+    } else {
+        tail
+    }
+}
+
 pub fn is_sorted_array<T: std::cmp::PartialOrd>(data: &[T]) -> bool {
     if data.len() < 2 {
         return false;
@@ -188,33 +380,23 @@ impl<'a> MPTCalculator<'a> {
         is_annu: bool,
         standard_deviation_result: &mut f64,
     ) -> Errors {
-        let mut mean = f64::NAN;
-        let ret = MPTCalculator::from_v(values).average(&mut mean);
-        if ret != Errors::ClErrorCodeNoError {
-            return ret;
-        }
-
-        if values.iter().find(|x| !x.is_finite()) != None {
-            return Errors::ClErrorCodeNoError;
-        }
-
-        let accumalte = values
-            .iter()
-            .filter(|x| x.is_finite())
-            .fold(0.0, |acc, x| acc + (x - mean) * (x - mean));
-
-        if values.len() == 0 {
-            *standard_deviation_result = 0.0
-        } else {
-            *standard_deviation_result = (accumalte / (values.len() as f64 - 1.0)).sqrt()
-        }
-
-        if is_annu {
-            *standard_deviation_result =
-                *standard_deviation_result * get_annual_multiplier(freq, false).sqrt()
-        }
+        return Self::standard_deviation_by_mode_internal(
+            values,
+            freq,
+            is_annu,
+            enums::ClVarianceMode::ClVarianceSample,
+            standard_deviation_result,
+        );
+    }
 
-        return Errors::ClErrorCodeNoError;
+    pub(crate) fn standard_deviation_by_mode_internal(
+        values: &[f64],
+        freq: enums::ClFrequency,
+        is_annu: bool,
+        mode: enums::ClVarianceMode,
+        standard_deviation_result: &mut f64,
+    ) -> Errors {
+        return kernels::standard_deviation(values, freq, is_annu, mode, standard_deviation_result);
     }
 
     pub(crate) fn array_subtraction_internal(
@@ -424,28 +606,7 @@ impl<'a> MPTCalculator<'a> {
     }
 
     pub(crate) fn total_return_accumulat(values: &[f64], result: &mut f64) -> Errors {
-        if values.len() == 0 {
-            return Errors::ClErrorCodeInvalidPara;
-        }
-        *result = f64::NAN;
-        let mut acct_return = 1.0;
-        if values
-            .iter()
-            .try_for_each(|v| {
-                if !v.is_finite() {
-                    return ControlFlow::Break(());
-                } else {
-                    acct_return *= 1.0 + v / 100.0;
-                    return ControlFlow::Continue(());
-                }
-            })
-            .is_break()
-        {
-            return Errors::ClErrorCodeNoError;
-        }
-        *result = (acct_return - 1.0) * 100.0;
-
-        return Errors::ClErrorCodeNoError;
+        return kernels::total_return(values, result);
     }
 
     pub(crate) fn calc_annu_total_return(
@@ -486,14 +647,83 @@ impl<'a> MPTCalculator<'a> {
         }
         true
     }
+
+    ///compare two values with the absolute tolerance, two NAN values are equal and two INF values are equal if
+    ///they have the same sign, so the NAN results of the bindings can be compared as well.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///
+    ///assert_eq!(
+    ///    MPTCalculator::approx_eq(1.0000001, 1.0, 1e-6)
+    ///        && MPTCalculator::approx_eq(f64::NAN, f64::NAN, 1e-6)
+    ///        && !MPTCalculator::approx_eq(1.00001, 1.0, 1e-6),
+    ///    true
+    ///);
+    ///```
+    pub fn approx_eq(a: f64, b: f64, tol: f64) -> bool {
+        if a.is_nan() || b.is_nan() {
+            return a.is_nan() && b.is_nan();
+        }
+        return a == b || (a - b).abs() <= tol;
+    }
+
+    ///compare two arrays by approx_eq element by element.
+    pub fn approx_eq_array(a: &[f64], b: &[f64], tol: f64) -> bool {
+        return a.len() == b.len() && a.iter().zip(b).all(|(x, y)| Self::approx_eq(*x, *y, tol));
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use super::is_sorted_array;
+    use super::{
+        get_annual_multiplier_by_calendar, is_sorted_array, normal_inverse_cdf, student_t_cdf,
+    };
+    use crate::{date_util::HolidayList, enums::ClFrequency};
     #[test]
     fn should_correct_sorted_order() {
         assert_eq!(is_sorted_array(&[1, 2, 3, 4, 5, 6]), true);
         assert_eq!(is_sorted_array(&[1.0, 2.0, 3.0, 4.0, 5.0, 1.0]), false);
     }
+
+    #[test]
+    fn should_correct_normal_distribution() {
+        assert_eq!((normal_inverse_cdf(0.975) - 1.959964).abs() < 1e-6, true);
+        assert_eq!((normal_inverse_cdf(0.01) + 2.326348).abs() < 1e-6, true);
+        assert_eq!(normal_inverse_cdf(1.0).is_nan(), true);
+        assert_eq!((student_t_cdf(2.0, 10.0) - 0.963306).abs() < 1e-6, true);
+        assert_eq!((student_t_cdf(-1.5, 3.0) - 0.115292).abs() < 1e-6, true);
+    }
+
+    #[test]
+    fn should_correct_annual_multiplier_by_calendar() {
+        //2022-12-26 and 2022-12-30 are the holidays of 2022, 2023-01-02 is the holiday of 2023
+        let holidays = HolidayList::from(&[44921, 44925, 44928]);
+        let dates = [44924, 44929, 44930, 44931];
+        let daily = ClFrequency::ClFrequencyDaily;
+        assert_eq!(
+            get_annual_multiplier_by_calendar(daily, &dates, Some(&holidays)),
+            258.5
+        );
+        assert_eq!(
+            get_annual_multiplier_by_calendar(daily, &dates[1..], Some(&holidays)),
+            259.0
+        );
+        assert_eq!(
+            get_annual_multiplier_by_calendar(daily, &dates, None),
+            260.0
+        );
+        assert_eq!(
+            get_annual_multiplier_by_calendar(daily, &[], None).is_nan(),
+            true
+        );
+        assert_eq!(
+            get_annual_multiplier_by_calendar(
+                ClFrequency::ClFrequencyMonthly,
+                &dates,
+                Some(&holidays)
+            ),
+            12.0
+        );
+    }
 }
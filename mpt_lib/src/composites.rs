@@ -0,0 +1,315 @@
+use crate::{
+    common::{get_annual_multiplier, is_valid_frequency},
+    enums::{self, Errors},
+    kernels, MPTCalculator,
+};
+
+impl<'a> MPTCalculator<'a> {
+    ///calculate the asset weighted composite return of one period, the values are the returns of the portfolios
+    ///in the composite, the weight of each portfolio is the market value at the begin of the period.
+    ///if the array has NAN/INF values,the result will be NAN
+    ///# Arguments
+    ///begin_values: the begin market value of each portfolio.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![1.2, 0.8, 1.5, -0.3, 2.1, 0.9];
+    ///let begin_values = vec![100.0, 250.0, 80.0, 40.0, 300.0, 30.0];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.composite_return(&begin_values, &mut res);
+    ///assert_eq!(
+    ///   err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 1.35625),
+    ///   true
+    ///);
+    ///```
+    pub fn composite_return(&self, begin_values: &[f64], composite_res: &mut f64) -> Errors {
+        if self.values.len() == 0 || begin_values.len() != self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *composite_res = f64::NAN;
+        if begin_values.iter().any(|x| x.is_finite() && *x < 0.0) {
+            return Errors::ClErrorCodeInvalidValue;
+        }
+        if self.values.iter().any(|x| !x.is_finite()) || begin_values.iter().any(|x| !x.is_finite())
+        {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let total_value = begin_values.iter().sum::<f64>();
+        if total_value > 0.0 {
+            self.weighted_average(begin_values, composite_res);
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the internal dispersion of the portfolio returns in a composite for one period, the values are
+    ///the returns of the portfolios. the standard deviation is equal weighted and uses the population formula.
+    ///if the array has NAN/INF values,the result will be NAN
+    ///# Arguments
+    ///std_dev: the equal weighted standard deviation.
+    ///
+    ///high: the highest portfolio return.
+    ///
+    ///low: the lowest portfolio return.
+    ///
+    ///interquartile: the range between the 75th and the 25th percentile.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![1.2, 0.8, 1.5, -0.3, 2.1, 0.9];
+    ///let mut std_dev = 0.0;
+    ///let mut high = 0.0;
+    ///let mut low = 0.0;
+    ///let mut interquartile = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.composite_dispersion(&mut std_dev, &mut high, &mut low, &mut interquartile);
+    ///assert_eq!(
+    ///   err == Errors::ClErrorCodeNoError
+    ///       && MPTCalculator::is_eq_double(std_dev, 0.734091)
+    ///       && MPTCalculator::is_eq_double(high, 2.1)
+    ///       && MPTCalculator::is_eq_double(low, -0.3)
+    ///       && MPTCalculator::is_eq_double(interquartile, 0.6),
+    ///   true
+    ///);
+    ///```
+    pub fn composite_dispersion(
+        &self,
+        std_dev: &mut f64,
+        high: &mut f64,
+        low: &mut f64,
+        interquartile: &mut f64,
+    ) -> Errors {
+        if self.values.len() == 0 {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *std_dev = f64::NAN;
+        *high = f64::NAN;
+        *low = f64::NAN;
+        *interquartile = f64::NAN;
+        if self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let count = self.values.len() as f64;
+        let mean = self.values.iter().sum::<f64>() / count;
+        *std_dev = (self
+            .values
+            .iter()
+            .fold(0.0, |acc, x| acc + (x - mean) * (x - mean))
+            / count)
+            .sqrt();
+        self.max(high);
+        self.min(low);
+
+        let mut upper_quartile = f64::NAN;
+        let mut lower_quartile = f64::NAN;
+        self.percentile(75, &mut upper_quartile);
+        self.percentile(25, &mut lower_quartile);
+        *interquartile = upper_quartile - lower_quartile;
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the three year annualized ex-post standard deviation of the composite, the latest three years
+    ///values of the array are used. if the array has NAN/INF values,the result will be NAN
+    ///# Arguments
+    ///freq: the frequence of source data, should not be daily.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![
+    ///   1.52768, 4.04616, 3.40287, -2.43748, 2.1044, -1.7708, -1.89656, 3.18186, 0.14197,
+    ///   3.71883, -0.9124, 0.80994, -1.66708, 3.78221, 0.03481, 2.64778, 0.27133, 1.24475,
+    ///   1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016, 1.40278, 1.51232,
+    ///   -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901, 3.73988, 1.59068,
+    ///   -4.18066, -0.69376, -5.99816,
+    ///];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.three_year_annualized_standard_deviation(
+    ///   enums::ClFrequency::ClFrequencyMonthly,
+    ///   &mut res,
+    ///);
+    ///assert_eq!(
+    ///   err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 8.522732),
+    ///   true
+    ///);
+    ///```
+    pub fn three_year_annualized_standard_deviation(
+        &self,
+        freq: enums::ClFrequency,
+        std_dev: &mut f64,
+    ) -> Errors {
+        if !is_valid_frequency(freq) || freq == enums::ClFrequency::ClFrequencyDaily {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *std_dev = f64::NAN;
+        let periods = (get_annual_multiplier(freq, false) * 3.0) as usize;
+        if self.values.len() < periods {
+            return Errors::ClErrorCodeInputLenTooShort;
+        }
+
+        return Self::standard_deviation_internal(
+            &self.values[self.values.len() - periods..],
+            freq,
+            true,
+            std_dev,
+        );
+    }
+
+    ///build the peer composite of a peer universe, the value of each period is the average or the median of the
+    ///peer returns of the period, the NAN/INF peer returns are excluded. the period is NAN if no peer has a
+    ///valid return.
+    ///
+    ///# Arguments
+    ///peers: the returns of the peers, all the peers should have the same length.
+    ///
+    ///method: the average or the median of the peers.
+    ///
+    ///composite: the return of the peer composite of each period.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let peer1 = vec![1.0, 2.0, -1.0];
+    ///let peer2 = vec![3.0, f64::NAN, -2.0];
+    ///let peer3 = vec![8.0, 4.0, -6.0];
+    ///let mut composite = Vec::new();
+    ///let err = MPTCalculator::peer_composite(
+    ///    &[&peer1, &peer2, &peer3],
+    ///    enums::ClPeerComposite::ClPeerMedian,
+    ///    &mut composite,
+    ///);
+    ///assert_eq!(err == Errors::ClErrorCodeNoError && composite == vec![3.0, 3.0, -2.0], true);
+    ///```
+    pub fn peer_composite(
+        peers: &[&[f64]],
+        method: enums::ClPeerComposite,
+        composite: &mut Vec<f64>,
+    ) -> Errors {
+        composite.clear();
+        if peers.len() == 0 || peers.iter().any(|x| x.len() != peers[0].len()) {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+
+        let mut period = Vec::with_capacity(peers.len());
+        for t in 0..peers[0].len() {
+            period.clear();
+            period.extend(peers.iter().map(|x| x[t]));
+            let mut value = f64::NAN;
+            match method {
+                enums::ClPeerComposite::ClPeerAverage => kernels::mean(&period, &mut value),
+                enums::ClPeerComposite::ClPeerMedian => {
+                    kernels::percentile(&period, 50, &mut value)
+                }
+            };
+            composite.push(value);
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        enums::{self, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_composite_return() {
+        let data = vec![1.2, 0.8, 1.5, -0.3, 2.1, 0.9];
+        let begin_values = vec![100.0, 250.0, 80.0, 40.0, 300.0, 30.0];
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.composite_return(&begin_values, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 1.35625),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_composite_dispersion() {
+        let data = vec![1.2, 0.8, 1.5, -0.3, 2.1, 0.9];
+        let mut std_dev = 0.0;
+        let mut high = 0.0;
+        let mut low = 0.0;
+        let mut interquartile = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.composite_dispersion(&mut std_dev, &mut high, &mut low, &mut interquartile);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(std_dev, 0.734091)
+                && MPTCalculator::is_eq_double(high, 2.1)
+                && MPTCalculator::is_eq_double(low, -0.3)
+                && MPTCalculator::is_eq_double(interquartile, 0.6),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_three_year_annualized_standard_deviation() {
+        let data = vec![1.0; 35];
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.three_year_annualized_standard_deviation(
+            enums::ClFrequency::ClFrequencyMonthly,
+            &mut res,
+        );
+        assert_eq!(err == Errors::ClErrorCodeInputLenTooShort, true);
+
+        let data = vec![1.0; 12];
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.three_year_annualized_standard_deviation(
+            enums::ClFrequency::ClFrequencyQuarterly,
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 0.0),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_peer_composite() {
+        let peer1 = vec![1.0, 2.0, -1.0, f64::NAN];
+        let peer2 = vec![3.0, f64::NAN, -2.0, f64::NAN];
+        let peer3 = vec![8.0, 4.0, -6.0, f64::INFINITY];
+        let mut composite = Vec::new();
+        let err = MPTCalculator::peer_composite(
+            &[&peer1, &peer2, &peer3],
+            enums::ClPeerComposite::ClPeerAverage,
+            &mut composite,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && composite[..3] == [4.0, 3.0, -3.0]
+                && composite[3].is_nan(),
+            true
+        );
+
+        let err = MPTCalculator::peer_composite(
+            &[&peer1, &peer2[1..]],
+            enums::ClPeerComposite::ClPeerMedian,
+            &mut composite,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && composite.len() == 0,
+            true
+        );
+        let err = MPTCalculator::peer_composite(
+            &[],
+            enums::ClPeerComposite::ClPeerMedian,
+            &mut composite,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+}
@@ -0,0 +1,68 @@
+///the total weight of a group of assets, e.g. a sector, should be from min to max.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupConstraint {
+    ///the indexes of the assets of the group.
+    pub assets: Vec<usize>,
+    pub min: f64,
+    pub max: f64,
+}
+
+///the constraints of the cvar and mean variance portfolio optimizers besides the long only and fully invested weights.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OptimizerConstraints {
+    pub groups: Vec<GroupConstraint>,
+    ///the current weight of each asset, it is empty if there is no turnover limit.
+    pub current_weights: Vec<f64>,
+    ///the maximum one way turnover from the current weights, it is half of the sum of the absolute weight changes.
+    pub max_turnover: f64,
+    ///the maximum number of the assets with positive weights, 0 is no limit. it is a heuristic, the asset with the
+    ///smallest weight is excluded and the portfolio is optimized again until the number is not larger than the limit.
+    pub max_assets: usize,
+}
+
+impl OptimizerConstraints {
+    pub fn new() -> OptimizerConstraints {
+        OptimizerConstraints {
+            groups: Vec::new(),
+            current_weights: Vec::new(),
+            max_turnover: f64::INFINITY,
+            max_assets: 0,
+        }
+    }
+
+    ///check the constraints of a portfolio of asset_count assets.
+    pub(crate) fn is_valid(&self, asset_count: usize) -> bool {
+        return self.groups.iter().all(|x| {
+            x.assets.len() > 0
+                && x.assets.iter().all(|i| *i < asset_count)
+                && x.min.is_finite()
+                && x.max.is_finite()
+                && x.min <= x.max
+        }) && (self.current_weights.len() == 0
+            || (self.current_weights.len() == asset_count
+                && self.current_weights.iter().all(|x| x.is_finite())))
+            && self.max_turnover >= 0.0
+            && (self.max_turnover.is_infinite() || self.current_weights.len() > 0);
+    }
+
+    ///check the group and turnover constraints of the weights within the tolerance.
+    pub(crate) fn is_satisfied(&self, weights: &[f64], tolerance: f64) -> bool {
+        return self.groups.iter().all(|x| {
+            let total: f64 = x.assets.iter().map(|i| weights[*i]).sum();
+            total >= x.min - tolerance && total <= x.max + tolerance
+        }) && (self.max_turnover.is_infinite()
+            || 0.5
+                * weights
+                    .iter()
+                    .zip(self.current_weights.iter())
+                    .map(|x| (x.0 - x.1).abs())
+                    .sum::<f64>()
+                <= self.max_turnover + tolerance);
+    }
+}
+
+impl Default for OptimizerConstraints {
+    fn default() -> Self {
+        Self::new()
+    }
+}
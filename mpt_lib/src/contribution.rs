@@ -0,0 +1,328 @@
+use crate::{
+    enums::{self, Errors},
+    kernels, MPTCalculator,
+};
+
+///the carino log linking factor of a return in fraction, it is 1 when the return is 0.
+fn carino_factor(r: f64) -> f64 {
+    if r.abs() < f64::EPSILON {
+        1.0
+    } else {
+        (1.0 + r).ln() / r
+    }
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///calculate the contribution of each asset to the compound portfolio return over all periods.
+    ///the contribution of one period is the weight multiplied by the return, and the periods are linked
+    ///geometrically by the carino or menchero smoothing, so the sum of the contributions is the total return.
+    ///the periods which have NAN/INF values in any weight or return are skipped.
+    ///# Arguments
+    ///weights_matrix: the begin of period weight array of each asset, should have the same length as the returns.
+    ///
+    ///returns_matrix: the return array of each asset.
+    ///
+    ///linking_method: the smoothing method to link the periods.
+    ///
+    ///contributions: the contribution of each asset to the total return, in percent.
+    ///
+    ///total_return: the compound portfolio return, in percent.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let equity_weights = vec![0.6, 0.55, 0.5];
+    ///let bond_weights = vec![0.4, 0.45, 0.5];
+    ///let equity = vec![2.0, -1.0, 3.0];
+    ///let bond = vec![0.5, 0.3, -0.2];
+    ///let mut contributions = Vec::new();
+    ///let mut total_return = 0.0;
+    ///let err = MPTCalculator::return_contribution(
+    ///    &[&equity_weights, &bond_weights],
+    ///    &[&equity, &bond],
+    ///    enums::ClLinkingMethod::ClLinkingCarino,
+    ///    &mut contributions,
+    ///    &mut total_return,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(&contributions, &[2.155516, 0.237383])
+    ///        && MPTCalculator::is_eq_double(total_return, 2.392899),
+    ///    true
+    ///);
+    ///```
+    pub fn return_contribution(
+        weights_matrix: &[&[f64]],
+        returns_matrix: &[&[f64]],
+        linking_method: enums::ClLinkingMethod,
+        contributions: &mut Vec<f64>,
+        total_return: &mut f64,
+    ) -> Errors {
+        if returns_matrix.len() == 0
+            || weights_matrix.len() != returns_matrix.len()
+            || returns_matrix
+                .iter()
+                .chain(weights_matrix.iter())
+                .any(|x| x.len() != returns_matrix[0].len())
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *total_return = f64::NAN;
+        contributions.clear();
+        contributions.resize(returns_matrix.len(), f64::NAN);
+
+        let n = returns_matrix.len();
+        let periods: Vec<usize> = (0..returns_matrix[0].len())
+            .filter(|t| {
+                (0..n)
+                    .all(|i| weights_matrix[i][*t].is_finite() && returns_matrix[i][*t].is_finite())
+            })
+            .collect();
+        if periods.len() == 0 {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let period_contributions: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                periods
+                    .iter()
+                    .map(|t| weights_matrix[i][*t] * returns_matrix[i][*t] / 100.0)
+                    .collect()
+            })
+            .collect();
+        let portfolio: Vec<f64> = (0..periods.len())
+            .map(|k| (0..n).map(|i| period_contributions[i][k]).sum())
+            .collect();
+        let total = portfolio.iter().fold(1.0, |acc, x| acc * (1.0 + x)) - 1.0;
+
+        let factors: Vec<f64> = match linking_method {
+            enums::ClLinkingMethod::ClLinkingCarino => {
+                let k = carino_factor(total);
+                portfolio.iter().map(|x| carino_factor(*x) / k).collect()
+            }
+            enums::ClLinkingMethod::ClLinkingMenchero => {
+                let count = periods.len() as f64;
+                let m = if total.abs() < f64::EPSILON {
+                    1.0
+                } else {
+                    (total / count) / ((1.0 + total).powf(1.0 / count) - 1.0)
+                };
+                let sum: f64 = portfolio.iter().sum();
+                let sum_square: f64 = portfolio.iter().map(|x| x * x).sum();
+                let alpha = if sum_square > 0.0 {
+                    (total - m * sum) / sum_square
+                } else {
+                    0.0
+                };
+                portfolio.iter().map(|x| m + alpha * x).collect()
+            }
+        };
+
+        for i in 0..n {
+            contributions[i] = period_contributions[i]
+                .iter()
+                .zip(factors.iter())
+                .map(|x| x.0 * x.1)
+                .sum::<f64>()
+                * 100.0;
+        }
+        *total_return = total * 100.0;
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the concentration of the compound return in the best and the worst n periods, the total return
+    ///without the n highest returns or the n lowest returns, and the share of the total return attributable to
+    ///them, (total return - total return without them) / total return * 100, e.g. missing the 10 best days of the
+    ///market. the equal returns are excluded by the order of the periods. if the array has NAN/INF values,the result
+    ///will be NAN.
+    ///
+    ///# Arguments
+    ///n: the number of the best or the worst periods, it should be less than the number of the periods.
+    ///
+    ///total_return: the compound return of all the periods, in percent.
+    ///
+    ///without_best: the compound return without the best n periods, in percent.
+    ///
+    ///without_worst: the compound return without the worst n periods, in percent.
+    ///
+    ///best_share: the percentage of the total return attributable to the best n periods.
+    ///
+    ///worst_share: the percentage of the total return attributable to the worst n periods, it is negative if the
+    ///total return is positive.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let data = vec![10.0, -10.0, 5.0, 0.0];
+    ///let (mut total, mut without_best, mut without_worst) = (f64::NAN, f64::NAN, f64::NAN);
+    ///let (mut best_share, mut worst_share) = (f64::NAN, f64::NAN);
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.concentration_of_returns(
+    ///    1,
+    ///    &mut total,
+    ///    &mut without_best,
+    ///    &mut without_worst,
+    ///    &mut best_share,
+    ///    &mut worst_share,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(total, 3.95)
+    ///        && MPTCalculator::is_eq_double(without_best, -5.5)
+    ///        && MPTCalculator::is_eq_double(without_worst, 15.5)
+    ///        && MPTCalculator::is_eq_double(best_share, 239.240506),
+    ///    true
+    ///);
+    ///```
+    pub fn concentration_of_returns(
+        &self,
+        n: usize,
+        total_return: &mut f64,
+        without_best: &mut f64,
+        without_worst: &mut f64,
+        best_share: &mut f64,
+        worst_share: &mut f64,
+    ) -> Errors {
+        *total_return = f64::NAN;
+        *without_best = f64::NAN;
+        *without_worst = f64::NAN;
+        *best_share = f64::NAN;
+        *worst_share = f64::NAN;
+        if n == 0 || n >= self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        if self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let mut order: Vec<usize> = (0..self.values.len()).collect();
+        order.sort_by(|a, b| self.values[*b].total_cmp(&self.values[*a]));
+        let without = |excluded: &[usize]| {
+            let rest: Vec<f64> = (0..self.values.len())
+                .filter(|t| !excluded.contains(t))
+                .map(|t| self.values[t])
+                .collect();
+            let mut res = f64::NAN;
+            kernels::total_return(&rest, &mut res);
+            res
+        };
+        kernels::total_return(self.values, total_return);
+        *without_best = without(&order[..n]);
+        *without_worst = without(&order[order.len() - n..]);
+        if *total_return != 0.0 {
+            *best_share = (*total_return - *without_best) / *total_return * 100.0;
+            *worst_share = (*total_return - *without_worst) / *total_return * 100.0;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        enums::{self, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_return_contribution() {
+        let equity_weights = vec![0.6, 0.55, 0.5, 0.5];
+        let bond_weights = vec![0.4, 0.45, 0.5, 0.5];
+        let equity = vec![2.0, -1.0, 3.0, f64::NAN];
+        let bond = vec![0.5, 0.3, -0.2, 1.0];
+        let mut contributions = Vec::new();
+        let mut total_return = 0.0;
+        let err = MPTCalculator::return_contribution(
+            &[&equity_weights, &bond_weights],
+            &[&equity, &bond],
+            enums::ClLinkingMethod::ClLinkingMenchero,
+            &mut contributions,
+            &mut total_return,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&contributions, &[2.15626, 0.236638])
+                && MPTCalculator::is_eq_double(total_return, 2.392899),
+            true
+        );
+
+        let err = MPTCalculator::return_contribution(
+            &[&equity_weights, &bond_weights],
+            &[&equity, &bond],
+            enums::ClLinkingMethod::ClLinkingCarino,
+            &mut contributions,
+            &mut total_return,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&contributions, &[2.155516, 0.237383]),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_concentration_of_returns() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477,
+        ];
+        let (mut total, mut without_best, mut without_worst) = (f64::NAN, f64::NAN, f64::NAN);
+        let (mut best_share, mut worst_share) = (f64::NAN, f64::NAN);
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.concentration_of_returns(
+            2,
+            &mut total,
+            &mut without_best,
+            &mut without_worst,
+            &mut best_share,
+            &mut worst_share,
+        );
+        let compound = |values: &[f64]| {
+            (values.iter().fold(1.0, |acc, x| acc * (1.0 + x / 100.0)) - 1.0) * 100.0
+        };
+        let best_removed = compound(&[
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 1.59564, 0.86793,
+            2.41477,
+        ]);
+        let worst_removed = compound(&[
+            -0.0668, 2.20588, -0.91563, -0.76766, 3.43456, 4.99825, 3.89481, 1.59564, 0.86793,
+            2.41477,
+        ]);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(total, compound(&data))
+                && MPTCalculator::is_eq_double(without_best, best_removed)
+                && MPTCalculator::is_eq_double(without_worst, worst_removed)
+                && MPTCalculator::is_eq_double(best_share, (total - best_removed) / total * 100.0)
+                && MPTCalculator::is_eq_double(
+                    worst_share,
+                    (total - worst_removed) / total * 100.0
+                )
+                && worst_share < 0.0,
+            true
+        );
+
+        let mut missing = data.clone();
+        missing[3] = f64::NAN;
+        let err = MPTCalculator::from_v(&missing).concentration_of_returns(
+            2,
+            &mut total,
+            &mut without_best,
+            &mut without_worst,
+            &mut best_share,
+            &mut worst_share,
+        );
+        assert_eq!(err == Errors::ClErrorCodeNoError && total.is_nan(), true);
+        let err = mpt.concentration_of_returns(
+            12,
+            &mut total,
+            &mut without_best,
+            &mut without_worst,
+            &mut best_share,
+            &mut worst_share,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::{enums::Errors, MPTCalculator};
+
+///the pearson correlation of the periods which have finite values in both series, return (correlation, count).
+///the correlation is NAN if the count is less than min_overlap or one series is constant in the periods.
+pub(crate) fn pairwise_correlation(x: &[f64], y: &[f64], min_overlap: usize) -> (f64, usize) {
+    let (mut x_sum, mut y_sum, mut count) = (0.0, 0.0, 0);
+    for (a, b) in x.iter().zip(y.iter()) {
+        if a.is_finite() && b.is_finite() {
+            x_sum += a;
+            y_sum += b;
+            count += 1;
+        }
+    }
+    if count < min_overlap.max(2) {
+        return (f64::NAN, count);
+    }
+    let x_mean = x_sum / count as f64;
+    let y_mean = y_sum / count as f64;
+    let (mut xx, mut yy, mut xy) = (0.0, 0.0, 0.0);
+    for (a, b) in x.iter().zip(y.iter()) {
+        if a.is_finite() && b.is_finite() {
+            xx += (a - x_mean) * (a - x_mean);
+            yy += (b - y_mean) * (b - y_mean);
+            xy += (a - x_mean) * (b - y_mean);
+        }
+    }
+    if xx <= 0.0 || yy <= 0.0 {
+        return (f64::NAN, count);
+    }
+    ((xy / (xx * yy).sqrt()).clamp(-1.0, 1.0), count)
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///calculate the correlation matrix of the series by the pairwise complete observations, the correlation of two
+    ///series uses the periods which have finite values in both series, so a missing period of one series does not
+    ///drop the period of the other pairs. the correlation of a pair with less than min_overlap common periods or a
+    ///constant series is NAN.
+    ///
+    ///# Arguments
+    ///series: the return array of each series, should have the same length.
+    ///
+    ///min_overlap: the minimum number of the common periods of a pair, it is 2 at least.
+    ///
+    ///matrix: the correlation matrix, matrix[i][j] is the correlation of the series i and the series j.
+    ///
+    ///counts: counts[i][j] is the number of the common periods of the series i and the series j.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let x = vec![1.0, 2.0, 3.0, f64::NAN];
+    ///let y = vec![2.0, 4.0, 6.0, 8.0];
+    ///let z = vec![f64::NAN, 3.0, 2.0, 1.0];
+    ///let mut matrix = Vec::new();
+    ///let mut counts = Vec::new();
+    ///let err = MPTCalculator::correlation_matrix(&[&x, &y, &z], 2, &mut matrix, &mut counts);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(matrix[0][1], 1.0)
+    ///        && MPTCalculator::is_eq_double(matrix[1][2], -1.0)
+    ///        && counts[0] == vec![3, 3, 2],
+    ///    true
+    ///);
+    ///```
+    pub fn correlation_matrix(
+        series: &[&[f64]],
+        min_overlap: usize,
+        matrix: &mut Vec<Vec<f64>>,
+        counts: &mut Vec<Vec<usize>>,
+    ) -> Errors {
+        instrument!(
+            "correlation_matrix",
+            rows = series.first().map_or(0, |x| x.len()),
+            cols = series.len()
+        );
+        matrix.clear();
+        counts.clear();
+        if series.len() == 0 || series.iter().any(|x| x.len() != series[0].len()) {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+
+        let n = series.len();
+        *matrix = vec![vec![f64::NAN; n]; n];
+        *counts = vec![vec![0; n]; n];
+        for i in 0..n {
+            for j in i..n {
+                let (correlation, count) = pairwise_correlation(series[i], series[j], min_overlap);
+                matrix[i][j] = if i == j && correlation.is_finite() {
+                    1.0
+                } else {
+                    correlation
+                };
+                matrix[j][i] = matrix[i][j];
+                counts[i][j] = count;
+                counts[j][i] = count;
+            }
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{enums::Errors, MPTCalculator};
+
+    #[test]
+    fn should_correct_correlation_matrix() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477,
+        ];
+        let bmk_data = vec![
+            0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+            1.40278, 1.51232,
+        ];
+        let mut missing = bmk_data.clone();
+        missing[0] = f64::NAN;
+        missing[5] = f64::NAN;
+        let short = vec![f64::NAN; 10]
+            .into_iter()
+            .chain([1.0, 2.0])
+            .collect::<Vec<f64>>();
+        let mut matrix = Vec::new();
+        let mut counts = Vec::new();
+        let err = MPTCalculator::correlation_matrix(
+            &[&data, &bmk_data, &missing, &short],
+            3,
+            &mut matrix,
+            &mut counts,
+        );
+
+        let mut expected = f64::NAN;
+        MPTCalculator::from_v_b(&data, &bmk_data).correlation(&mut expected);
+        let mut expected_missing = f64::NAN;
+        MPTCalculator::from_v_b(&data, &missing).correlation(&mut expected_missing);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(matrix[0][1], expected)
+                && MPTCalculator::is_eq_double(matrix[2][0], expected_missing)
+                && MPTCalculator::is_eq_double(matrix[1][2], 1.0)
+                && matrix[0][0] == 1.0
+                && matrix[3][0].is_nan()
+                && matrix[3][3].is_nan()
+                && counts[0][2] == 10
+                && counts[3][1] == 2,
+            true
+        );
+
+        let err =
+            MPTCalculator::correlation_matrix(&[&data, &data[..3]], 2, &mut matrix, &mut counts);
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && matrix.len() == 0,
+            true
+        );
+    }
+}
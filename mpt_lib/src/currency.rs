@@ -0,0 +1,141 @@
+use crate::{enums::Errors, MPTCalculator};
+
+impl<'a> MPTCalculator<'a> {
+    ///convert a local currency return array into the target currency, the output is an same size array.
+    ///if the value or the fx return is NAN/INF, the output value will be NAN
+    ///#Arguments:
+    ///fx_returns: the return of the local currency against the target currency of each period.
+    ///
+    ///# Examples
+    ///```
+    /// use mpt_lib::MPTCalculator;
+    /// use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![1.0, -0.5, 2.0];
+    ///let fx_returns = vec![0.5, -1.0, 0.3];
+    ///const EXPECTED_RES: [f64; 3] = [1.505, -1.495, 2.306];
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let mut res = [f64::NAN; 3];
+    ///let err = mpt.convert_currency(&fx_returns, &mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double_array(&res, &EXPECTED_RES),
+    ///    true
+    ///);
+    ///```
+    pub fn convert_currency(&self, fx_returns: &[f64], output: &mut [f64]) -> Errors {
+        if self.values.len() == 0
+            || fx_returns.len() != self.values.len()
+            || output.len() < self.values.len()
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        self.values.iter().enumerate().for_each(|x| {
+            if x.1.is_finite() && fx_returns[x.0].is_finite() {
+                output[x.0] = ((1.0 + x.1 / 100.0) * (1.0 + fx_returns[x.0] / 100.0) - 1.0) * 100.0;
+            } else {
+                output[x.0] = f64::NAN;
+            }
+        });
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///construct the hedged return array in the target currency, the currency exposure of each period is hedged
+    ///by a forward contract which is opened at the begin and settled at the end of the period.
+    ///the output is an same size array. if any input is NAN/INF, the output value will be NAN
+    ///#Arguments:
+    ///fx_returns: the return of the local currency against the target currency of each period.
+    ///
+    ///spot_rates: the spot rate at the begin of each period, the price of one local currency unit in the target currency.
+    ///
+    ///forward_points: the forward points at the begin of each period, in the same unit as the spot rate.
+    ///
+    ///hedge_ratio: the hedged percentage of the currency exposure, 1.0 is fully hedged.
+    ///
+    ///# Examples
+    ///```
+    /// use mpt_lib::MPTCalculator;
+    /// use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![1.0, -0.5, 2.0];
+    ///let fx_returns = vec![0.5, -1.0, 0.3];
+    ///let spot_rates = vec![1.10, 1.1055, 1.094445];
+    ///let forward_points = vec![0.001, 0.0012, 0.0009];
+    ///const EXPECTED_RES: [f64; 3] = [1.095909, -0.386452, 2.088233];
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let mut res = [f64::NAN; 3];
+    ///let err = mpt.hedged_return(&fx_returns, &spot_rates, &forward_points, 1.0, &mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double_array(&res, &EXPECTED_RES),
+    ///    true
+    ///);
+    ///```
+    pub fn hedged_return(
+        &self,
+        fx_returns: &[f64],
+        spot_rates: &[f64],
+        forward_points: &[f64],
+        hedge_ratio: f64,
+        output: &mut [f64],
+    ) -> Errors {
+        if self.values.len() == 0
+            || fx_returns.len() != self.values.len()
+            || spot_rates.len() != self.values.len()
+            || forward_points.len() != self.values.len()
+            || output.len() < self.values.len()
+            || !hedge_ratio.is_finite()
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let ret = self.convert_currency(fx_returns, output);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        for i in 0..self.values.len() {
+            let spot = spot_rates[i];
+            if !output[i].is_finite() || !spot.is_finite() || spot <= 0.0 {
+                output[i] = f64::NAN;
+                continue;
+            }
+            let forward = spot + forward_points[i];
+            let settle = spot * (1.0 + fx_returns[i] / 100.0);
+            output[i] += hedge_ratio * (forward - settle) / spot * 100.0;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{enums::Errors, MPTCalculator};
+
+    #[test]
+    fn should_correct_convert_currency() {
+        let data = vec![1.0, -0.5, 2.0, f64::NAN];
+        let fx_returns = vec![0.5, -1.0, 0.3, 1.0];
+        let mpt = MPTCalculator::from_v(&data);
+        let mut res = [0.0; 4];
+        let err = mpt.convert_currency(&fx_returns, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&res[0..3], &[1.505, -1.495, 2.306])
+                && res[3].is_nan(),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_hedged_return() {
+        let data = vec![1.0, -0.5, 2.0];
+        let fx_returns = vec![0.5, -1.0, 0.3];
+        let spot_rates = vec![1.10, 1.1055, 1.094445];
+        let forward_points = vec![0.001, 0.0012, 0.0009];
+        let mpt = MPTCalculator::from_v(&data);
+        let mut res = [f64::NAN; 3];
+        let err = mpt.hedged_return(&fx_returns, &spot_rates, &forward_points, 0.5, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&res, &[1.300455, -0.940726, 2.197117]),
+            true
+        );
+    }
+}
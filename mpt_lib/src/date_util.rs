@@ -1,3 +1,6 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
 use chrono::NaiveDate;
 use chrono::{Datelike, Days, Months};
 
@@ -54,6 +57,16 @@ pub fn to_semi_annu_begin(date: &mut NaiveDate) {
     *date = NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap();
 }
 
+pub fn to_semi_month_begin(date: &mut NaiveDate) {
+    let day = if date.day() > 15 { 16 } else { 1 };
+    *date = NaiveDate::from_ymd_opt(date.year(), date.month(), day).unwrap();
+}
+
+pub fn to_bi_month_begin(date: &mut NaiveDate) {
+    let month = (date.month() + 1) / 2 * 2 - 1;
+    *date = NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap();
+}
+
 pub fn to_year_begin(date: &mut NaiveDate) {
     *date = NaiveDate::from_ymd_opt(date.year(), 1, 1).unwrap();
 }
@@ -65,6 +78,8 @@ pub fn to_period_begin(freq: ClFrequency, date: &mut NaiveDate) {
         ClFrequency::ClFrequencyQuarterly => to_quarter_begin(date),
         ClFrequency::ClFrequencySemiannually => to_semi_annu_begin(date),
         ClFrequency::ClFrequencyAnnually => to_year_begin(date),
+        ClFrequency::ClFrequencySemimonthly => to_semi_month_begin(date),
+        ClFrequency::ClFrequencyBimonthly => to_bi_month_begin(date),
         _ => (),
     }
 }
@@ -89,6 +104,21 @@ pub fn to_semi_annu_end(date: &mut NaiveDate) {
         NaiveDate::from_ymd_opt(date.year(), month, last_day_of_month(date.year(), month)).unwrap();
 }
 
+pub fn to_semi_month_end(date: &mut NaiveDate) {
+    let day = if date.day() > 15 {
+        last_day_of_month(date.year(), date.month())
+    } else {
+        15
+    };
+    *date = NaiveDate::from_ymd_opt(date.year(), date.month(), day).unwrap();
+}
+
+pub fn to_bi_month_end(date: &mut NaiveDate) {
+    let month = (date.month() + 1) / 2 * 2;
+    *date =
+        NaiveDate::from_ymd_opt(date.year(), month, last_day_of_month(date.year(), month)).unwrap();
+}
+
 pub fn to_year_end(date: &mut NaiveDate) {
     *date = NaiveDate::from_ymd_opt(date.year(), 12, last_day_of_month(date.year(), 12)).unwrap();
 }
@@ -100,6 +130,8 @@ pub fn to_period_end(freq: ClFrequency, date: &mut NaiveDate) {
         ClFrequency::ClFrequencyQuarterly => to_quarter_end(date),
         ClFrequency::ClFrequencySemiannually => to_semi_annu_end(date),
         ClFrequency::ClFrequencyAnnually => to_year_end(date),
+        ClFrequency::ClFrequencySemimonthly => to_semi_month_end(date),
+        ClFrequency::ClFrequencyBimonthly => to_bi_month_end(date),
         _ => (),
     }
 }
@@ -124,6 +156,86 @@ pub fn to_period_end_int(freq: ClFrequency, n_date: u64) -> u64 {
     to_int(&naive_date)
 }
 
+pub fn to_iso_week_begin(date: &mut NaiveDate) {
+    *date = *date - Days::new(date.weekday().num_days_from_monday().into());
+}
+
+pub fn to_iso_week_end(date: &mut NaiveDate) {
+    *date = *date + Days::new((6 - date.weekday().num_days_from_monday()).into());
+}
+
+///the iso 8601 year and week number of the date, the week begins at monday
+pub fn iso_week(n_date: u64) -> (i32, u32) {
+    let mut naive_date = NaiveDate::default();
+    from_int(n_date, &mut naive_date);
+    let week = naive_date.iso_week();
+    (week.year(), week.week())
+}
+
+///the actual/actual(ISDA) year fraction from start_date to end_date, the days in a leap year
+///are divided by 366 and the others by 365
+pub fn year_fraction(start_date: u64, end_date: u64) -> f64 {
+    if end_date < start_date {
+        return -year_fraction(end_date, start_date);
+    }
+    let mut start = NaiveDate::default();
+    let mut end = NaiveDate::default();
+    from_int(start_date, &mut start);
+    from_int(end_date, &mut end);
+    let days_of_year = |year: i32| if is_leap_year(year) { 366.0 } else { 365.0 };
+    if start.year() == end.year() {
+        return (end - start).num_days() as f64 / days_of_year(start.year());
+    }
+    let next_year_begin = NaiveDate::from_ymd_opt(start.year() + 1, 1, 1).unwrap();
+    let end_year_begin = NaiveDate::from_ymd_opt(end.year(), 1, 1).unwrap();
+    (next_year_begin - start).num_days() as f64 / days_of_year(start.year())
+        + (end.year() - start.year() - 1) as f64
+        + (end - end_year_begin).num_days() as f64 / days_of_year(end.year())
+}
+
+///the index of the period which the date belongs to, the adjacent periods have the adjacent indexes.
+///the week begins at sunday as to_week_begin.
+pub fn period_index(freq: ClFrequency, n_date: u64) -> i64 {
+    period_index_by_week(freq, n_date, false)
+}
+
+///the index of the period which the date belongs to as period_index, the week begins at monday as
+///iso_week if is_iso_week is true, otherwise at sunday.
+pub fn period_index_by_week(freq: ClFrequency, n_date: u64, is_iso_week: bool) -> i64 {
+    let mut naive_date = NaiveDate::default();
+    from_int(n_date, &mut naive_date);
+    let year = naive_date.year() as i64;
+    let month = naive_date.month() as i64;
+    match freq {
+        ClFrequency::ClFrequencyDaily => n_date as i64,
+        ClFrequency::ClFrequencyWeekly if is_iso_week => {
+            to_iso_week_begin(&mut naive_date);
+            (to_int(&naive_date) as i64 - 2) / 7
+        }
+        ClFrequency::ClFrequencyWeekly => (to_period_begin_int(freq, n_date) as i64 - 1) / 7,
+        ClFrequency::ClFrequencyMonthly => year * 12 + month - 1,
+        ClFrequency::ClFrequencyQuarterly => year * 4 + (month - 1) / 3,
+        ClFrequency::ClFrequencySemiannually => year * 2 + (month - 1) / 6,
+        ClFrequency::ClFrequencyAnnually => year,
+        ClFrequency::ClFrequencySemimonthly => {
+            (year * 12 + month - 1) * 2 + if naive_date.day() > 15 { 1 } else { 0 }
+        }
+        ClFrequency::ClFrequencyBimonthly => year * 6 + (month - 1) / 2,
+        _ => 0,
+    }
+}
+
+///the number of periods from the period of d1 to the period of d2, it is negative if d2 is before d1
+pub fn periods_between(freq: ClFrequency, d1: u64, d2: u64) -> i64 {
+    period_index(freq, d2) - period_index(freq, d1)
+}
+
+///the number of periods from the period of d1 to the period of d2 as periods_between, the week
+///begins at monday as iso_week if is_iso_week is true.
+pub fn periods_between_by_week(freq: ClFrequency, d1: u64, d2: u64, is_iso_week: bool) -> i64 {
+    period_index_by_week(freq, d2, is_iso_week) - period_index_by_week(freq, d1, is_iso_week)
+}
+
 pub fn to_n_period_begin_int(freq: ClFrequency, n: i32, n_date: u64) -> u64 {
     let mut naive_date = NaiveDate::default();
     from_int(n_date, &mut naive_date);
@@ -262,24 +374,286 @@ pub fn to_n_period(
                 _ => (),
             }
         }
+        ClFrequency::ClFrequencySemimonthly => {
+            //move by whole months and then by the remaining half month
+            let half = n.rem_euclid(2);
+            let months = (n - half) / 2;
+            if months > 0 {
+                match date.checked_add_months(Months::new(months as u32)) {
+                    Some(new_date) => *date = new_date,
+                    _ => return false,
+                }
+            } else {
+                let m = months * (-1);
+                match date.checked_sub_months(Months::new(m as u32)) {
+                    Some(new_date) => *date = new_date,
+                    _ => return false,
+                }
+            }
+            if half == 1 {
+                if date.day() > 15 {
+                    match date.checked_add_months(Months::new(1)) {
+                        Some(new_date) => {
+                            *date = NaiveDate::from_ymd_opt(
+                                new_date.year(),
+                                new_date.month(),
+                                date.day() - 15,
+                            )
+                            .unwrap()
+                        }
+                        _ => return false,
+                    }
+                } else {
+                    let day = (date.day() + 15).min(last_day_of_month(date.year(), date.month()));
+                    *date = NaiveDate::from_ymd_opt(date.year(), date.month(), day).unwrap();
+                }
+            }
+            match action {
+                ClDateMoveAction::ClMoveToEnd => to_semi_month_end(date),
+                ClDateMoveAction::ClMoveToBegin => to_semi_month_begin(date),
+                _ => (),
+            }
+        }
+        ClFrequency::ClFrequencyBimonthly => {
+            if n > 0 {
+                match date.checked_add_months(Months::new(n as u32 * 2)) {
+                    Some(new_date) => *date = new_date,
+                    _ => return false,
+                }
+            } else {
+                let m = n * (-1);
+                match date.checked_sub_months(Months::new(m as u32 * 2)) {
+                    Some(new_date) => *date = new_date,
+                    _ => return false,
+                }
+            }
+            match action {
+                ClDateMoveAction::ClMoveToEnd => to_bi_month_end(date),
+                ClDateMoveAction::ClMoveToBegin => to_bi_month_begin(date),
+                _ => (),
+            }
+        }
         _ => (),
     }
     return true;
 }
 
+/// a holiday calendar used by the business day functions, the weekend is always not a business day.
+/// the calendar must be Send + Sync, so one calendar can be shared by the threads through an Arc
+pub trait HolidayCalendar: Send + Sync {
+    fn is_holiday(&self, n_date: u64) -> bool;
+}
+
+/// a shared calendar, so an Arc<HolidayList> or an Arc<dyn HolidayCalendar> can be passed to the business day functions
+impl<T: HolidayCalendar + ?Sized> HolidayCalendar for Arc<T> {
+    fn is_holiday(&self, n_date: u64) -> bool {
+        (**self).is_holiday(n_date)
+    }
+}
+
+/// a holiday calendar built from a list of holiday dates, the invalid dates less than 2 are ignored
+pub struct HolidayList {
+    holidays: HashSet<u64>,
+}
+
+impl HolidayList {
+    pub fn from(holidays: &[i32]) -> HolidayList {
+        HolidayList {
+            holidays: holidays
+                .iter()
+                .filter(|x| **x >= 2)
+                .map(|x| *x as u64)
+                .collect(),
+        }
+    }
+}
+
+impl HolidayCalendar for HolidayList {
+    fn is_holiday(&self, n_date: u64) -> bool {
+        self.holidays.contains(&n_date)
+    }
+}
+
+pub fn is_business_day(n_date: u64, calendar: Option<&dyn HolidayCalendar>) -> bool {
+    if is_weekend(n_date) {
+        return false;
+    }
+    match calendar {
+        Some(c) => !c.is_holiday(n_date),
+        None => true,
+    }
+}
+
+///the maximum number of the days to search for the next business day, a calendar without a business day in it
+///is invalid.
+const MAX_NON_BUSINESS_DAYS: u32 = 366;
+
+///move the date one day forward or backward, it is None before the first date 2.
+fn next_day(n_date: u64, forward: bool) -> Option<u64> {
+    if forward {
+        Some(n_date + 1)
+    } else if n_date > 2 {
+        Some(n_date - 1)
+    } else {
+        None
+    }
+}
+
+///move the date to a business day, ClMoveToEnd is the following business day,
+///ClMoveToBegin is the preceding business day. it is 0 if there is no business day in 366 days or before the first
+///date 2.
+pub fn to_business_day(
+    n_date: u64,
+    action: ClDateMoveAction,
+    calendar: Option<&dyn HolidayCalendar>,
+) -> u64 {
+    let forward = match action {
+        ClDateMoveAction::ClMoveToEnd => true,
+        ClDateMoveAction::ClMoveToBegin => false,
+        _ => return n_date,
+    };
+    let mut n_date = n_date;
+    let mut days = 0;
+    while !is_business_day(n_date, calendar) {
+        days += 1;
+        match next_day(n_date, forward) {
+            Some(next) if days <= MAX_NON_BUSINESS_DAYS => n_date = next,
+            _ => return 0,
+        }
+    }
+    n_date
+}
+
+///add n business days to the date, n can be negative. it is 0 if there is no business day in 366 days or before the
+///first date 2.
+pub fn add_business_days(n_date: u64, n: i32, calendar: Option<&dyn HolidayCalendar>) -> u64 {
+    let mut n_date = n_date;
+    let mut count = 0;
+    let mut days = 0;
+    while count < n.abs() {
+        days += 1;
+        match next_day(n_date, n > 0) {
+            Some(next) if days <= MAX_NON_BUSINESS_DAYS => n_date = next,
+            _ => return 0,
+        }
+        if is_business_day(n_date, calendar) {
+            count += 1;
+            days = 0;
+        }
+    }
+    n_date
+}
+
+///the business days number in (start_date, end_date], it is negative if end_date is before start_date
+pub fn business_days_between(
+    start_date: u64,
+    end_date: u64,
+    calendar: Option<&dyn HolidayCalendar>,
+) -> i32 {
+    if end_date < start_date {
+        return -business_days_between(end_date, start_date, calendar);
+    }
+    let mut count = 0;
+    for n_date in start_date + 1..end_date + 1 {
+        if is_business_day(n_date, calendar) {
+            count += 1;
+        }
+    }
+    count
+}
+
+///the business days number of a year, it can be used as the annual multiplier of daily data
+pub fn business_days_in_year(year: i32, calendar: Option<&dyn HolidayCalendar>) -> i32 {
+    let begin = to_int(&NaiveDate::from_ymd_opt(year, 1, 1).unwrap());
+    let end = to_int(&NaiveDate::from_ymd_opt(year, 12, 31).unwrap());
+    business_days_between(begin - 1, end, calendar)
+}
+
+///the average business days number of the years from the year of start_date to the year of end_date, it is the
+///annual multiplier of the daily data by the calendar. it is NAN if end_date is before start_date or start_date is
+///before the first date 2.
+pub fn annual_business_days(
+    start_date: u64,
+    end_date: u64,
+    calendar: Option<&dyn HolidayCalendar>,
+) -> f64 {
+    let (mut start, mut end) = (NaiveDate::MIN, NaiveDate::MIN);
+    if start_date < 2
+        || end_date < start_date
+        || !from_int(start_date, &mut start)
+        || !from_int(end_date, &mut end)
+    {
+        return f64::NAN;
+    }
+    let days: i32 = (start.year()..end.year() + 1)
+        .map(|year| business_days_in_year(year, calendar))
+        .sum();
+    days as f64 / (end.year() - start.year() + 1) as f64
+}
+
+///infer the frequency of the ascending dates by the median of the gaps in days, the median tolerates the irregular
+///gaps such as the weekends, the holidays and the missing periods while they are less than half of the gaps.
+///the duplicated dates are ignored, it is ClFrequencyUnknown if the dates are not ascending, there is no gap or
+///the median gap is longer than 500 days.
+pub fn infer_frequency(dates: &[i32]) -> ClFrequency {
+    if dates.windows(2).any(|x| x[1] < x[0]) {
+        return ClFrequency::ClFrequencyUnknown;
+    }
+    let mut gaps: Vec<i32> = dates
+        .windows(2)
+        .map(|x| x[1] - x[0])
+        .filter(|x| *x > 0)
+        .collect();
+    if gaps.len() == 0 {
+        return ClFrequency::ClFrequencyUnknown;
+    }
+    gaps.sort();
+    let median = if gaps.len() % 2 == 1 {
+        gaps[gaps.len() / 2] as f64
+    } else {
+        (gaps[gaps.len() / 2 - 1] + gaps[gaps.len() / 2]) as f64 / 2.0
+    };
+    if median < 4.0 {
+        ClFrequency::ClFrequencyDaily
+    } else if median < 11.0 {
+        ClFrequency::ClFrequencyWeekly
+    } else if median < 22.0 {
+        ClFrequency::ClFrequencySemimonthly
+    } else if median < 45.0 {
+        ClFrequency::ClFrequencyMonthly
+    } else if median < 75.0 {
+        ClFrequency::ClFrequencyBimonthly
+    } else if median < 135.0 {
+        ClFrequency::ClFrequencyQuarterly
+    } else if median < 270.0 {
+        ClFrequency::ClFrequencySemiannually
+    } else if median <= 500.0 {
+        ClFrequency::ClFrequencyAnnually
+    } else {
+        ClFrequency::ClFrequencyUnknown
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use std::sync::Arc;
+
     use chrono::{Datelike, NaiveDate};
 
     use crate::{
         date_util::{
-            self, to_month_end, to_period_end, to_quarter_end, to_semi_annu_end, to_week_end,
-            to_year_end,
+            self, to_month_end, to_period_begin, to_period_end, to_quarter_end, to_semi_annu_end,
+            to_week_end, to_year_end, HolidayCalendar,
         },
         enums::{ClDateMoveAction, ClFrequency},
     };
 
-    use super::to_n_period;
+    use super::{
+        add_business_days, annual_business_days, business_days_between, business_days_in_year,
+        infer_frequency, is_business_day, iso_week, period_index, period_index_by_week,
+        periods_between, periods_between_by_week, to_business_day, to_iso_week_begin,
+        to_iso_week_end, to_n_period, year_fraction, HolidayList,
+    };
     #[test]
     fn should_correct_from_int() {
         let mut dt = NaiveDate::default();
@@ -622,4 +996,223 @@ mod test {
         );
         assert_eq!(dt.year() == 2018 && dt.month() == 7 && dt.day() == 15, true);
     }
+
+    #[test]
+    fn should_correct_business_days() {
+        //2022-12-23 is friday, 2022-12-26 is a holiday
+        let friday = date_util::to_int(&NaiveDate::from_ymd_opt(2022, 12, 23).unwrap());
+        let holidays = HolidayList::from(&[(friday + 3) as i32]);
+        assert_eq!(is_business_day(friday + 1, None), false);
+        assert_eq!(is_business_day(friday + 3, None), true);
+        assert_eq!(is_business_day(friday + 3, Some(&holidays)), false);
+        assert_eq!(add_business_days(friday, 1, None), friday + 3);
+        assert_eq!(add_business_days(friday, 1, Some(&holidays)), friday + 4);
+        assert_eq!(add_business_days(friday + 4, -1, Some(&holidays)), friday);
+        assert_eq!(business_days_between(friday, friday + 7, None), 5);
+        assert_eq!(
+            business_days_between(friday, friday + 7, Some(&holidays)),
+            4
+        );
+        assert_eq!(
+            business_days_between(friday + 7, friday, Some(&holidays)),
+            -4
+        );
+        assert_eq!(
+            to_business_day(friday + 1, ClDateMoveAction::ClMoveToEnd, Some(&holidays)),
+            friday + 4
+        );
+        assert_eq!(
+            to_business_day(friday + 3, ClDateMoveAction::ClMoveToBegin, Some(&holidays)),
+            friday
+        );
+        assert_eq!(business_days_in_year(2022, None), 260);
+        assert_eq!(business_days_in_year(2022, Some(&holidays)), 259);
+        assert_eq!(
+            annual_business_days(friday, friday + 13, Some(&holidays)),
+            259.5
+        );
+        assert_eq!(annual_business_days(friday, friday, Some(&holidays)), 259.0);
+        assert_eq!(annual_business_days(friday, friday + 13, None), 260.0);
+        assert_eq!(
+            annual_business_days(friday + 13, friday, None).is_nan(),
+            true
+        );
+
+        //a calendar without business days and the dates before the first date
+        struct NoBusinessDay;
+        impl HolidayCalendar for NoBusinessDay {
+            fn is_holiday(&self, _n_date: u64) -> bool {
+                true
+            }
+        }
+        assert_eq!(
+            to_business_day(friday, ClDateMoveAction::ClMoveToEnd, Some(&NoBusinessDay)),
+            0
+        );
+        assert_eq!(add_business_days(friday, -2, Some(&NoBusinessDay)), 0);
+        let first = HolidayList::from(&[-5, 2]);
+        assert_eq!(
+            to_business_day(3, ClDateMoveAction::ClMoveToBegin, Some(&first)),
+            3
+        );
+        assert_eq!(
+            to_business_day(2, ClDateMoveAction::ClMoveToBegin, Some(&first)),
+            0
+        );
+        assert_eq!(add_business_days(3, -1, None), 2);
+        assert_eq!(add_business_days(3, -2, None), 0);
+    }
+
+    #[test]
+    fn should_correct_shared_calendar() {
+        let friday = date_util::to_int(&NaiveDate::from_ymd_opt(2022, 12, 23).unwrap());
+        let holidays: Arc<dyn HolidayCalendar> =
+            Arc::new(HolidayList::from(&[(friday + 3) as i32]));
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let calendar = Arc::clone(&holidays);
+                std::thread::spawn(move || add_business_days(friday, i, Some(&calendar)))
+            })
+            .collect();
+        let dates: Vec<u64> = handles.into_iter().map(|x| x.join().unwrap()).collect();
+        assert_eq!(dates, vec![friday, friday + 4, friday + 5, friday + 6]);
+    }
+
+    #[test]
+    fn should_correct_period_index() {
+        //2022-12-31 is saturday, 2023-01-01 is sunday
+        let d1 = date_util::to_int(&NaiveDate::from_ymd_opt(2022, 12, 31).unwrap());
+        let d2 = d1 + 1;
+        assert_eq!(periods_between(ClFrequency::ClFrequencyDaily, d1, d2), 1);
+        assert_eq!(periods_between(ClFrequency::ClFrequencyWeekly, d1, d2), 1);
+        assert_eq!(
+            periods_between(ClFrequency::ClFrequencyWeekly, d2, d2 + 6),
+            0
+        );
+        assert_eq!(periods_between(ClFrequency::ClFrequencyMonthly, d1, d2), 1);
+        assert_eq!(
+            periods_between(ClFrequency::ClFrequencyQuarterly, d2, d1),
+            -1
+        );
+        assert_eq!(
+            periods_between(ClFrequency::ClFrequencySemiannually, d1 - 200, d2),
+            2
+        );
+        assert_eq!(periods_between(ClFrequency::ClFrequencyAnnually, d1, d2), 1);
+        assert_eq!(period_index(ClFrequency::ClFrequencyMonthly, d2), 2023 * 12);
+        assert_eq!(iso_week(d1), (2022, 52));
+        assert_eq!(iso_week(d2), (2022, 52));
+        assert_eq!(iso_week(d2 + 1), (2023, 1));
+        assert_eq!(
+            periods_between_by_week(ClFrequency::ClFrequencyWeekly, d1, d2, true),
+            0
+        );
+        assert_eq!(
+            periods_between_by_week(ClFrequency::ClFrequencyWeekly, d2, d2 + 1, true),
+            1
+        );
+        assert_eq!(
+            periods_between_by_week(ClFrequency::ClFrequencyWeekly, d2 + 1, d2 + 7, true),
+            0
+        );
+        assert_eq!(
+            period_index_by_week(ClFrequency::ClFrequencyWeekly, d2 + 1, true),
+            (d2 as i64 + 1 - 2) / 7
+        );
+
+        let mut date = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+        to_iso_week_begin(&mut date);
+        assert_eq!(date, NaiveDate::from_ymd_opt(2022, 12, 26).unwrap());
+        to_iso_week_end(&mut date);
+        assert_eq!(date, NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
+    }
+
+    #[test]
+    fn should_correct_semi_month_and_bi_month() {
+        let mut date = NaiveDate::from_ymd_opt(2023, 2, 20).unwrap();
+        to_period_begin(ClFrequency::ClFrequencySemimonthly, &mut date);
+        assert_eq!(date, NaiveDate::from_ymd_opt(2023, 2, 16).unwrap());
+        to_period_end(ClFrequency::ClFrequencySemimonthly, &mut date);
+        assert_eq!(date, NaiveDate::from_ymd_opt(2023, 2, 28).unwrap());
+        to_period_begin(ClFrequency::ClFrequencyBimonthly, &mut date);
+        assert_eq!(date, NaiveDate::from_ymd_opt(2023, 1, 1).unwrap());
+        to_period_end(ClFrequency::ClFrequencyBimonthly, &mut date);
+        assert_eq!(date, NaiveDate::from_ymd_opt(2023, 2, 28).unwrap());
+
+        let mut date = NaiveDate::from_ymd_opt(2023, 1, 20).unwrap();
+        to_n_period(
+            ClFrequency::ClFrequencySemimonthly,
+            3,
+            ClDateMoveAction::ClMoveToEnd,
+            &mut date,
+        );
+        assert_eq!(date, NaiveDate::from_ymd_opt(2023, 3, 15).unwrap());
+        to_n_period(
+            ClFrequency::ClFrequencySemimonthly,
+            -1,
+            ClDateMoveAction::ClMoveToEnd,
+            &mut date,
+        );
+        assert_eq!(date, NaiveDate::from_ymd_opt(2023, 2, 28).unwrap());
+        to_n_period(
+            ClFrequency::ClFrequencyBimonthly,
+            1,
+            ClDateMoveAction::ClMoveToBegin,
+            &mut date,
+        );
+        assert_eq!(date, NaiveDate::from_ymd_opt(2023, 3, 1).unwrap());
+
+        let d1 = date_util::to_int(&NaiveDate::from_ymd_opt(2022, 12, 31).unwrap());
+        assert_eq!(
+            periods_between(ClFrequency::ClFrequencySemimonthly, d1, d1 + 15),
+            1
+        );
+        assert_eq!(
+            periods_between(ClFrequency::ClFrequencySemimonthly, d1, d1 + 16),
+            2
+        );
+        assert_eq!(
+            periods_between(ClFrequency::ClFrequencyBimonthly, d1, d1 + 59),
+            1
+        );
+    }
+
+    #[test]
+    fn should_correct_year_fraction() {
+        let d1 = date_util::to_int(&NaiveDate::from_ymd_opt(2006, 4, 30).unwrap());
+        let d2 = date_util::to_int(&NaiveDate::from_ymd_opt(2007, 3, 31).unwrap());
+        let d3 = date_util::to_int(&NaiveDate::from_ymd_opt(2009, 1, 1).unwrap());
+        assert_eq!((year_fraction(d1, d2) - 335.0 / 365.0).abs() < 1e-12, true);
+        assert_eq!((year_fraction(d2, d1) + 335.0 / 365.0).abs() < 1e-12, true);
+        assert_eq!(
+            (year_fraction(d2, d3) - (276.0 / 365.0 + 1.0)).abs() < 1e-12,
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_infer_frequency() {
+        //2024-01-01 is monday, the weekends and a holiday are skipped
+        let daily = vec![
+            45292, 45293, 45294, 45295, 45296, 45299, 45300, 45302, 45303, 45306,
+        ];
+        assert_eq!(infer_frequency(&daily), ClFrequency::ClFrequencyDaily);
+        let weekly = vec![45296, 45303, 45310, 45324, 45331];
+        assert_eq!(infer_frequency(&weekly), ClFrequency::ClFrequencyWeekly);
+        //month ends with a missing month and a duplicated date
+        let monthly = vec![45322, 45351, 45351, 45382, 45443, 45473, 45504];
+        assert_eq!(infer_frequency(&monthly), ClFrequency::ClFrequencyMonthly);
+        let quarterly = vec![45382, 45473, 45565, 45657];
+        assert_eq!(
+            infer_frequency(&quarterly),
+            ClFrequency::ClFrequencyQuarterly
+        );
+        let annually = vec![44926, 45291, 45657];
+        assert_eq!(infer_frequency(&annually), ClFrequency::ClFrequencyAnnually);
+        assert_eq!(infer_frequency(&[45291]), ClFrequency::ClFrequencyUnknown);
+        assert_eq!(
+            infer_frequency(&[45473, 45382]),
+            ClFrequency::ClFrequencyUnknown
+        );
+    }
 }
@@ -0,0 +1,742 @@
+use crate::{
+    common::{ln_gamma, normal_cdf},
+    enums::{self, Errors},
+    optimizer::nelder_mead,
+    MPTCalculator,
+};
+
+///the statistic and the asymptotic p value of a normality test, a small p value rejects the normal distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NormalityTestResult {
+    pub statistic: f64,
+    pub p_value: f64,
+}
+
+impl NormalityTestResult {
+    pub fn new() -> NormalityTestResult {
+        NormalityTestResult {
+            statistic: f64::NAN,
+            p_value: f64::NAN,
+        }
+    }
+}
+
+impl Default for NormalityTestResult {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///the fitted parameters of a return distribution by maximum likelihood, the scale is the standard deviation of the
+///distribution. the degrees of freedom is NAN for the normal distribution and the skewness is 0 for the normal and
+///the student t distribution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DistributionFit {
+    pub location: f64,
+    pub scale: f64,
+    pub degrees_of_freedom: f64,
+    pub skewness: f64,
+    pub log_likelihood: f64,
+    pub aic: f64,
+}
+
+impl DistributionFit {
+    pub fn new() -> DistributionFit {
+        DistributionFit {
+            location: f64::NAN,
+            scale: f64::NAN,
+            degrees_of_freedom: f64::NAN,
+            skewness: f64::NAN,
+            log_likelihood: f64::NAN,
+            aic: f64::NAN,
+        }
+    }
+}
+
+impl Default for DistributionFit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///one bin of the histogram, the bin includes the start and excludes the end except the last bin of the
+///fixed bin count, the percent is the count divided by the size of the values.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HistogramBin {
+    pub start: f64,
+    pub end: f64,
+    pub count: usize,
+    pub percent: f64,
+}
+
+///the log density of hansen's skewed t distribution with zero mean and unit variance, the skewness is in (-1, 1)
+///and it is the standardized student t distribution when the skewness is 0.
+fn skew_t_log_density(z: f64, degrees_of_freedom: f64, skewness: f64) -> f64 {
+    let eta = degrees_of_freedom;
+    let c = (ln_gamma((eta + 1.0) / 2.0) - ln_gamma(eta / 2.0)).exp()
+        / (std::f64::consts::PI * (eta - 2.0)).sqrt();
+    let a = 4.0 * skewness * c * (eta - 2.0) / (eta - 1.0);
+    let b = (1.0 + 3.0 * skewness * skewness - a * a).sqrt();
+    let side = if z < -a / b {
+        1.0 - skewness
+    } else {
+        1.0 + skewness
+    };
+    let w = (b * z + a) / side;
+    return (b * c).ln() - (eta + 1.0) / 2.0 * (1.0 + w * w / (eta - 2.0)).ln();
+}
+
+///the sorted standardized values by the mean and the sample standard deviation, return None if the values has
+///NAN/INF values or the standard deviation is 0.
+fn sorted_standardized(values: &[f64]) -> Option<Vec<f64>> {
+    if values.len() < 2 || values.iter().any(|x| !x.is_finite()) {
+        return None;
+    }
+    let count = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / count;
+    let std_dev =
+        (values.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (count - 1.0)).sqrt();
+    if !(std_dev > 0.0) {
+        return None;
+    }
+    let mut z: Vec<f64> = values.iter().map(|x| (x - mean) / std_dev).collect();
+    z.sort_by(|a, b| a.total_cmp(b));
+    return Some(z);
+}
+
+///the survival function of the kolmogorov distribution.
+fn kolmogorov_survival(lambda: f64) -> f64 {
+    let mut sum = 0.0;
+    let mut sign = 1.0;
+    for j in 1..=100 {
+        let j = j as f64;
+        let term = sign * 2.0 * (-2.0 * j * j * lambda * lambda).exp();
+        sum += term;
+        if term.abs() <= 1e-10 * sum.abs() {
+            return sum.clamp(0.0, 1.0);
+        }
+        sign = -sign;
+    }
+    return 1.0;
+}
+
+///the bound of the normal cdf of the anderson darling statistic, so the far outlier does not make the log infinite.
+const ANDERSON_DARLING_EPSILON: f64 = 1e-15;
+
+///the maximum number of the bins of a histogram, the bin count or the bin width above it is invalid.
+const MAX_BINS: usize = 10_000;
+
+///the equal width bins between the min and max of the values, the max value is in the last bin.
+///return the start of the first bin, the bin width and the count of each bin.
+fn equal_width_bins(values: &[f64], bins: usize) -> (f64, f64, Vec<usize>) {
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let width = (max - min) / bins as f64;
+    let mut counts = vec![0; bins];
+    values.iter().for_each(|x| {
+        let i = if width > 0.0 {
+            (((x - min) / width) as usize).min(bins - 1)
+        } else {
+            0
+        };
+        counts[i] += 1;
+    });
+    return (min, width, counts);
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///calculate the shannon entropy of the return distribution in nats, the values are grouped into equal width bins
+    ///between the min and max value. if the array has NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///bins: the number of bins, it is 10000 at most.
+    ///
+    ///entropy: the entropy, it is between 0 and ln(bins).
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let mut res = f64::NAN;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.return_entropy(10, &mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 1.938412),
+    ///    true
+    ///);
+    ///```
+    pub fn return_entropy(&self, bins: usize, entropy: &mut f64) -> Errors {
+        if self.values.len() == 0 || bins == 0 || bins > MAX_BINS {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *entropy = f64::NAN;
+        if self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let count = self.values.len() as f64;
+        let (_, _, counts) = equal_width_bins(self.values, bins);
+        *entropy = -counts
+            .iter()
+            .filter(|x| **x > 0)
+            .map(|x| *x as f64 / count * (*x as f64 / count).ln())
+            .sum::<f64>();
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the relative entropy(kullback leibler divergence) of the return distribution to the normal distribution
+    ///with the same mean and sample standard deviation, the values are grouped into ceil(log2(n)) + 1 equal width bins
+    ///and the first and last bins of the normal distribution are extended to infinity.
+    ///if the array has NAN/INF values or the standard deviation is 0,the result will be NAN.
+    ///
+    ///# Arguments
+    ///relative_entropy: the relative entropy in nats, it is 0 when the histogram is the same as the normal distribution.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let mut res = f64::NAN;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.relative_entropy_vs_normal(&mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 0.153083),
+    ///    true
+    ///);
+    ///```
+    pub fn relative_entropy_vs_normal(&self, relative_entropy: &mut f64) -> Errors {
+        if self.values.len() == 0 {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *relative_entropy = f64::NAN;
+        if self.values.len() < 2 || self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let count = self.values.len() as f64;
+        let mean = self.values.iter().sum::<f64>() / count;
+        let std_dev = (self
+            .values
+            .iter()
+            .map(|x| (x - mean) * (x - mean))
+            .sum::<f64>()
+            / (count - 1.0))
+            .sqrt();
+        if !(std_dev > 0.0) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let bins = count.log2().ceil() as usize + 1;
+        let (min, width, counts) = equal_width_bins(self.values, bins);
+        let bound = |i: usize| {
+            if i == 0 {
+                f64::NEG_INFINITY
+            } else if i == bins {
+                f64::INFINITY
+            } else {
+                (min + i as f64 * width - mean) / std_dev
+            }
+        };
+        *relative_entropy = counts
+            .iter()
+            .enumerate()
+            .filter(|x| *x.1 > 0)
+            .map(|x| {
+                let p = *x.1 as f64 / count;
+                let q = normal_cdf(bound(x.0 + 1)) - normal_cdf(bound(x.0));
+                p * (p / q).ln()
+            })
+            .sum();
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the kolmogorov smirnov test of the values against the normal distribution with the same mean and
+    ///sample standard deviation, the p value is from the asymptotic kolmogorov distribution, it is conservative as the
+    ///parameters are estimated from the values. if the array has NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///result: the max distance between the empirical and the normal distribution function and its p value.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::distribution::NormalityTestResult;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let mut res = NormalityTestResult::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.kolmogorov_smirnov_test(&mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(res.statistic, 0.162612)
+    ///        && MPTCalculator::is_eq_double(res.p_value, 0.271972),
+    ///    true
+    ///);
+    ///```
+    pub fn kolmogorov_smirnov_test(&self, result: &mut NormalityTestResult) -> Errors {
+        if self.values.len() == 0 {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *result = NormalityTestResult::new();
+        let z = match sorted_standardized(self.values) {
+            Some(z) => z,
+            None => return Errors::ClErrorCodeNoError,
+        };
+
+        let count = z.len() as f64;
+        let distance = z
+            .iter()
+            .enumerate()
+            .map(|x| {
+                let cdf = normal_cdf(*x.1);
+                f64::max((x.0 + 1) as f64 / count - cdf, cdf - x.0 as f64 / count)
+            })
+            .fold(0.0, f64::max);
+        let sqrt_count = count.sqrt();
+        result.statistic = distance;
+        result.p_value = kolmogorov_survival((sqrt_count + 0.12 + 0.11 / sqrt_count) * distance);
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the anderson darling test of the values against the normal distribution with the same mean and
+    ///sample standard deviation, the p value is from the d'agostino and stephens approximation of the statistic
+    ///adjusted by (1 + 0.75 / n + 2.25 / n^2). if the array has NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///result: the unadjusted anderson darling statistic and its p value.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::distribution::NormalityTestResult;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let mut res = NormalityTestResult::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.anderson_darling_test(&mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(res.statistic, 1.202855)
+    ///        && MPTCalculator::is_eq_double(res.p_value, 0.003345),
+    ///    true
+    ///);
+    ///```
+    pub fn anderson_darling_test(&self, result: &mut NormalityTestResult) -> Errors {
+        if self.values.len() == 0 {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *result = NormalityTestResult::new();
+        let z = match sorted_standardized(self.values) {
+            Some(z) => z,
+            None => return Errors::ClErrorCodeNoError,
+        };
+
+        let n = z.len();
+        let count = n as f64;
+        let cdf: Vec<f64> = z
+            .iter()
+            .map(|x| normal_cdf(*x).clamp(ANDERSON_DARLING_EPSILON, 1.0 - ANDERSON_DARLING_EPSILON))
+            .collect();
+        let statistic = -count
+            - (0..n)
+                .map(|i| (2 * i + 1) as f64 * (cdf[i].ln() + (1.0 - cdf[n - 1 - i]).ln()))
+                .sum::<f64>()
+                / count;
+        let adjusted = statistic * (1.0 + 0.75 / count + 2.25 / (count * count));
+        result.statistic = statistic;
+        result.p_value = if adjusted >= 0.6 {
+            (1.2937 - 5.709 * adjusted + 0.0186 * adjusted * adjusted).exp()
+        } else if adjusted >= 0.34 {
+            (0.9177 - 4.279 * adjusted - 1.38 * adjusted * adjusted).exp()
+        } else if adjusted >= 0.2 {
+            1.0 - (-8.318 + 42.796 * adjusted - 59.938 * adjusted * adjusted).exp()
+        } else {
+            1.0 - (-13.436 + 101.14 * adjusted - 223.73 * adjusted * adjusted).exp()
+        };
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///fit the distribution of the values by maximum likelihood, the normal distribution is fitted in closed form and
+    ///the student t and skew t distributions are fitted by the nelder mead method from the normal fit.
+    ///the skew t is hansen's skewed t distribution, the degrees of freedom is larger than 2 so the variance exists.
+    ///if the array has NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///kind: the distribution to fit.
+    ///
+    ///fit: the fitted parameters, the log likelihood and the akaike information criterion.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::distribution::DistributionFit;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let mut fit = DistributionFit::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.fit_distribution(enums::ClDistributionKind::ClDistributionStudentT, &mut fit);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(fit.location, 0.153938)
+    ///        && MPTCalculator::is_eq_double(fit.scale, 5.416759)
+    ///        && MPTCalculator::is_eq_double(fit.degrees_of_freedom, 2.875457)
+    ///        && MPTCalculator::is_eq_double(fit.aic, 213.676167),
+    ///    true
+    ///);
+    ///```
+    pub fn fit_distribution(
+        &self,
+        kind: enums::ClDistributionKind,
+        fit: &mut DistributionFit,
+    ) -> Errors {
+        if self.values.len() == 0 {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *fit = DistributionFit::new();
+        if self.values.len() < 2 || self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let count = self.values.len() as f64;
+        let mean = self.values.iter().sum::<f64>() / count;
+        let std_dev = (self
+            .values
+            .iter()
+            .map(|x| (x - mean) * (x - mean))
+            .sum::<f64>()
+            / count)
+            .sqrt();
+        if !(std_dev > 0.0) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let parameters = match kind {
+            enums::ClDistributionKind::ClDistributionNormal => {
+                fit.location = mean;
+                fit.scale = std_dev;
+                fit.skewness = 0.0;
+                fit.log_likelihood =
+                    -count / 2.0 * ((2.0 * std::f64::consts::PI * std_dev * std_dev).ln() + 1.0);
+                2.0
+            }
+            enums::ClDistributionKind::ClDistributionStudentT
+            | enums::ClDistributionKind::ClDistributionSkewT => {
+                let is_skew = kind == enums::ClDistributionKind::ClDistributionSkewT;
+                let negative_log_likelihood = |x: &[f64]| {
+                    let scale = x[1].exp();
+                    let degrees_of_freedom = 2.0 + x[2].exp();
+                    let skewness = if is_skew { x[3].tanh() } else { 0.0 };
+                    -self
+                        .values
+                        .iter()
+                        .map(|v| {
+                            skew_t_log_density((v - x[0]) / scale, degrees_of_freedom, skewness)
+                                - x[1]
+                        })
+                        .sum::<f64>()
+                };
+                let mut start = vec![mean, std_dev.ln(), 4.0_f64.ln()];
+                if is_skew {
+                    start.push(0.0);
+                }
+                let (x, value) = nelder_mead(negative_log_likelihood, &start, 0.1, 20000);
+                fit.location = x[0];
+                fit.scale = x[1].exp();
+                fit.degrees_of_freedom = 2.0 + x[2].exp();
+                fit.skewness = if is_skew { x[3].tanh() } else { 0.0 };
+                fit.log_likelihood = -value;
+                start.len() as f64
+            }
+        };
+        fit.aic = 2.0 * parameters - 2.0 * fit.log_likelihood;
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the histogram of the values, ClHistogramBinCount splits the range between the min and max value into
+    ///equal width bins, ClHistogramBinWidth uses the bins of the width aligned to the multiples of the width.
+    ///there are 10000 bins at most. if the array has NAN/INF values,the histogram will be empty.
+    ///
+    ///# Arguments
+    ///bins: the bin count or the bin width.
+    ///
+    ///histogram: the bins sorted by the start.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let mut histogram = Vec::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.histogram(enums::ClHistogramBins::ClHistogramBinWidth(5.0), &mut histogram);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && histogram.len() == 5
+    ///        && MPTCalculator::is_eq_double(histogram[0].start, -20.0)
+    ///        && histogram[4].count == 17
+    ///        && MPTCalculator::is_eq_double(histogram[4].percent, 47.222222),
+    ///    true
+    ///);
+    ///```
+    pub fn histogram(
+        &self,
+        bins: enums::ClHistogramBins,
+        histogram: &mut Vec<HistogramBin>,
+    ) -> Errors {
+        let valid = match bins {
+            enums::ClHistogramBins::ClHistogramBinCount(count) => count > 0 && count <= MAX_BINS,
+            enums::ClHistogramBins::ClHistogramBinWidth(width) => width > 0.0 && width.is_finite(),
+        };
+        if self.values.len() == 0 || !valid {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        histogram.clear();
+        if self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let (start, width, counts) = match bins {
+            enums::ClHistogramBins::ClHistogramBinCount(count) => {
+                equal_width_bins(self.values, count)
+            }
+            enums::ClHistogramBins::ClHistogramBinWidth(width) => {
+                let min = self.values.iter().cloned().fold(f64::INFINITY, f64::min);
+                let max = self
+                    .values
+                    .iter()
+                    .cloned()
+                    .fold(f64::NEG_INFINITY, f64::max);
+                let start = (min / width).floor() * width;
+                let bins = ((max - start) / width).floor() + 1.0;
+                if !(bins <= MAX_BINS as f64) {
+                    return Errors::ClErrorCodeInvalidPara;
+                }
+                let mut counts = vec![0; bins as usize];
+                self.values.iter().for_each(|x| {
+                    let i = (((x - start) / width).floor() as usize).min(counts.len() - 1);
+                    counts[i] += 1;
+                });
+                (start, width, counts)
+            }
+        };
+        let count = self.values.len() as f64;
+        histogram.extend(counts.iter().enumerate().map(|x| HistogramBin {
+            start: start + x.0 as f64 * width,
+            end: start + (x.0 + 1) as f64 * width,
+            count: *x.1,
+            percent: *x.1 as f64 / count * 100.0,
+        }));
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DistributionFit, NormalityTestResult};
+    use crate::{
+        enums::{self, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_return_entropy() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let mut res = f64::NAN;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.return_entropy(5, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 1.285335),
+            true
+        );
+
+        let err = mpt.relative_entropy_vs_normal(&mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 0.153083),
+            true
+        );
+
+        let data = vec![1.0, 1.0, 1.0];
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.return_entropy(4, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res == 0.0, true);
+        let err = mpt.relative_entropy_vs_normal(&mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res.is_nan(), true);
+        let err = mpt.return_entropy(0, &mut res);
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+
+    #[test]
+    fn should_correct_normality_test() {
+        let data = vec![
+            0.5, -0.3, 1.2, 0.1, -0.8, 0.4, -0.2, 0.9, -1.1, 0.3, 0.0, -0.5, 0.7, -0.6, 0.2, 1.5,
+            -1.4, 0.6,
+        ];
+        let mut res = NormalityTestResult::new();
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.kolmogorov_smirnov_test(&mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(res.statistic, 0.068533)
+                && MPTCalculator::is_eq_double(res.p_value, 0.99999),
+            true
+        );
+
+        let err = mpt.anderson_darling_test(&mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(res.statistic, 0.083803)
+                && MPTCalculator::is_eq_double(res.p_value, 0.998119),
+            true
+        );
+
+        let mut data = vec![0.0; 199];
+        data.push(100.0);
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.anderson_darling_test(&mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && res.statistic.is_finite() && res.p_value < 0.01,
+            true
+        );
+
+        let data = vec![1.0, f64::NAN, 2.0];
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.anderson_darling_test(&mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && res.statistic.is_nan() && res.p_value.is_nan(),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_fit_distribution() {
+        let data = vec![
+            1.52768, 4.04616, 3.40287, -2.43748, 2.1044, -1.7708, -1.89656, 3.18186, 0.14197,
+            3.71883, -0.9124, 0.80994, -1.66708, 3.78221, 0.03481, 2.64778, 0.27133, 1.24475,
+            1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016, 1.40278, 1.51232,
+            -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901, 3.73988, 1.59068,
+            -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526, -8.43036, -0.84062,
+            1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864, -10.64778, 8.75952,
+        ];
+        let mut fit = DistributionFit::new();
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.fit_distribution(enums::ClDistributionKind::ClDistributionNormal, &mut fit);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(fit.location, -0.346618)
+                && MPTCalculator::is_eq_double(fit.scale, 4.389688)
+                && fit.degrees_of_freedom.is_nan()
+                && MPTCalculator::is_eq_double(fit.log_likelihood, -156.502624)
+                && MPTCalculator::is_eq_double(fit.aic, 317.005247),
+            true
+        );
+
+        let err = mpt.fit_distribution(enums::ClDistributionKind::ClDistributionSkewT, &mut fit);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(fit.location, -0.481041)
+                && MPTCalculator::is_eq_double(fit.scale, 5.572577)
+                && MPTCalculator::is_eq_double(fit.degrees_of_freedom, 2.979989)
+                && MPTCalculator::is_eq_double(fit.skewness, -0.600746)
+                && MPTCalculator::is_eq_double(fit.log_likelihood, -147.651288)
+                && MPTCalculator::is_eq_double(fit.aic, 303.302577),
+            true
+        );
+
+        let data = vec![1.0, f64::NAN, 2.0];
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.fit_distribution(enums::ClDistributionKind::ClDistributionNormal, &mut fit);
+        assert_eq!(err == Errors::ClErrorCodeNoError && fit.aic.is_nan(), true);
+    }
+
+    #[test]
+    fn should_correct_histogram() {
+        let data = vec![1.0, -2.5, 0.5, 3.0, -0.5, 2.0, 0.0, 1.5];
+        let mut histogram = Vec::new();
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.histogram(
+            enums::ClHistogramBins::ClHistogramBinCount(4),
+            &mut histogram,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && histogram.len() == 4
+                && MPTCalculator::is_eq_double(histogram[0].start, -2.5)
+                && MPTCalculator::is_eq_double(histogram[3].end, 3.0)
+                && histogram.iter().map(|x| x.count).collect::<Vec<usize>>() == vec![1, 2, 3, 2]
+                && MPTCalculator::is_eq_double(histogram[2].percent, 37.5),
+            true
+        );
+
+        let err = mpt.histogram(
+            enums::ClHistogramBins::ClHistogramBinWidth(1.0),
+            &mut histogram,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && histogram.len() == 7
+                && MPTCalculator::is_eq_double(histogram[0].start, -3.0)
+                && MPTCalculator::is_eq_double(histogram[6].end, 4.0)
+                && histogram.iter().map(|x| x.count).collect::<Vec<usize>>()
+                    == vec![1, 0, 1, 2, 2, 1, 1],
+            true
+        );
+
+        let err = mpt.histogram(
+            enums::ClHistogramBins::ClHistogramBinWidth(0.0),
+            &mut histogram,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+
+        //too many bins
+        let err = mpt.histogram(
+            enums::ClHistogramBins::ClHistogramBinWidth(1e-300),
+            &mut histogram,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && histogram.len() == 0,
+            true
+        );
+        let err = mpt.histogram(
+            enums::ClHistogramBins::ClHistogramBinCount(usize::MAX),
+            &mut histogram,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{
+    common::{annualize_return, is_valid_frequency},
+    enums::{self, Errors},
+    kernels, MPTCalculator,
+};
+
+///the sorted depth of the draw down of each period from the running peak of the compound value, in percent.
+///return None if the values has NAN/INF values.
+fn sorted_draw_down_depths(values: &[f64]) -> Option<Vec<f64>> {
+    if values.iter().any(|x| !x.is_finite()) {
+        return None;
+    }
+    let mut depths = kernels::draw_down_depths(values);
+    depths.sort_by(|a, b| a.total_cmp(b));
+    return Some(depths);
+}
+
+///the linear interpolated alpha quantile of the sorted depths and the average depth of the tail beyond it.
+fn draw_down_at_risk_internal(depths: &[f64], alpha: f64) -> (f64, f64) {
+    let depth = kernels::sorted_quantile(depths, alpha);
+    let tail: Vec<f64> = depths.iter().filter(|x| **x >= depth).cloned().collect();
+    let conditional = tail.iter().sum::<f64>() / tail.len() as f64;
+    return (depth, conditional);
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///calculate the draw down at risk value of an array, it is the alpha quantile of the draw down of each period
+    ///from the running peak, the quantile is linear interpolated. if the array has NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///alpha: the confidence level between 0 and 1, e.g. 0.95.
+    ///
+    ///draw_down_at_risk: the draw down at risk, in percent.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let data = vec![
+    ///    2.1, -3.4, 1.2, -0.8, -2.5, 3.1, 1.7, -1.1, 0.6, -4.2, 2.8, 1.5, -0.9, 2.2, -1.6, -2.9,
+    ///    0.4, 3.3, -1.2, 1.9, -2.6, 0.7, 1.4, -0.5,
+    ///];
+    ///let mut res = f64::NAN;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.draw_down_at_risk(0.95, &mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, -5.318131),
+    ///    true
+    ///);
+    ///```
+    pub fn draw_down_at_risk(&self, alpha: f64, draw_down_at_risk: &mut f64) -> Errors {
+        if self.values.len() == 0 || !(alpha >= 0.0 && alpha <= 1.0) {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *draw_down_at_risk = f64::NAN;
+        if let Some(depths) = sorted_draw_down_depths(self.values) {
+            *draw_down_at_risk = -draw_down_at_risk_internal(&depths, alpha).0;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the conditional draw down at risk value of an array, it is the average of the draw downs of the periods
+    ///which are not less deep than the draw down at risk. if the array has NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///alpha: the confidence level between 0 and 1, e.g. 0.95.
+    ///
+    ///conditional_draw_down_at_risk: the conditional draw down at risk, in percent.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let data = vec![
+    ///    2.1, -3.4, 1.2, -0.8, -2.5, 3.1, 1.7, -1.1, 0.6, -4.2, 2.8, 1.5, -0.9, 2.2, -1.6, -2.9,
+    ///    0.4, 3.3, -1.2, 1.9, -2.6, 0.7, 1.4, -0.5,
+    ///];
+    ///let mut res = f64::NAN;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.conditional_draw_down_at_risk(0.8, &mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, -4.628399),
+    ///    true
+    ///);
+    ///```
+    pub fn conditional_draw_down_at_risk(
+        &self,
+        alpha: f64,
+        conditional_draw_down_at_risk: &mut f64,
+    ) -> Errors {
+        if self.values.len() == 0 || !(alpha >= 0.0 && alpha <= 1.0) {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *conditional_draw_down_at_risk = f64::NAN;
+        if let Some(depths) = sorted_draw_down_depths(self.values) {
+            *conditional_draw_down_at_risk = -draw_down_at_risk_internal(&depths, alpha).1;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the conditional draw down ratio value of an array, it is the annualized geometric return divided by
+    ///the absolute conditional draw down at risk. if the array has NAN/INF values or the conditional draw down at risk
+    ///is 0,the result will be NAN.
+    ///
+    ///# Arguments
+    ///freq: the frequence of source data.
+    ///
+    ///alpha: the confidence level between 0 and 1, e.g. 0.95.
+    ///
+    ///ratio: the conditional draw down ratio.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![
+    ///    2.1, -3.4, 1.2, -0.8, -2.5, 3.1, 1.7, -1.1, 0.6, -4.2, 2.8, 1.5, -0.9, 2.2, -1.6, -2.9,
+    ///    0.4, 3.3, -1.2, 1.9, -2.6, 0.7, 1.4, -0.5,
+    ///];
+    ///let mut res = f64::NAN;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err =
+    ///    mpt.conditional_draw_down_ratio(enums::ClFrequency::ClFrequencyMonthly, 0.8, &mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 0.071052),
+    ///    true
+    ///);
+    ///```
+    pub fn conditional_draw_down_ratio(
+        &self,
+        freq: enums::ClFrequency,
+        alpha: f64,
+        ratio: &mut f64,
+    ) -> Errors {
+        *ratio = f64::NAN;
+        if !is_valid_frequency(freq) {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let mut conditional_draw_down_at_risk = f64::NAN;
+        let ret = self.conditional_draw_down_at_risk(alpha, &mut conditional_draw_down_at_risk);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        if !conditional_draw_down_at_risk.is_finite() || conditional_draw_down_at_risk == 0.0 {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let total_return = (self
+            .values
+            .iter()
+            .fold(1.0, |acc, v| acc * (1.0 + v / 100.0))
+            - 1.0)
+            * 100.0;
+        let annu_total_return =
+            annualize_return(total_return, freq, self.values.len() as f64, true);
+        if annu_total_return.is_finite() {
+            *ratio = annu_total_return / conditional_draw_down_at_risk.abs();
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        enums::{self, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_draw_down_at_risk() {
+        let data = vec![
+            2.1, -3.4, 1.2, -0.8, -2.5, 3.1, 1.7, -1.1, 0.6, -4.2, 2.8, 1.5, -0.9, 2.2, -1.6, -2.9,
+            0.4, 3.3, -1.2, 1.9, -2.6, 0.7, 1.4, -0.5,
+        ];
+        let mut res = f64::NAN;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.draw_down_at_risk(0.8, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, -3.173724),
+            true
+        );
+
+        let err = mpt.conditional_draw_down_at_risk(0.95, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, -5.475662),
+            true
+        );
+
+        let err =
+            mpt.conditional_draw_down_ratio(enums::ClFrequency::ClFrequencyMonthly, 0.95, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 0.060058),
+            true
+        );
+
+        let data = vec![1.0, 2.0, 3.0];
+        let mpt = MPTCalculator::from_v(&data);
+        let err =
+            mpt.conditional_draw_down_ratio(enums::ClFrequency::ClFrequencyMonthly, 0.95, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res.is_nan(), true);
+
+        let data = vec![1.0, f64::NAN, 3.0];
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.draw_down_at_risk(0.95, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res.is_nan(), true);
+        let err = mpt.draw_down_at_risk(1.5, &mut res);
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+        res = 1.0;
+        let err =
+            mpt.conditional_draw_down_ratio(enums::ClFrequency::ClFrequencyUnknown, 0.95, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeInvalidPara && res.is_nan(), true);
+    }
+}
@@ -1,5 +1,5 @@
+use core::{fmt, fmt::Display};
 use num_enum::TryFromPrimitive;
-use std::{error::Error, fmt, fmt::Display};
 #[derive(TryFromPrimitive)]
 #[repr(u32)]
 #[derive(PartialEq, Debug, Clone, Copy)]
@@ -14,6 +14,8 @@ pub enum Errors {
     ClErrorCodeDidNotSetHoliday,
     ClErrorCodeUnsortedByDate,
     ClErrorCodeJni,
+    ClErrorCodeInsufficientData,
+    ClErrorCodeCancelled,
     ClErrorCodeUnknown = 1000,
     ClErrorMleCodeLogStalbeVar = 1001,
     ClErrorCodeFtqLogStalbeVar = 1002,
@@ -21,7 +23,9 @@ pub enum Errors {
     ClErrorCodeEcfLogStalbeVar = 1004,
     ClErrorCodeCurHergeException = 1005,
 }
-impl Error for Errors {
+//core::error::Error needs rust 1.81, so the impl is only built with std.
+#[cfg(feature = "std")]
+impl std::error::Error for Errors {
     fn description(&self) -> &str {
         "Calculation failed:"
     }
@@ -29,13 +33,13 @@ impl Error for Errors {
 
 impl Display for Errors {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "calculaton failed:{}", self)
+        write!(f, "calculaton failed:{:?}", self)
     }
 }
 
 #[derive(TryFromPrimitive)]
 #[repr(i16)]
-#[derive(PartialEq, Clone, Copy)]
+#[derive(PartialEq, Clone, Copy, Debug)]
 pub enum ClFrequency {
     ClFrequencyUnknown = -1,
     ClFrequencyDaily,        //= 0,
@@ -44,6 +48,8 @@ pub enum ClFrequency {
     ClFrequencyQuarterly,    //3
     ClFrequencyAnnually,     //4
     ClFrequencySemiannually, //5
+    ClFrequencySemimonthly,  //6
+    ClFrequencyBimonthly,    //7
 }
 
 #[derive(TryFromPrimitive)]
@@ -86,3 +92,224 @@ pub enum ClCreditQualityRating {
     CLGradeAA = 6,
     CLGradeAAA = 7,
 }
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClLinkingMethod {
+    ClLinkingCarino = 0,
+    ClLinkingMenchero = 1,
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClCaptureMethod {
+    ClCaptureGeometricAverage = 0,
+    ClCaptureCumulative = 1,
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClVarianceMode {
+    ClVarianceSample = 0,
+    ClVariancePopulation = 1,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClSemiDeviationTarget {
+    ClSemiDeviationMean,
+    ClSemiDeviationZero,
+    ClSemiDeviationValue(f64),
+    ClSemiDeviationRiskFree,
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClDrawDownBlock {
+    ClDrawDownBlockAnnual = 0,
+    ClDrawDownBlockCalendarYear = 1,
+    ClDrawDownBlockRolling = 2,
+    ClDrawDownBlockEpisode = 3,
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClVolatilityMethod {
+    ClVolatilityCloseToClose = 0,
+    ClVolatilityParkinson = 1,
+    ClVolatilityGarmanKlass = 2,
+    ClVolatilityRogersSatchell = 3,
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClDownsideBetaMethod {
+    ClDownsideBetaBearBull = 0,
+    ClDownsideBetaBelowMean = 1,
+    ClDownsideBetaBelowRiskFree = 2,
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClDistributionKind {
+    ClDistributionNormal = 0,
+    ClDistributionStudentT = 1,
+    ClDistributionSkewT = 2,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClHistogramBins {
+    ClHistogramBinCount(usize),
+    ClHistogramBinWidth(f64),
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClRegressionMethod {
+    ClRegressionOls,
+    ClRegressionTheilSen,
+    ///the huber tuning constant in the robust standard deviations of the residuals, e.g. 1.345.
+    ClRegressionHuber(f64),
+    ///the percent of each tail clipped before the ordinary least squares, it is from 0 to 49.
+    ClRegressionWinsorized(i32),
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClFillPolicy {
+    ClFillForward = 0,
+    ClFillZero = 1,
+    ClFillLinear = 2,
+    ClFillDrop = 3,
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClComputeBackend {
+    ClComputeCpu = 0,
+    ClComputeGpu = 1,
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClStatisticUnit {
+    ClUnitPercent = 0,
+    ClUnitRatio = 1,
+    ClUnitDate = 2,
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClMethodologyVersion {
+    ClMethodologyV1 = 1,
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClPeerComposite {
+    ClPeerAverage = 0,
+    ClPeerMedian = 1,
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClTieBreak {
+    ClTieBreakShared = 0,
+    ClTieBreakStable = 1,
+    ClTieBreakSecondaryKey = 2,
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClNanRankPolicy {
+    ClNanExcludeAndRenumber = 0,
+    ClNanRankLast = 1,
+    ClNanError = 2,
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClNormalizeMethod {
+    ClNormalizeZScore = 0,
+    ClNormalizeMinMax = 1,
+    ClNormalizePercentile = 2,
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClScoreTransform {
+    ClScoreIdentity = 0,
+    ClScoreNegate = 1,
+    ClScoreAbsolute = 2,
+    ClScoreNegateAbsolute = 3,
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClLinkage {
+    ClLinkageSingle = 0,
+    ClLinkageComplete = 1,
+    ClLinkageAverage = 2,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClClusterCut {
+    ///the number of the clusters.
+    ClClusterCount(usize),
+    ///the maximum merge distance of a cluster, the distance is sqrt((1 - correlation) / 2), it is from 0 to 1.
+    ClClusterThreshold(f64),
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClCmlTarget {
+    ///the target standard deviation of the portfolio.
+    ClCmlTargetRisk(f64),
+    ///the target return of the portfolio.
+    ClCmlTargetReturn(f64),
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClActiveObjective {
+    ClActiveMinTrackingError = 0,
+    ClActiveMaxReturn = 1,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClUnsmoothMethod {
+    ClUnsmoothGeltner,
+    ///the number of the autocorrelation lags removed by the okunev white method, it is 1 at least.
+    ClUnsmoothOkunevWhite(usize),
+}
+
+#[derive(TryFromPrimitive)]
+#[repr(i16)]
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClChangepointMethod {
+    ClChangepointBinarySegmentation = 0,
+    ClChangepointPelt = 1,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ClProbabilityMethod {
+    ///the normal approximation of the compound return by the mean and the standard deviation of the log returns.
+    ClProbabilityNormal,
+    ///the circular block bootstrap of the block size and the number of the samples.
+    ClProbabilityBlockBootstrap(usize, usize),
+}
@@ -0,0 +1,737 @@
+use crate::{
+    common::DataGroup,
+    date_util,
+    enums::{self, Errors},
+    kernels, MPTCalculator,
+};
+
+///one episode of the return series, e.g. a streak, a run up or a draw down, the value is in percent.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Episode {
+    pub start_date: i32,
+    pub end_date: i32,
+    pub periods: i32,
+    pub value: f64,
+}
+
+///the counts, average lengths and all episodes of the up and down streaks.
+#[derive(Clone, Debug)]
+pub struct StreakDistribution {
+    pub up_count: i32,
+    pub up_average_periods: f64,
+    pub down_count: i32,
+    pub down_average_periods: f64,
+    pub up_streaks: Vec<Episode>,
+    pub down_streaks: Vec<Episode>,
+}
+
+impl StreakDistribution {
+    pub fn new() -> StreakDistribution {
+        StreakDistribution {
+            up_count: 0,
+            up_average_periods: f64::NAN,
+            down_count: 0,
+            down_average_periods: f64::NAN,
+            up_streaks: Vec::new(),
+            down_streaks: Vec::new(),
+        }
+    }
+}
+
+impl Default for StreakDistribution {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///convert the group of positions into an episode, the start date is the begin of the period of the first value.
+pub(crate) fn to_episode(dates: &[i32], freq: enums::ClFrequency, group: &DataGroup) -> Episode {
+    Episode {
+        start_date: date_util::to_period_begin_int(freq, dates[group.start] as u64) as i32,
+        end_date: date_util::to_period_end_int(freq, dates[group.end] as u64) as i32,
+        periods: (group.end - group.start) as i32 + 1,
+        value: group.data,
+    }
+}
+
+///split the accumulated series into the non-overlapping segments by the max segment search, the largest segment
+///is found first and then the parts before and after it are searched again, the positions are offset in the table.
+fn segment_table(values: &[f64], offset: usize, sign: f64, table: &mut Vec<DataGroup>) {
+    if values.len() < 2 {
+        return;
+    }
+    let mut dg = DataGroup::new();
+    MPTCalculator::get_max_segment(values, 0, values.len() - 1, sign, &mut dg);
+    if !(dg.start < dg.end && dg.data > 0.0) {
+        return;
+    }
+    table.push(DataGroup {
+        start: dg.start + offset,
+        end: dg.end + offset,
+        data: dg.data,
+    });
+    segment_table(&values[..dg.start + 1], offset, sign, table);
+    segment_table(&values[dg.end..], offset + dg.end, sign, table);
+}
+
+fn average_periods(streaks: &[Episode]) -> f64 {
+    if streaks.len() == 0 {
+        return f64::NAN;
+    }
+    streaks.iter().map(|x| x.periods as f64).sum::<f64>() / streaks.len() as f64
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///calculate the distribution of the up and down streaks of an array, an up streak is the consecutive positive
+    ///returns and a down streak is the consecutive negative returns, a zero return or a NAN/INF value ends the
+    ///streak. the input data should sort by date.
+    ///
+    ///# Arguments
+    ///dates: the date of value
+    ///
+    ///freq: the frequence of source data.
+    ///
+    ///distribution: the counts, average lengths and the episodes of the up and down streaks.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///use mpt_lib::episodes::StreakDistribution;
+    ///
+    ///let data = vec![1.0, 2.0, -1.0, f64::NAN, -2.0, 0.0, 3.0, -1.0, -1.0, -1.0];
+    ///let dates = vec![
+    ///    44957, 44985, 45016, 45046, 45077, 45107, 45138, 45169, 45199, 45230,
+    ///];
+    ///let mut distribution = StreakDistribution::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.streak_distribution(
+    ///    &dates,
+    ///    enums::ClFrequency::ClFrequencyMonthly,
+    ///    &mut distribution,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && distribution.up_count == 2
+    ///        && MPTCalculator::is_eq_double(distribution.up_average_periods, 1.5)
+    ///        && distribution.down_count == 3
+    ///        && MPTCalculator::is_eq_double(distribution.down_average_periods, 5.0 / 3.0),
+    ///    true
+    ///);
+    ///```
+    pub fn streak_distribution(
+        &self,
+        dates: &[i32],
+        freq: enums::ClFrequency,
+        distribution: &mut StreakDistribution,
+    ) -> Errors {
+        if self.values.len() == 0 || dates.len() != self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *distribution = StreakDistribution::new();
+        if dates.windows(2).any(|x| x[0] >= x[1]) {
+            return Errors::ClErrorCodeUnsortedByDate;
+        }
+
+        //the current streak, the data is the compound growth and the sign of the streak
+        let mut current: Option<(DataGroup, bool)> = None;
+        let close = |streak: Option<(DataGroup, bool)>, distribution: &mut StreakDistribution| {
+            if let Some((mut group, is_up)) = streak {
+                group.data = (group.data - 1.0) * 100.0;
+                let episode = to_episode(dates, freq, &group);
+                if is_up {
+                    distribution.up_streaks.push(episode);
+                } else {
+                    distribution.down_streaks.push(episode);
+                }
+            }
+        };
+        for i in 0..self.values.len() {
+            let v = self.values[i];
+            if !v.is_finite() || v == 0.0 {
+                close(current.take(), distribution);
+                continue;
+            }
+            let is_up = v > 0.0;
+            match current.as_mut() {
+                Some((group, up)) if *up == is_up => {
+                    group.data *= 1.0 + v / 100.0;
+                    group.end = i;
+                }
+                _ => {
+                    close(current.take(), distribution);
+                    current = Some((
+                        DataGroup {
+                            start: i,
+                            end: i,
+                            data: 1.0 + v / 100.0,
+                        },
+                        is_up,
+                    ));
+                }
+            }
+        }
+        close(current.take(), distribution);
+
+        distribution.up_count = distribution.up_streaks.len() as i32;
+        distribution.down_count = distribution.down_streaks.len() as i32;
+        distribution.up_average_periods = average_periods(&distribution.up_streaks);
+        distribution.down_average_periods = average_periods(&distribution.down_streaks);
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///list the episodes of the accumulated return, the sign 1.0 lists the draw downs and -1.0 lists the run ups.
+    fn episode_table(
+        &self,
+        dates: &[i32],
+        freq: enums::ClFrequency,
+        sign: f64,
+        table: &mut Vec<Episode>,
+    ) -> Errors {
+        if self.values.len() == 0 || dates.len() != self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        table.clear();
+        if self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let mut log_accum_series = vec![0.0; self.values.len() + 1];
+        for i in 0..self.values.len() {
+            log_accum_series[i + 1] = (1.0 + self.values[i] / 100.0).ln() + log_accum_series[i];
+        }
+        let mut groups = Vec::new();
+        segment_table(&log_accum_series, 0, sign, &mut groups);
+        groups.sort_by_key(|x| x.start);
+
+        for group in groups.iter() {
+            let position = DataGroup {
+                start: group.start,
+                end: group.end - 1,
+                data: ((-sign * group.data).exp() - 1.0) * 100.0,
+            };
+            table.push(to_episode(dates, freq, &position));
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///list all draw down episodes of an array sorted by date, the largest draw down is the max draw down and the others are
+    ///the largest draw downs of the remaining periods before and after it. if the array has NAN/INF values,the table will be empty.
+    ///
+    ///# Arguments
+    ///dates: the date of value
+    ///
+    ///freq: the frequence of source data.
+    ///
+    ///table: the draw down episodes, the value is the compound loss in percent.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![2.0, -1.0, 3.0, -4.0, 1.0, 2.0, -3.0, 5.0, 1.0, -2.0];
+    ///let dates = vec![
+    ///    44957, 44985, 45016, 45046, 45077, 45107, 45138, 45169, 45199, 45230,
+    ///];
+    ///let mut table = Vec::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.max_draw_down_table(&dates, enums::ClFrequency::ClFrequencyMonthly, &mut table);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && table.len() == 3
+    ///        && MPTCalculator::is_eq_double(table[1].value, -4.067776),
+    ///    true
+    ///);
+    ///```
+    pub fn max_draw_down_table(
+        &self,
+        dates: &[i32],
+        freq: enums::ClFrequency,
+        table: &mut Vec<Episode>,
+    ) -> Errors {
+        return self.episode_table(dates, freq, 1.0, table);
+    }
+
+    ///calculate the quantiles of the depth of the draw down episodes of max_draw_down_table, the quantile 0.5 is the
+    ///median draw down and the quantile 0.95 is the draw down deeper than 95% of the episodes, the quantiles are
+    ///linear interpolated. if there is no draw down episode the quantiles are NAN.
+    ///
+    ///# Arguments
+    ///dates: the date of value
+    ///
+    ///freq: the frequence of source data.
+    ///
+    ///qs: the quantiles between 0 and 1.
+    ///
+    ///quantiles: the draw down of each quantile, in percent.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![
+    ///    2.1, -3.4, 1.2, -0.8, -2.5, 3.1, 1.7, -1.1, 0.6, -4.2, 2.8, 1.5, -0.9, 2.2, -1.6, -2.9,
+    ///    0.4, 3.3, -1.2, 1.9, -2.6, 0.7, 1.4, -0.5,
+    ///];
+    ///let dates = vec![
+    ///    44773, 44804, 44834, 44865, 44895, 44926, 44957, 44985, 45016, 45046, 45077, 45107,
+    ///    45138, 45169, 45199, 45230, 45260, 45291, 45322, 45351, 45382, 45412, 45443, 45473,
+    ///];
+    ///let mut quantiles = Vec::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.draw_down_quantiles(
+    ///    &dates,
+    ///    enums::ClFrequency::ClFrequencyMonthly,
+    ///    &[0.5, 0.95],
+    ///    &mut quantiles,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(&quantiles, &[-1.9, -5.241416]),
+    ///    true
+    ///);
+    ///```
+    pub fn draw_down_quantiles(
+        &self,
+        dates: &[i32],
+        freq: enums::ClFrequency,
+        qs: &[f64],
+        quantiles: &mut Vec<f64>,
+    ) -> Errors {
+        if qs.len() == 0 || qs.iter().any(|q| !(*q >= 0.0 && *q <= 1.0)) {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        quantiles.clear();
+        quantiles.resize(qs.len(), f64::NAN);
+
+        let mut table = Vec::new();
+        let ret = self.max_draw_down_table(dates, freq, &mut table);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        if table.len() == 0 {
+            return Errors::ClErrorCodeNoError;
+        }
+        let mut depths: Vec<f64> = table.iter().map(|x| -x.value).collect();
+        depths.sort_by(|a, b| a.total_cmp(b));
+        for i in 0..qs.len() {
+            quantiles[i] = -kernels::sorted_quantile(&depths, qs[i]);
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///list all run up episodes of an array sorted by date, the largest run up is the max gain and the others are the
+    ///largest run ups of the remaining periods before and after it. if the array has NAN/INF values,the table will be empty.
+    ///
+    ///# Arguments
+    ///dates: the date of value
+    ///
+    ///freq: the frequence of source data.
+    ///
+    ///table: the run up episodes, the value is the compound gain in percent.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![2.0, -1.0, 3.0, -4.0, 1.0, 2.0, -3.0, 5.0, 1.0, -2.0];
+    ///let dates = vec![
+    ///    44957, 44985, 45016, 45046, 45077, 45107, 45138, 45169, 45199, 45230,
+    ///];
+    ///let mut table = Vec::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.max_run_up_table(&dates, enums::ClFrequency::ClFrequencyMonthly, &mut table);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && table.len() == 3
+    ///        && MPTCalculator::is_eq_double(table[0].value, 4.0094)
+    ///        && MPTCalculator::is_eq_double(table[2].value, 6.05),
+    ///    true
+    ///);
+    ///```
+    pub fn max_run_up_table(
+        &self,
+        dates: &[i32],
+        freq: enums::ClFrequency,
+        table: &mut Vec<Episode>,
+    ) -> Errors {
+        return self.episode_table(dates, freq, -1.0, table);
+    }
+
+    ///calculate the high watermark series of an array, the high watermark of a period is the highest cumulative
+    ///return from the begin to the period, it is 0 before the cumulative return is positive. it is the level the
+    ///performance fee is charged above. if the array has NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///series: the high watermark of each period, it is the cumulative return in percent.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let data = vec![-1.0, 10.0, -10.0, 5.0, 20.0];
+    ///let mut series = vec![f64::NAN; data.len()];
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.high_watermark_series(&mut series);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(&series, &[0.0, 8.9, 8.9, 8.9, 23.4926]),
+    ///    true
+    ///);
+    ///```
+    pub fn high_watermark_series(&self, series: &mut [f64]) -> Errors {
+        if self.values.len() == 0 || series.len() < self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        series.iter_mut().for_each(|x| *x = f64::NAN);
+        if self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let mut wealth = 1.0;
+        let mut high = 1.0;
+        for (i, value) in self.values.iter().enumerate() {
+            wealth *= 1.0 + value / 100.0;
+            high = f64::max(high, wealth);
+            series[i] = (high - 1.0) * 100.0;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the max time to a new high of an array, it is the longest time the cumulative return is below its
+    ///high watermark, the number of the periods from the high to the new high, or to the last period if the high is
+    ///not recovered. a new high equals the high at least. if the cumulative return is never below the high
+    ///watermark or the array has NAN/INF values, the periods and the dates are 0.
+    ///
+    ///# Arguments
+    ///dates: the date of value
+    ///
+    ///freq: the frequence of source data.
+    ///
+    ///periods: the max number of the periods to a new high.
+    ///
+    ///high_date: the end date of the period of the high, it is the begin of the first period below the high.
+    ///
+    ///new_high_date: the date of the new high, it is 0 if the high is not recovered.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![2.0, -1.0, 3.0, -4.0, 1.0, 2.0, -3.0, 5.0, 1.0, -2.0];
+    ///let dates = vec![
+    ///    44957, 44985, 45016, 45046, 45077, 45107, 45138, 45169, 45199, 45230,
+    ///];
+    ///let (mut periods, mut high_date, mut new_high_date) = (0, 0, 0);
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.max_time_to_new_high(
+    ///    &dates,
+    ///    enums::ClFrequency::ClFrequencyMonthly,
+    ///    &mut periods,
+    ///    &mut high_date,
+    ///    &mut new_high_date,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && periods == 5
+    ///        && high_date == 45017
+    ///        && new_high_date == 45169,
+    ///    true
+    ///);
+    ///```
+    pub fn max_time_to_new_high(
+        &self,
+        dates: &[i32],
+        freq: enums::ClFrequency,
+        periods: &mut i32,
+        high_date: &mut i32,
+        new_high_date: &mut i32,
+    ) -> Errors {
+        if self.values.len() == 0 || dates.len() != self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *periods = 0;
+        *high_date = 0;
+        *new_high_date = 0;
+        if self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        //the position 0 is the begin of the first period and the position i is the end of the period i - 1
+        let n = self.values.len();
+        let mut wealth = vec![1.0; n + 1];
+        for i in 0..n {
+            wealth[i + 1] = wealth[i] * (1.0 + self.values[i] / 100.0);
+        }
+        let mut high = 0;
+        let mut longest: Option<(usize, usize, bool)> = None;
+        for i in 1..=n {
+            let is_new_high = wealth[i] >= wealth[high];
+            let is_below = (is_new_high && i - high > 1) || (!is_new_high && i == n);
+            if is_below && longest.map_or(true, |x| i - high > x.1 - x.0) {
+                longest = Some((high, i, is_new_high));
+            }
+            if is_new_high {
+                high = i;
+            }
+        }
+
+        if let Some((start, end, is_recovered)) = longest {
+            *periods = (end - start) as i32;
+            *high_date = date_util::to_period_begin_int(freq, dates[start] as u64) as i32;
+            if is_recovered {
+                *new_high_date = dates[end - 1];
+            }
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::StreakDistribution;
+    use crate::{
+        enums::{self, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_streak_distribution() {
+        let data = vec![1.0, 2.0, -1.0, f64::NAN, -2.0, 0.0, 3.0, -1.0, -1.0, -1.0];
+        let dates = vec![
+            44957, 44985, 45016, 45046, 45077, 45107, 45138, 45169, 45199, 45230,
+        ];
+        let mut distribution = StreakDistribution::new();
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.streak_distribution(
+            &dates,
+            enums::ClFrequency::ClFrequencyMonthly,
+            &mut distribution,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && distribution.up_count == 2
+                && distribution.down_count == 3
+                && distribution.up_streaks[0].start_date == 44927
+                && distribution.up_streaks[0].end_date == 44985
+                && distribution.up_streaks[0].periods == 2
+                && MPTCalculator::is_eq_double(distribution.up_streaks[0].value, 3.02)
+                && distribution.down_streaks[0].periods == 1
+                && MPTCalculator::is_eq_double(distribution.down_streaks[0].value, -1.0)
+                && distribution.down_streaks[1].start_date == 45047
+                && distribution.down_streaks[1].periods == 1
+                && MPTCalculator::is_eq_double(distribution.down_streaks[1].value, -2.0)
+                && MPTCalculator::is_eq_double(distribution.down_streaks[2].value, -2.9701)
+                && MPTCalculator::is_eq_double(distribution.up_average_periods, 1.5)
+                && MPTCalculator::is_eq_double(distribution.down_average_periods, 5.0 / 3.0),
+            true
+        );
+
+        let mut dates = dates.clone();
+        dates.swap(3, 4);
+        let err = mpt.streak_distribution(
+            &dates,
+            enums::ClFrequency::ClFrequencyMonthly,
+            &mut distribution,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeUnsortedByDate && distribution.up_count == 0,
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_max_run_up_table() {
+        let data = vec![2.0, -1.0, 3.0, -4.0, 1.0, 2.0, -3.0, 5.0, 1.0, -2.0];
+        let dates = vec![
+            44957, 44985, 45016, 45046, 45077, 45107, 45138, 45169, 45199, 45230,
+        ];
+        let mut table = Vec::new();
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.max_run_up_table(&dates, enums::ClFrequency::ClFrequencyMonthly, &mut table);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && table.len() == 3
+                && table[0].start_date == 44927
+                && table[0].end_date == 45016
+                && table[0].periods == 3
+                && MPTCalculator::is_eq_double(table[0].value, 4.0094)
+                && table[1].start_date == 45047
+                && table[1].end_date == 45107
+                && MPTCalculator::is_eq_double(table[1].value, 3.02)
+                && table[2].periods == 2
+                && MPTCalculator::is_eq_double(table[2].value, 6.05),
+            true
+        );
+
+        let data = vec![2.0, f64::NAN, 3.0];
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.max_run_up_table(
+            &dates[0..3],
+            enums::ClFrequency::ClFrequencyMonthly,
+            &mut table,
+        );
+        assert_eq!(err == Errors::ClErrorCodeNoError && table.len() == 0, true);
+    }
+
+    #[test]
+    fn should_correct_max_draw_down_table() {
+        let data = vec![
+            2.1, -3.4, 1.2, -0.8, -2.5, 3.1, 1.7, -1.1, 0.6, -4.2, 2.8, 1.5, -0.9, 2.2, -1.6, -2.9,
+            0.4, 3.3, -1.2, 1.9, -2.6, 0.7, 1.4, -0.5,
+        ];
+        let dates = vec![
+            44773, 44804, 44834, 44865, 44895, 44926, 44957, 44985, 45016, 45046, 45077, 45107,
+            45138, 45169, 45199, 45230, 45260, 45291, 45322, 45351, 45382, 45412, 45443, 45473,
+        ];
+        let mut table = Vec::new();
+        let mpt = MPTCalculator::from_v(&data);
+        let err =
+            mpt.max_draw_down_table(&dates, enums::ClFrequency::ClFrequencyMonthly, &mut table);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && table.len() == 6
+                && table[0].start_date == 44774
+                && table[0].end_date == 45046
+                && table[0].periods == 9
+                && MPTCalculator::is_eq_double(table[0].value, -5.504022)
+                && table[2].start_date == 45170
+                && table[2].periods == 2
+                && MPTCalculator::is_eq_double(table[2].value, -4.4536)
+                && MPTCalculator::is_eq_double(table[5].value, -0.5),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_draw_down_quantiles() {
+        let data = vec![
+            2.1, -3.4, 1.2, -0.8, -2.5, 3.1, 1.7, -1.1, 0.6, -4.2, 2.8, 1.5, -0.9, 2.2, -1.6, -2.9,
+            0.4, 3.3, -1.2, 1.9, -2.6, 0.7, 1.4, -0.5,
+        ];
+        let dates = vec![
+            44773, 44804, 44834, 44865, 44895, 44926, 44957, 44985, 45016, 45046, 45077, 45107,
+            45138, 45169, 45199, 45230, 45260, 45291, 45322, 45351, 45382, 45412, 45443, 45473,
+        ];
+        let mut quantiles = Vec::new();
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.draw_down_quantiles(
+            &dates,
+            enums::ClFrequency::ClFrequencyMonthly,
+            &[0.0, 0.5, 0.95, 1.0],
+            &mut quantiles,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(
+                    &quantiles,
+                    &[-0.5, -1.9, -5.241416, -5.504022]
+                ),
+            true
+        );
+
+        let data = vec![1.0, 2.0, 3.0];
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.draw_down_quantiles(
+            &dates[0..3],
+            enums::ClFrequency::ClFrequencyMonthly,
+            &[0.5],
+            &mut quantiles,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && quantiles.len() == 1 && quantiles[0].is_nan(),
+            true
+        );
+
+        let err = mpt.draw_down_quantiles(
+            &dates[0..3],
+            enums::ClFrequency::ClFrequencyMonthly,
+            &[1.5],
+            &mut quantiles,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+
+    #[test]
+    fn should_correct_high_watermark() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let dates = vec![
+            38776, 38807, 38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082, 39113,
+            39141, 39172, 39202, 39233, 39263, 39294, 39325, 39355, 39386, 39416, 39447, 39478,
+            39507, 39538, 39568, 39599, 39629, 39660, 39691, 39721, 39752, 39782, 39813, 39844,
+        ];
+        let mpt = MPTCalculator::from_v(&data);
+        let mut series = vec![f64::NAN; data.len()];
+        let err = mpt.high_watermark_series(&mut series);
+        let mut cumulative = Vec::new();
+        let mut wealth = 1.0;
+        for value in data.iter() {
+            wealth *= 1.0 + value / 100.0;
+            cumulative.push((wealth - 1.0) * 100.0);
+        }
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && series[0] == 0.0
+                && series.windows(2).all(|x| x[0] <= x[1])
+                && series.iter().zip(cumulative.iter()).all(|x| x.0 >= x.1)
+                && MPTCalculator::is_eq_double(
+                    series[35],
+                    cumulative.iter().fold(0.0, |acc, x| f64::max(acc, *x))
+                ),
+            true
+        );
+
+        let (mut periods, mut high_date, mut new_high_date) = (0, 0, 0);
+        let err = mpt.max_time_to_new_high(
+            &dates,
+            enums::ClFrequency::ClFrequencyMonthly,
+            &mut periods,
+            &mut high_date,
+            &mut new_high_date,
+        );
+        let (mut max_draw_down, mut peek_date, mut valley_date) = (f64::NAN, 0, 0);
+        let (mut draw_down_month, mut recovery_month, mut recovery_date) = (0, 0, 0);
+        mpt.max_draw_down(
+            &dates,
+            enums::ClFrequency::ClFrequencyMonthly,
+            &mut max_draw_down,
+            &mut peek_date,
+            &mut valley_date,
+            &mut draw_down_month,
+            &mut recovery_month,
+            &mut recovery_date,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && periods == draw_down_month
+                && high_date == peek_date
+                && new_high_date == 0,
+            true
+        );
+
+        let rising = vec![1.0, 2.0, 0.5];
+        let err = MPTCalculator::from_v(&rising).max_time_to_new_high(
+            &dates[..3],
+            enums::ClFrequency::ClFrequencyMonthly,
+            &mut periods,
+            &mut high_date,
+            &mut new_high_date,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && periods == 0 && high_date == 0,
+            true
+        );
+
+        let mut missing = data.clone();
+        missing[3] = f64::NAN;
+        let err = MPTCalculator::from_v(&missing).high_watermark_series(&mut series);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && series.iter().all(|x| x.is_nan()),
+            true
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use crate::{
+    cancel::CancellationToken,
+    common::{get_annual_multiplier, is_valid_frequency, normal_cdf},
+    enums::{self, Errors},
+    MPTCalculator,
+};
+
+///the seed of the bootstrap, so the probabilities are the same between runs.
+const BOOTSTRAP_SEED: u64 = 0x9E37_79B9_7F4A_7C15;
+
+///the splitmix64 generator of the bootstrap.
+pub(crate) struct SplitMix64 {
+    pub(crate) state: u64,
+}
+
+impl SplitMix64 {
+    pub(crate) fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    ///a uniform index from 0 to n - 1.
+    pub(crate) fn index(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///calculate the probability of achieving the target annual return over the horizon for the goals based
+    ///reporting. the normal approximation assumes the log returns are independent and normal with the mean and the
+    ///standard deviation of the history, and the block bootstrap compounds the blocks of the consecutive history
+    ///returns, which keep the autocorrelation within a block, from the random starts, the history wraps around at
+    ///the end. the bootstrap is seeded so the result is the same between runs. if the array has NAN/INF values,the
+    ///result will be NAN.
+    ///
+    ///# Arguments
+    ///freq: the frequence of source data.
+    ///
+    ///target_annual_return: the target annualized return in percent.
+    ///
+    ///horizon: the horizon in years, the number of the periods is rounded and is 1 at least.
+    ///
+    ///method: the normal approximation or the block bootstrap.
+    ///
+    ///probability: the probability that the annualized return over the horizon is not less than the target.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, ClProbabilityMethod, Errors};
+    ///
+    ///let data = vec![1.0, -0.5, 2.0, 0.5, 1.5, -1.0, 3.0, 0.0, -2.0, 1.2, 0.8, 0.4];
+    ///let mut probability = f64::NAN;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.probability_of_achieving(
+    ///    enums::ClFrequency::ClFrequencyMonthly,
+    ///    5.0,
+    ///    3.0,
+    ///    ClProbabilityMethod::ClProbabilityNormal,
+    ///    &mut probability,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(probability, 0.760562),
+    ///    true
+    ///);
+    ///```
+    pub fn probability_of_achieving(
+        &self,
+        freq: enums::ClFrequency,
+        target_annual_return: f64,
+        horizon: f64,
+        method: enums::ClProbabilityMethod,
+        probability: &mut f64,
+    ) -> Errors {
+        return self.probability_of_achieving_with_cancel(
+            freq,
+            target_annual_return,
+            horizon,
+            method,
+            &CancellationToken::new(),
+            probability,
+        );
+    }
+
+    ///calculate the probability of achieving the target as probability_of_achieving, the block bootstrap checks
+    ///the token for each sample and returns ClErrorCodeCancelled after it is cancelled.
+    pub fn probability_of_achieving_with_cancel(
+        &self,
+        freq: enums::ClFrequency,
+        target_annual_return: f64,
+        horizon: f64,
+        method: enums::ClProbabilityMethod,
+        cancel: &CancellationToken,
+        probability: &mut f64,
+    ) -> Errors {
+        *probability = f64::NAN;
+        let n = self.values.len();
+        if n < 2
+            || !is_valid_frequency(freq)
+            || !(target_annual_return > -100.0 && target_annual_return.is_finite())
+            || !(horizon > 0.0 && horizon.is_finite())
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        if let enums::ClProbabilityMethod::ClProbabilityBlockBootstrap(block, samples) = method {
+            if block == 0 || block > n || samples == 0 {
+                return Errors::ClErrorCodeInvalidPara;
+            }
+        }
+        if self.values.iter().any(|x| !x.is_finite() || *x <= -100.0) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let periods = (horizon * get_annual_multiplier(freq, false))
+            .round()
+            .max(1.0) as usize;
+        let target = horizon * (1.0 + target_annual_return / 100.0).ln();
+        let logs: Vec<f64> = self.values.iter().map(|x| (1.0 + x / 100.0).ln()).collect();
+        *probability = match method {
+            enums::ClProbabilityMethod::ClProbabilityNormal => {
+                let mean = logs.iter().sum::<f64>() / n as f64;
+                let variance =
+                    logs.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1) as f64;
+                let expected = mean * periods as f64;
+                if variance > 0.0 {
+                    1.0 - normal_cdf((target - expected) / (variance * periods as f64).sqrt())
+                } else if expected >= target {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            enums::ClProbabilityMethod::ClProbabilityBlockBootstrap(block, samples) => {
+                let mut generator = SplitMix64 {
+                    state: BOOTSTRAP_SEED,
+                };
+                let mut achieved = 0;
+                for _ in 0..samples {
+                    if cancel.is_cancelled() {
+                        return Errors::ClErrorCodeCancelled;
+                    }
+                    let mut total = 0.0;
+                    let mut count = 0;
+                    while count < periods {
+                        let start = generator.index(n);
+                        for j in 0..block.min(periods - count) {
+                            total += logs[(start + j) % n];
+                        }
+                        count += block;
+                    }
+                    if total >= target {
+                        achieved += 1;
+                    }
+                }
+                achieved as f64 / samples as f64
+            }
+        };
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        cancel::CancellationToken,
+        common::normal_cdf,
+        enums::{ClFrequency, ClProbabilityMethod, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_probability_of_achieving() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -0.68658, -0.46681, 4.22095, -0.7, 1.27331, 0.46123, 0.76369, 1.32347,
+        ];
+        let mpt = MPTCalculator::from_v(&data);
+        let logs: Vec<f64> = data.iter().map(|x| (1.0 + x / 100.0).ln()).collect();
+        let mean = logs.iter().sum::<f64>() / 36.0;
+        let deviation = (logs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 35.0).sqrt();
+
+        let mut normal = f64::NAN;
+        let err = mpt.probability_of_achieving(
+            ClFrequency::ClFrequencyMonthly,
+            8.0,
+            2.0,
+            ClProbabilityMethod::ClProbabilityNormal,
+            &mut normal,
+        );
+        let z = (2.0 * 1.08_f64.ln() - 24.0 * mean) / (deviation * 24.0_f64.sqrt());
+        let expected = normal_cdf(z);
+        let mut bootstrap = f64::NAN;
+        let err_bootstrap = mpt.probability_of_achieving(
+            ClFrequency::ClFrequencyMonthly,
+            8.0,
+            2.0,
+            ClProbabilityMethod::ClProbabilityBlockBootstrap(1, 20000),
+            &mut bootstrap,
+        );
+        let mut repeated = f64::NAN;
+        mpt.probability_of_achieving(
+            ClFrequency::ClFrequencyMonthly,
+            8.0,
+            2.0,
+            ClProbabilityMethod::ClProbabilityBlockBootstrap(1, 20000),
+            &mut repeated,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && err_bootstrap == Errors::ClErrorCodeNoError
+                && (normal - (1.0 - expected)).abs() < 1e-6
+                && (bootstrap - normal).abs() < 0.03
+                && bootstrap == repeated,
+            true
+        );
+
+        let mut blocks = f64::NAN;
+        mpt.probability_of_achieving(
+            ClFrequency::ClFrequencyMonthly,
+            -50.0,
+            1.0,
+            ClProbabilityMethod::ClProbabilityBlockBootstrap(6, 1000),
+            &mut blocks,
+        );
+        assert_eq!(blocks, 1.0);
+
+        let err = mpt.probability_of_achieving(
+            ClFrequency::ClFrequencyMonthly,
+            8.0,
+            2.0,
+            ClProbabilityMethod::ClProbabilityBlockBootstrap(37, 1000),
+            &mut blocks,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && blocks.is_nan(),
+            true
+        );
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let err = mpt.probability_of_achieving_with_cancel(
+            ClFrequency::ClFrequencyMonthly,
+            8.0,
+            2.0,
+            ClProbabilityMethod::ClProbabilityBlockBootstrap(1, 20000),
+            &cancel,
+            &mut blocks,
+        );
+        assert_eq!(err == Errors::ClErrorCodeCancelled && blocks.is_nan(), true);
+    }
+}
@@ -0,0 +1,274 @@
+//! the experimental gpu kernels of the batch statistics by wgpu. the shaders use f32, so the results have about
+//! 6 significant digits. every function returns None if there is no gpu or the input is too large for the gpu, then
+//! the caller falls back to the cpu.
+use std::sync::{mpsc, OnceLock};
+
+use wgpu::util::DeviceExt;
+
+const WORKGROUP_SIZE: u32 = 64;
+
+const COVARIANCE_SHADER: &str = r#"
+struct Params {
+    rows: u32,
+    cols: u32,
+    window: u32,
+    pad: u32,
+}
+
+@group(0) @binding(0) var<storage, read> data: array<f32>;
+@group(0) @binding(1) var<uniform> params: Params;
+@group(0) @binding(2) var<storage, read_write> output: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    let j = id.y;
+    if (i >= params.cols || j > i) {
+        return;
+    }
+    var sum = 0.0;
+    for (var k = 0u; k < params.rows; k = k + 1u) {
+        sum = sum + data[i * params.rows + k] * data[j * params.rows + k];
+    }
+    output[i * params.cols + j] = sum / f32(params.rows - 1u);
+}
+"#;
+
+const ROLLING_SHADER: &str = r#"
+struct Params {
+    rows: u32,
+    cols: u32,
+    window: u32,
+    pad: u32,
+}
+
+@group(0) @binding(0) var<storage, read> data: array<f32>;
+@group(0) @binding(1) var<uniform> params: Params;
+@group(0) @binding(2) var<storage, read_write> output: array<f32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let t = id.x;
+    let c = id.y;
+    if (t >= params.rows || t + 1u < params.window) {
+        return;
+    }
+    let begin = c * params.rows + t + 1u - params.window;
+    var mean = 0.0;
+    for (var k = 0u; k < params.window; k = k + 1u) {
+        mean = mean + data[begin + k];
+    }
+    mean = mean / f32(params.window);
+    var sum = 0.0;
+    for (var k = 0u; k < params.window; k = k + 1u) {
+        let d = data[begin + k] - mean;
+        sum = sum + d * d;
+    }
+    output[c * params.rows + t] = sqrt(sum / f32(params.window - 1u));
+}
+"#;
+
+struct Gpu {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    limits: wgpu::Limits,
+}
+
+///the device is created once and shared by all the calls, it is None if there is no gpu.
+fn gpu() -> Option<&'static Gpu> {
+    static GPU: OnceLock<Option<Gpu>> = OnceLock::new();
+    return GPU
+        .get_or_init(|| {
+            let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+            let adapter =
+                pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+                    power_preference: wgpu::PowerPreference::HighPerformance,
+                    force_fallback_adapter: false,
+                    compatible_surface: None,
+                }))?;
+            let limits = adapter.limits();
+            let (device, queue) = pollster::block_on(adapter.request_device(
+                &wgpu::DeviceDescriptor {
+                    label: None,
+                    required_features: wgpu::Features::empty(),
+                    required_limits: limits.clone(),
+                    memory_hints: wgpu::MemoryHints::Performance,
+                },
+                None,
+            ))
+            .ok()?;
+            Some(Gpu {
+                device,
+                queue,
+                limits,
+            })
+        })
+        .as_ref();
+}
+
+///run the shader on the column-major data, the invocation (x, y) covers dispatch_x * dispatch_y items.
+fn run(
+    shader: &str,
+    data: &[f32],
+    params: [u32; 4],
+    dispatch: (u32, u32),
+    output_len: usize,
+) -> Option<Vec<f32>> {
+    let gpu = gpu()?;
+    let input_size = (data.len() * 4) as u64;
+    let output_size = (output_len * 4) as u64;
+    let max_size = gpu.limits.max_storage_buffer_binding_size as u64;
+    let max_dispatch = gpu.limits.max_compute_workgroups_per_dimension;
+    if input_size > max_size
+        || output_size > max_size
+        || output_size > gpu.limits.max_buffer_size
+        || dispatch.0 > max_dispatch
+        || dispatch.1 > max_dispatch
+    {
+        return None;
+    }
+
+    let device = &gpu.device;
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(shader.into()),
+    });
+    let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: None,
+        layout: None,
+        module: &module,
+        entry_point: Some("main"),
+        compilation_options: Default::default(),
+        cache: None,
+    });
+    let bytes: Vec<u8> = data.iter().flat_map(|x| x.to_ne_bytes()).collect();
+    let input = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: None,
+        contents: &bytes,
+        usage: wgpu::BufferUsages::STORAGE,
+    });
+    let params: Vec<u8> = params.iter().flat_map(|x| x.to_ne_bytes()).collect();
+    let uniform = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: None,
+        contents: &params,
+        usage: wgpu::BufferUsages::UNIFORM,
+    });
+    let output = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: output_size,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        mapped_at_creation: false,
+    });
+    let staging = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: output_size,
+        usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout: &pipeline.get_bind_group_layout(0),
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: input.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: uniform.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: output.as_entire_binding(),
+            },
+        ],
+    });
+
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    {
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: None,
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(dispatch.0, dispatch.1, 1);
+    }
+    encoder.copy_buffer_to_buffer(&output, 0, &staging, 0, output_size);
+    gpu.queue.submit(Some(encoder.finish()));
+
+    let slice = staging.slice(..);
+    let (sender, receiver) = mpsc::channel();
+    slice.map_async(wgpu::MapMode::Read, move |x| {
+        let _ = sender.send(x);
+    });
+    let _ = device.poll(wgpu::Maintain::Wait);
+    receiver.recv().ok()?.ok()?;
+    let result = slice
+        .get_mapped_range()
+        .chunks_exact(4)
+        .map(|x| f32::from_ne_bytes([x[0], x[1], x[2], x[3]]))
+        .collect();
+    staging.unmap();
+    return Some(result);
+}
+
+///the columns minus their means in f32, the means are removed in f64 to keep the precision of the f32 sums.
+fn centered(columns: &[&[f64]]) -> Vec<f32> {
+    return columns
+        .iter()
+        .flat_map(|column| {
+            let mean = column.iter().sum::<f64>() / column.len() as f64;
+            column.iter().map(move |x| (x - mean) as f32)
+        })
+        .collect();
+}
+
+///the sample covariance matrix of the columns, the columns should have the same length and no NAN/INF values.
+pub(crate) fn covariance_matrix(columns: &[&[f64]]) -> Option<Vec<Vec<f64>>> {
+    let (rows, cols) = (columns[0].len(), columns.len());
+    let output = run(
+        COVARIANCE_SHADER,
+        &centered(columns),
+        [rows as u32, cols as u32, 0, 0],
+        ((cols as u32).div_ceil(WORKGROUP_SIZE), cols as u32),
+        cols * cols,
+    )?;
+    let mut cov = vec![vec![0.0; cols]; cols];
+    for i in 0..cols {
+        for j in 0..=i {
+            cov[i][j] = output[i * cols + j] as f64;
+            cov[j][i] = cov[i][j];
+        }
+    }
+    return Some(cov);
+}
+
+///the sample standard deviation of each rolling window of the columns, the columns should have the same length and
+///no NAN/INF values, the values before the first full window are NAN.
+pub(crate) fn rolling_standard_deviation(
+    columns: &[&[f64]],
+    window: usize,
+) -> Option<Vec<Vec<f64>>> {
+    let (rows, cols) = (columns[0].len(), columns.len());
+    let output = run(
+        ROLLING_SHADER,
+        &centered(columns),
+        [rows as u32, cols as u32, window as u32, 0],
+        ((rows as u32).div_ceil(WORKGROUP_SIZE), cols as u32),
+        rows * cols,
+    )?;
+    return Some(
+        output
+            .chunks_exact(rows)
+            .map(|column| {
+                column
+                    .iter()
+                    .enumerate()
+                    .map(|(t, x)| if t + 1 < window { f64::NAN } else { *x as f64 })
+                    .collect()
+            })
+            .collect(),
+    );
+}
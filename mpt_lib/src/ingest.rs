@@ -0,0 +1,798 @@
+use std::io::{BufRead, Lines};
+#[cfg(feature = "parquet")]
+use std::sync::Arc;
+#[cfg(any(feature = "parquet", feature = "mmap"))]
+use std::{fs::File, path::Path};
+
+use chrono::NaiveDate;
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
+#[cfg(feature = "parquet")]
+use parquet::{
+    basic::{Repetition, Type as PhysicalType},
+    data_type::{DoubleType, Int32Type},
+    file::{
+        properties::WriterProperties,
+        reader::{FileReader, SerializedFileReader},
+        writer::SerializedFileWriter,
+    },
+    record::Field,
+    schema::types::Type,
+};
+
+use crate::{
+    common::{get_annual_multiplier, is_valid_frequency},
+    date_util,
+    enums::{self, Errors},
+};
+
+///the rows of one chunk of a dated return file, columns has one vector of values for each asset.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReturnChunk {
+    pub dates: Vec<i32>,
+    pub columns: Vec<Vec<f64>>,
+}
+
+///read a dated return csv file in chunks of rows so the memory is bounded by the chunk size instead of the file size.
+///the first line is the header with the date column and the asset names, each following line is the date and the
+///returns of the assets. the date can be an integer date or yyyy-mm-dd, the empty or unparsable returns are NAN.
+///the iterator stops after the first error.
+///# Examples
+///```
+///use mpt_lib::ingest::CsvChunkReader;
+///
+///let csv = "date,fund,index\n2024-01-31,1.5,1.2\n2024-02-29,,0.8\n45382,-0.5,-0.3\n";
+///let mut reader = CsvChunkReader::new(csv.as_bytes(), 2).unwrap();
+///let first = reader.next().unwrap().unwrap();
+///let second = reader.next().unwrap().unwrap();
+///assert_eq!(
+///    reader.names() == ["fund", "index"]
+///        && first.dates == vec![45322, 45351]
+///        && first.columns[0][1].is_nan()
+///        && second.columns[1] == vec![-0.3]
+///        && reader.next().is_none(),
+///    true
+///);
+///```
+pub struct CsvChunkReader<R: BufRead> {
+    lines: Lines<R>,
+    names: Vec<String>,
+    chunk_size: usize,
+    done: bool,
+}
+
+impl<R: BufRead> CsvChunkReader<R> {
+    ///create the reader and read the header line, chunk_size is the max number of rows of one chunk.
+    pub fn new(reader: R, chunk_size: usize) -> Result<CsvChunkReader<R>, Errors> {
+        if chunk_size == 0 {
+            return Err(Errors::ClErrorCodeInvalidPara);
+        }
+        let mut lines = reader.lines();
+        let header = match lines.next() {
+            Some(Ok(header)) => header,
+            _ => return Err(Errors::ClErrorCodeInvalidValue),
+        };
+        let names: Vec<String> = header
+            .split(',')
+            .skip(1)
+            .map(|x| x.trim().to_string())
+            .collect();
+        if names.len() == 0 {
+            return Err(Errors::ClErrorCodeInvalidValue);
+        }
+        return Ok(CsvChunkReader {
+            lines,
+            names,
+            chunk_size,
+            done: false,
+        });
+    }
+
+    ///the asset names of the header.
+    pub fn names(&self) -> &[String] {
+        return &self.names;
+    }
+
+    fn read_row(&self, line: &str, chunk: &mut ReturnChunk) -> Errors {
+        let mut fields = line.split(',');
+        let date = match parse_date(fields.next().unwrap_or("")) {
+            Some(date) => date,
+            None => return Errors::ClErrorCodeInvalidDate,
+        };
+        let values: Vec<f64> = fields
+            .map(|x| x.trim().parse::<f64>().unwrap_or(f64::NAN))
+            .collect();
+        if values.len() != self.names.len() {
+            return Errors::ClErrorCodeInvalidValue;
+        }
+        chunk.dates.push(date);
+        chunk
+            .columns
+            .iter_mut()
+            .zip(values)
+            .for_each(|(column, v)| column.push(v));
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+impl<R: BufRead> Iterator for CsvChunkReader<R> {
+    type Item = Result<ReturnChunk, Errors>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut chunk = ReturnChunk {
+            dates: Vec::with_capacity(self.chunk_size),
+            columns: vec![Vec::with_capacity(self.chunk_size); self.names.len()],
+        };
+        while chunk.dates.len() < self.chunk_size {
+            let line = match self.lines.next() {
+                Some(Ok(line)) => line,
+                Some(Err(_)) => {
+                    self.done = true;
+                    return Some(Err(Errors::ClErrorCodeInvalidValue));
+                }
+                None => {
+                    self.done = true;
+                    break;
+                }
+            };
+            if line.trim().len() == 0 {
+                continue;
+            }
+            let err = self.read_row(&line, &mut chunk);
+            if err != Errors::ClErrorCodeNoError {
+                self.done = true;
+                return Some(Err(err));
+            }
+        }
+        if chunk.dates.len() == 0 {
+            return None;
+        }
+        return Some(Ok(chunk));
+    }
+}
+
+fn parse_date(text: &str) -> Option<i32> {
+    let text = text.trim();
+    if let Ok(date) = text.parse::<i32>() {
+        return Some(date);
+    }
+    return NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .ok()
+        .map(|x| date_util::to_int(&x) as i32);
+}
+
+///the days between 1970-01-01 and the integer date 0.
+#[cfg(feature = "parquet")]
+const UNIX_EPOCH_DATE: i32 = 25569;
+
+///read a dated return parquet file by row groups, each row group is one chunk, so the memory is bounded by the size
+///of the row group. the first column is the date, it can be an integer date or a parquet DATE, the other columns are
+///the returns of the assets, the null values are NAN. the iterator stops after the first error.
+#[cfg(feature = "parquet")]
+pub struct ParquetChunkReader {
+    reader: SerializedFileReader<File>,
+    names: Vec<String>,
+    row_group: usize,
+    done: bool,
+}
+
+#[cfg(feature = "parquet")]
+impl ParquetChunkReader {
+    ///open the parquet file and read the asset names from its schema.
+    pub fn new(path: &Path) -> Result<ParquetChunkReader, Errors> {
+        let reader = File::open(path)
+            .ok()
+            .and_then(|file| SerializedFileReader::new(file).ok())
+            .ok_or(Errors::ClErrorCodeInvalidValue)?;
+        let names: Vec<String> = reader
+            .metadata()
+            .file_metadata()
+            .schema_descr()
+            .columns()
+            .iter()
+            .skip(1)
+            .map(|x| x.name().to_string())
+            .collect();
+        if names.len() == 0 {
+            return Err(Errors::ClErrorCodeInvalidValue);
+        }
+        return Ok(ParquetChunkReader {
+            reader,
+            names,
+            row_group: 0,
+            done: false,
+        });
+    }
+
+    ///the asset names of the schema.
+    pub fn names(&self) -> &[String] {
+        return &self.names;
+    }
+
+    fn read_row_group(&self, chunk: &mut ReturnChunk) -> Errors {
+        let row_group = match self.reader.get_row_group(self.row_group) {
+            Ok(row_group) => row_group,
+            Err(_) => return Errors::ClErrorCodeInvalidValue,
+        };
+        let rows = match row_group.get_row_iter(None) {
+            Ok(rows) => rows,
+            Err(_) => return Errors::ClErrorCodeInvalidValue,
+        };
+        for row in rows {
+            let row = match row {
+                Ok(row) => row,
+                Err(_) => return Errors::ClErrorCodeInvalidValue,
+            };
+            let mut fields = row.get_column_iter().map(|x| x.1);
+            let date = match fields.next() {
+                Some(Field::Int(date)) => *date,
+                Some(Field::Date(date)) => *date + UNIX_EPOCH_DATE,
+                _ => return Errors::ClErrorCodeInvalidDate,
+            };
+            chunk.dates.push(date);
+            chunk
+                .columns
+                .iter_mut()
+                .zip(fields)
+                .for_each(|(column, field)| {
+                    column.push(match field {
+                        Field::Double(v) => *v,
+                        Field::Float(v) => *v as f64,
+                        Field::Int(v) => *v as f64,
+                        Field::Long(v) => *v as f64,
+                        _ => f64::NAN,
+                    })
+                });
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl Iterator for ParquetChunkReader {
+    type Item = Result<ReturnChunk, Errors>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.row_group >= self.reader.num_row_groups() {
+            return None;
+        }
+        let mut chunk = ReturnChunk {
+            dates: Vec::new(),
+            columns: vec![Vec::new(); self.names.len()],
+        };
+        let err = self.read_row_group(&mut chunk);
+        self.row_group += 1;
+        if err != Errors::ClErrorCodeNoError {
+            self.done = true;
+            return Some(Err(err));
+        }
+        return Some(Ok(chunk));
+    }
+}
+
+///write the dated values to a parquet file, the first column is the integer date named date and the other columns are
+///the values named by names, the NAN/INF values are written as null.
+///
+///# Arguments
+///path: the parquet file.
+///
+///dates: the date of each row.
+///
+///names: the name of each column.
+///
+///columns: the values of each column, the same size as the dates.
+#[cfg(feature = "parquet")]
+pub fn write_parquet(path: &Path, dates: &[i32], names: &[&str], columns: &[&[f64]]) -> Errors {
+    if names.len() == 0
+        || names.len() != columns.len()
+        || columns.iter().any(|x| x.len() != dates.len())
+    {
+        return Errors::ClErrorCodeInvalidPara;
+    }
+    let write = || -> parquet::errors::Result<()> {
+        let mut fields = vec![Arc::new(
+            Type::primitive_type_builder("date", PhysicalType::INT32)
+                .with_repetition(Repetition::REQUIRED)
+                .build()?,
+        )];
+        for name in names {
+            fields.push(Arc::new(
+                Type::primitive_type_builder(name, PhysicalType::DOUBLE)
+                    .with_repetition(Repetition::OPTIONAL)
+                    .build()?,
+            ));
+        }
+        let schema = Type::group_type_builder("schema")
+            .with_fields(fields)
+            .build()?;
+        let mut writer = SerializedFileWriter::new(
+            File::create(path)?,
+            Arc::new(schema),
+            Arc::new(WriterProperties::builder().build()),
+        )?;
+        let mut row_group = writer.next_row_group()?;
+        let mut index = 0;
+        while let Some(mut column) = row_group.next_column()? {
+            if index == 0 {
+                column.typed::<Int32Type>().write_batch(dates, None, None)?;
+            } else {
+                let values = columns[index - 1];
+                let valid: Vec<f64> = values.iter().filter(|x| x.is_finite()).cloned().collect();
+                let levels: Vec<i16> = values.iter().map(|x| x.is_finite() as i16).collect();
+                column
+                    .typed::<DoubleType>()
+                    .write_batch(&valid, Some(&levels), None)?;
+            }
+            column.close()?;
+            index += 1;
+        }
+        row_group.close()?;
+        writer.close()?;
+        return Ok(());
+    };
+    if write().is_err() {
+        return Errors::ClErrorCodeInvalidValue;
+    }
+    return Errors::ClErrorCodeNoError;
+}
+
+///the running statistics of a return series which is fed chunk by chunk, it keeps only the count, the mean, the sum of
+///squared deviations and the compound growth, so the whole series is never held in memory.
+///if any value is NAN/INF, the results will be NAN.
+///# Examples
+///```
+///use mpt_lib::ingest::RunningStatistics;
+///use mpt_lib::MPTCalculator;
+///use mpt_lib::enums::{self, Errors};
+///
+///let mut stats = RunningStatistics::new();
+///stats.update(&[1.0, -2.0, 3.0]);
+///stats.update(&[0.5, -1.5, 2.0]);
+///let mut res = f64::NAN;
+///let err = stats.standard_deviation(enums::ClFrequency::ClFrequencyMonthly, true, &mut res);
+///assert_eq!(
+///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 6.752777),
+///    true
+///);
+///```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RunningStatistics {
+    count: usize,
+    mean: f64,
+    sum_square: f64,
+    growth: f64,
+    has_invalid: bool,
+}
+
+impl Default for RunningStatistics {
+    fn default() -> Self {
+        RunningStatistics::new()
+    }
+}
+
+impl RunningStatistics {
+    pub fn new() -> RunningStatistics {
+        RunningStatistics {
+            count: 0,
+            mean: 0.0,
+            sum_square: 0.0,
+            growth: 1.0,
+            has_invalid: false,
+        }
+    }
+
+    ///add the values of the next chunk, the values should be in date order.
+    pub fn update(&mut self, values: &[f64]) {
+        for v in values {
+            if !v.is_finite() {
+                self.has_invalid = true;
+                continue;
+            }
+            self.count += 1;
+            let delta = v - self.mean;
+            self.mean += delta / self.count as f64;
+            self.sum_square += delta * (v - self.mean);
+            self.growth *= 1.0 + v / 100.0;
+        }
+    }
+
+    ///the number of values added so far.
+    pub fn count(&self) -> usize {
+        return self.count;
+    }
+
+    ///the arithmetic mean of the values added so far.
+    pub fn mean(&self, result: &mut f64) -> Errors {
+        *result = f64::NAN;
+        if !self.has_invalid && self.count > 0 {
+            *result = self.mean;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///the sample standard deviation of the values added so far, it is the same as MPTCalculator::standard_deviation.
+    pub fn standard_deviation(
+        &self,
+        freq: enums::ClFrequency,
+        is_annu: bool,
+        result: &mut f64,
+    ) -> Errors {
+        if is_annu && !is_valid_frequency(freq) {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *result = f64::NAN;
+        if self.has_invalid || self.count < 2 {
+            return Errors::ClErrorCodeNoError;
+        }
+        *result = (self.sum_square / (self.count - 1) as f64).sqrt();
+        if is_annu {
+            *result *= get_annual_multiplier(freq, false).sqrt();
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///the compound return of the values added so far, in percent.
+    pub fn cumulative_return(&self, result: &mut f64) -> Errors {
+        *result = f64::NAN;
+        if !self.has_invalid && self.count > 0 {
+            *result = (self.growth - 1.0) * 100.0;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+///the running statistics of each column of a dated return file which is fed chunk by chunk, e.g. by CsvChunkReader
+///or ParquetChunkReader, so the statistics of all the assets are calculated with the memory bounded by the chunk size.
+///# Examples
+///```
+///use mpt_lib::ingest::{ChunkStatistics, CsvChunkReader};
+///use mpt_lib::MPTCalculator;
+///use mpt_lib::enums::Errors;
+///
+///let csv = "date,fund,index\n45322,1.5,1.2\n45351,-0.5,0.8\n45382,2.0,-0.3\n";
+///let reader = CsvChunkReader::new(csv.as_bytes(), 2).unwrap();
+///let mut stats = ChunkStatistics::new(reader.names().len());
+///for chunk in reader {
+///    assert_eq!(stats.update(&chunk.unwrap()), Errors::ClErrorCodeNoError);
+///}
+///let mut res = f64::NAN;
+///stats.column(1).mean(&mut res);
+///assert_eq!(MPTCalculator::is_eq_double(res, 0.566667), true);
+///```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChunkStatistics {
+    columns: Vec<RunningStatistics>,
+}
+
+impl ChunkStatistics {
+    ///create the statistics of the columns, columns is the number of the assets.
+    pub fn new(columns: usize) -> ChunkStatistics {
+        ChunkStatistics {
+            columns: vec![RunningStatistics::new(); columns],
+        }
+    }
+
+    ///add the rows of the next chunk, the chunks should be in date order and have the same columns as the statistics.
+    pub fn update(&mut self, chunk: &ReturnChunk) -> Errors {
+        if chunk.columns.len() != self.columns.len()
+            || chunk.columns.iter().any(|x| x.len() != chunk.dates.len())
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        self.columns
+            .iter_mut()
+            .zip(chunk.columns.iter())
+            .for_each(|(stats, values)| stats.update(values));
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///the running statistics of the column j.
+    pub fn column(&self, j: usize) -> &RunningStatistics {
+        &self.columns[j]
+    }
+
+    pub fn columns(&self) -> &[RunningStatistics] {
+        &self.columns
+    }
+}
+
+///a column-major matrix of returns borrowed from a flat buffer, e.g. a memory-mapped file, the column j is the rows
+///from j * rows to (j + 1) * rows. the columns are slices of the buffer, so the matrix can be passed to the batch
+///functions without copying the values.
+///# Examples
+///```
+///use mpt_lib::ingest::ColumnMatrix;
+///
+///let data = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+///let matrix = ColumnMatrix::new(&data, 3, 2).unwrap();
+///assert_eq!(
+///    matrix.column(1) == [4.0, 5.0, 6.0] && matrix.columns() == vec![&data[..3], &data[3..]],
+///    true
+///);
+///```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColumnMatrix<'a> {
+    data: &'a [f64],
+    rows: usize,
+    cols: usize,
+}
+
+impl<'a> ColumnMatrix<'a> {
+    ///create the matrix of the shape, the size of the buffer must be rows * cols.
+    pub fn new(data: &'a [f64], rows: usize, cols: usize) -> Result<ColumnMatrix<'a>, Errors> {
+        if rows.checked_mul(cols) != Some(data.len()) {
+            return Err(Errors::ClErrorCodeInvalidPara);
+        }
+        return Ok(ColumnMatrix { data, rows, cols });
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    ///the values of the column j, it panics if j is out of the range.
+    pub fn column(&self, j: usize) -> &'a [f64] {
+        &self.data[j * self.rows..(j + 1) * self.rows]
+    }
+
+    ///the slices of all the columns, it is the returns matrix of the batch functions.
+    pub fn columns(&self) -> Vec<&'a [f64]> {
+        return (0..self.cols).map(|j| self.column(j)).collect();
+    }
+}
+
+///a memory-mapped file of a column-major f64 matrix in the native byte order, the file has no header, so the shape
+///is given by the caller. the values are read from the page cache when they are used, so the resident memory is not
+///bounded by the size of the matrix.
+#[cfg(feature = "mmap")]
+pub struct MappedMatrix {
+    mmap: Mmap,
+    rows: usize,
+    cols: usize,
+}
+
+#[cfg(feature = "mmap")]
+impl MappedMatrix {
+    ///map the file of the shape, the size of the file must be rows * cols * 8 bytes.
+    ///the file must not be changed while it is mapped.
+    pub fn open(path: &Path, rows: usize, cols: usize) -> Result<MappedMatrix, Errors> {
+        let size = rows
+            .checked_mul(cols)
+            .and_then(|x| x.checked_mul(std::mem::size_of::<f64>()))
+            .ok_or(Errors::ClErrorCodeInvalidPara)?;
+        let file = File::open(path).map_err(|_| Errors::ClErrorCodeInvalidValue)?;
+        let len = file
+            .metadata()
+            .map_err(|_| Errors::ClErrorCodeInvalidValue)?
+            .len();
+        if len != size as u64 {
+            return Err(Errors::ClErrorCodeInvalidPara);
+        }
+        if size == 0 {
+            return Err(Errors::ClErrorCodeInvalidPara);
+        }
+        let mmap = unsafe { Mmap::map(&file) }.map_err(|_| Errors::ClErrorCodeInvalidValue)?;
+        if mmap.as_ptr().align_offset(std::mem::align_of::<f64>()) != 0 {
+            return Err(Errors::ClErrorCodeInvalidValue);
+        }
+        return Ok(MappedMatrix { mmap, rows, cols });
+    }
+
+    ///the matrix view of the mapped values.
+    pub fn matrix(&self) -> ColumnMatrix<'_> {
+        //the map is aligned and has rows * cols values, any bit pattern is a valid f64.
+        let data = unsafe {
+            std::slice::from_raw_parts(self.mmap.as_ptr() as *const f64, self.rows * self.cols)
+        };
+        return ColumnMatrix {
+            data,
+            rows: self.rows,
+            cols: self.cols,
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ChunkStatistics, ColumnMatrix, CsvChunkReader, ReturnChunk, RunningStatistics};
+    use crate::{
+        enums::{self, Errors},
+        kernels, MPTCalculator,
+    };
+
+    ///a temp file path unique to the test process, so the parallel runs do not share the file.
+    #[cfg(any(feature = "parquet", feature = "mmap"))]
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        std::env::temp_dir().join(format!("mpt_lib_{}_{}_{}", std::process::id(), nanos, name))
+    }
+
+    #[test]
+    fn should_correct_csv_chunk_reader() {
+        let data = vec![1.0, -2.0, 3.0, 0.5, -1.5, 2.0, 0.8];
+        let mut csv = String::from("date,fund\n");
+        for i in 0..data.len() {
+            csv.push_str(&format!("{},{}\n\n", 45292 + i, data[i]));
+        }
+        let reader = CsvChunkReader::new(csv.as_bytes(), 3).unwrap();
+        let mut stats = RunningStatistics::new();
+        let mut chunks = 0;
+        for chunk in reader {
+            let chunk = chunk.unwrap();
+            stats.update(&chunk.columns[0]);
+            chunks += 1;
+        }
+        let mut expected = f64::NAN;
+        let mut res = f64::NAN;
+        let mpt = MPTCalculator::from_v(&data);
+        mpt.standard_deviation(enums::ClFrequency::ClFrequencyDaily, false, &mut expected);
+        stats.standard_deviation(enums::ClFrequency::ClFrequencyDaily, false, &mut res);
+        assert_eq!(
+            chunks == 3 && stats.count() == 7 && MPTCalculator::is_eq_double(res, expected),
+            true
+        );
+        kernels::total_return(&data, &mut expected);
+        stats.cumulative_return(&mut res);
+        assert_eq!(MPTCalculator::is_eq_double(res, expected), true);
+
+        stats.update(&[f64::NAN]);
+        stats.mean(&mut res);
+        assert_eq!(res.is_nan(), true);
+
+        let mut reader = CsvChunkReader::new("date,a,b\n45292,1.0\n".as_bytes(), 3).unwrap();
+        assert_eq!(reader.next(), Some(Err(Errors::ClErrorCodeInvalidValue)));
+        assert_eq!(reader.next(), None);
+        let mut reader = CsvChunkReader::new("date,a\n2024-13-01,1.0\n".as_bytes(), 3).unwrap();
+        assert_eq!(reader.next(), Some(Err(Errors::ClErrorCodeInvalidDate)));
+        assert_eq!(
+            CsvChunkReader::new("".as_bytes(), 3).err(),
+            Some(Errors::ClErrorCodeInvalidValue)
+        );
+    }
+
+    #[test]
+    fn should_correct_chunk_statistics() {
+        let fund = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317,
+        ];
+        let index = vec![
+            0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+            1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133,
+        ];
+        let mut csv = String::from("date,fund,index\n");
+        for i in 0..fund.len() {
+            csv.push_str(&format!("{},{},{}\n", 45292 + i, fund[i], index[i]));
+        }
+        let reader = CsvChunkReader::new(csv.as_bytes(), 4).unwrap();
+        let mut stats = ChunkStatistics::new(reader.names().len());
+        let mut chunks = 0;
+        for chunk in reader {
+            assert_eq!(stats.update(&chunk.unwrap()), Errors::ClErrorCodeNoError);
+            chunks += 1;
+        }
+        assert_eq!(chunks, 5);
+        for (j, values) in [&fund, &index].iter().enumerate() {
+            let mpt = MPTCalculator::from_v(values);
+            let (mut expected, mut res) = (f64::NAN, f64::NAN);
+            mpt.average(&mut expected);
+            stats.column(j).mean(&mut res);
+            assert_eq!(MPTCalculator::is_eq_double(res, expected), true);
+            mpt.standard_deviation(enums::ClFrequency::ClFrequencyMonthly, true, &mut expected);
+            stats.column(j).standard_deviation(
+                enums::ClFrequency::ClFrequencyMonthly,
+                true,
+                &mut res,
+            );
+            assert_eq!(MPTCalculator::is_eq_double(res, expected), true);
+            kernels::total_return(values, &mut expected);
+            stats.column(j).cumulative_return(&mut res);
+            assert_eq!(
+                MPTCalculator::is_eq_double(res, expected) && stats.column(j).count() == fund.len(),
+                true
+            );
+        }
+
+        let chunk = ReturnChunk {
+            dates: vec![45292],
+            columns: vec![vec![1.0]],
+        };
+        assert_eq!(stats.update(&chunk), Errors::ClErrorCodeInvalidPara);
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn should_correct_parquet_round_trip() {
+        use super::{write_parquet, ParquetChunkReader};
+
+        let path = temp_path("should_correct_parquet_round_trip.parquet");
+        let fund = vec![1.5, f64::NAN, -0.5];
+        let index = vec![1.2, 0.8, -0.3];
+        let err = write_parquet(
+            &path,
+            &[45322, 45351, 45382],
+            &["fund", "index"],
+            &[&fund, &index],
+        );
+        let chunks: Vec<_> = ParquetChunkReader::new(&path).unwrap().collect();
+        std::fs::remove_file(&path).unwrap();
+        let chunk = chunks[0].as_ref().unwrap();
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && chunks.len() == 1
+                && chunk.dates == vec![45322, 45351, 45382]
+                && chunk.columns[0][1].is_nan()
+                && chunk.columns[0][2] == -0.5
+                && chunk.columns[1] == index,
+            true
+        );
+
+        let err = write_parquet(&path, &[45322], &["fund"], &[&fund]);
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+
+    #[test]
+    fn should_correct_column_matrix() {
+        let data = vec![1.0, -2.0, 3.0, 0.5, -1.5, 2.0];
+        let matrix = ColumnMatrix::new(&data, 2, 3).unwrap();
+        let columns = matrix.columns();
+        assert_eq!(
+            matrix.rows() == 2
+                && matrix.cols() == 3
+                && columns.len() == 3
+                && columns[2] == [-1.5, 2.0]
+                && std::ptr::eq(columns[1].as_ptr(), &data[2]),
+            true
+        );
+        assert_eq!(
+            ColumnMatrix::new(&data, 4, 2).err(),
+            Some(Errors::ClErrorCodeInvalidPara)
+        );
+        assert_eq!(
+            ColumnMatrix::new(&data, usize::MAX, 2).err(),
+            Some(Errors::ClErrorCodeInvalidPara)
+        );
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn should_correct_mapped_matrix() {
+        use super::MappedMatrix;
+
+        let path = temp_path("should_correct_mapped_matrix.bin");
+        let fund: Vec<f64> = vec![1.5, -2.0, 0.5, 3.0];
+        let index = vec![1.2, -1.5, 0.8, 2.5];
+        let bytes: Vec<u8> = fund
+            .iter()
+            .chain(index.iter())
+            .flat_map(|x| x.to_ne_bytes())
+            .collect();
+        std::fs::write(&path, bytes).unwrap();
+        let mapped = MappedMatrix::open(&path, 4, 2).unwrap();
+        let matrix = mapped.matrix();
+        let mpt = MPTCalculator::from_v_b(matrix.column(0), matrix.column(1));
+        let (mut expected, mut res) = (f64::NAN, f64::NAN);
+        MPTCalculator::from_v_b(&fund, &index).beta(&mut expected);
+        let err = mpt.beta(&mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && res == expected && matrix.columns()[1] == index,
+            true
+        );
+        assert_eq!(
+            MappedMatrix::open(&path, 3, 2).err(),
+            Some(Errors::ClErrorCodeInvalidPara)
+        );
+        drop(mapped);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,108 @@
+//! the tracing instrumentation of the heavy computations, the batch, rolling, optimizer and backtest functions open
+//! an info span named by the function with the input size fields, and emit a debug event with the elapsed
+//! microseconds when they return. without the tracing feature the instrument macro expands to nothing.
+
+///the guard of an instrumented computation, it exits the span after the elapsed time event.
+#[cfg(feature = "tracing")]
+pub(crate) struct TimedSpan {
+    _span: tracing::span::EnteredSpan,
+    start: std::time::Instant,
+}
+
+#[cfg(feature = "tracing")]
+impl TimedSpan {
+    pub(crate) fn new(span: tracing::Span) -> TimedSpan {
+        TimedSpan {
+            _span: span.entered(),
+            start: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for TimedSpan {
+    fn drop(&mut self) {
+        tracing::debug!(
+            elapsed_us = self.start.elapsed().as_micros() as u64,
+            "completed"
+        );
+    }
+}
+
+///instrument the rest of the enclosing block, e.g. instrument!("pca", rows = n, cols = m);
+#[cfg(feature = "tracing")]
+macro_rules! instrument {
+    ($name:expr $(, $field:ident = $value:expr)*) => {
+        let _timed_span = crate::instrument::TimedSpan::new(
+            tracing::info_span!(target: "mpt_lib", $name $(, $field = $value)*),
+        );
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! instrument {
+    ($name:expr $(, $field:ident = $value:expr)*) => {};
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use tracing::{
+        span::{Attributes, Id, Record},
+        subscriber::with_default,
+        Event, Metadata, Subscriber,
+    };
+
+    use crate::{enums::ClComputeBackend, MPTCalculator};
+
+    struct Recorder {
+        spans: Arc<Mutex<Vec<String>>>,
+        events: Arc<Mutex<usize>>,
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut spans = self.spans.lock().unwrap();
+            spans.push(span.metadata().name().to_string());
+            Id::from_u64(spans.len() as u64)
+        }
+        fn record(&self, _span: &Id, _values: &Record<'_>) {}
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+        fn event(&self, _event: &Event<'_>) {
+            *self.events.lock().unwrap() += 1;
+        }
+        fn enter(&self, _span: &Id) {}
+        fn exit(&self, _span: &Id) {}
+    }
+
+    #[test]
+    fn should_correct_instrument() {
+        let spans = Arc::new(Mutex::new(Vec::new()));
+        let events = Arc::new(Mutex::new(0));
+        let recorder = Recorder {
+            spans: spans.clone(),
+            events: events.clone(),
+        };
+        let fund = vec![1.0, 3.0, 5.0, 5.0];
+        let mut results = Vec::new();
+        let mut used_backend = ClComputeBackend::ClComputeCpu;
+        with_default(recorder, || {
+            MPTCalculator::rolling_standard_deviation_batch(
+                &[&fund],
+                2,
+                ClComputeBackend::ClComputeCpu,
+                &mut results,
+                &mut used_backend,
+            );
+        });
+        assert_eq!(
+            *spans.lock().unwrap() == vec!["rolling_standard_deviation_batch"]
+                && *events.lock().unwrap() == 1,
+            true
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use crate::{
+    enums::{self, Errors},
+    MPTCalculator,
+};
+
+///the names of the invariants checked by check_invariants.
+pub const INVARIANTS: [&str; 8] = [
+    "standard_deviation_non_negative",
+    "downside_deviation_non_negative",
+    "average_between_min_max",
+    "omega_non_negative",
+    "r_squared_between_0_100",
+    "max_draw_down_non_positive",
+    "self_beta_one",
+    "self_capture_one_hundred",
+];
+
+fn check(
+    violated: &mut Vec<&'static str>,
+    name: &'static str,
+    err: Errors,
+    value: f64,
+    holds: fn(f64) -> bool,
+) {
+    if err == Errors::ClErrorCodeNoError && value.is_finite() && !holds(value) {
+        violated.push(name);
+    }
+}
+
+///check the invariants of the statistics of the calculator, return the names of the violated invariants.
+///a statistic which returns an error or a NAN/INF value is not checked, so the invariants hold for any input,
+///including the empty, single element, all zero and all equal arrays. the benchmark and the risk free can be empty.
+///# Examples
+///```
+///use mpt_lib::enums;
+///use mpt_lib::invariants;
+///use mpt_lib::MPTCalculator;
+///
+///let data = vec![1.0, -2.0, 3.0, 0.5, -1.5, 2.0];
+///let bmk_data = vec![0.8, -1.5, 2.5, 0.2, -1.0, 1.5];
+///let rf_data = vec![0.1; 6];
+///let mpt = MPTCalculator::from(&data, &bmk_data, &rf_data);
+///let violated = invariants::check_invariants(&mpt, enums::ClFrequency::ClFrequencyMonthly);
+///assert_eq!(violated.len(), 0);
+///```
+pub fn check_invariants(calc: &MPTCalculator, freq: enums::ClFrequency) -> Vec<&'static str> {
+    let mut violated = Vec::new();
+
+    let mut res = f64::NAN;
+    let err = calc.standard_deviation(freq, true, &mut res);
+    check(&mut violated, INVARIANTS[0], err, res, |x| x >= 0.0);
+
+    if calc.riskfree.len() == calc.values.len() {
+        let err = calc.downside_deviation(freq, true, &mut res);
+        check(&mut violated, INVARIANTS[1], err, res, |x| x >= 0.0);
+    }
+
+    let valid: Vec<f64> = calc
+        .values
+        .iter()
+        .filter(|x| x.is_finite())
+        .cloned()
+        .collect();
+    if valid.len() > 0 {
+        let min = valid.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = valid.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let err = calc.average(&mut res);
+        let tol = 1e-9 * max.abs().max(min.abs()).max(1.0);
+        if err == Errors::ClErrorCodeNoError && !(res >= min - tol && res <= max + tol) {
+            violated.push(INVARIANTS[2]);
+        }
+    }
+
+    if calc.riskfree.len() == calc.values.len() {
+        let err = calc.omega(freq, false, &mut res);
+        check(&mut violated, INVARIANTS[3], err, res, |x| x >= 0.0);
+    }
+
+    if calc.benchmark.len() == calc.values.len() {
+        let err = calc.r_squared(&mut res);
+        check(&mut violated, INVARIANTS[4], err, res, |x| {
+            x >= -1e-9 && x <= 100.0 + 1e-9
+        });
+    }
+
+    if calc.values.len() > 0 {
+        let dates: Vec<i32> = (0..calc.values.len() as i32).map(|x| 45291 + x).collect();
+        let (mut peek_date, mut valley_date, mut months, mut recovery_months, mut recovery_date) =
+            (0, 0, 0, 0, 0);
+        let err = calc.max_draw_down(
+            &dates,
+            enums::ClFrequency::ClFrequencyDaily,
+            &mut res,
+            &mut peek_date,
+            &mut valley_date,
+            &mut months,
+            &mut recovery_months,
+            &mut recovery_date,
+        );
+        check(&mut violated, INVARIANTS[5], err, res, |x| x <= 0.0);
+    }
+
+    let itself = MPTCalculator::from_v_b(calc.values, calc.values);
+    let err = itself.beta(&mut res);
+    check(&mut violated, INVARIANTS[6], err, res, |x| {
+        (x - 1.0).abs() < 1e-9
+    });
+
+    //the capture ratios keep the output if they return early, so the output is reset before each call.
+    let mut capture_return = f64::NAN;
+    res = f64::NAN;
+    let err = itself.upside_capture(&mut res, &mut capture_return);
+    check(&mut violated, INVARIANTS[7], err, res, |x| {
+        (x - 100.0).abs() < 1e-9
+    });
+    res = f64::NAN;
+    let err = itself.downside_capture(&mut res, &mut capture_return);
+    check(&mut violated, INVARIANTS[7], err, res, |x| {
+        (x - 100.0).abs() < 1e-9
+    });
+
+    violated.dedup();
+    return violated;
+}
+
+#[cfg(test)]
+mod test {
+    use super::check_invariants;
+    use crate::{enums, MPTCalculator};
+    use proptest::prelude::*;
+
+    fn violated(values: &[f64], benchmark: &[f64], riskfree: &[f64]) -> Vec<&'static str> {
+        let mpt = MPTCalculator::from(values, benchmark, riskfree);
+        return check_invariants(&mpt, enums::ClFrequency::ClFrequencyMonthly);
+    }
+
+    #[test]
+    fn should_correct_invariants_of_pathological_inputs() {
+        let inputs: Vec<Vec<f64>> = vec![
+            vec![],
+            vec![1.5],
+            vec![0.0; 2],
+            vec![0.0; 12],
+            vec![2.5; 3],
+            vec![2.5; 12],
+            vec![-100.0, 50.0, 0.0],
+            vec![1.0, f64::NAN, 2.0],
+        ];
+        for values in inputs.iter() {
+            let riskfree = vec![0.1; values.len()];
+            for benchmark in [values.clone(), vec![0.0; values.len()], riskfree.clone()] {
+                assert_eq!(
+                    violated(values, &benchmark, &riskfree),
+                    Vec::<&str>::new(),
+                    "{:?} {:?}",
+                    values,
+                    benchmark
+                );
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn should_correct_invariants_of_random_inputs(
+            rows in prop::collection::vec((-30.0..30.0f64, -30.0..30.0f64, 0.0..1.0f64), 0..48)
+        ) {
+            let values: Vec<f64> = rows.iter().map(|x| x.0).collect();
+            let benchmark: Vec<f64> = rows.iter().map(|x| x.1).collect();
+            let riskfree: Vec<f64> = rows.iter().map(|x| x.2).collect();
+            prop_assert_eq!(violated(&values, &benchmark, &riskfree), Vec::<&str>::new());
+        }
+    }
+}
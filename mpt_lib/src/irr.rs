@@ -0,0 +1,456 @@
+use crate::{common::is_sorted_array, enums::Errors, MPTCalculator};
+
+const IRR_MAX_ITERATION: usize = 100;
+const IRR_TOLERANCE: f64 = 1e-10;
+const IRR_MIN_RATE: f64 = -0.999999;
+
+///solve the rate which make the future value of the cash flows equal to the end value.
+///the cash flows are given as (amount, periods to the end), use newton method first and
+///the bisection method as the fallback if newton does not converge.
+fn solve_rate(flows: &[(f64, f64)], end_value: f64) -> Option<f64> {
+    let future_value = |rate: f64| {
+        flows
+            .iter()
+            .fold(-end_value, |acc, x| acc + x.0 * (1.0 + rate).powf(x.1))
+    };
+    let derivative = |rate: f64| {
+        flows
+            .iter()
+            .fold(0.0, |acc, x| acc + x.0 * x.1 * (1.0 + rate).powf(x.1 - 1.0))
+    };
+
+    let mut rate = 0.1;
+    for _ in 0..IRR_MAX_ITERATION {
+        let fv = future_value(rate);
+        let d = derivative(rate);
+        if !fv.is_finite() || !d.is_finite() || d == 0.0 {
+            break;
+        }
+        let new_rate = rate - fv / d;
+        if !new_rate.is_finite() || new_rate <= IRR_MIN_RATE {
+            break;
+        }
+        if (new_rate - rate).abs() < IRR_TOLERANCE {
+            return Some(new_rate);
+        }
+        rate = new_rate;
+    }
+
+    let mut low = IRR_MIN_RATE;
+    let mut high = 1.0;
+    let fv_low = future_value(low);
+    while future_value(high) * fv_low > 0.0 {
+        high *= 2.0;
+        if high > 1e6 {
+            return None;
+        }
+    }
+    for _ in 0..IRR_MAX_ITERATION * 10 {
+        let mid = (low + high) / 2.0;
+        let fv_mid = future_value(mid);
+        if fv_mid == 0.0 || (high - low) / 2.0 < IRR_TOLERANCE {
+            return Some(mid);
+        }
+        if fv_mid * fv_low > 0.0 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Some((low + high) / 2.0)
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///calculate the money weighted return(internal rate of return) of periodic cash flows.
+    ///the values are the external cash flows into the portfolio at the begin of each period,
+    ///the first value is usually the begin market value, a withdrawal is negative.
+    ///if the array has NAN/INF values,the result will be NAN
+    ///# Arguments
+    ///end_value: the market value at the end of the last period.
+    ///
+    ///irr_res: the return of one period.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![100.0, 50.0, -20.0, 10.0];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.irr(150.0, &mut res);
+    ///assert_eq!(
+    ///   err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 1.873244),
+    ///   true
+    ///);
+    ///```
+    pub fn irr(&self, end_value: f64, irr_res: &mut f64) -> Errors {
+        if self.values.len() == 0 {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *irr_res = f64::NAN;
+        if !end_value.is_finite() || self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let n = self.values.len();
+        let flows: Vec<(f64, f64)> = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|v| (*v.1, (n - v.0) as f64))
+            .collect();
+        match solve_rate(&flows, end_value) {
+            Some(rate) => *irr_res = rate * 100.0,
+            None => return Errors::ClErrorCodeCcFaild,
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the money weighted return(internal rate of return) of dated cash flows, the day count is actual/365.
+    ///the values are the external cash flows into the portfolio, the first value is usually the begin market value,
+    ///a withdrawal is negative. if the array has NAN/INF values,the result will be NAN
+    ///# Arguments
+    ///dates: the date of cash flows, should be sorted.
+    ///
+    ///end_date: the date of the end value.
+    ///
+    ///end_value: the market value at the end date.
+    ///
+    ///is_annu: the flag of annualize, if false the result is the cumulative return from the first date to end date.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![1000.0, 500.0, -200.0];
+    ///let dates = vec![43831, 44013, 44104];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.xirr(&dates, 44196, 1400.0, true, &mut res);
+    ///assert_eq!(
+    ///   err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 8.355883),
+    ///   true
+    ///);
+    ///```
+    pub fn xirr(
+        &self,
+        dates: &[i32],
+        end_date: i32,
+        end_value: f64,
+        is_annu: bool,
+        irr_res: &mut f64,
+    ) -> Errors {
+        if self.values.len() == 0 || dates.len() != self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *irr_res = f64::NAN;
+        if dates.len() > 1 && !is_sorted_array(dates) || dates[dates.len() - 1] > end_date {
+            return Errors::ClErrorCodeUnsortedByDate;
+        }
+        if dates[0] == end_date {
+            return Errors::ClErrorCodeInvalidDate;
+        }
+        if !end_value.is_finite() || self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let flows: Vec<(f64, f64)> = self
+            .values
+            .iter()
+            .zip(dates.iter())
+            .map(|v| (*v.0, (end_date - v.1) as f64 / 365.0))
+            .collect();
+        match solve_rate(&flows, end_value) {
+            Some(rate) => {
+                if is_annu {
+                    *irr_res = rate * 100.0;
+                } else {
+                    *irr_res =
+                        ((1.0 + rate).powf((end_date - dates[0]) as f64 / 365.0) - 1.0) * 100.0;
+                }
+            }
+            None => return Errors::ClErrorCodeCcFaild,
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the gap between the investor return(money weighted) and the fund return(time weighted).
+    ///the values are the periodic fund returns, the asset of the fund is rolled forward by the returns and the
+    ///dated net cash flows, a cash flow is invested at the end of the period which contains its date.
+    ///the investor return is the xirr of the begin value and the dated cash flows.
+    ///if the array has NAN/INF values,the result will be NAN
+    ///# Arguments
+    ///dates: the end date of each period, should be sorted.
+    ///
+    ///begin_date: the begin date of the first period.
+    ///
+    ///begin_value: the asset of the fund at the begin date.
+    ///
+    ///flow_dates: the date of the cash flows, should be sorted and after the begin date, not after the last date.
+    ///
+    ///cash_flows: the net cash flows, a redemption is negative.
+    ///
+    ///is_annu: the flag of annualize, the day count is actual/365.
+    ///
+    ///investor_return: the money weighted return.
+    ///
+    ///fund_return: the time weighted return.
+    ///
+    ///return_gap: the investor return minus the fund return.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let data = vec![2.0, -1.0, 3.0, 1.5, -2.0, 1.0];
+    ///let dates = vec![43861, 43890, 43921, 43951, 43982, 44012];
+    ///let flow_dates = vec![43861, 43890, 43921, 43982, 44000];
+    ///let cash_flows = vec![100.0, -50.0, 200.0, -300.0, 50.0];
+    ///let mut investor_return = 0.0;
+    ///let mut fund_return = 0.0;
+    ///let mut return_gap = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.investor_return_gap(
+    ///    &dates,
+    ///    43830,
+    ///    1000.0,
+    ///    &flow_dates,
+    ///    &cash_flows,
+    ///    true,
+    ///    &mut investor_return,
+    ///    &mut fund_return,
+    ///    &mut return_gap,
+    ///);
+    ///assert_eq!(
+    ///   err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(return_gap, -1.136059),
+    ///   true
+    ///);
+    ///```
+    pub fn investor_return_gap(
+        &self,
+        dates: &[i32],
+        begin_date: i32,
+        begin_value: f64,
+        flow_dates: &[i32],
+        cash_flows: &[f64],
+        is_annu: bool,
+        investor_return: &mut f64,
+        fund_return: &mut f64,
+        return_gap: &mut f64,
+    ) -> Errors {
+        if self.values.len() == 0
+            || dates.len() != self.values.len()
+            || flow_dates.len() != cash_flows.len()
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *investor_return = f64::NAN;
+        *fund_return = f64::NAN;
+        *return_gap = f64::NAN;
+        if dates.windows(2).any(|x| x[0] >= x[1]) || flow_dates.windows(2).any(|x| x[0] >= x[1]) {
+            return Errors::ClErrorCodeUnsortedByDate;
+        }
+        let end_date = dates[dates.len() - 1];
+        if begin_date >= dates[0] || flow_dates.iter().any(|x| *x <= begin_date || *x > end_date) {
+            return Errors::ClErrorCodeInvalidDate;
+        }
+        if !begin_value.is_finite() || cash_flows.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let mut total_return = f64::NAN;
+        Self::total_return_accumulat(self.values, &mut total_return);
+        if !total_return.is_finite() {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let mut flow_pos = 0;
+        let end_value = self
+            .values
+            .iter()
+            .zip(dates.iter())
+            .fold(begin_value, |acc, v| {
+                let mut asset = acc * (1.0 + v.0 / 100.0);
+                while flow_pos < flow_dates.len() && flow_dates[flow_pos] <= *v.1 {
+                    asset += cash_flows[flow_pos];
+                    flow_pos += 1;
+                }
+                asset
+            });
+
+        let mut flows = Vec::with_capacity(cash_flows.len() + 1);
+        flows.push(begin_value);
+        flows.extend_from_slice(cash_flows);
+        let mut all_dates = Vec::with_capacity(flow_dates.len() + 1);
+        all_dates.push(begin_date);
+        all_dates.extend_from_slice(flow_dates);
+
+        let mut investor_total_return = f64::NAN;
+        let ret = MPTCalculator::from_v(&flows).xirr(
+            &all_dates,
+            end_date,
+            end_value,
+            is_annu,
+            &mut investor_total_return,
+        );
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+
+        if is_annu {
+            total_return =
+                ((1.0 + total_return / 100.0).powf(365.0 / (end_date - begin_date) as f64) - 1.0)
+                    * 100.0;
+        }
+        *investor_return = investor_total_return;
+        *fund_return = total_return;
+        *return_gap = investor_total_return - total_return;
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{enums::Errors, MPTCalculator};
+
+    #[test]
+    fn should_correct_irr() {
+        let data = vec![100.0, 50.0, -20.0, 10.0];
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.irr(150.0, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 1.873244),
+            true
+        );
+
+        let data = vec![100.0, f64::NAN];
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.irr(150.0, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res.is_nan(), true);
+    }
+
+    #[test]
+    fn should_correct_xirr() {
+        let data = vec![1000.0, 500.0, -200.0];
+        let dates = vec![43831, 44013, 44104];
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.xirr(&dates, 44196, 1400.0, true, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 8.355883),
+            true
+        );
+        let err = mpt.xirr(&dates, 44196, 1400.0, false, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 8.355883),
+            true
+        );
+        let err = mpt.xirr(&dates, 44377, 1450.0, true, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 7.997386),
+            true
+        );
+        let err = mpt.xirr(&dates, 44377, 1450.0, false, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 12.197337),
+            true
+        );
+        let err = mpt.xirr(&dates, 44100, 1400.0, true, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeUnsortedByDate, true);
+    }
+
+    #[test]
+    fn should_correct_investor_return_gap() {
+        let data = vec![2.0, -1.0, 3.0, 1.5, -2.0, 1.0];
+        let dates = vec![43861, 43890, 43921, 43951, 43982, 44012];
+        let flow_dates = vec![43861, 43890, 43921, 43982, 44000];
+        let cash_flows = vec![100.0, -50.0, 200.0, -300.0, 50.0];
+        let mut investor_return = 0.0;
+        let mut fund_return = 0.0;
+        let mut return_gap = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.investor_return_gap(
+            &dates,
+            43830,
+            1000.0,
+            &flow_dates,
+            &cash_flows,
+            true,
+            &mut investor_return,
+            &mut fund_return,
+            &mut return_gap,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(investor_return, 8.077620)
+                && MPTCalculator::is_eq_double(fund_return, 9.213678)
+                && MPTCalculator::is_eq_double(return_gap, -1.136059),
+            true
+        );
+
+        let err = mpt.investor_return_gap(
+            &dates,
+            43830,
+            1000.0,
+            &flow_dates[..4],
+            &cash_flows[..4],
+            true,
+            &mut investor_return,
+            &mut fund_return,
+            &mut return_gap,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(investor_return, 8.101867)
+                && MPTCalculator::is_eq_double(return_gap, -1.111811),
+            true
+        );
+
+        let err = mpt.investor_return_gap(
+            &dates,
+            43830,
+            1000.0,
+            &[],
+            &[],
+            false,
+            &mut investor_return,
+            &mut fund_return,
+            &mut return_gap,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(investor_return, 4.492732)
+                && MPTCalculator::is_eq_double(return_gap, 0.0),
+            true
+        );
+
+        let err = mpt.investor_return_gap(
+            &dates,
+            43830,
+            1000.0,
+            &[43830],
+            &[100.0],
+            true,
+            &mut investor_return,
+            &mut fund_return,
+            &mut return_gap,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidDate && return_gap.is_nan(),
+            true
+        );
+        let err = mpt.investor_return_gap(
+            &dates,
+            43830,
+            1000.0,
+            &[43921, 43890],
+            &[100.0, -50.0],
+            true,
+            &mut investor_return,
+            &mut fund_return,
+            &mut return_gap,
+        );
+        assert_eq!(err == Errors::ClErrorCodeUnsortedByDate, true);
+    }
+}
@@ -0,0 +1,397 @@
+//! the core math of the statistics, it only uses core and alloc, so it can be built without std for the embedded
+//! risk engines. without the std feature the float functions are provided by the libm feature. it is the only part
+//! of the statistics built without std, MPTCalculator and the other statistics modules need the std feature.
+use alloc::vec::Vec;
+
+use crate::enums::{self, Errors};
+
+#[cfg(all(not(feature = "std"), not(feature = "libm")))]
+compile_error!("mpt_lib needs the std feature or the libm feature");
+
+#[cfg(feature = "std")]
+fn sqrt(x: f64) -> f64 {
+    x.sqrt()
+}
+
+#[cfg(not(feature = "std"))]
+fn sqrt(x: f64) -> f64 {
+    libm::sqrt(x)
+}
+
+///the observation number per year of the frequency, is_fd uses 250 business days for the daily frequency,
+///otherwise 365.25 days. the multiplier of an unknown frequency is NAN.
+pub fn annual_multiplier(freq: enums::ClFrequency, is_fd: bool) -> f64 {
+    match freq {
+        enums::ClFrequency::ClFrequencyDaily if is_fd => 250.0,
+        enums::ClFrequency::ClFrequencyDaily => 365.25,
+        enums::ClFrequency::ClFrequencyWeekly => 52.0,
+        enums::ClFrequency::ClFrequencyMonthly => 12.0,
+        enums::ClFrequency::ClFrequencyQuarterly => 4.0,
+        enums::ClFrequency::ClFrequencySemiannually => 2.0,
+        enums::ClFrequency::ClFrequencyAnnually => 1.0,
+        enums::ClFrequency::ClFrequencySemimonthly => 24.0,
+        enums::ClFrequency::ClFrequencyBimonthly => 6.0,
+        _ => f64::NAN,
+    }
+}
+
+///the divisor of the sum of the squared deviations, it is n - 1 for the sample variance and n for the population variance.
+pub fn variance_divisor(count: usize, mode: enums::ClVarianceMode) -> f64 {
+    match mode {
+        enums::ClVarianceMode::ClVarianceSample => count as f64 - 1.0,
+        enums::ClVarianceMode::ClVariancePopulation => count as f64,
+    }
+}
+
+///check the sample size of a statistic, the empty values are invalid and the values less than min_count are
+///insufficient for the statistic.
+pub fn check_sample_size(count: usize, min_count: usize) -> Errors {
+    if count == 0 {
+        return Errors::ClErrorCodeInvalidPara;
+    }
+    if count < min_count {
+        return Errors::ClErrorCodeInsufficientData;
+    }
+    return Errors::ClErrorCodeNoError;
+}
+
+///calculate the average value of the values not include NAN/INF values, it is the same as MPTCalculator::average.
+///# Examples
+///```
+///use mpt_lib::kernels;
+///use mpt_lib::enums::Errors;
+///let data = vec![10.0, 20.0, f64::NAN, 30.0];
+///let mut res = f64::NAN;
+///let err = kernels::mean(&data, &mut res);
+///assert_eq!(err == Errors::ClErrorCodeNoError && res == 20.0, true);
+///```
+pub fn mean(values: &[f64], result: &mut f64) -> Errors {
+    *result = values.iter().filter(|x| x.is_finite()).sum::<f64>()
+        / values.iter().filter(|x| x.is_finite()).count() as f64;
+    return Errors::ClErrorCodeNoError;
+}
+
+///calculate the standard deviation value of the values, if the values have NAN/INF values,the result will be NAN.
+///it is the same as MPTCalculator::standard_deviation_by_mode without the sample size check.
+///
+///# Arguments
+///values: the source data.
+///
+///freq: the frequence of source data.
+///
+///is_annu: the flag of annualize.
+///
+///mode: the sample or the population standard deviation.
+///# Examples
+///```
+///use mpt_lib::kernels;
+///use mpt_lib::MPTCalculator;
+///use mpt_lib::enums::{self, Errors};
+///let data = vec![1.0, -2.0, 3.0, 0.5, -1.5, 2.0];
+///let mut res = f64::NAN;
+///let err = kernels::standard_deviation(
+///    &data,
+///    enums::ClFrequency::ClFrequencyMonthly,
+///    true,
+///    enums::ClVarianceMode::ClVarianceSample,
+///    &mut res,
+///);
+///assert_eq!(err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 6.752777), true);
+///```
+pub fn standard_deviation(
+    values: &[f64],
+    freq: enums::ClFrequency,
+    is_annu: bool,
+    mode: enums::ClVarianceMode,
+    result: &mut f64,
+) -> Errors {
+    let mut avg = f64::NAN;
+    let ret = mean(values, &mut avg);
+    if ret != Errors::ClErrorCodeNoError {
+        return ret;
+    }
+
+    if values.iter().any(|x| !x.is_finite()) {
+        return Errors::ClErrorCodeNoError;
+    }
+
+    let accumalte = values
+        .iter()
+        .fold(0.0, |acc, x| acc + (x - avg) * (x - avg));
+
+    if values.len() == 0 {
+        *result = 0.0
+    } else {
+        *result = sqrt(accumalte / variance_divisor(values.len(), mode))
+    }
+
+    if is_annu {
+        *result = *result * sqrt(annual_multiplier(freq, false))
+    }
+
+    return Errors::ClErrorCodeNoError;
+}
+
+///calculate the covariance of the values and the benchmark, if the values or the benchmark have NAN/INF values,
+///the result will be NAN.
+///# Examples
+///```
+///use mpt_lib::kernels;
+///use mpt_lib::enums::{self, Errors};
+///let data = vec![1.0, 2.0, 3.0];
+///let bmk_data = vec![2.0, 4.0, 6.0];
+///let mut res = f64::NAN;
+///let err = kernels::covariance(&data, &bmk_data, enums::ClVarianceMode::ClVarianceSample, &mut res);
+///assert_eq!(err == Errors::ClErrorCodeNoError && res == 2.0, true);
+///```
+pub fn covariance(
+    values: &[f64],
+    benchmark: &[f64],
+    mode: enums::ClVarianceMode,
+    result: &mut f64,
+) -> Errors {
+    *result = f64::NAN;
+    if values.len() != benchmark.len() {
+        return Errors::ClErrorCodeInvalidPara;
+    }
+    let ret = check_sample_size(values.len(), 2);
+    if ret != Errors::ClErrorCodeNoError {
+        return ret;
+    }
+    if values
+        .iter()
+        .chain(benchmark.iter())
+        .any(|x| !x.is_finite())
+    {
+        return Errors::ClErrorCodeNoError;
+    }
+
+    let (mut avg, mut bmk_avg) = (f64::NAN, f64::NAN);
+    mean(values, &mut avg);
+    mean(benchmark, &mut bmk_avg);
+    *result = values
+        .iter()
+        .zip(benchmark.iter())
+        .fold(0.0, |acc, (x, y)| acc + (x - avg) * (y - bmk_avg))
+        / variance_divisor(values.len(), mode);
+    return Errors::ClErrorCodeNoError;
+}
+
+///calculate the compounded total return of the values in percent, if the values have NAN/INF values,
+///the result will be NAN.
+///# Examples
+///```
+///use mpt_lib::kernels;
+///use mpt_lib::MPTCalculator;
+///use mpt_lib::enums::Errors;
+///let data = vec![10.0, -10.0];
+///let mut res = f64::NAN;
+///let err = kernels::total_return(&data, &mut res);
+///assert_eq!(err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, -1.0), true);
+///```
+pub fn total_return(values: &[f64], result: &mut f64) -> Errors {
+    if values.len() == 0 {
+        return Errors::ClErrorCodeInvalidPara;
+    }
+    *result = f64::NAN;
+    if values.iter().any(|x| !x.is_finite()) {
+        return Errors::ClErrorCodeNoError;
+    }
+    *result = (values.iter().fold(1.0, |acc, v| acc * (1.0 + v / 100.0)) - 1.0) * 100.0;
+    return Errors::ClErrorCodeNoError;
+}
+
+///calculate the linear interpolated percentile value of the values not include NAN/INF values.
+///
+///# Arguments
+///values: the source data.
+///
+///nth: the percentile, it is from 0 to 100.
+///# Examples
+///```
+///use mpt_lib::kernels;
+///use mpt_lib::enums::Errors;
+///let data = vec![4.0, 1.0, f64::NAN, 3.0, 2.0];
+///let mut res = f64::NAN;
+///let err = kernels::percentile(&data, 75, &mut res);
+///assert_eq!(err == Errors::ClErrorCodeNoError && res == 3.25, true);
+///```
+pub fn percentile(values: &[f64], nth: i32, result: &mut f64) -> Errors {
+    if nth < 0 || nth > 100 {
+        return Errors::ClErrorCodeInvalidPara;
+    }
+    *result = f64::NAN;
+    let mut sorted: Vec<f64> = values.iter().filter(|x| x.is_finite()).cloned().collect();
+    if sorted.len() == 0 {
+        return Errors::ClErrorCodeNoError;
+    }
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let remain = nth as f64 * (sorted.len() - 1) as f64 / 100.0;
+    let n = remain as usize;
+    *result = sorted[n];
+    if n + 1 < sorted.len() {
+        *result += (remain - n as f64) * (sorted[n + 1] - sorted[n]);
+    }
+    return Errors::ClErrorCodeNoError;
+}
+
+///the linear interpolated q quantile of the sorted values, q is from 0 to 1, 0 is the smallest value and 1 is the
+///largest value. the values should be sorted ascending and not be empty.
+///# Examples
+///```
+///use mpt_lib::kernels;
+///let res = kernels::sorted_quantile(&[1.0, 2.0, 4.0], 0.75);
+///assert_eq!(res, 3.0);
+///```
+pub fn sorted_quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let n = pos as usize;
+    let mut value = sorted[n];
+    if n + 1 < sorted.len() {
+        value += (pos - n as f64) * (sorted[n + 1] - sorted[n]);
+    }
+    value
+}
+
+///the depth of the draw down of each period from the running peak of the compound value, in percent, the depth is
+///positive. the values should not have NAN/INF values.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+pub(crate) fn draw_down_depths(values: &[f64]) -> Vec<f64> {
+    let mut wealth = 1.0;
+    let mut peak: f64 = 1.0;
+    values
+        .iter()
+        .map(|v| {
+            wealth *= 1.0 + v / 100.0;
+            peak = peak.max(wealth);
+            (1.0 - wealth / peak) * 100.0
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::{
+        covariance, draw_down_depths, percentile, sorted_quantile, standard_deviation, total_return,
+    };
+    use crate::{
+        enums::{self, Errors},
+        stats, MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_kernels() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477,
+        ];
+        let bmk_data = vec![
+            1.22072, -2.0668, 0.20588, 0.91563, 1.76766, 2.21429, 2.43456, 1.99825, 0.89481,
+            1.59564, -1.86793, 0.41477,
+        ];
+        let mpt = MPTCalculator::from_v(&data);
+        let freq = enums::ClFrequency::ClFrequencyMonthly;
+
+        let mut expected = f64::NAN;
+        let mut res = f64::NAN;
+        mpt.standard_deviation_by_mode(
+            freq,
+            true,
+            enums::ClVarianceMode::ClVariancePopulation,
+            &mut expected,
+        );
+        let err = standard_deviation(
+            &data,
+            freq,
+            true,
+            enums::ClVarianceMode::ClVariancePopulation,
+            &mut res,
+        );
+        assert_eq!(err == Errors::ClErrorCodeNoError && res == expected, true);
+
+        stats::percentile(&data, 30, &mut expected);
+        let err = percentile(&data, 30, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, expected),
+            true
+        );
+
+        let err = covariance(
+            &data,
+            &data,
+            enums::ClVarianceMode::ClVarianceSample,
+            &mut res,
+        );
+        mpt.standard_deviation(freq, false, &mut expected);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(res, expected * expected),
+            true
+        );
+        let err = covariance(
+            &data,
+            &bmk_data[1..],
+            enums::ClVarianceMode::ClVarianceSample,
+            &mut res,
+        );
+        assert_eq!(err == Errors::ClErrorCodeInvalidPara && res.is_nan(), true);
+
+        let err = total_return(&[1.0, f64::NAN], &mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res.is_nan(), true);
+
+        let mut sorted = data.clone();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        percentile(&data, 30, &mut expected);
+        assert_eq!(
+            MPTCalculator::is_eq_double(sorted_quantile(&sorted, 0.3), expected)
+                && sorted_quantile(&sorted, 0.0) == sorted[0]
+                && sorted_quantile(&sorted, 1.0) == sorted[11],
+            true
+        );
+
+        let depths = draw_down_depths(&[10.0, -50.0, 20.0, 100.0]);
+        assert_eq!(
+            MPTCalculator::is_eq_double_array(&depths, &[0.0, 50.0, 40.0, 0.0]),
+            true
+        );
+    }
+}
+
+#[cfg(all(test, not(feature = "std")))]
+mod no_std_test {
+    use super::{mean, percentile, standard_deviation, total_return};
+    use crate::enums::{self, Errors};
+
+    #[test]
+    fn should_correct_kernels_without_std() {
+        let data = [1.0, -2.0, 3.0, 0.5, -1.5, 2.0];
+        let mut res = f64::NAN;
+        let err = mean(&data, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && (res - 0.5).abs() < 1e-12,
+            true
+        );
+
+        let err = standard_deviation(
+            &data,
+            enums::ClFrequency::ClFrequencyMonthly,
+            true,
+            enums::ClVarianceMode::ClVarianceSample,
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && (res - 6.752777).abs() < 1e-6,
+            true
+        );
+
+        let err = percentile(&[4.0, 1.0, f64::NAN, 3.0, 2.0], 75, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res == 3.25, true);
+
+        let err = total_return(&[10.0, -10.0], &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && (res + 1.0).abs() < 1e-12,
+            true
+        );
+    }
+}
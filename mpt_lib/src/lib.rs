@@ -1,3 +1,4 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 //! The full name of MPT is modern portfolio theory.
 //! It is an economic framework through which investors try to take minimal market risks and achieve maximum returns for a given investment portfolio
 //! </br>
@@ -17,6 +18,8 @@
 //! ClFrequencyQuarterly,    //3
 //! ClFrequencyAnnually,     //4
 //! ClFrequencySemiannually, //5
+//! ClFrequencySemimonthly,  //6
+//! ClFrequencyBimonthly,    //7
 //! }
 //!```
 //!rank type:
@@ -35,17 +38,156 @@
 //! ClRankTypeQuartAsc = 10,
 //! ClRankTypeQuartDec = 11,
 //! }
+//!```
 //!
+//! ## no_std
+//! only the kernels and enums modules are built without std: the mean, standard deviation, covariance, total return,
+//! percentile and annual multiplier of the kernels only use core and alloc, build the lib with
+//! `default-features = false, features = ["libm"]` to use them. MPTCalculator and all the other statistics modules
+//! need the std feature, the Error impl of enums::Errors needs it too.
 //!
+//! ## Thread safety
+//! MPTCalculator only borrows the input slices and all the result types own their data, so they are Send + Sync and
+//! can be used from the threads and the async handlers. a HolidayCalendar is Send + Sync too, one calendar can be
+//! shared by an Arc<dyn HolidayCalendar>.
 //!
+extern crate alloc;
+
+#[cfg(feature = "std")]
+#[macro_use]
+mod instrument;
+
+#[cfg(feature = "std")]
 mod absolute_statistics;
+#[cfg(feature = "std")]
 mod array;
+#[cfg(feature = "std")]
+mod batch;
+#[cfg(feature = "std")]
 mod common;
-mod date_util;
+#[cfg(feature = "std")]
+mod composites;
+#[cfg(feature = "std")]
+mod contribution;
+#[cfg(feature = "std")]
+mod correlation;
+#[cfg(feature = "std")]
+mod currency;
+#[cfg(feature = "std")]
+mod draw_down_risk;
+#[cfg(feature = "std")]
+mod goals;
+#[cfg(feature = "gpu")]
+mod gpu;
+#[cfg(feature = "std")]
+mod irr;
+#[cfg(feature = "std")]
+mod linalg;
+#[cfg(feature = "std")]
+mod optimizer;
+#[cfg(feature = "std")]
+mod pca;
+#[cfg(feature = "std")]
 mod rank;
+#[cfg(feature = "std")]
+mod realized_volatility;
+#[cfg(feature = "std")]
+mod regression;
+#[cfg(feature = "std")]
 mod relative_statistics;
+#[cfg(feature = "std")]
+mod smoothing;
+#[cfg(feature = "std")]
+mod time_weighted_return;
+#[cfg(feature = "std")]
+mod volatility_target;
 
+#[cfg(feature = "std")]
+pub mod backtest;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(feature = "std")]
+pub mod cancel;
+#[cfg(feature = "std")]
+pub mod clustering;
+#[cfg(feature = "std")]
+pub mod constraints;
+#[cfg(feature = "std")]
+pub mod date_util;
+#[cfg(feature = "std")]
+pub mod distribution;
 pub mod enums;
+#[cfg(feature = "std")]
+pub mod episodes;
+#[cfg(feature = "std")]
+pub mod ingest;
+#[cfg(all(feature = "std", any(test, feature = "invariants")))]
+pub mod invariants;
+pub mod kernels;
+#[cfg(feature = "std")]
+pub mod methodology;
+#[cfg(feature = "std")]
 pub mod mpt_calculator;
+#[cfg(feature = "std")]
+pub mod performance_fee;
+#[cfg(feature = "std")]
+pub mod persistence;
+#[cfg(feature = "std")]
+pub mod regime;
+#[cfg(feature = "std")]
+pub mod registry;
+#[cfg(feature = "std")]
+pub mod report;
+#[cfg(feature = "std")]
+pub mod risk_budget;
+#[cfg(feature = "std")]
+pub mod scenarios;
+#[cfg(feature = "std")]
+pub mod scoring;
+#[cfg(feature = "std")]
+pub mod seasonality;
+#[cfg(feature = "std")]
+pub mod series;
+#[cfg(feature = "std")]
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod test_vectors;
+#[cfg(feature = "std")]
+pub use self::date_util::{
+    add_business_days, annual_business_days, business_days_between, business_days_in_year,
+    is_business_day, to_business_day, HolidayCalendar, HolidayList,
+};
+#[cfg(feature = "std")]
 pub use self::mpt_calculator::check_and_convert;
+#[cfg(feature = "std")]
 pub use self::mpt_calculator::MPTCalculator;
+
+//the calculator and the result types must stay Send + Sync, so they can be shared by the threads and the async
+//handlers, it is checked at compile time.
+#[cfg(feature = "std")]
+const _: () = {
+    fn assert_send_sync<T: Send + Sync>() {}
+    #[allow(dead_code)]
+    fn assert_thread_safety() {
+        assert_send_sync::<MPTCalculator<'static>>();
+        assert_send_sync::<enums::Errors>();
+        assert_send_sync::<HolidayList>();
+        assert_send_sync::<std::sync::Arc<dyn HolidayCalendar>>();
+        assert_send_sync::<ingest::ReturnChunk>();
+        assert_send_sync::<ingest::RunningStatistics>();
+        assert_send_sync::<ingest::ChunkStatistics>();
+        assert_send_sync::<ingest::CsvChunkReader<&'static [u8]>>();
+        assert_send_sync::<ingest::ColumnMatrix<'static>>();
+        #[cfg(feature = "parquet")]
+        assert_send_sync::<ingest::ParquetChunkReader>();
+        #[cfg(feature = "mmap")]
+        assert_send_sync::<ingest::MappedMatrix>();
+        assert_send_sync::<report::StandardPack>();
+        assert_send_sync::<backtest::BacktestResult>();
+        assert_send_sync::<cache::StatisticsCache>();
+        assert_send_sync::<cancel::CancellationToken>();
+        assert_send_sync::<series::SeriesProfile>();
+        assert_send_sync::<distribution::DistributionFit>();
+        assert_send_sync::<test_vectors::TestVector>();
+    }
+};
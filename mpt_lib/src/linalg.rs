@@ -0,0 +1,214 @@
+use crate::cancel::CancellationToken;
+
+pub(crate) const LINALG_EPSILON: f64 = 1e-12;
+
+///invert a square matrix by gauss-jordan elimination with partial pivoting,
+///return None if the matrix is singular.
+pub(crate) fn invert_matrix(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    if n == 0 || matrix.iter().any(|row| row.len() != n) {
+        return None;
+    }
+    let mut a: Vec<Vec<f64>> = matrix.to_vec();
+    let mut inv: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+
+    for col in 0..n {
+        let mut pivot = col;
+        for row in col + 1..n {
+            if a[row][col].abs() > a[pivot][col].abs() {
+                pivot = row;
+            }
+        }
+        if !a[pivot][col].is_finite() || a[pivot][col].abs() < LINALG_EPSILON {
+            return None;
+        }
+        a.swap(col, pivot);
+        inv.swap(col, pivot);
+
+        let p = a[col][col];
+        for j in 0..n {
+            a[col][j] /= p;
+            inv[col][j] /= p;
+        }
+        for row in 0..n {
+            if row != col {
+                let factor = a[row][col];
+                if factor != 0.0 {
+                    for j in 0..n {
+                        a[row][j] -= factor * a[col][j];
+                        inv[row][j] -= factor * inv[col][j];
+                    }
+                }
+            }
+        }
+    }
+    Some(inv)
+}
+
+///multiply a matrix and a vector.
+pub(crate) fn mat_vec_mul(matrix: &[Vec<f64>], vector: &[f64]) -> Vec<f64> {
+    matrix
+        .iter()
+        .map(|row| row.iter().zip(vector.iter()).map(|x| x.0 * x.1).sum())
+        .collect()
+}
+
+///the sample covariance matrix of the columns, the columns should have the same length and no NAN/INF values.
+pub(crate) fn covariance_matrix(columns: &[&[f64]]) -> Vec<Vec<f64>> {
+    return covariance_matrix_by_column(columns, &CancellationToken::new(), &mut |_, _| {})
+        .unwrap_or_default();
+}
+
+///the sample covariance matrix of the columns, the token is checked and the progress is reported for each column,
+///return None if it is cancelled.
+pub(crate) fn covariance_matrix_by_column(
+    columns: &[&[f64]],
+    cancel: &CancellationToken,
+    progress: &mut dyn FnMut(usize, usize),
+) -> Option<Vec<Vec<f64>>> {
+    let n = columns.len();
+    let count = if n > 0 { columns[0].len() } else { 0 };
+    let means: Vec<f64> = columns
+        .iter()
+        .map(|c| c.iter().sum::<f64>() / count as f64)
+        .collect();
+    let mut cov = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        if cancel.is_cancelled() {
+            return None;
+        }
+        for j in i..n {
+            let mut sum = 0.0;
+            for k in 0..count {
+                sum += (columns[i][k] - means[i]) * (columns[j][k] - means[j]);
+            }
+            cov[i][j] = sum / (count as f64 - 1.0);
+            cov[j][i] = cov[i][j];
+        }
+        progress(i + 1, n);
+    }
+    Some(cov)
+}
+
+///the eigenvalues and eigenvectors of a symmetric matrix by the cyclic jacobi method, the eigenvalues are sorted
+///in descending order, the eigenvectors[i] is the unit eigenvector of eigenvalues[i].
+pub(crate) fn symmetric_eigen(matrix: &[Vec<f64>]) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = matrix.len();
+    let mut a: Vec<Vec<f64>> = matrix.to_vec();
+    let mut v: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+
+    for _ in 0..100 {
+        let mut off_diagonal = 0.0;
+        for i in 0..n {
+            for j in i + 1..n {
+                off_diagonal += a[i][j] * a[i][j];
+            }
+        }
+        if off_diagonal < LINALG_EPSILON * LINALG_EPSILON {
+            break;
+        }
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[p][q].abs() < f64::MIN_POSITIVE {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let akp = a[k][p];
+                    let akq = a[k][q];
+                    a[k][p] = c * akp - s * akq;
+                    a[k][q] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let apk = a[p][k];
+                    let aqk = a[q][k];
+                    a[p][k] = c * apk - s * aqk;
+                    a[q][k] = s * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let vkp = v[k][p];
+                    let vkq = v[k][q];
+                    v[k][p] = c * vkp - s * vkq;
+                    v[k][q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|x, y| a[*y][*y].total_cmp(&a[*x][*x]));
+    let eigenvalues = order.iter().map(|i| a[*i][*i]).collect();
+    let eigenvectors = order
+        .iter()
+        .map(|i| {
+            let mut vector: Vec<f64> = (0..n).map(|k| v[k][*i]).collect();
+            //make the largest component positive, so the sign of the vector is stable
+            let max_pos = (0..n)
+                .max_by(|x, y| vector[*x].abs().total_cmp(&vector[*y].abs()))
+                .unwrap_or(0);
+            if n > 0 && vector[max_pos] < 0.0 {
+                vector.iter_mut().for_each(|x| *x = -*x);
+            }
+            vector
+        })
+        .collect();
+    (eigenvalues, eigenvectors)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{covariance_matrix, invert_matrix, mat_vec_mul, symmetric_eigen};
+    use crate::MPTCalculator;
+
+    #[test]
+    fn should_correct_invert_matrix() {
+        let m = vec![vec![4.0, 7.0], vec![2.0, 6.0]];
+        let inv = invert_matrix(&m).unwrap();
+        assert_eq!(
+            MPTCalculator::is_eq_double_array(&inv[0], &[0.6, -0.7])
+                && MPTCalculator::is_eq_double_array(&inv[1], &[-0.2, 0.4]),
+            true
+        );
+        assert_eq!(
+            MPTCalculator::is_eq_double_array(&mat_vec_mul(&m, &[1.0, 1.0]), &[11.0, 8.0]),
+            true
+        );
+        assert_eq!(
+            invert_matrix(&vec![vec![1.0, 2.0], vec![2.0, 4.0]]).is_none(),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_symmetric_eigen() {
+        let cov = covariance_matrix(&[&[1.0, 2.0, 3.0], &[2.0, 4.0, 6.0]]);
+        assert_eq!(
+            MPTCalculator::is_eq_double_array(&cov[0], &[1.0, 2.0])
+                && MPTCalculator::is_eq_double_array(&cov[1], &[2.0, 4.0]),
+            true
+        );
+        let m = vec![
+            vec![2.0, 1.0, 0.0],
+            vec![1.0, 2.0, 0.0],
+            vec![0.0, 0.0, 5.0],
+        ];
+        let (values, vectors) = symmetric_eigen(&m);
+        assert_eq!(
+            MPTCalculator::is_eq_double_array(&values, &[5.0, 3.0, 1.0]),
+            true
+        );
+        let h = 0.5_f64.sqrt();
+        assert_eq!(
+            MPTCalculator::is_eq_double_array(&vectors[0], &[0.0, 0.0, 1.0])
+                && MPTCalculator::is_eq_double_array(&vectors[1], &[h, h, 0.0]),
+            true
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use crate::enums::{self, ClMethodologyVersion};
+
+///the methodology version of the current formulas, a new version is added when a formula variant changes, and the
+///old versions keep their variants so the historical reports can be reproduced.
+pub const CURRENT_METHODOLOGY: ClMethodologyVersion = ClMethodologyVersion::ClMethodologyV1;
+
+///the frozen formula variants of a methodology version.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Methodology {
+    pub version: ClMethodologyVersion,
+    ///the method of the upside and the downside capture ratios.
+    pub capture_method: enums::ClCaptureMethod,
+    ///the annualized return is geometric, otherwise it is arithmetic.
+    pub geometric_annualization: bool,
+    ///the sample or the population standard deviation.
+    pub variance_mode: enums::ClVarianceMode,
+    ///the constant subtracted from the average draw down of the sterling ratio, in percent.
+    pub sterling_constant: f64,
+}
+
+impl Methodology {
+    ///the formula variants of the version.
+    ///# Examples
+    ///```
+    ///use mpt_lib::enums;
+    ///use mpt_lib::methodology::{Methodology, CURRENT_METHODOLOGY};
+    ///
+    ///let methodology = Methodology::from_version(enums::ClMethodologyVersion::ClMethodologyV1);
+    ///assert_eq!(
+    ///    methodology.capture_method == enums::ClCaptureMethod::ClCaptureGeometricAverage
+    ///        && methodology.sterling_constant == 10.0
+    ///        && Methodology::current().version == CURRENT_METHODOLOGY,
+    ///    true
+    ///);
+    ///```
+    pub fn from_version(version: ClMethodologyVersion) -> Methodology {
+        match version {
+            ClMethodologyVersion::ClMethodologyV1 => Methodology {
+                version,
+                capture_method: enums::ClCaptureMethod::ClCaptureGeometricAverage,
+                geometric_annualization: true,
+                variance_mode: enums::ClVarianceMode::ClVarianceSample,
+                sterling_constant: 10.0,
+            },
+        }
+    }
+
+    ///the formula variants of CURRENT_METHODOLOGY.
+    pub fn current() -> Methodology {
+        return Methodology::from_version(CURRENT_METHODOLOGY);
+    }
+}
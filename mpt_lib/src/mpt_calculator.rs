@@ -7,6 +7,8 @@ pub struct MPTCalculator<'a> {
     pub riskfree: &'a [f64],
 }
 
+//the entry of the c library, the pointers are checked by the caller and the null checks below.
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub fn check_and_convert<'a>(
     values: *const f64,
     bmk_values: *const f64,
@@ -0,0 +1,2106 @@
+use crate::{
+    cancel::CancellationToken,
+    constraints::{GroupConstraint, OptimizerConstraints},
+    enums::{self, Errors},
+    linalg::{covariance_matrix, mat_vec_mul, LINALG_EPSILON},
+    MPTCalculator,
+};
+
+const SIMPLEX_EPSILON: f64 = 1e-9;
+///the max number of the rounds of the alternating projections onto the constraints.
+const PROJECTION_ITERATIONS: usize = 1000;
+///the tolerance of the group and turnover constraints of the quadratic programming weights.
+const CONSTRAINT_TOLERANCE: f64 = 1e-6;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) enum ConstraintType {
+    LessEqual,
+    GreaterEqual,
+    Equal,
+}
+
+///one linear constraint of the linear programming, coefficients * x (<=, >=, =) rhs.
+pub(crate) struct LinearConstraint {
+    pub coefficients: Vec<f64>,
+    pub constraint_type: ConstraintType,
+    pub rhs: f64,
+}
+
+fn pivot(tableau: &mut [Vec<f64>], basis: &mut [usize], row: usize, col: usize) {
+    let p = tableau[row][col];
+    tableau[row].iter_mut().for_each(|x| *x /= p);
+    for i in 0..tableau.len() {
+        if i != row {
+            let factor = tableau[i][col];
+            if factor != 0.0 {
+                for j in 0..tableau[i].len() {
+                    tableau[i][j] -= factor * tableau[row][j];
+                }
+            }
+        }
+    }
+    basis[row] = col;
+}
+
+///minimize the cost over the allowed columns by the simplex method with bland's rule,
+///return false if the problem is unbounded or the token is cancelled.
+fn simplex_iterate(
+    tableau: &mut [Vec<f64>],
+    basis: &mut [usize],
+    cost: &[f64],
+    allowed: &[bool],
+    cancel: &CancellationToken,
+) -> bool {
+    let rhs = cost.len();
+    loop {
+        if cancel.is_cancelled() {
+            return false;
+        }
+        let entering = (0..rhs).find(|j| {
+            allowed[*j]
+                && cost[*j]
+                    - (0..tableau.len())
+                        .map(|i| cost[basis[i]] * tableau[i][*j])
+                        .sum::<f64>()
+                    < -SIMPLEX_EPSILON
+        });
+        let col = match entering {
+            Some(col) => col,
+            None => return true,
+        };
+        let mut leaving: Option<usize> = None;
+        for i in 0..tableau.len() {
+            if tableau[i][col] > SIMPLEX_EPSILON {
+                leaving = match leaving {
+                    None => Some(i),
+                    Some(l) => {
+                        let r = tableau[i][rhs] / tableau[i][col];
+                        let rl = tableau[l][rhs] / tableau[l][col];
+                        if r < rl - SIMPLEX_EPSILON
+                            || (r < rl + SIMPLEX_EPSILON && basis[i] < basis[l])
+                        {
+                            Some(i)
+                        } else {
+                            Some(l)
+                        }
+                    }
+                };
+            }
+        }
+        match leaving {
+            Some(row) => pivot(tableau, basis, row, col),
+            None => return false,
+        }
+    }
+}
+
+///minimize objective * x subject to the constraints and x >= 0 by the two phase simplex method,
+///return None if the problem is infeasible or unbounded, or the token is cancelled.
+pub(crate) fn linear_programming(
+    objective: &[f64],
+    constraints: &[LinearConstraint],
+    cancel: &CancellationToken,
+) -> Option<Vec<f64>> {
+    let n = objective.len();
+    let m = constraints.len();
+    if constraints.iter().any(|c| c.coefficients.len() != n) {
+        return None;
+    }
+    let slack_count = constraints
+        .iter()
+        .filter(|c| c.constraint_type != ConstraintType::Equal)
+        .count();
+    let artificial_begin = n + slack_count;
+    let cols = artificial_begin + m;
+
+    let mut tableau = vec![vec![0.0; cols + 1]; m];
+    let mut basis = vec![0; m];
+    let mut slack = n;
+    for (i, c) in constraints.iter().enumerate() {
+        //keep the right hand side non negative
+        let sign = if c.rhs < 0.0 { -1.0 } else { 1.0 };
+        for j in 0..n {
+            tableau[i][j] = sign * c.coefficients[j];
+        }
+        tableau[i][cols] = sign * c.rhs;
+        match c.constraint_type {
+            ConstraintType::LessEqual => {
+                tableau[i][slack] = sign;
+                slack += 1;
+            }
+            ConstraintType::GreaterEqual => {
+                tableau[i][slack] = -sign;
+                slack += 1;
+            }
+            ConstraintType::Equal => {}
+        }
+        tableau[i][artificial_begin + i] = 1.0;
+        basis[i] = artificial_begin + i;
+    }
+
+    //phase one, minimize the sum of the artificial variables
+    let mut cost = vec![0.0; cols];
+    cost[artificial_begin..].iter_mut().for_each(|x| *x = 1.0);
+    let allowed = vec![true; cols];
+    simplex_iterate(&mut tableau, &mut basis, &cost, &allowed, cancel);
+    if cancel.is_cancelled() {
+        return None;
+    }
+    let infeasibility: f64 = (0..m)
+        .filter(|i| basis[*i] >= artificial_begin)
+        .map(|i| tableau[i][cols])
+        .sum();
+    if infeasibility > SIMPLEX_EPSILON.sqrt() {
+        return None;
+    }
+    for i in 0..m {
+        if basis[i] >= artificial_begin {
+            if let Some(col) = (0..artificial_begin).find(|j| tableau[i][*j].abs() > LINALG_EPSILON)
+            {
+                pivot(&mut tableau, &mut basis, i, col);
+            }
+        }
+    }
+
+    //phase two, minimize the objective without the artificial variables
+    let mut cost = vec![0.0; cols];
+    cost[..n].copy_from_slice(objective);
+    let allowed: Vec<bool> = (0..cols).map(|j| j < artificial_begin).collect();
+    if !simplex_iterate(&mut tableau, &mut basis, &cost, &allowed, cancel) {
+        return None;
+    }
+    let mut x = vec![0.0; n];
+    for i in 0..m {
+        if basis[i] < n {
+            x[basis[i]] = tableau[i][cols];
+        }
+    }
+    return Some(x);
+}
+
+///minimize the function by the nelder mead simplex method from the start point, the initial simplex moves each
+///coordinate of the start point by the step. stop when the simplex and its function values are converged or after
+///max_iterations, return the best point and its function value.
+pub(crate) fn nelder_mead<F: Fn(&[f64]) -> f64>(
+    f: F,
+    start: &[f64],
+    step: f64,
+    max_iterations: usize,
+) -> (Vec<f64>, f64) {
+    let n = start.len();
+    let mut points: Vec<Vec<f64>> = vec![start.to_vec()];
+    for i in 0..n {
+        let mut point = start.to_vec();
+        point[i] += step;
+        points.push(point);
+    }
+    let mut values: Vec<f64> = points.iter().map(|x| f(x)).collect();
+    let along = |from: &[f64], to: &[f64], t: f64| -> Vec<f64> {
+        from.iter()
+            .zip(to.iter())
+            .map(|x| x.0 + t * (x.1 - x.0))
+            .collect()
+    };
+
+    for _ in 0..max_iterations {
+        let mut order: Vec<usize> = (0..=n).collect();
+        order.sort_by(|a, b| values[*a].total_cmp(&values[*b]));
+        points = order.iter().map(|i| points[*i].clone()).collect();
+        values = order.iter().map(|i| values[*i]).collect();
+        let size = (1..=n)
+            .flat_map(|i| (0..n).map(move |j| (i, j)))
+            .map(|x| (points[x.0][x.1] - points[0][x.1]).abs())
+            .fold(0.0, f64::max);
+        if (values[n] - values[0]).abs() <= 1e-13 * values[0].abs() && size < 1e-9 {
+            break;
+        }
+
+        let centroid: Vec<f64> = (0..n)
+            .map(|j| points[..n].iter().map(|x| x[j]).sum::<f64>() / n as f64)
+            .collect();
+        let reflected = along(&centroid, &points[n], -1.0);
+        let reflected_value = f(&reflected);
+        if values[0] <= reflected_value && reflected_value < values[n - 1] {
+            points[n] = reflected;
+            values[n] = reflected_value;
+        } else if reflected_value < values[0] {
+            let expanded = along(&centroid, &reflected, 2.0);
+            let expanded_value = f(&expanded);
+            if expanded_value < reflected_value {
+                points[n] = expanded;
+                values[n] = expanded_value;
+            } else {
+                points[n] = reflected;
+                values[n] = reflected_value;
+            }
+        } else {
+            let (contracted, accept) = if reflected_value < values[n] {
+                let contracted = along(&centroid, &reflected, 0.5);
+                let contracted_value = f(&contracted);
+                (
+                    (contracted, contracted_value),
+                    contracted_value <= reflected_value,
+                )
+            } else {
+                let contracted = along(&centroid, &points[n], 0.5);
+                let contracted_value = f(&contracted);
+                ((contracted, contracted_value), contracted_value < values[n])
+            };
+            if accept {
+                points[n] = contracted.0;
+                values[n] = contracted.1;
+            } else {
+                for i in 1..=n {
+                    points[i] = along(&points[0], &points[i], 0.5);
+                    values[i] = f(&points[i]);
+                }
+            }
+        }
+    }
+
+    let best = (0..=n)
+        .min_by(|a, b| values[*a].total_cmp(&values[*b]))
+        .unwrap_or(0);
+    return (points[best].clone(), values[best]);
+}
+
+///the euclidean projection of the point onto the long only and fully invested weights.
+fn project_to_simplex(point: &[f64]) -> Vec<f64> {
+    let mut sorted = point.to_vec();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    let mut sum = 0.0;
+    let mut theta = 0.0;
+    for (i, v) in sorted.iter().enumerate() {
+        sum += v;
+        let t = (sum - 1.0) / (i + 1) as f64;
+        if v - t > 0.0 {
+            theta = t;
+        }
+    }
+    return point.iter().map(|x| (x - theta).max(0.0)).collect();
+}
+
+///the euclidean projection of the point onto the weights whose l1 distance to the center is not larger than radius.
+fn project_to_l1_ball(point: &[f64], center: &[f64], radius: f64) -> Vec<f64> {
+    let distance: Vec<f64> = point.iter().zip(center.iter()).map(|x| x.0 - x.1).collect();
+    if distance.iter().map(|x| x.abs()).sum::<f64>() <= radius {
+        return point.to_vec();
+    }
+    let mut sorted: Vec<f64> = distance.iter().map(|x| x.abs()).collect();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    let mut sum = 0.0;
+    let mut theta = 0.0;
+    for (i, v) in sorted.iter().enumerate() {
+        sum += v;
+        let t = (sum - radius) / (i + 1) as f64;
+        if v - t > 0.0 {
+            theta = t;
+        }
+    }
+    return distance
+        .iter()
+        .zip(center.iter())
+        .map(|(d, c)| c + d.signum() * (d.abs() - theta).max(0.0))
+        .collect();
+}
+
+///the euclidean projection of the point onto the weights whose total weight of the group is from min to max.
+fn project_to_group(point: &[f64], group: &GroupConstraint) -> Vec<f64> {
+    let total: f64 = group.assets.iter().map(|i| point[*i]).sum();
+    let shift = (total.clamp(group.min, group.max) - total) / group.assets.len() as f64;
+    let mut projected = point.to_vec();
+    group.assets.iter().for_each(|i| projected[*i] += shift);
+    return projected;
+}
+
+///the projection of the point onto the long only and fully invested weights which satisfy the group and turnover
+///constraints and are zero for the excluded assets, by the dykstra alternating projections onto each constraint.
+///the simplex is projected last, so the weights are always long only and fully invested.
+fn project_to_constraints(
+    point: &[f64],
+    limits: &OptimizerConstraints,
+    excluded: &[bool],
+) -> Vec<f64> {
+    let n = point.len();
+    let included: Vec<usize> = (0..n).filter(|j| !excluded[*j]).collect();
+    let project_included = |x: &[f64]| -> Vec<f64> {
+        let projected = project_to_simplex(&included.iter().map(|j| x[*j]).collect::<Vec<f64>>());
+        let mut weights = vec![0.0; n];
+        included
+            .iter()
+            .zip(projected.iter())
+            .for_each(|x| weights[*x.0] = *x.1);
+        weights
+    };
+    if limits.groups.len() == 0 && limits.max_turnover.is_infinite() {
+        return project_included(point);
+    }
+
+    //the groups, the turnover and the simplex, each has the increment of the dykstra projections
+    let set_count = limits.groups.len() + 2;
+    let mut increments = vec![vec![0.0; n]; set_count];
+    let mut weights = point.to_vec();
+    for _ in 0..PROJECTION_ITERATIONS {
+        //the weights may stay the same for several rounds while the increments change
+        let mut change: f64 = 0.0;
+        for (k, increment) in increments.iter_mut().enumerate() {
+            let shifted: Vec<f64> = weights
+                .iter()
+                .zip(increment.iter())
+                .map(|x| x.0 + x.1)
+                .collect();
+            let projected = if k < limits.groups.len() {
+                project_to_group(&shifted, &limits.groups[k])
+            } else if k == limits.groups.len() && limits.max_turnover.is_finite() {
+                project_to_l1_ball(&shifted, &limits.current_weights, 2.0 * limits.max_turnover)
+            } else if k == limits.groups.len() {
+                shifted.clone()
+            } else {
+                project_included(&shifted)
+            };
+            for i in 0..n {
+                let next = shifted[i] - projected[i];
+                change = change.max((next - increment[i]).abs());
+                increment[i] = next;
+            }
+            weights = projected;
+        }
+        if change < 1e-15 {
+            break;
+        }
+    }
+    return weights;
+}
+
+///minimize 0.5 * w' * quadratic * w - linear * w over the long only and fully invested weights by the accelerated
+///projected gradient method, the quadratic matrix should be positive semi definite. the weights are projected onto
+///the group and turnover constraints of limits, and the weights of the excluded assets are zero. the token is
+///checked for each iteration, return None if it is cancelled.
+pub(crate) fn simplex_quadratic_programming(
+    quadratic: &[Vec<f64>],
+    linear: &[f64],
+    limits: &OptimizerConstraints,
+    excluded: &[bool],
+    cancel: &CancellationToken,
+) -> Option<Vec<f64>> {
+    let n = linear.len();
+    //the gershgorin bound of the largest eigenvalue is the lipschitz constant of the gradient
+    let lipschitz = quadratic
+        .iter()
+        .map(|row| row.iter().map(|x| x.abs()).sum::<f64>())
+        .fold(0.0, f64::max);
+    let lipschitz = if lipschitz > LINALG_EPSILON {
+        lipschitz
+    } else {
+        1.0
+    };
+    //the projection onto the group and turnover constraints is iterative, the step is limited by the largest linear
+    //coefficient too, so the point stays near the weights and the projection keeps its precision.
+    let lipschitz = if limits.groups.len() > 0 || limits.max_turnover.is_finite() {
+        lipschitz + linear.iter().map(|x| x.abs()).fold(0.0, f64::max)
+    } else {
+        lipschitz
+    };
+    let mut weights = vec![1.0 / n as f64; n];
+    let mut momentum = weights.clone();
+    let mut t: f64 = 1.0;
+    for _ in 0..100000 {
+        if cancel.is_cancelled() {
+            return None;
+        }
+        let gradient: Vec<f64> = (0..n)
+            .map(|i| (0..n).map(|j| quadratic[i][j] * momentum[j]).sum::<f64>() - linear[i])
+            .collect();
+        let step: Vec<f64> = (0..n)
+            .map(|i| momentum[i] - gradient[i] / lipschitz)
+            .collect();
+        let next = project_to_constraints(&step, limits, excluded);
+        let t_next = (1.0 + (1.0 + 4.0 * t * t).sqrt()) / 2.0;
+        let change = (0..n)
+            .map(|i| (next[i] - weights[i]).abs())
+            .fold(0.0, f64::max);
+        momentum = (0..n)
+            .map(|i| next[i] + (t - 1.0) / t_next * (next[i] - weights[i]))
+            .collect();
+        weights = next;
+        t = t_next;
+        if change < 1e-14 {
+            break;
+        }
+    }
+    return Some(weights);
+}
+
+///minimize the quadratic function as simplex_quadratic_programming with the group, turnover and cardinality
+///constraints of limits. while the number of the assets with positive weights is larger than max_assets, the asset
+///with the smallest weight is excluded and the weights are optimized again. return None if it is cancelled or the
+///constraints can not be satisfied.
+fn constrained_quadratic_programming(
+    quadratic: &[Vec<f64>],
+    linear: &[f64],
+    limits: &OptimizerConstraints,
+    cancel: &CancellationToken,
+) -> Option<Vec<f64>> {
+    let n = linear.len();
+    let mut excluded = vec![false; n];
+    loop {
+        //the constraints can not be satisfied if the projection of the equal weights does not satisfy them
+        let feasible = project_to_constraints(&vec![1.0 / n as f64; n], limits, &excluded);
+        if !limits.is_satisfied(&feasible, CONSTRAINT_TOLERANCE) {
+            return None;
+        }
+        let weights = simplex_quadratic_programming(quadratic, linear, limits, &excluded, cancel)?;
+        let held: Vec<usize> = (0..n)
+            .filter(|j| weights[*j] > SIMPLEX_EPSILON.sqrt())
+            .collect();
+        if limits.max_assets > 0 && held.len() > limits.max_assets {
+            if let Some(smallest) = held
+                .iter()
+                .min_by(|a, b| weights[**a].total_cmp(&weights[**b]))
+            {
+                excluded[*smallest] = true;
+                continue;
+            }
+        }
+        return Some(weights);
+    }
+}
+
+///the average return and the sample covariance matrix of the assets, the periods which have NAN/INF values in any
+///series are skipped. return None if there are less than 2 periods.
+fn mean_covariance(returns_matrix: &[&[f64]]) -> Option<(Vec<f64>, Vec<Vec<f64>>)> {
+    let periods: Vec<usize> = (0..returns_matrix[0].len())
+        .filter(|i| returns_matrix.iter().all(|x| x[*i].is_finite()))
+        .collect();
+    if periods.len() < 2 {
+        return None;
+    }
+    let columns: Vec<Vec<f64>> = returns_matrix
+        .iter()
+        .map(|x| periods.iter().map(|i| x[*i]).collect())
+        .collect();
+    let column_refs: Vec<&[f64]> = columns.iter().map(|x| x.as_slice()).collect();
+    let means = columns
+        .iter()
+        .map(|x| x.iter().sum::<f64>() / x.len() as f64)
+        .collect();
+    return Some((means, covariance_matrix(&column_refs)));
+}
+
+impl<'a> MPTCalculator<'a> {
+    fn cvar_optimize(
+        returns_matrix: &[&[f64]],
+        confidence: f64,
+        target_return: Option<f64>,
+        limits: &OptimizerConstraints,
+        cancel: &CancellationToken,
+        weights: &mut Vec<f64>,
+        cvar: &mut f64,
+    ) -> Errors {
+        instrument!(
+            "cvar_optimize",
+            rows = returns_matrix.first().map_or(0, |x| x.len()),
+            cols = returns_matrix.len()
+        );
+        if returns_matrix.len() == 0
+            || !(confidence > 0.0 && confidence < 1.0)
+            || target_return.map_or(false, |x| !x.is_finite())
+            || !limits.is_valid(returns_matrix.len())
+            || returns_matrix
+                .iter()
+                .any(|x| x.len() != returns_matrix[0].len())
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *cvar = f64::NAN;
+        weights.clear();
+
+        let scenarios: Vec<usize> = (0..returns_matrix[0].len())
+            .filter(|i| returns_matrix.iter().all(|x| x[*i].is_finite()))
+            .collect();
+        if scenarios.len() < 2 {
+            return Errors::ClErrorCodeInputLenTooShort;
+        }
+
+        //rockafellar-uryasev formulation, the variables are the weights, the var split into the positive
+        //and negative parts, the excess loss of each scenario over the var, and the absolute weight change
+        //of each asset if the turnover is limited.
+        let n = returns_matrix.len();
+        let s = scenarios.len();
+        let var_pos = n;
+        let var_neg = n + 1;
+        let excess_begin = n + 2;
+        let change_begin = excess_begin + s;
+        let count = change_begin
+            + if limits.max_turnover.is_finite() {
+                n
+            } else {
+                0
+            };
+
+        let mut objective = vec![0.0; count];
+        objective[var_pos] = 1.0;
+        objective[var_neg] = -1.0;
+        let excess_weight = 1.0 / ((1.0 - confidence) * s as f64);
+        objective[excess_begin..change_begin]
+            .iter_mut()
+            .for_each(|x| *x = excess_weight);
+
+        let mut constraints = Vec::with_capacity(s + 2);
+        for (k, i) in scenarios.iter().enumerate() {
+            //the excess loss is larger than the loss of the scenario minus the var
+            let mut coefficients = vec![0.0; count];
+            for j in 0..n {
+                coefficients[j] = -returns_matrix[j][*i];
+            }
+            coefficients[var_pos] = -1.0;
+            coefficients[var_neg] = 1.0;
+            coefficients[excess_begin + k] = -1.0;
+            constraints.push(LinearConstraint {
+                coefficients,
+                constraint_type: ConstraintType::LessEqual,
+                rhs: 0.0,
+            });
+        }
+        let mut coefficients = vec![0.0; count];
+        coefficients[..n].iter_mut().for_each(|x| *x = 1.0);
+        constraints.push(LinearConstraint {
+            coefficients,
+            constraint_type: ConstraintType::Equal,
+            rhs: 1.0,
+        });
+        if let Some(target) = target_return {
+            let mut coefficients = vec![0.0; count];
+            for j in 0..n {
+                coefficients[j] =
+                    scenarios.iter().map(|i| returns_matrix[j][*i]).sum::<f64>() / s as f64;
+            }
+            constraints.push(LinearConstraint {
+                coefficients,
+                constraint_type: ConstraintType::GreaterEqual,
+                rhs: target,
+            });
+        }
+        for group in limits.groups.iter() {
+            let mut coefficients = vec![0.0; count];
+            group.assets.iter().for_each(|j| coefficients[*j] = 1.0);
+            constraints.push(LinearConstraint {
+                coefficients: coefficients.clone(),
+                constraint_type: ConstraintType::GreaterEqual,
+                rhs: group.min,
+            });
+            constraints.push(LinearConstraint {
+                coefficients,
+                constraint_type: ConstraintType::LessEqual,
+                rhs: group.max,
+            });
+        }
+        if limits.max_turnover.is_finite() {
+            for j in 0..n {
+                //the change is larger than the weight minus the current weight and its opposite
+                for sign in [1.0, -1.0] {
+                    let mut coefficients = vec![0.0; count];
+                    coefficients[j] = sign;
+                    coefficients[change_begin + j] = -1.0;
+                    constraints.push(LinearConstraint {
+                        coefficients,
+                        constraint_type: ConstraintType::LessEqual,
+                        rhs: sign * limits.current_weights[j],
+                    });
+                }
+            }
+            let mut coefficients = vec![0.0; count];
+            coefficients[change_begin..]
+                .iter_mut()
+                .for_each(|x| *x = 1.0);
+            constraints.push(LinearConstraint {
+                coefficients,
+                constraint_type: ConstraintType::LessEqual,
+                rhs: 2.0 * limits.max_turnover,
+            });
+        }
+
+        loop {
+            let x = match linear_programming(&objective, &constraints, cancel) {
+                Some(x) => x,
+                None if cancel.is_cancelled() => return Errors::ClErrorCodeCancelled,
+                None => return Errors::ClErrorCodeCcFaild,
+            };
+            let held: Vec<usize> = (0..n).filter(|j| x[*j] > SIMPLEX_EPSILON.sqrt()).collect();
+            if limits.max_assets > 0 && held.len() > limits.max_assets {
+                //exclude the asset with the smallest weight and optimize again
+                if let Some(smallest) = held.iter().min_by(|a, b| x[**a].total_cmp(&x[**b])) {
+                    let mut coefficients = vec![0.0; count];
+                    coefficients[*smallest] = 1.0;
+                    constraints.push(LinearConstraint {
+                        coefficients,
+                        constraint_type: ConstraintType::LessEqual,
+                        rhs: 0.0,
+                    });
+                    continue;
+                }
+            }
+            *cvar = objective.iter().zip(x.iter()).map(|x| x.0 * x.1).sum();
+            weights.extend_from_slice(&x[..n]);
+            return Errors::ClErrorCodeNoError;
+        }
+    }
+
+    ///calculate the long only weights which minimize the conditional value at risk of the portfolio by the
+    ///rockafellar-uryasev linear programming on the historical scenarios, the periods which have NAN/INF values
+    ///in any series are skipped.
+    ///# Arguments
+    ///returns_matrix: the return array of each asset, should have the same length, each period is a scenario.
+    ///
+    ///confidence: the confidence level of the cvar, e.g. 0.95.
+    ///
+    ///weights: the optimal weight of each asset, the sum is 1.
+    ///
+    ///cvar: the conditional value at risk of the optimal portfolio, the average loss beyond the var, positive means loss.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let x = vec![2.0, -4.0, 1.0, 3.0, -1.0];
+    ///let y = vec![1.0, 2.0, -3.0, 0.5, 1.5];
+    ///let mut weights = Vec::new();
+    ///let mut cvar = 0.0;
+    ///let err = MPTCalculator::min_cvar_weights(&[&x, &y], 0.8, &mut weights, &mut cvar);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(&weights, &[0.5, 0.5])
+    ///        && MPTCalculator::is_eq_double(cvar, 1.0),
+    ///    true
+    ///);
+    ///```
+    pub fn min_cvar_weights(
+        returns_matrix: &[&[f64]],
+        confidence: f64,
+        weights: &mut Vec<f64>,
+        cvar: &mut f64,
+    ) -> Errors {
+        return MPTCalculator::cvar_optimize(
+            returns_matrix,
+            confidence,
+            None,
+            &OptimizerConstraints::new(),
+            &CancellationToken::new(),
+            weights,
+            cvar,
+        );
+    }
+
+    ///calculate the min cvar weights as min_cvar_weights, the simplex checks the token for each pivot and returns
+    ///ClErrorCodeCancelled after it is cancelled.
+    pub fn min_cvar_weights_with_cancel(
+        returns_matrix: &[&[f64]],
+        confidence: f64,
+        cancel: &CancellationToken,
+        weights: &mut Vec<f64>,
+        cvar: &mut f64,
+    ) -> Errors {
+        return MPTCalculator::cvar_optimize(
+            returns_matrix,
+            confidence,
+            None,
+            &OptimizerConstraints::new(),
+            cancel,
+            weights,
+            cvar,
+        );
+    }
+
+    ///calculate the long only weights which minimize the conditional value at risk of the portfolio with the
+    ///average return not less than the target return, the periods which have NAN/INF values in any series are skipped.
+    ///return ClErrorCodeCcFaild if the target return can not be reached.
+    ///# Arguments
+    ///returns_matrix: the return array of each asset, should have the same length, each period is a scenario.
+    ///
+    ///confidence: the confidence level of the cvar, e.g. 0.95.
+    ///
+    ///target_return: the minimum average return of one period of the portfolio.
+    ///
+    ///weights: the optimal weight of each asset, the sum is 1.
+    ///
+    ///cvar: the conditional value at risk of the optimal portfolio, the average loss beyond the var, positive means loss.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let x = vec![2.0, -4.0, 1.0, 3.0, -1.0];
+    ///let y = vec![1.0, 2.0, -3.0, 0.5, 1.5];
+    ///let mut weights = Vec::new();
+    ///let mut cvar = 0.0;
+    ///let err = MPTCalculator::mean_cvar_weights(&[&x, &y], 0.8, 0.35, &mut weights, &mut cvar);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(&weights, &[0.25, 0.75])
+    ///        && MPTCalculator::is_eq_double(cvar, 2.0),
+    ///    true
+    ///);
+    ///```
+    pub fn mean_cvar_weights(
+        returns_matrix: &[&[f64]],
+        confidence: f64,
+        target_return: f64,
+        weights: &mut Vec<f64>,
+        cvar: &mut f64,
+    ) -> Errors {
+        return MPTCalculator::cvar_optimize(
+            returns_matrix,
+            confidence,
+            Some(target_return),
+            &OptimizerConstraints::new(),
+            &CancellationToken::new(),
+            weights,
+            cvar,
+        );
+    }
+
+    ///calculate the mean cvar weights as mean_cvar_weights, the simplex checks the token for each pivot and returns
+    ///ClErrorCodeCancelled after it is cancelled.
+    pub fn mean_cvar_weights_with_cancel(
+        returns_matrix: &[&[f64]],
+        confidence: f64,
+        target_return: f64,
+        cancel: &CancellationToken,
+        weights: &mut Vec<f64>,
+        cvar: &mut f64,
+    ) -> Errors {
+        return MPTCalculator::cvar_optimize(
+            returns_matrix,
+            confidence,
+            Some(target_return),
+            &OptimizerConstraints::new(),
+            cancel,
+            weights,
+            cvar,
+        );
+    }
+
+    ///calculate the min cvar weights as min_cvar_weights with the group, turnover and cardinality constraints.
+    ///return ClErrorCodeCcFaild if the constraints can not be satisfied, the simplex checks the token for each pivot
+    ///and returns ClErrorCodeCancelled after it is cancelled.
+    ///# Arguments
+    ///returns_matrix: the return array of each asset, should have the same length, each period is a scenario.
+    ///
+    ///confidence: the confidence level of the cvar, e.g. 0.95.
+    ///
+    ///limits: the constraints of the weights.
+    ///
+    ///cancel: the cancellation token of the optimization.
+    ///
+    ///weights: the optimal weight of each asset, the sum is 1.
+    ///
+    ///cvar: the conditional value at risk of the optimal portfolio, the average loss beyond the var, positive means loss.
+    ///# Examples
+    ///```
+    ///use mpt_lib::cancel::CancellationToken;
+    ///use mpt_lib::constraints::{GroupConstraint, OptimizerConstraints};
+    ///use mpt_lib::enums::Errors;
+    ///use mpt_lib::MPTCalculator;
+    ///
+    ///let x = vec![2.0, -4.0, 1.0, 3.0, -1.0];
+    ///let y = vec![1.0, 2.0, -3.0, 0.5, 1.5];
+    ///let mut limits = OptimizerConstraints::new();
+    ///limits.groups.push(GroupConstraint {
+    ///    assets: vec![0],
+    ///    min: 0.0,
+    ///    max: 0.25,
+    ///});
+    ///let mut weights = Vec::new();
+    ///let mut cvar = 0.0;
+    ///let err = MPTCalculator::min_cvar_weights_with_constraints(
+    ///    &[&x, &y],
+    ///    0.8,
+    ///    &limits,
+    ///    &CancellationToken::new(),
+    ///    &mut weights,
+    ///    &mut cvar,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(&weights, &[0.25, 0.75]),
+    ///    true
+    ///);
+    ///```
+    pub fn min_cvar_weights_with_constraints(
+        returns_matrix: &[&[f64]],
+        confidence: f64,
+        limits: &OptimizerConstraints,
+        cancel: &CancellationToken,
+        weights: &mut Vec<f64>,
+        cvar: &mut f64,
+    ) -> Errors {
+        return MPTCalculator::cvar_optimize(
+            returns_matrix,
+            confidence,
+            None,
+            limits,
+            cancel,
+            weights,
+            cvar,
+        );
+    }
+
+    ///calculate the mean cvar weights as mean_cvar_weights with the group, turnover and cardinality constraints.
+    ///return ClErrorCodeCcFaild if the target return and the constraints can not be satisfied, the simplex checks the
+    ///token for each pivot and returns ClErrorCodeCancelled after it is cancelled.
+    pub fn mean_cvar_weights_with_constraints(
+        returns_matrix: &[&[f64]],
+        confidence: f64,
+        target_return: f64,
+        limits: &OptimizerConstraints,
+        cancel: &CancellationToken,
+        weights: &mut Vec<f64>,
+        cvar: &mut f64,
+    ) -> Errors {
+        return MPTCalculator::cvar_optimize(
+            returns_matrix,
+            confidence,
+            Some(target_return),
+            limits,
+            cancel,
+            weights,
+            cvar,
+        );
+    }
+
+    ///calculate the long only weights which maximize the quadratic utility E(r) - 0.5 * risk_aversion * var(r) of the
+    ///mean variance investor, the utility is of the decimal returns, the periods which have NAN/INF values in any
+    ///series are skipped.
+    ///# Arguments
+    ///returns_matrix: the return array of each asset, should have the same length.
+    ///
+    ///risk_aversion: the risk aversion coefficient of the investor, e.g. 2 to 4, it should be positive.
+    ///
+    ///weights: the optimal weight of each asset, the sum is 1.
+    ///
+    ///certainty_equivalent: the riskless return of one period with the same utility as the optimal portfolio,
+    ///in percent.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let x = vec![2.0, -4.0, 1.0, 3.0, -1.0];
+    ///let y = vec![1.0, 2.0, -3.0, 0.5, 1.5];
+    ///let mut weights = Vec::new();
+    ///let mut certainty_equivalent = 0.0;
+    ///let err = MPTCalculator::optimal_weights_for_risk_aversion(
+    ///    &[&x, &y],
+    ///    4.0,
+    ///    &mut weights,
+    ///    &mut certainty_equivalent,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(&weights, &[0.078101, 0.921899])
+    ///        && MPTCalculator::is_eq_double(certainty_equivalent, 0.323492),
+    ///    true
+    ///);
+    ///```
+    pub fn optimal_weights_for_risk_aversion(
+        returns_matrix: &[&[f64]],
+        risk_aversion: f64,
+        weights: &mut Vec<f64>,
+        certainty_equivalent: &mut f64,
+    ) -> Errors {
+        return MPTCalculator::optimal_weights_for_risk_aversion_with_cancel(
+            returns_matrix,
+            risk_aversion,
+            &CancellationToken::new(),
+            weights,
+            certainty_equivalent,
+        );
+    }
+
+    ///calculate the optimal weights as optimal_weights_for_risk_aversion, the solver checks the token for each
+    ///iteration and returns ClErrorCodeCancelled after it is cancelled.
+    pub fn optimal_weights_for_risk_aversion_with_cancel(
+        returns_matrix: &[&[f64]],
+        risk_aversion: f64,
+        cancel: &CancellationToken,
+        weights: &mut Vec<f64>,
+        certainty_equivalent: &mut f64,
+    ) -> Errors {
+        return MPTCalculator::optimal_weights_for_risk_aversion_with_constraints(
+            returns_matrix,
+            risk_aversion,
+            &OptimizerConstraints::new(),
+            cancel,
+            weights,
+            certainty_equivalent,
+        );
+    }
+
+    ///calculate the optimal weights as optimal_weights_for_risk_aversion with the group, turnover and cardinality
+    ///constraints. return ClErrorCodeCcFaild if the constraints can not be satisfied, the solver checks the token for
+    ///each iteration and returns ClErrorCodeCancelled after it is cancelled.
+    ///# Arguments
+    ///returns_matrix: the return array of each asset, should have the same length.
+    ///
+    ///risk_aversion: the risk aversion coefficient of the investor, e.g. 2 to 4, it should be positive.
+    ///
+    ///limits: the constraints of the weights.
+    ///
+    ///cancel: the cancellation token of the optimization.
+    ///
+    ///weights: the optimal weight of each asset, the sum is 1.
+    ///
+    ///certainty_equivalent: the riskless return of one period with the same utility as the optimal portfolio,
+    ///in percent.
+    ///# Examples
+    ///```
+    ///use mpt_lib::cancel::CancellationToken;
+    ///use mpt_lib::constraints::{GroupConstraint, OptimizerConstraints};
+    ///use mpt_lib::enums::Errors;
+    ///use mpt_lib::MPTCalculator;
+    ///
+    ///let x = vec![2.0, -4.0, 1.0, 3.0, -1.0];
+    ///let y = vec![1.0, 2.0, -3.0, 0.5, 1.5];
+    ///let mut limits = OptimizerConstraints::new();
+    ///limits.groups.push(GroupConstraint {
+    ///    assets: vec![0],
+    ///    min: 0.0,
+    ///    max: 0.05,
+    ///});
+    ///let mut weights = Vec::new();
+    ///let mut certainty_equivalent = 0.0;
+    ///let err = MPTCalculator::optimal_weights_for_risk_aversion_with_constraints(
+    ///    &[&x, &y],
+    ///    4.0,
+    ///    &limits,
+    ///    &CancellationToken::new(),
+    ///    &mut weights,
+    ///    &mut certainty_equivalent,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(&weights, &[0.05, 0.95]),
+    ///    true
+    ///);
+    ///```
+    pub fn optimal_weights_for_risk_aversion_with_constraints(
+        returns_matrix: &[&[f64]],
+        risk_aversion: f64,
+        limits: &OptimizerConstraints,
+        cancel: &CancellationToken,
+        weights: &mut Vec<f64>,
+        certainty_equivalent: &mut f64,
+    ) -> Errors {
+        instrument!(
+            "optimal_weights_for_risk_aversion",
+            rows = returns_matrix.first().map_or(0, |x| x.len()),
+            cols = returns_matrix.len()
+        );
+        weights.clear();
+        *certainty_equivalent = f64::NAN;
+        if returns_matrix.len() == 0
+            || !(risk_aversion.is_finite() && risk_aversion > 0.0)
+            || !limits.is_valid(returns_matrix.len())
+            || returns_matrix
+                .iter()
+                .any(|x| x.len() != returns_matrix[0].len())
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let (means, covariance) = match mean_covariance(returns_matrix) {
+            Some(x) => x,
+            None => return Errors::ClErrorCodeInputLenTooShort,
+        };
+
+        //the variance of the percent returns is divided by 100 for the utility of the decimal returns in percent
+        let quadratic: Vec<Vec<f64>> = covariance
+            .iter()
+            .map(|row| row.iter().map(|x| x * risk_aversion / 100.0).collect())
+            .collect();
+        *weights = match constrained_quadratic_programming(&quadratic, &means, limits, cancel) {
+            Some(x) => x,
+            None if cancel.is_cancelled() => return Errors::ClErrorCodeCancelled,
+            None => return Errors::ClErrorCodeCcFaild,
+        };
+        let mean: f64 = means.iter().zip(weights.iter()).map(|x| x.0 * x.1).sum();
+        let variance: f64 = (0..weights.len())
+            .map(|i| {
+                (0..weights.len())
+                    .map(|j| weights[i] * covariance[i][j] * weights[j])
+                    .sum::<f64>()
+            })
+            .sum();
+        *certainty_equivalent = mean - 0.5 * risk_aversion * variance / 100.0;
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the capital market line, it is the return of the combinations of the risk free asset and the
+    ///tangency portfolio, riskfree + slope * risk, the slope is the sharpe ratio of the tangency portfolio.
+    ///# Arguments
+    ///riskfree: the risk free return.
+    ///
+    ///tangency_return: the expected return of the tangency portfolio.
+    ///
+    ///tangency_risk: the standard deviation of the tangency portfolio, it should be positive.
+    ///
+    ///risks: the standard deviations of the points of the line.
+    ///
+    ///returns: the expected return of each point of the line.
+    ///
+    ///slope: the slope of the line.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let mut returns = Vec::new();
+    ///let mut slope = f64::NAN;
+    ///let err =
+    ///    MPTCalculator::capital_market_line(2.0, 8.0, 12.0, &[0.0, 6.0, 18.0], &mut returns, &mut slope);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && returns == vec![2.0, 5.0, 11.0] && slope == 0.5,
+    ///    true
+    ///);
+    ///```
+    pub fn capital_market_line(
+        riskfree: f64,
+        tangency_return: f64,
+        tangency_risk: f64,
+        risks: &[f64],
+        returns: &mut Vec<f64>,
+        slope: &mut f64,
+    ) -> Errors {
+        returns.clear();
+        *slope = f64::NAN;
+        if !riskfree.is_finite()
+            || !tangency_return.is_finite()
+            || !(tangency_risk.is_finite() && tangency_risk > 0.0)
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *slope = (tangency_return - riskfree) / tangency_risk;
+        returns.extend(risks.iter().map(|x| {
+            if x.is_finite() && *x >= 0.0 {
+                riskfree + *slope * x
+            } else {
+                f64::NAN
+            }
+        }));
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the two fund separation of a target portfolio on the capital market line, it is the split of the
+    ///capital between the tangency portfolio and the risk free asset. the tangency weight larger than 1 borrows at
+    ///the risk free return.
+    ///# Arguments
+    ///riskfree: the risk free return.
+    ///
+    ///tangency_return: the expected return of the tangency portfolio, it should be different from the risk free
+    ///return for a target return.
+    ///
+    ///tangency_risk: the standard deviation of the tangency portfolio, it should be positive.
+    ///
+    ///target: the target risk or the target return of the portfolio, the target risk should not be negative.
+    ///
+    ///tangency_weight: the weight of the tangency portfolio.
+    ///
+    ///riskfree_weight: the weight of the risk free asset, it is negative if it is borrowed.
+    ///
+    ///leverage: the total assets of the portfolio divided by the capital, it is 1 without borrowing or short selling.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{ClCmlTarget, Errors};
+    ///
+    ///let (mut tangency_weight, mut riskfree_weight, mut leverage) = (0.0, 0.0, 0.0);
+    ///let err = MPTCalculator::cml_allocation(
+    ///    2.0,
+    ///    8.0,
+    ///    12.0,
+    ///    ClCmlTarget::ClCmlTargetReturn(11.0),
+    ///    &mut tangency_weight,
+    ///    &mut riskfree_weight,
+    ///    &mut leverage,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && tangency_weight == 1.5
+    ///        && riskfree_weight == -0.5
+    ///        && leverage == 1.5,
+    ///    true
+    ///);
+    ///```
+    pub fn cml_allocation(
+        riskfree: f64,
+        tangency_return: f64,
+        tangency_risk: f64,
+        target: enums::ClCmlTarget,
+        tangency_weight: &mut f64,
+        riskfree_weight: &mut f64,
+        leverage: &mut f64,
+    ) -> Errors {
+        *tangency_weight = f64::NAN;
+        *riskfree_weight = f64::NAN;
+        *leverage = f64::NAN;
+        if !riskfree.is_finite()
+            || !tangency_return.is_finite()
+            || !(tangency_risk.is_finite() && tangency_risk > 0.0)
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let weight = match target {
+            enums::ClCmlTarget::ClCmlTargetRisk(risk) if risk.is_finite() && risk >= 0.0 => {
+                risk / tangency_risk
+            }
+            enums::ClCmlTarget::ClCmlTargetReturn(target_return)
+                if target_return.is_finite() && tangency_return != riskfree =>
+            {
+                (target_return - riskfree) / (tangency_return - riskfree)
+            }
+            _ => return Errors::ClErrorCodeInvalidPara,
+        };
+        *tangency_weight = weight;
+        *riskfree_weight = 1.0 - weight;
+        *leverage = weight.max(1.0 - weight).max(1.0);
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the implied excess returns of the assets by the reverse optimization, risk_aversion * covariance *
+    ///weights, they are the expected returns which make the weights optimal for the quadratic utility, e.g. the
+    ///equilibrium returns of the market weights as the prior of the black litterman model.
+    ///# Arguments
+    ///weights: the weight of each asset, e.g. the market capitalization weights.
+    ///
+    ///covariance: the covariance matrix of the percent returns of the assets.
+    ///
+    ///risk_aversion: the risk aversion coefficient of the utility of the decimal returns, e.g. 2.5.
+    ///
+    ///returns: the implied excess return of each asset, in percent.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let covariance = vec![vec![16.0, 4.0], vec![4.0, 36.0]];
+    ///let mut returns = Vec::new();
+    ///let err = MPTCalculator::implied_returns(&[0.6, 0.4], &covariance, 2.5, &mut returns);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(&returns, &[0.28, 0.42]),
+    ///    true
+    ///);
+    ///```
+    pub fn implied_returns(
+        weights: &[f64],
+        covariance: &[Vec<f64>],
+        risk_aversion: f64,
+        returns: &mut Vec<f64>,
+    ) -> Errors {
+        returns.clear();
+        if weights.len() == 0
+            || covariance.len() != weights.len()
+            || covariance.iter().any(|x| x.len() != weights.len())
+            || !risk_aversion.is_finite()
+            || weights.iter().any(|x| !x.is_finite())
+            || covariance.iter().any(|x| x.iter().any(|v| !v.is_finite()))
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        returns.extend(
+            mat_vec_mul(covariance, weights)
+                .iter()
+                .map(|x| x * risk_aversion / 100.0),
+        );
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the long only weights of a benchmark relative mandate, ClActiveMinTrackingError minimizes the
+    ///tracking error with the expected active return not less than the target, and ClActiveMaxReturn maximizes the
+    ///expected active return with the tracking error not larger than the budget. the expected returns are the
+    ///average returns, the periods which have NAN/INF values in any series are skipped. return ClErrorCodeCcFaild if
+    ///the target active return can not be reached.
+    ///# Arguments
+    ///returns_matrix: the return array of each asset, should have the same length.
+    ///
+    ///benchmark_weights: the benchmark weight of each asset, they are long only and the sum is 1.
+    ///
+    ///objective: the objective of the optimization.
+    ///
+    ///target: the target active return of ClActiveMinTrackingError or the tracking error budget of
+    ///ClActiveMaxReturn, of one period in percent.
+    ///
+    ///weights: the optimal weight of each asset, the sum is 1.
+    ///
+    ///tracking_error: the standard deviation of the active returns of the optimal weights, it is not annualized.
+    ///
+    ///active_return: the expected active return of one period of the optimal weights.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{ClActiveObjective, Errors};
+    ///
+    ///let x = vec![2.0, -4.0, 1.0, 3.0, -1.0];
+    ///let y = vec![1.0, 2.0, -3.0, 0.5, 1.5];
+    ///let mut weights = Vec::new();
+    ///let (mut tracking_error, mut active_return) = (0.0, 0.0);
+    ///let err = MPTCalculator::active_weights(
+    ///    &[&x, &y],
+    ///    &[0.5, 0.5],
+    ///    ClActiveObjective::ClActiveMaxReturn,
+    ///    1.0,
+    ///    &mut weights,
+    ///    &mut tracking_error,
+    ///    &mut active_return,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(&weights, &[0.252501, 0.747499])
+    ///        && MPTCalculator::is_eq_double(tracking_error, 1.0),
+    ///    true
+    ///);
+    ///```
+    pub fn active_weights(
+        returns_matrix: &[&[f64]],
+        benchmark_weights: &[f64],
+        objective: enums::ClActiveObjective,
+        target: f64,
+        weights: &mut Vec<f64>,
+        tracking_error: &mut f64,
+        active_return: &mut f64,
+    ) -> Errors {
+        return MPTCalculator::active_weights_with_cancel(
+            returns_matrix,
+            benchmark_weights,
+            objective,
+            target,
+            &CancellationToken::new(),
+            weights,
+            tracking_error,
+            active_return,
+        );
+    }
+
+    ///calculate the active weights as active_weights, the solver checks the token for each iteration and returns
+    ///ClErrorCodeCancelled after it is cancelled.
+    pub fn active_weights_with_cancel(
+        returns_matrix: &[&[f64]],
+        benchmark_weights: &[f64],
+        objective: enums::ClActiveObjective,
+        target: f64,
+        cancel: &CancellationToken,
+        weights: &mut Vec<f64>,
+        tracking_error: &mut f64,
+        active_return: &mut f64,
+    ) -> Errors {
+        return MPTCalculator::active_weights_with_constraints(
+            returns_matrix,
+            benchmark_weights,
+            objective,
+            target,
+            &OptimizerConstraints::new(),
+            cancel,
+            weights,
+            tracking_error,
+            active_return,
+        );
+    }
+
+    ///calculate the active weights as active_weights with the group, turnover and cardinality constraints of the
+    ///weights. return ClErrorCodeCcFaild if the target active return or the constraints can not be satisfied, the
+    ///solver checks the token for each iteration and returns ClErrorCodeCancelled after it is cancelled.
+    pub fn active_weights_with_constraints(
+        returns_matrix: &[&[f64]],
+        benchmark_weights: &[f64],
+        objective: enums::ClActiveObjective,
+        target: f64,
+        limits: &OptimizerConstraints,
+        cancel: &CancellationToken,
+        weights: &mut Vec<f64>,
+        tracking_error: &mut f64,
+        active_return: &mut f64,
+    ) -> Errors {
+        instrument!(
+            "active_weights",
+            rows = returns_matrix.first().map_or(0, |x| x.len()),
+            cols = returns_matrix.len()
+        );
+        weights.clear();
+        *tracking_error = f64::NAN;
+        *active_return = f64::NAN;
+        if returns_matrix.len() == 0
+            || benchmark_weights.len() != returns_matrix.len()
+            || benchmark_weights.iter().any(|x| !x.is_finite() || *x < 0.0)
+            || (benchmark_weights.iter().sum::<f64>() - 1.0).abs() > SIMPLEX_EPSILON
+            || !target.is_finite()
+            || (objective == enums::ClActiveObjective::ClActiveMaxReturn && target < 0.0)
+            || !limits.is_valid(returns_matrix.len())
+            || returns_matrix
+                .iter()
+                .any(|x| x.len() != returns_matrix[0].len())
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let (means, covariance) = match mean_covariance(returns_matrix) {
+            Some(x) => x,
+            None => return Errors::ClErrorCodeInputLenTooShort,
+        };
+
+        //maximize the active return - 0.5 / kappa * the active variance, both the tracking error and the active
+        //return increase with kappa, so kappa is searched for the target.
+        let benchmark_covariance = mat_vec_mul(&covariance, benchmark_weights);
+        let solve = |kappa: f64| -> Option<(Vec<f64>, f64, f64)> {
+            let linear: Vec<f64> = benchmark_covariance
+                .iter()
+                .zip(means.iter())
+                .map(|x| x.0 + kappa * x.1)
+                .collect();
+            let w = constrained_quadratic_programming(&covariance, &linear, limits, cancel)?;
+            let active: Vec<f64> = w
+                .iter()
+                .zip(benchmark_weights.iter())
+                .map(|x| x.0 - x.1)
+                .collect();
+            let variance: f64 = active
+                .iter()
+                .zip(mat_vec_mul(&covariance, &active).iter())
+                .map(|x| x.0 * x.1)
+                .sum();
+            let active_return = active.iter().zip(means.iter()).map(|x| x.0 * x.1).sum();
+            Some((w, variance.max(0.0).sqrt(), active_return))
+        };
+        let failed = || {
+            if cancel.is_cancelled() {
+                Errors::ClErrorCodeCancelled
+            } else {
+                Errors::ClErrorCodeCcFaild
+            }
+        };
+        let is_reached = |result: &(Vec<f64>, f64, f64)| match objective {
+            enums::ClActiveObjective::ClActiveMinTrackingError => result.2 >= target,
+            enums::ClActiveObjective::ClActiveMaxReturn => result.1 > target,
+        };
+
+        let mut best = match solve(0.0) {
+            Some(x) => x,
+            None => return failed(),
+        };
+        if !is_reached(&best) {
+            let (mut low, mut high) = (0.0, 1e-6);
+            let mut result = match solve(high) {
+                Some(x) => x,
+                None => return failed(),
+            };
+            while !is_reached(&result) && high < 1e12 {
+                low = high;
+                best = result;
+                high *= 4.0;
+                result = match solve(high) {
+                    Some(x) => x,
+                    None => return failed(),
+                };
+            }
+            if !is_reached(&result) {
+                if objective == enums::ClActiveObjective::ClActiveMinTrackingError {
+                    return Errors::ClErrorCodeCcFaild;
+                }
+                best = result;
+            } else {
+                for _ in 0..100 {
+                    let middle = (low + high) / 2.0;
+                    let result = match solve(middle) {
+                        Some(x) => x,
+                        None => return failed(),
+                    };
+                    if is_reached(&result) {
+                        high = middle;
+                    } else {
+                        low = middle;
+                        best = result;
+                    }
+                }
+                if objective == enums::ClActiveObjective::ClActiveMinTrackingError {
+                    best = match solve(high) {
+                        Some(x) => x,
+                        None => return failed(),
+                    };
+                }
+            }
+        }
+        *weights = best.0;
+        *tracking_error = best.1;
+        *active_return = best.2;
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        linear_programming, simplex_quadratic_programming, ConstraintType, LinearConstraint,
+    };
+    use crate::{
+        cancel::CancellationToken,
+        constraints::{GroupConstraint, OptimizerConstraints},
+        enums::{ClActiveObjective, ClCmlTarget, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_linear_programming() {
+        //max 3x + 2y, x + y <= 4, x + 3y <= 6, x <= 3
+        let constraints = vec![
+            LinearConstraint {
+                coefficients: vec![1.0, 1.0],
+                constraint_type: ConstraintType::LessEqual,
+                rhs: 4.0,
+            },
+            LinearConstraint {
+                coefficients: vec![1.0, 3.0],
+                constraint_type: ConstraintType::LessEqual,
+                rhs: 6.0,
+            },
+            LinearConstraint {
+                coefficients: vec![1.0, 0.0],
+                constraint_type: ConstraintType::LessEqual,
+                rhs: 3.0,
+            },
+        ];
+        let x = linear_programming(&[-3.0, -2.0], &constraints, &CancellationToken::new()).unwrap();
+        assert_eq!(MPTCalculator::is_eq_double_array(&x, &[3.0, 1.0]), true);
+
+        let infeasible = vec![LinearConstraint {
+            coefficients: vec![1.0, 1.0],
+            constraint_type: ConstraintType::Equal,
+            rhs: -1.0,
+        }];
+        assert_eq!(
+            linear_programming(&[1.0, 1.0], &infeasible, &CancellationToken::new()).is_none(),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_min_cvar_weights() {
+        let x = vec![2.0, -4.0, 1.0, 3.0, -1.0, f64::NAN];
+        let y = vec![1.0, 2.0, -3.0, 0.5, 1.5, 1.0];
+        let mut weights = Vec::new();
+        let mut cvar = 0.0;
+        let err = MPTCalculator::min_cvar_weights(&[&x, &y], 0.8, &mut weights, &mut cvar);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&weights, &[0.5, 0.5])
+                && MPTCalculator::is_eq_double(cvar, 1.0),
+            true
+        );
+
+        let cancel = CancellationToken::new();
+        cancel.clone().cancel();
+        let err = MPTCalculator::min_cvar_weights_with_cancel(
+            &[&x, &y],
+            0.8,
+            &cancel,
+            &mut weights,
+            &mut cvar,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeCancelled && weights.len() == 0 && cvar.is_nan(),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_mean_cvar_weights() {
+        let x = vec![2.0, -4.0, 1.0, 3.0, -1.0];
+        let y = vec![1.0, 2.0, -3.0, 0.5, 1.5];
+        let mut weights = Vec::new();
+        let mut cvar = 0.0;
+        let err = MPTCalculator::mean_cvar_weights(&[&x, &y], 0.8, 0.35, &mut weights, &mut cvar);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&weights, &[0.25, 0.75])
+                && MPTCalculator::is_eq_double(cvar, 2.0),
+            true
+        );
+
+        let err = MPTCalculator::mean_cvar_weights(&[&x, &y], 0.8, 1.0, &mut weights, &mut cvar);
+        assert_eq!(err, Errors::ClErrorCodeCcFaild);
+    }
+
+    #[test]
+    fn should_correct_cvar_weights_with_constraints() {
+        let x = vec![2.0, -4.0, 1.0, 3.0, -1.0];
+        let y = vec![1.0, 2.0, -3.0, 0.5, 1.5];
+        let z = vec![0.5, 0.5, 0.5, 0.5, 0.5];
+        let mut weights = Vec::new();
+        let mut cvar = 0.0;
+        let mut limits = OptimizerConstraints::new();
+        let err = MPTCalculator::min_cvar_weights_with_constraints(
+            &[&x, &y, &z],
+            0.8,
+            &limits,
+            &CancellationToken::new(),
+            &mut weights,
+            &mut cvar,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&weights, &[0.0, 0.0, 1.0])
+                && MPTCalculator::is_eq_double(cvar, -0.5),
+            true
+        );
+
+        limits.groups.push(GroupConstraint {
+            assets: vec![0, 1],
+            min: 0.5,
+            max: 1.0,
+        });
+        let err = MPTCalculator::min_cvar_weights_with_constraints(
+            &[&x, &y, &z],
+            0.8,
+            &limits,
+            &CancellationToken::new(),
+            &mut weights,
+            &mut cvar,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&weights, &[0.25, 0.25, 0.5]),
+            true
+        );
+
+        limits.groups.clear();
+        limits.current_weights = vec![0.5, 0.5, 0.0];
+        limits.max_turnover = 0.25;
+        let err = MPTCalculator::min_cvar_weights_with_constraints(
+            &[&x, &y, &z],
+            0.8,
+            &limits,
+            &CancellationToken::new(),
+            &mut weights,
+            &mut cvar,
+        );
+        let turnover: f64 = weights
+            .iter()
+            .zip(limits.current_weights.iter())
+            .map(|x| (x.0 - x.1).abs())
+            .sum::<f64>()
+            / 2.0;
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(turnover, 0.25)
+                && MPTCalculator::is_eq_double(weights[2], 0.25),
+            true
+        );
+
+        let mut limits = OptimizerConstraints::new();
+        limits.max_assets = 1;
+        let err = MPTCalculator::mean_cvar_weights_with_constraints(
+            &[&x, &y],
+            0.8,
+            0.35,
+            &limits,
+            &CancellationToken::new(),
+            &mut weights,
+            &mut cvar,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&weights, &[0.0, 1.0])
+                && MPTCalculator::is_eq_double(cvar, 3.0),
+            true
+        );
+
+        limits.max_turnover = 0.1;
+        let err = MPTCalculator::min_cvar_weights_with_constraints(
+            &[&x, &y, &z],
+            0.8,
+            &limits,
+            &CancellationToken::new(),
+            &mut weights,
+            &mut cvar,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let err = MPTCalculator::mean_cvar_weights_with_constraints(
+            &[&x, &y, &z],
+            0.8,
+            0.35,
+            &OptimizerConstraints::new(),
+            &cancel,
+            &mut weights,
+            &mut cvar,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeCancelled && weights.len() == 0,
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_optimal_weights_for_risk_aversion() {
+        let x = vec![2.0, -4.0, 1.0, 3.0, -1.0, f64::NAN];
+        let y = vec![1.0, 2.0, -3.0, 0.5, 1.5, 1.0];
+        let (var_x, var_y, cov) = (7.7, 3.925, -2.35);
+        let (mean_x, mean_y) = (0.2, 0.4);
+        let mut weights = Vec::new();
+        let mut certainty_equivalent = 0.0;
+        for risk_aversion in [4.0, 10.0, 20.0] {
+            let err = MPTCalculator::optimal_weights_for_risk_aversion(
+                &[&x, &y],
+                risk_aversion,
+                &mut weights,
+                &mut certainty_equivalent,
+            );
+            //the closed form of two assets without the long only bounds
+            let a = risk_aversion / 100.0;
+            let w = ((mean_x - mean_y) / a + var_y - cov) / (var_x + var_y - 2.0 * cov);
+            let variance =
+                w * w * var_x + (1.0 - w) * (1.0 - w) * var_y + 2.0 * w * (1.0 - w) * cov;
+            let expected = w * mean_x + (1.0 - w) * mean_y - 0.5 * a * variance;
+            assert_eq!(
+                err == Errors::ClErrorCodeNoError
+                    && MPTCalculator::is_eq_double_array(&weights, &[w, 1.0 - w])
+                    && MPTCalculator::is_eq_double(certainty_equivalent, expected),
+                true
+            );
+        }
+
+        let err = MPTCalculator::optimal_weights_for_risk_aversion(
+            &[&x, &y],
+            0.1,
+            &mut weights,
+            &mut certainty_equivalent,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&weights, &[0.0, 1.0]),
+            true
+        );
+        let err = MPTCalculator::optimal_weights_for_risk_aversion(
+            &[&x, &y],
+            0.0,
+            &mut weights,
+            &mut certainty_equivalent,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && weights.len() == 0,
+            true
+        );
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let err = MPTCalculator::optimal_weights_for_risk_aversion_with_cancel(
+            &[&x, &y],
+            4.0,
+            &cancel,
+            &mut weights,
+            &mut certainty_equivalent,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeCancelled
+                && weights.len() == 0
+                && certainty_equivalent.is_nan(),
+            true
+        );
+
+        let quadratic = vec![
+            vec![2.0, 0.0, 0.0],
+            vec![0.0, 2.0, 0.0],
+            vec![0.0, 0.0, 2.0],
+        ];
+        let w = simplex_quadratic_programming(
+            &quadratic,
+            &[1.0, 0.0, -3.0],
+            &OptimizerConstraints::new(),
+            &[false; 3],
+            &CancellationToken::new(),
+        )
+        .unwrap();
+        assert_eq!(
+            MPTCalculator::is_eq_double_array(&w, &[0.75, 0.25, 0.0]),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_optimal_weights_for_risk_aversion_with_constraints() {
+        let x = vec![2.0, -4.0, 1.0, 3.0, -1.0];
+        let y = vec![1.0, 2.0, -3.0, 0.5, 1.5];
+        let z = vec![-1.0, 1.0, 2.0, -2.0, 1.5];
+        let cancel = CancellationToken::new();
+        let mut weights = Vec::new();
+        let mut certainty_equivalent = 0.0;
+        let mut optimize = |limits: &OptimizerConstraints, weights: &mut Vec<f64>| {
+            MPTCalculator::optimal_weights_for_risk_aversion_with_constraints(
+                &[&x, &y, &z],
+                10.0,
+                limits,
+                &cancel,
+                weights,
+                &mut certainty_equivalent,
+            )
+        };
+        let mut free_weights = Vec::new();
+        let err = optimize(&OptimizerConstraints::new(), &mut free_weights);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && free_weights.iter().all(|w| *w > 0.05),
+            true
+        );
+
+        //the group of the first two assets is bound by its max
+        let mut limits = OptimizerConstraints::new();
+        limits.groups.push(GroupConstraint {
+            assets: vec![0, 1],
+            min: 0.0,
+            max: 0.5,
+        });
+        let err = optimize(&limits, &mut weights);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && free_weights[0] + free_weights[1] > 0.5
+                && MPTCalculator::is_eq_double(weights[0] + weights[1], 0.5)
+                && MPTCalculator::is_eq_double(weights.iter().sum::<f64>(), 1.0),
+            true
+        );
+
+        //the turnover from the equal weights is bound by its limit
+        let mut limits = OptimizerConstraints::new();
+        limits.current_weights = vec![1.0 / 3.0; 3];
+        limits.max_turnover = 0.05;
+        let err = optimize(&limits, &mut weights);
+        let turnover = |w: &[f64]| 0.5 * w.iter().map(|x| (x - 1.0 / 3.0).abs()).sum::<f64>();
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && turnover(&free_weights) > 0.05
+                && MPTCalculator::is_eq_double(turnover(&weights), 0.05),
+            true
+        );
+
+        //the asset with the smallest weight is excluded
+        let mut limits = OptimizerConstraints::new();
+        limits.max_assets = 2;
+        let err = optimize(&limits, &mut weights);
+        let smallest = (0..3)
+            .min_by(|a, b| free_weights[*a].total_cmp(&free_weights[*b]))
+            .unwrap();
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && weights[smallest] == 0.0
+                && weights.iter().filter(|w| **w > 0.0).count() == 2,
+            true
+        );
+
+        //the two assets of the closed form with the group bound
+        let mut limits = OptimizerConstraints::new();
+        limits.groups.push(GroupConstraint {
+            assets: vec![0],
+            min: 0.0,
+            max: 0.05,
+        });
+        let err = MPTCalculator::optimal_weights_for_risk_aversion_with_constraints(
+            &[&x, &y],
+            4.0,
+            &limits,
+            &cancel,
+            &mut weights,
+            &mut certainty_equivalent,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&weights, &[0.05, 0.95]),
+            true
+        );
+
+        limits.groups.push(GroupConstraint {
+            assets: vec![1],
+            min: 0.0,
+            max: 0.5,
+        });
+        let err = MPTCalculator::optimal_weights_for_risk_aversion_with_constraints(
+            &[&x, &y],
+            4.0,
+            &limits,
+            &cancel,
+            &mut weights,
+            &mut certainty_equivalent,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeCcFaild && weights.len() == 0,
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_capital_market_line() {
+        let mut returns = Vec::new();
+        let mut slope = f64::NAN;
+        let err = MPTCalculator::capital_market_line(
+            0.25,
+            1.0,
+            3.0,
+            &[0.0, 1.5, 6.0, -1.0],
+            &mut returns,
+            &mut slope,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&returns[..3], &[0.25, 0.625, 1.75])
+                && returns[3].is_nan()
+                && slope == 0.25,
+            true
+        );
+
+        let (mut tangency_weight, mut riskfree_weight, mut leverage) = (0.0, 0.0, 0.0);
+        let err = MPTCalculator::cml_allocation(
+            0.25,
+            1.0,
+            3.0,
+            ClCmlTarget::ClCmlTargetRisk(1.5),
+            &mut tangency_weight,
+            &mut riskfree_weight,
+            &mut leverage,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && tangency_weight == 0.5
+                && riskfree_weight == 0.5
+                && leverage == 1.0,
+            true
+        );
+        let err = MPTCalculator::cml_allocation(
+            0.25,
+            1.0,
+            3.0,
+            ClCmlTarget::ClCmlTargetReturn(-0.5),
+            &mut tangency_weight,
+            &mut riskfree_weight,
+            &mut leverage,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && tangency_weight == -1.0
+                && riskfree_weight == 2.0
+                && leverage == 2.0,
+            true
+        );
+        let err = MPTCalculator::cml_allocation(
+            1.0,
+            1.0,
+            3.0,
+            ClCmlTarget::ClCmlTargetReturn(2.0),
+            &mut tangency_weight,
+            &mut riskfree_weight,
+            &mut leverage,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && tangency_weight.is_nan(),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_implied_returns() {
+        let x = vec![2.0, -4.0, 1.0, 3.0, -1.0];
+        let y = vec![1.0, 2.0, -3.0, 0.5, 1.5];
+        let mut weights = Vec::new();
+        let mut certainty_equivalent = 0.0;
+        MPTCalculator::optimal_weights_for_risk_aversion(
+            &[&x, &y],
+            10.0,
+            &mut weights,
+            &mut certainty_equivalent,
+        );
+        let covariance = vec![vec![7.7, -2.35], vec![-2.35, 3.925]];
+        let mut returns = Vec::new();
+        let err = MPTCalculator::implied_returns(&weights, &covariance, 10.0, &mut returns);
+        //the reverse optimization recovers the average returns up to the constant of the full investment
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(returns[0] - returns[1], 0.2 - 0.4),
+            true
+        );
+
+        let err = MPTCalculator::implied_returns(&weights, &covariance[..1], 10.0, &mut returns);
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && returns.len() == 0,
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_active_weights() {
+        let x = vec![2.0, -4.0, 1.0, 3.0, -1.0];
+        let y = vec![1.0, 2.0, -3.0, 0.5, 1.5];
+        let z = vec![0.5, 1.0, 0.0, -0.5, 1.0];
+        let benchmark = [0.4, 0.4, 0.2];
+        let mut weights = Vec::new();
+        let (mut tracking_error, mut active_return) = (0.0, 0.0);
+        let err = MPTCalculator::active_weights(
+            &[&x, &y, &z],
+            &benchmark,
+            ClActiveObjective::ClActiveMaxReturn,
+            0.5,
+            &mut weights,
+            &mut tracking_error,
+            &mut active_return,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(tracking_error, 0.5)
+                && active_return > 0.0
+                && MPTCalculator::is_eq_double(weights.iter().sum::<f64>(), 1.0),
+            true
+        );
+
+        //the min tracking error of the max active return reaches the same weights
+        let budget_weights = weights.clone();
+        let budget_return = active_return;
+        let err = MPTCalculator::active_weights(
+            &[&x, &y, &z],
+            &benchmark,
+            ClActiveObjective::ClActiveMinTrackingError,
+            budget_return,
+            &mut weights,
+            &mut tracking_error,
+            &mut active_return,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&weights, &budget_weights)
+                && MPTCalculator::is_eq_double(tracking_error, 0.5),
+            true
+        );
+
+        let err = MPTCalculator::active_weights(
+            &[&x, &y, &z],
+            &benchmark,
+            ClActiveObjective::ClActiveMinTrackingError,
+            -1.0,
+            &mut weights,
+            &mut tracking_error,
+            &mut active_return,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&weights, &benchmark)
+                && tracking_error < 1e-6,
+            true
+        );
+
+        let err = MPTCalculator::active_weights(
+            &[&x, &y, &z],
+            &benchmark,
+            ClActiveObjective::ClActiveMinTrackingError,
+            1.0,
+            &mut weights,
+            &mut tracking_error,
+            &mut active_return,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeCcFaild && weights.len() == 0,
+            true
+        );
+
+        let err = MPTCalculator::active_weights(
+            &[&x, &y, &z],
+            &[0.5, 0.5, 0.5],
+            ClActiveObjective::ClActiveMaxReturn,
+            0.5,
+            &mut weights,
+            &mut tracking_error,
+            &mut active_return,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let err = MPTCalculator::active_weights_with_cancel(
+            &[&x, &y, &z],
+            &benchmark,
+            ClActiveObjective::ClActiveMaxReturn,
+            0.5,
+            &cancel,
+            &mut weights,
+            &mut tracking_error,
+            &mut active_return,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeCancelled && weights.len() == 0 && tracking_error.is_nan(),
+            true
+        );
+
+        //the group of the third asset and the turnover from the benchmark are bound by their limits
+        let turnover = |w: &[f64]| {
+            0.5 * w
+                .iter()
+                .zip(benchmark.iter())
+                .map(|x| (x.0 - x.1).abs())
+                .sum::<f64>()
+        };
+        let mut limits = OptimizerConstraints::new();
+        limits.groups.push(GroupConstraint {
+            assets: vec![2],
+            min: 0.0,
+            max: 0.3,
+        });
+        let err = MPTCalculator::active_weights_with_constraints(
+            &[&x, &y, &z],
+            &benchmark,
+            ClActiveObjective::ClActiveMaxReturn,
+            0.5,
+            &limits,
+            &CancellationToken::new(),
+            &mut weights,
+            &mut tracking_error,
+            &mut active_return,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && budget_weights[2] > 0.3
+                && MPTCalculator::is_eq_double(weights[2], 0.3)
+                && MPTCalculator::is_eq_double(tracking_error, 0.5),
+            true
+        );
+
+        let mut limits = OptimizerConstraints::new();
+        limits.current_weights = benchmark.to_vec();
+        limits.max_turnover = 0.1;
+        let err = MPTCalculator::active_weights_with_constraints(
+            &[&x, &y, &z],
+            &benchmark,
+            ClActiveObjective::ClActiveMaxReturn,
+            0.5,
+            &limits,
+            &CancellationToken::new(),
+            &mut weights,
+            &mut tracking_error,
+            &mut active_return,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && turnover(&budget_weights) > 0.1
+                && MPTCalculator::is_eq_double(turnover(&weights), 0.1)
+                && tracking_error < 0.5,
+            true
+        );
+
+        let mut limits = OptimizerConstraints::new();
+        limits.max_assets = 1;
+        let err = MPTCalculator::active_weights_with_constraints(
+            &[&x, &y, &z],
+            &benchmark,
+            ClActiveObjective::ClActiveMaxReturn,
+            10.0,
+            &limits,
+            &CancellationToken::new(),
+            &mut weights,
+            &mut tracking_error,
+            &mut active_return,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && weights.iter().filter(|w| **w > 0.0).count() == 1,
+            true
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{
+    enums::Errors,
+    linalg::{covariance_matrix, symmetric_eigen},
+    MPTCalculator,
+};
+
+impl<'a> MPTCalculator<'a> {
+    ///calculate the principal component analysis of the return matrix by the eigen decomposition of the covariance
+    ///matrix, the periods which have NAN/INF values in any series are skipped.
+    ///# Arguments
+    ///returns_matrix: the return array of each series, should have the same length.
+    ///
+    ///n_components: the number of principal components to output, should not be larger than the number of series.
+    ///
+    ///eigenvalues: the variance of each principal component, in descending order.
+    ///
+    ///explained_variance: the ratio of the variance explained by each principal component.
+    ///
+    ///loadings: the unit eigenvector of each principal component, loadings[i][j] is the weight of series j.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let x = vec![1.0, -2.0, 3.0, 0.5, -1.5, 2.0];
+    ///let y = vec![0.8, -1.5, 2.0, 1.0, -2.0, 1.2];
+    ///let mut eigenvalues = Vec::new();
+    ///let mut explained_variance = Vec::new();
+    ///let mut loadings = Vec::new();
+    ///let err = MPTCalculator::pca(
+    ///    &[&x, &y],
+    ///    1,
+    ///    &mut eigenvalues,
+    ///    &mut explained_variance,
+    ///    &mut loadings,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(&eigenvalues, &[6.245995])
+    ///        && MPTCalculator::is_eq_double_array(&explained_variance, &[0.977311])
+    ///        && MPTCalculator::is_eq_double_array(&loadings[0], &[0.774004, 0.633181]),
+    ///    true
+    ///);
+    ///```
+    pub fn pca(
+        returns_matrix: &[&[f64]],
+        n_components: usize,
+        eigenvalues: &mut Vec<f64>,
+        explained_variance: &mut Vec<f64>,
+        loadings: &mut Vec<Vec<f64>>,
+    ) -> Errors {
+        instrument!(
+            "pca",
+            rows = returns_matrix.first().map_or(0, |x| x.len()),
+            cols = returns_matrix.len()
+        );
+        if returns_matrix.len() == 0
+            || n_components == 0
+            || n_components > returns_matrix.len()
+            || returns_matrix
+                .iter()
+                .any(|x| x.len() != returns_matrix[0].len())
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        eigenvalues.clear();
+        explained_variance.clear();
+        loadings.clear();
+
+        let valid_rows: Vec<usize> = (0..returns_matrix[0].len())
+            .filter(|i| returns_matrix.iter().all(|x| x[*i].is_finite()))
+            .collect();
+        if valid_rows.len() < 2 {
+            return Errors::ClErrorCodeInputLenTooShort;
+        }
+        let columns: Vec<Vec<f64>> = returns_matrix
+            .iter()
+            .map(|x| valid_rows.iter().map(|i| x[*i]).collect())
+            .collect();
+        let column_refs: Vec<&[f64]> = columns.iter().map(|x| x.as_slice()).collect();
+
+        let (values, vectors) = symmetric_eigen(&covariance_matrix(&column_refs));
+        let total_variance: f64 = values.iter().sum();
+        for i in 0..n_components {
+            eigenvalues.push(values[i]);
+            explained_variance.push(if total_variance > 0.0 {
+                values[i] / total_variance
+            } else {
+                f64::NAN
+            });
+            loadings.push(vectors[i].clone());
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{enums::Errors, MPTCalculator};
+
+    #[test]
+    fn should_correct_pca() {
+        let x = vec![1.0, -2.0, 3.0, 0.5, -1.5, 2.0, f64::NAN];
+        let y = vec![0.8, -1.5, 2.0, 1.0, -2.0, 1.2, 3.0];
+        let mut eigenvalues = Vec::new();
+        let mut explained_variance = Vec::new();
+        let mut loadings = Vec::new();
+        let err = MPTCalculator::pca(
+            &[&x, &y],
+            2,
+            &mut eigenvalues,
+            &mut explained_variance,
+            &mut loadings,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&eigenvalues, &[6.245995, 0.145005])
+                && MPTCalculator::is_eq_double_array(&explained_variance, &[0.977311, 0.022689])
+                && MPTCalculator::is_eq_double_array(&loadings[0], &[0.774004, 0.633181]),
+            true
+        );
+
+        let err = MPTCalculator::pca(
+            &[&x, &y],
+            3,
+            &mut eigenvalues,
+            &mut explained_variance,
+            &mut loadings,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+}
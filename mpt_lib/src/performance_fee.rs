@@ -0,0 +1,220 @@
+use crate::{
+    common::{get_annual_multiplier, is_valid_frequency},
+    enums::{self, Errors},
+    MPTCalculator,
+};
+
+///the net returns of the investor and the cash flows of a high watermark performance fee.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PerformanceFeeReport {
+    ///the return of each period after the accrued performance fee, in percent.
+    pub net_returns: Vec<f64>,
+    ///the performance fee crystallized at the end of each period, in percent of the initial investment.
+    pub fees: Vec<f64>,
+    ///the high watermark after each period, it is the cumulative net return in percent.
+    pub high_watermarks: Vec<f64>,
+    ///the sum of the crystallized fees, in percent of the initial investment.
+    pub total_fee: f64,
+    ///the performance fee accrued but not crystallized after the last period, in percent of the initial investment.
+    pub accrued_fee: f64,
+}
+
+impl PerformanceFeeReport {
+    pub fn new() -> PerformanceFeeReport {
+        PerformanceFeeReport {
+            net_returns: Vec::new(),
+            fees: Vec::new(),
+            high_watermarks: Vec::new(),
+            total_fee: f64::NAN,
+            accrued_fee: f64::NAN,
+        }
+    }
+}
+
+impl Default for PerformanceFeeReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///apply a high watermark performance fee to a gross return array. the fee is accrued at the end of each period
+    ///as the rate of the gross asset value above the hurdle level, and it is crystallized, paid, at the end of each
+    ///crystallization period, then the high watermark is reset to the net asset value if it is higher. the hurdle
+    ///level compounds the annual hurdle from the high watermark since the begin of the crystallization period, it is
+    ///the high watermark if the hurdle is 0. the net returns include the accrued fee like the published net asset
+    ///value of a fund. if the array has NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///freq: the frequence of source data.
+    ///
+    ///rate: the performance fee rate in percent, e.g. 20.
+    ///
+    ///hurdle: the annual hurdle return in percent, it should not be negative.
+    ///
+    ///crystallization_periods: the number of the periods of a crystallization period, e.g. 12 for the annual
+    ///crystallization of the monthly data.
+    ///
+    ///report: the net returns and the fees.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///use mpt_lib::performance_fee::PerformanceFeeReport;
+    ///
+    ///let data = vec![10.0, -5.0, 10.0, 0.0];
+    ///let mut report = PerformanceFeeReport::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.performance_fee(
+    ///    enums::ClFrequency::ClFrequencyQuarterly,
+    ///    20.0,
+    ///    0.0,
+    ///    2,
+    ///    &mut report,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(&report.net_returns, &[8.0, -4.074074, 8.0, 0.0])
+    ///        && MPTCalculator::is_eq_double_array(&report.fees, &[0.0, 0.9, 0.0, 2.072]),
+    ///    true
+    ///);
+    ///```
+    pub fn performance_fee(
+        &self,
+        freq: enums::ClFrequency,
+        rate: f64,
+        hurdle: f64,
+        crystallization_periods: usize,
+        report: &mut PerformanceFeeReport,
+    ) -> Errors {
+        *report = PerformanceFeeReport::new();
+        if self.values.len() == 0
+            || !is_valid_frequency(freq)
+            || !(rate >= 0.0 && rate < 100.0)
+            || !(hurdle >= 0.0 && hurdle.is_finite())
+            || crystallization_periods == 0
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let n = self.values.len();
+        report.net_returns = vec![f64::NAN; n];
+        report.fees = vec![f64::NAN; n];
+        report.high_watermarks = vec![f64::NAN; n];
+        if self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let hurdle_per_period =
+            (1.0 + hurdle / 100.0).powf(1.0 / get_annual_multiplier(freq, false));
+        let (mut gross, mut net, mut high) = (1.0, 1.0, 1.0);
+        let mut accrued = 0.0;
+        let mut total_fee = 0.0;
+        for t in 0..n {
+            let k = t % crystallization_periods + 1;
+            gross *= 1.0 + self.values[t] / 100.0;
+            accrued = rate / 100.0 * f64::max(gross - high * hurdle_per_period.powi(k as i32), 0.0);
+            let previous = net;
+            net = gross - accrued;
+            report.net_returns[t] = (net / previous - 1.0) * 100.0;
+            report.fees[t] = 0.0;
+            if k == crystallization_periods {
+                report.fees[t] = accrued * 100.0;
+                total_fee += accrued;
+                accrued = 0.0;
+                gross = net;
+                high = f64::max(high, net);
+            }
+            report.high_watermarks[t] = (high - 1.0) * 100.0;
+        }
+        report.total_fee = total_fee * 100.0;
+        report.accrued_fee = accrued * 100.0;
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PerformanceFeeReport;
+    use crate::{
+        enums::{self, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_performance_fee() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let mpt = MPTCalculator::from_v(&data);
+        let mut report = PerformanceFeeReport::new();
+        let err = mpt.performance_fee(
+            enums::ClFrequency::ClFrequencyMonthly,
+            20.0,
+            5.0,
+            12,
+            &mut report,
+        );
+
+        //the first year is crystallized above the hurdle
+        let gross: f64 = data[..12]
+            .iter()
+            .fold(1.0, |acc, x| acc * (1.0 + x / 100.0));
+        let fee = 0.2 * (gross - 1.05);
+        let mut net_growth = 1.0;
+        report.net_returns[..12]
+            .iter()
+            .for_each(|x| net_growth *= 1.0 + x / 100.0);
+        let mut gross_total = 1.0;
+        let mut net_total = 1.0;
+        for (g, r) in data.iter().zip(report.net_returns.iter()) {
+            gross_total *= 1.0 + g / 100.0;
+            net_total *= 1.0 + r / 100.0;
+        }
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(report.fees[11], fee * 100.0)
+                && MPTCalculator::is_eq_double(net_growth, gross - fee)
+                && MPTCalculator::is_eq_double(
+                    report.high_watermarks[11],
+                    (gross - fee - 1.0) * 100.0
+                )
+                && report.fees[23] == 0.0
+                && report.fees[35] == 0.0
+                && MPTCalculator::is_eq_double(report.total_fee, fee * 100.0)
+                && MPTCalculator::is_eq_double(report.accrued_fee, 0.0)
+                && report.high_watermarks[35] == report.high_watermarks[11]
+                && MPTCalculator::is_eq_double(gross_total - net_total, fee * gross_total / gross),
+            true
+        );
+
+        //no fee below the hurdle
+        let err = MPTCalculator::from_v(&data[..12]).performance_fee(
+            enums::ClFrequency::ClFrequencyMonthly,
+            20.0,
+            50.0,
+            12,
+            &mut report,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&report.net_returns, &data[..12])
+                && MPTCalculator::is_eq_double(report.total_fee, 0.0),
+            true
+        );
+
+        let err = mpt.performance_fee(
+            enums::ClFrequency::ClFrequencyMonthly,
+            100.0,
+            0.0,
+            12,
+            &mut report,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && report.net_returns.len() == 0,
+            true
+        );
+    }
+}
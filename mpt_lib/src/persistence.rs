@@ -0,0 +1,245 @@
+use crate::{
+    common::normal_cdf,
+    correlation::pairwise_correlation,
+    enums::{ClRankType, Errors},
+    kernels, MPTCalculator,
+};
+
+///the performance persistence statistics of the funds between the consecutive windows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PersistenceReport {
+    ///window_returns[k][j] is the compound return of the fund j in the window k.
+    pub window_returns: Vec<Vec<f64>>,
+    ///the spearman rank correlation of the window returns of each window and its next window.
+    pub rank_correlations: Vec<f64>,
+    ///the average of the finite rank correlations.
+    pub average_rank_correlation: f64,
+    ///the numbers of the funds above the median in both windows, above then not above, not above then above, and
+    ///not above in both windows, they are summed over all the consecutive windows.
+    pub winner_winner: usize,
+    pub winner_loser: usize,
+    pub loser_winner: usize,
+    pub loser_loser: usize,
+    ///(winner_winner * loser_loser) / (winner_loser * loser_winner), it is larger than 1 if the performance persists.
+    pub cross_product_ratio: f64,
+    ///the chi-square statistic of the contingency table with 1 degree of freedom.
+    pub chi_square: f64,
+    ///the p value of the chi-square statistic.
+    pub p_value: f64,
+}
+
+impl PersistenceReport {
+    pub fn new() -> PersistenceReport {
+        PersistenceReport {
+            window_returns: Vec::new(),
+            rank_correlations: Vec::new(),
+            average_rank_correlation: f64::NAN,
+            winner_winner: 0,
+            winner_loser: 0,
+            loser_winner: 0,
+            loser_loser: 0,
+            cross_product_ratio: f64::NAN,
+            chi_square: f64::NAN,
+            p_value: f64::NAN,
+        }
+    }
+}
+
+impl Default for PersistenceReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///the winners of the returns, they are above the median of the finite returns.
+fn winners(returns: &[f64]) -> Vec<bool> {
+    let mut median = f64::NAN;
+    kernels::percentile(returns, 50, &mut median);
+    returns.iter().map(|x| *x > median).collect()
+}
+
+///test the performance persistence of the funds. the history is split into the consecutive windows of the window
+///length from the first period, the remaining periods less than a window are ignored. the funds are ranked by the
+///compound returns of each window, and the persistence of each window to its next window is measured by the spearman
+///rank correlation and the winner loser contingency table, a winner is above the median of the funds. the funds
+///without the finite returns in both windows are excluded from the pair of windows.
+///
+///# Arguments
+///funds: the returns of each fund, all the funds should have the same length.
+///
+///window: the number of the periods of a window, there should be 2 windows at least.
+///
+///report: the persistence statistics.
+///# Examples
+///```
+///use mpt_lib::enums::Errors;
+///use mpt_lib::persistence::{self, PersistenceReport};
+///
+///let fund1 = vec![3.0, 2.0, 2.5, 3.0];
+///let fund2 = vec![2.0, 1.0, 1.5, 1.0];
+///let fund3 = vec![-1.0, 0.5, 0.0, -0.5];
+///let fund4 = vec![0.0, -2.0, -1.0, 0.5];
+///let mut report = PersistenceReport::new();
+///let err = persistence::persistence_tests(&[&fund1, &fund2, &fund3, &fund4], 2, &mut report);
+///assert_eq!(
+///    err == Errors::ClErrorCodeNoError
+///        && report.window_returns.len() == 2
+///        && report.winner_winner == 2
+///        && report.loser_loser == 2
+///        && report.rank_correlations[0] > 0.5,
+///    true
+///);
+///```
+pub fn persistence_tests(
+    funds: &[&[f64]],
+    window: usize,
+    report: &mut PersistenceReport,
+) -> Errors {
+    *report = PersistenceReport::new();
+    if funds.len() < 2
+        || funds.iter().any(|x| x.len() != funds[0].len())
+        || window == 0
+        || funds[0].len() / window < 2
+    {
+        return Errors::ClErrorCodeInvalidPara;
+    }
+    instrument!(
+        "persistence_tests",
+        rows = funds[0].len(),
+        cols = funds.len()
+    );
+
+    let windows = funds[0].len() / window;
+    report.window_returns = (0..windows)
+        .map(|k| {
+            funds
+                .iter()
+                .map(|x| {
+                    let mut res = f64::NAN;
+                    kernels::total_return(&x[k * window..(k + 1) * window], &mut res);
+                    res
+                })
+                .collect()
+        })
+        .collect();
+
+    for pair in report.window_returns.windows(2) {
+        let (current, next): (Vec<f64>, Vec<f64>) = pair[0]
+            .iter()
+            .zip(pair[1].iter())
+            .filter(|x| x.0.is_finite() && x.1.is_finite())
+            .unzip();
+        let mut current_ranks = vec![f64::NAN; current.len()];
+        let mut next_ranks = vec![f64::NAN; next.len()];
+        MPTCalculator::from_v(&current).rank(ClRankType::ClRankTypeAsc as i16, &mut current_ranks);
+        MPTCalculator::from_v(&next).rank(ClRankType::ClRankTypeAsc as i16, &mut next_ranks);
+        report
+            .rank_correlations
+            .push(pairwise_correlation(&current_ranks, &next_ranks, 3).0);
+
+        for (was, is) in winners(&current).iter().zip(winners(&next).iter()) {
+            match (was, is) {
+                (true, true) => report.winner_winner += 1,
+                (true, false) => report.winner_loser += 1,
+                (false, true) => report.loser_winner += 1,
+                (false, false) => report.loser_loser += 1,
+            }
+        }
+    }
+
+    let correlations: Vec<f64> = report
+        .rank_correlations
+        .iter()
+        .filter(|x| x.is_finite())
+        .copied()
+        .collect();
+    if correlations.len() > 0 {
+        report.average_rank_correlation =
+            correlations.iter().sum::<f64>() / correlations.len() as f64;
+    }
+
+    let (ww, wl, lw, ll) = (
+        report.winner_winner as f64,
+        report.winner_loser as f64,
+        report.loser_winner as f64,
+        report.loser_loser as f64,
+    );
+    if wl * lw > 0.0 {
+        report.cross_product_ratio = ww * ll / (wl * lw);
+    } else if ww * ll > 0.0 {
+        report.cross_product_ratio = f64::INFINITY;
+    }
+    let margins = (ww + wl) * (lw + ll) * (ww + lw) * (wl + ll);
+    if margins > 0.0 {
+        report.chi_square = (ww + wl + lw + ll) * (ww * ll - wl * lw).powi(2) / margins;
+        report.p_value = 2.0 * (1.0 - normal_cdf(report.chi_square.sqrt()));
+    }
+    return Errors::ClErrorCodeNoError;
+}
+
+#[cfg(test)]
+mod test {
+    use super::{persistence_tests, PersistenceReport};
+    use crate::{enums::Errors, MPTCalculator};
+
+    #[test]
+    fn should_correct_persistence_tests() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+        ];
+        //the funds with the persistent excess returns, and a fund without the first window
+        let mut funds: Vec<Vec<f64>> = (0..6)
+            .map(|j| data.iter().map(|x| x + 0.3 * j as f64).collect())
+            .collect();
+        funds[5][0] = f64::NAN;
+        let series: Vec<&[f64]> = funds.iter().map(|x| &x[..]).collect();
+        let mut report = PersistenceReport::new();
+        let err = persistence_tests(&series, 4, &mut report);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && report.window_returns.len() == 4
+                && report.window_returns[0][5].is_nan()
+                && report.rank_correlations.len() == 3
+                && MPTCalculator::is_eq_double(report.rank_correlations[0], 1.0)
+                && MPTCalculator::is_eq_double(report.average_rank_correlation, 1.0)
+                && report.winner_winner == 8
+                && report.loser_loser == 9
+                && report.winner_loser + report.loser_winner == 0
+                && report.cross_product_ratio.is_infinite()
+                && MPTCalculator::is_eq_double(report.chi_square, 17.0)
+                && report.p_value < 0.001,
+            true
+        );
+
+        //the reversal of the ranks
+        let reversed: Vec<Vec<f64>> = (0..4)
+            .map(|j| {
+                data[..8]
+                    .iter()
+                    .enumerate()
+                    .map(|(t, x)| {
+                        let sign = if t < 4 { 1.0 } else { -1.0 };
+                        x + sign * 0.3 * j as f64
+                    })
+                    .collect()
+            })
+            .collect();
+        let series: Vec<&[f64]> = reversed.iter().map(|x| &x[..]).collect();
+        let err = persistence_tests(&series, 4, &mut report);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(report.rank_correlations[0], -1.0)
+                && report.winner_loser == 2
+                && report.loser_winner == 2
+                && MPTCalculator::is_eq_double(report.cross_product_ratio, 0.0),
+            true
+        );
+
+        let err = persistence_tests(&series, 5, &mut report);
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && report.window_returns.len() == 0,
+            true
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use std::cmp::Ordering;
 
 use crate::{
-    enums::{ClRankType, Errors},
+    enums::{ClNanRankPolicy, ClRankType, ClTieBreak, Errors},
     MPTCalculator,
 };
 use math::round;
@@ -50,6 +50,50 @@ fn absolute_rank_internal(
     rank: &mut Vec<f64>,
     sort_values: &mut Vec<(f64, usize)>,
     is_asc: bool,
+) {
+    absolute_rank_by_tie_break(
+        values,
+        &[],
+        ClTieBreak::ClTieBreakShared,
+        rank,
+        sort_values,
+        is_asc,
+    );
+}
+
+///the order of two values of the rank, the NAN/INF values are always after the valid values in the descending
+///order, the equal values are ordered by the secondary key for ClTieBreakSecondaryKey. the sort is stable, so the
+///values still equal keep the input order.
+fn rank_order(
+    a: &(f64, usize),
+    b: &(f64, usize),
+    secondary_key: &[f64],
+    tie_break: ClTieBreak,
+    is_asc: bool,
+) -> Ordering {
+    let o = if is_asc {
+        a.0.total_cmp(&b.0)
+    } else {
+        match (a.0.is_finite(), b.0.is_finite()) {
+            (true, true) => b.0.total_cmp(&a.0),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => Ordering::Equal,
+        }
+    };
+    if o == Ordering::Equal && tie_break == ClTieBreak::ClTieBreakSecondaryKey {
+        return secondary_key[a.1].total_cmp(&secondary_key[b.1]);
+    }
+    return o;
+}
+
+fn absolute_rank_by_tie_break(
+    values: &[f64],
+    secondary_key: &[f64],
+    tie_break: ClTieBreak,
+    rank: &mut Vec<f64>,
+    sort_values: &mut Vec<(f64, usize)>,
+    is_asc: bool,
 ) {
     values
         .iter()
@@ -61,18 +105,18 @@ fn absolute_rank_internal(
         })
         .count();
 
-    if is_asc {
-        sort_values.sort_by(|a, b| a.0.total_cmp(&b.0))
-    } else {
-        sort_values.sort_by(|a, b| {
-            if a.0.is_finite() && b.0.is_finite() {
-                b.0.total_cmp(&a.0)
-            } else if !b.0.is_finite() {
-                Ordering::Less
+    sort_values.sort_by(|a, b| rank_order(a, b, secondary_key, tie_break, is_asc));
+    if tie_break != ClTieBreak::ClTieBreakShared {
+        let mut rank_value = 0.0;
+        sort_values.iter().for_each(|v| {
+            if v.0.is_finite() {
+                rank_value += 1.0;
+                rank[v.1] = rank_value;
             } else {
-                Ordering::Equal
+                rank[v.1] = f64::NAN;
             }
-        })
+        });
+        return;
     }
 
     /*let mut rank_value = 1.0;
@@ -125,6 +169,8 @@ fn absolute_rank_internal(
 
 fn rank_internal(
     values: &[f64],
+    secondary_key: &[f64],
+    tie_break: ClTieBreak,
     rank_type_enum: &Option<ClRankType>,
     rank_vec: &mut Vec<f64>,
     sort_values: &mut Vec<(f64, usize)>,
@@ -150,14 +196,35 @@ fn rank_internal(
 
     match rank_type_enum {
         Some(ClRankType::ClRankTypeAsc) | Some(ClRankType::ClRankTypeDec) => {
-            absolute_rank_internal(values, rank_vec, sort_values, is_asc);
+            absolute_rank_by_tie_break(
+                values,
+                secondary_key,
+                tie_break,
+                rank_vec,
+                sort_values,
+                is_asc,
+            );
         }
         Some(ClRankType::ClRankTypePercAsc) | Some(ClRankType::ClRankTypePercDec) => {
-            absolute_rank_internal(values, rank_vec, sort_values, is_asc);
+            absolute_rank_by_tie_break(
+                values,
+                secondary_key,
+                tie_break,
+                rank_vec,
+                sort_values,
+                is_asc,
+            );
             percentile_rank(rank_vec);
         }
         Some(ClRankType::ClRankTypeDecAsc) | Some(ClRankType::ClRankTypeDecDec) => {
-            absolute_rank_internal(values, rank_vec, sort_values, is_asc);
+            absolute_rank_by_tie_break(
+                values,
+                secondary_key,
+                tie_break,
+                rank_vec,
+                sort_values,
+                is_asc,
+            );
             percentile_rank(rank_vec);
             rank_vec
                 .iter_mut()
@@ -165,7 +232,14 @@ fn rank_internal(
                 .for_each(|x| *x = round::ceil(*x / 10.0, 0))
         }
         Some(ClRankType::ClRankTypeQuinAsc) | Some(ClRankType::ClRankTypeQuinDec) => {
-            absolute_rank_internal(values, rank_vec, sort_values, is_asc);
+            absolute_rank_by_tie_break(
+                values,
+                secondary_key,
+                tie_break,
+                rank_vec,
+                sort_values,
+                is_asc,
+            );
             percentile_rank(rank_vec);
             rank_vec
                 .iter_mut()
@@ -173,7 +247,14 @@ fn rank_internal(
                 .for_each(|x| *x = round::ceil(*x / 20.0, 0))
         }
         Some(ClRankType::ClRankTypeQuartAsc) | Some(ClRankType::ClRankTypeQuartDec) => {
-            absolute_rank_internal(values, rank_vec, sort_values, is_asc);
+            absolute_rank_by_tie_break(
+                values,
+                secondary_key,
+                tie_break,
+                rank_vec,
+                sort_values,
+                is_asc,
+            );
             percentile_rank(rank_vec);
             rank_vec
                 .iter_mut()
@@ -463,6 +544,8 @@ impl<'a> MPTCalculator<'a> {
         let mut sort_values: Vec<(f64, usize)> = Vec::new();
         let ret = rank_internal(
             self.values,
+            &[],
+            ClTieBreak::ClTieBreakShared,
             &rank_type_enum,
             &mut rank_vec,
             &mut sort_values,
@@ -557,6 +640,50 @@ impl<'a> MPTCalculator<'a> {
     ///);
     ///```
     pub fn rank(&self, rank_type: i16, rank: &mut [f64]) -> Errors {
+        return self.rank_by_tie_break(rank_type, ClTieBreak::ClTieBreakShared, &[], rank);
+    }
+
+    ///calculate the rank series for a series with the tie-breaking of the equal values, so the ranks are the
+    ///same between runs. ClTieBreakShared gives the equal values the same rank, ClTieBreakStable gives the equal
+    ///values the consecutive ranks by the input order, and ClTieBreakSecondaryKey gives the equal values the
+    ///consecutive ranks by the ascending secondary key and then by the input order. the ranks of the NAN/INF
+    ///values are NAN for ClTieBreakStable and ClTieBreakSecondaryKey.
+    ///
+    ///# Arguments
+    ///rank_type is the rank type
+    ///
+    ///tie_break is the tie-breaking of the equal values.
+    ///
+    ///secondary_key is the secondary key of each value, it is only used by ClTieBreakSecondaryKey.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{ClRankType, ClTieBreak, Errors};
+    ///let data = vec![3.0, 1.0, 3.0, 2.0, 3.0];
+    ///let fund_ids = vec![30.0, 10.0, 10.0, 20.0, 20.0];
+    ///let mut res = [f64::NAN; 5];
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.rank_by_tie_break(
+    ///    ClRankType::ClRankTypeDec as i16,
+    ///    ClTieBreak::ClTieBreakSecondaryKey,
+    ///    &fund_ids,
+    ///    &mut res,
+    ///);
+    ///assert_eq!(err == Errors::ClErrorCodeNoError && res == [3.0, 5.0, 1.0, 4.0, 2.0], true);
+    ///```
+    pub fn rank_by_tie_break(
+        &self,
+        rank_type: i16,
+        tie_break: ClTieBreak,
+        secondary_key: &[f64],
+        rank: &mut [f64],
+    ) -> Errors {
+        if tie_break == ClTieBreak::ClTieBreakSecondaryKey
+            && secondary_key.len() != self.values.len()
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
         let rank_type_enum = ClRankType::try_from(rank_type).ok();
         match rank_type_enum {
             Some(ClRankType::ClRankTypeNoRank) | Some(ClRankType::ClRankTypeRaw) => {
@@ -570,6 +697,8 @@ impl<'a> MPTCalculator<'a> {
         let mut sort_values: Vec<(f64, usize)> = Vec::new();
         let ret = rank_internal(
             self.values,
+            secondary_key,
+            tie_break,
             &rank_type_enum,
             &mut rank_vec,
             &mut sort_values,
@@ -584,6 +713,105 @@ impl<'a> MPTCalculator<'a> {
         return ret;
     }
 
+    ///calculate the rank series for a series with the policy of the NAN/INF values, e.g. the funds with
+    ///insufficient history. ClNanExcludeAndRenumber ranks the valid values as if the NAN/INF values were not in
+    ///the universe and their ranks are NAN, ClNanRankLast also gives the NAN/INF values the last rank, it is the
+    ///valid value count + 1 for the absolute ranks and the last group for the other ranks, ClNanError returns
+    ///ClErrorCodeInvalidValue if there is any NAN/INF value.
+    ///
+    ///# Arguments
+    ///rank_type is the rank type
+    ///
+    ///tie_break is the tie-breaking of the equal values.
+    ///
+    ///secondary_key is the secondary key of each value, it is only used by ClTieBreakSecondaryKey.
+    ///
+    ///nan_policy is the policy of the NAN/INF values.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{ClNanRankPolicy, ClRankType, ClTieBreak, Errors};
+    ///let data = vec![3.0, f64::NAN, 1.0, 2.0];
+    ///let mut res = [f64::NAN; 4];
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.rank_by_nan_policy(
+    ///    ClRankType::ClRankTypeDec as i16,
+    ///    ClTieBreak::ClTieBreakShared,
+    ///    &[],
+    ///    ClNanRankPolicy::ClNanRankLast,
+    ///    &mut res,
+    ///);
+    ///assert_eq!(err == Errors::ClErrorCodeNoError && res == [1.0, 4.0, 3.0, 2.0], true);
+    ///```
+    pub fn rank_by_nan_policy(
+        &self,
+        rank_type: i16,
+        tie_break: ClTieBreak,
+        secondary_key: &[f64],
+        nan_policy: ClNanRankPolicy,
+        rank: &mut [f64],
+    ) -> Errors {
+        if rank.len() != self.values.len()
+            || (tie_break == ClTieBreak::ClTieBreakSecondaryKey
+                && secondary_key.len() != self.values.len())
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let last_rank = match ClRankType::try_from(rank_type).ok() {
+            Some(ClRankType::ClRankTypeNoRank) | Some(ClRankType::ClRankTypeRaw) => {
+                return self.rank_by_tie_break(rank_type, tie_break, secondary_key, rank);
+            }
+            Some(ClRankType::ClRankTypeAsc) | Some(ClRankType::ClRankTypeDec) => {
+                self.values.iter().filter(|x| x.is_finite()).count() as f64 + 1.0
+            }
+            Some(ClRankType::ClRankTypePercAsc) | Some(ClRankType::ClRankTypePercDec) => 100.0,
+            Some(ClRankType::ClRankTypeDecAsc) | Some(ClRankType::ClRankTypeDecDec) => 10.0,
+            Some(ClRankType::ClRankTypeQuinAsc) | Some(ClRankType::ClRankTypeQuinDec) => 5.0,
+            Some(ClRankType::ClRankTypeQuartAsc) | Some(ClRankType::ClRankTypeQuartDec) => 4.0,
+            None => return Errors::ClErrorCodeInvalidPara,
+        };
+
+        rank.fill(f64::NAN);
+        let valid: Vec<usize> = (0..self.values.len())
+            .filter(|i| self.values[*i].is_finite())
+            .collect();
+        if valid.len() < self.values.len() && nan_policy == ClNanRankPolicy::ClNanError {
+            return Errors::ClErrorCodeInvalidValue;
+        }
+        if valid.len() == 0 {
+            if nan_policy == ClNanRankPolicy::ClNanRankLast {
+                rank.fill(last_rank);
+            }
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let values: Vec<f64> = valid.iter().map(|i| self.values[*i]).collect();
+        let keys: Vec<f64> = if tie_break == ClTieBreak::ClTieBreakSecondaryKey {
+            valid.iter().map(|i| secondary_key[*i]).collect()
+        } else {
+            Vec::new()
+        };
+        let mut valid_rank = vec![f64::NAN; values.len()];
+        let ret = MPTCalculator::from_v(&values).rank_by_tie_break(
+            rank_type,
+            tie_break,
+            &keys,
+            &mut valid_rank,
+        );
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        if nan_policy == ClNanRankPolicy::ClNanRankLast {
+            rank.fill(last_rank);
+        }
+        valid
+            .iter()
+            .zip(valid_rank.iter())
+            .for_each(|(i, r)| rank[*i] = *r);
+        return Errors::ClErrorCodeNoError;
+    }
+
     ///calculate the position of a value in a series.
     ///
     ///# Arguments
@@ -694,6 +922,63 @@ impl<'a> MPTCalculator<'a> {
         });
         return Errors::ClErrorCodeNoError;
     }
+
+    ///calculate the breakpoints of the percentile, decile, quintile or quartile groups of a universe, the
+    ///breakpoint of a group is the last value of the group by the rank order, so a categorization table can be
+    ///produced directly. the groups without any value are NAN.
+    ///
+    ///# Arguments
+    ///rank_type is the rank type, it should be a percentile, decile, quintile or quartile rank type.
+    ///
+    ///break_points is the breakpoint of each group, it should have 100, 10, 5 or 4 elements at least.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{ClRankType, Errors};
+    ///let data = vec![5.0, 1.0, 8.0, 3.0, 2.0, f64::NAN, 7.0, 4.0, 6.0];
+    ///let mut res = [f64::NAN; 4];
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.rank_breakpoints(ClRankType::ClRankTypeQuartAsc as i16, &mut res);
+    ///assert_eq!(err == Errors::ClErrorCodeNoError && res == [3.0, 5.0, 7.0, 8.0], true);
+    ///```
+    pub fn rank_breakpoints(&self, rank_type: i16, break_points: &mut [f64]) -> Errors {
+        let (groups, is_asc) = match ClRankType::try_from(rank_type).ok() {
+            Some(ClRankType::ClRankTypePercAsc) => (100, true),
+            Some(ClRankType::ClRankTypePercDec) => (100, false),
+            Some(ClRankType::ClRankTypeDecAsc) => (10, true),
+            Some(ClRankType::ClRankTypeDecDec) => (10, false),
+            Some(ClRankType::ClRankTypeQuinAsc) => (5, true),
+            Some(ClRankType::ClRankTypeQuinDec) => (5, false),
+            Some(ClRankType::ClRankTypeQuartAsc) => (4, true),
+            Some(ClRankType::ClRankTypeQuartDec) => (4, false),
+            _ => return Errors::ClErrorCodeInvalidPara,
+        };
+        if break_points.len() < groups {
+            return Errors::ClErrorCodeInvalidOutput;
+        }
+        break_points.fill(f64::NAN);
+
+        let mut rank_vec = vec![f64::NAN; self.values.len()];
+        let ret = self.rank(rank_type, &mut rank_vec);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        rank_vec
+            .iter()
+            .zip(self.values.iter())
+            .filter(|x| x.0.is_finite() && *x.0 >= 1.0 && x.1.is_finite())
+            .for_each(|(rank, value)| {
+                let pos = (*rank as usize - 1).min(groups - 1);
+                if !break_points[pos].is_finite()
+                    || (is_asc && *value > break_points[pos])
+                    || (!is_asc && *value < break_points[pos])
+                {
+                    break_points[pos] = *value;
+                }
+            });
+        return Errors::ClErrorCodeNoError;
+    }
 }
 
 #[cfg(test)]
@@ -706,6 +991,8 @@ mod test {
     };
 
     #[test]
+    //the rounding inputs are not pi
+    #[allow(clippy::approx_constant)]
     fn test_ceil() {
         let rounded = round::ceil(3.14159, 3);
         assert_eq!(rounded, 3.142);
@@ -1005,4 +1292,189 @@ mod test {
             true
         );
     }
+
+    #[test]
+    fn should_correct_rank_breakpoints() {
+        let mut data = vec![
+            0.0, -1.55258, -1.65468, -2.52226, -3.34412, -3.34773, -3.45821, -3.69844, -3.73832,
+            -3.79032, -3.85064, -3.96988, -3.99454, -4.00891, -4.01786, -4.05028, -4.07808,
+            -4.20223, -4.21607, -4.23729,
+        ];
+        let mpt = MPTCalculator::from_v(&data);
+        let mut res = [f64::NAN; 100];
+        let mut expected = [f64::NAN; 100];
+        let err = mpt.rank_breakpoints(enums::ClRankType::ClRankTypePercDec as i16, &mut res);
+        mpt.percentile_rank_breakpoints(&[], false, &mut expected);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && res
+                    .iter()
+                    .zip(expected.iter())
+                    .all(|x| x.0 == x.1 || (x.0.is_nan() && x.1.is_nan())),
+            true
+        );
+
+        data.push(f64::NAN);
+        let mpt = MPTCalculator::from_v(&data);
+        let mut res = [f64::NAN; 5];
+        let err = mpt.rank_breakpoints(enums::ClRankType::ClRankTypeQuinDec as i16, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && res == [-3.34412, -3.73832, -3.99454, -4.07808, -4.23729],
+            true
+        );
+        let err = mpt.rank_breakpoints(enums::ClRankType::ClRankTypeQuinAsc as i16, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && res == [-4.05028, -3.96988, -3.69844, -2.52226, 0.0],
+            true
+        );
+
+        let err = mpt.rank_breakpoints(enums::ClRankType::ClRankTypeDecAsc as i16, &mut res);
+        assert_eq!(err, Errors::ClErrorCodeInvalidOutput);
+        let err = mpt.rank_breakpoints(enums::ClRankType::ClRankTypeAsc as i16, &mut res);
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+
+    #[test]
+    fn should_correct_rank_by_tie_break() {
+        let data = vec![2.0, f64::NAN, 1.0, 2.0, 2.0, 3.0];
+        let fund_ids = vec![6.0, 1.0, 5.0, 2.0, 4.0, 3.0];
+        let mpt = MPTCalculator::from_v(&data);
+        let mut res = [f64::NAN; 6];
+        let mut expected = [f64::NAN; 6];
+
+        let err = mpt.rank_by_tie_break(
+            enums::ClRankType::ClRankTypeAsc as i16,
+            enums::ClTieBreak::ClTieBreakShared,
+            &[],
+            &mut res,
+        );
+        mpt.rank(enums::ClRankType::ClRankTypeAsc as i16, &mut expected);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && res
+                    .iter()
+                    .zip(expected.iter())
+                    .all(|x| x.0 == x.1 || (x.0.is_nan() && x.1.is_nan())),
+            true
+        );
+
+        let err = mpt.rank_by_tie_break(
+            enums::ClRankType::ClRankTypeAsc as i16,
+            enums::ClTieBreak::ClTieBreakStable,
+            &[],
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && res[1].is_nan()
+                && [res[0], res[2], res[3], res[4], res[5]] == [2.0, 1.0, 3.0, 4.0, 5.0],
+            true
+        );
+
+        let err = mpt.rank_by_tie_break(
+            enums::ClRankType::ClRankTypeDec as i16,
+            enums::ClTieBreak::ClTieBreakSecondaryKey,
+            &fund_ids,
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && res[1].is_nan()
+                && [res[0], res[2], res[3], res[4], res[5]] == [4.0, 5.0, 2.0, 3.0, 1.0],
+            true
+        );
+
+        let shuffled = vec![2.0, 3.0, 2.0, 1.0, f64::NAN, 2.0];
+        let shuffled_ids = vec![4.0, 3.0, 6.0, 5.0, 1.0, 2.0];
+        let mut shuffled_res = [f64::NAN; 6];
+        MPTCalculator::from_v(&shuffled).rank_by_tie_break(
+            enums::ClRankType::ClRankTypeDec as i16,
+            enums::ClTieBreak::ClTieBreakSecondaryKey,
+            &shuffled_ids,
+            &mut shuffled_res,
+        );
+        assert_eq!(
+            fund_ids.iter().enumerate().all(|(i, id)| {
+                let j = shuffled_ids.iter().position(|x| x == id).unwrap();
+                res[i] == shuffled_res[j] || (res[i].is_nan() && shuffled_res[j].is_nan())
+            }),
+            true
+        );
+
+        let err = mpt.rank_by_tie_break(
+            enums::ClRankType::ClRankTypeDec as i16,
+            enums::ClTieBreak::ClTieBreakSecondaryKey,
+            &fund_ids[1..],
+            &mut res,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+
+    #[test]
+    fn should_correct_rank_by_nan_policy() {
+        let data = vec![2.0, f64::NAN, 1.0, f64::INFINITY, 4.0, 3.0];
+        let valid = vec![2.0, 1.0, 4.0, 3.0];
+        let mpt = MPTCalculator::from_v(&data);
+        let mut res = [f64::NAN; 6];
+        let mut expected = [f64::NAN; 4];
+
+        let err = mpt.rank_by_nan_policy(
+            enums::ClRankType::ClRankTypePercAsc as i16,
+            enums::ClTieBreak::ClTieBreakShared,
+            &[],
+            enums::ClNanRankPolicy::ClNanExcludeAndRenumber,
+            &mut res,
+        );
+        MPTCalculator::from_v(&valid)
+            .rank(enums::ClRankType::ClRankTypePercAsc as i16, &mut expected);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && res[1].is_nan()
+                && res[3].is_nan()
+                && [res[0], res[2], res[4], res[5]] == expected,
+            true
+        );
+
+        let err = mpt.rank_by_nan_policy(
+            enums::ClRankType::ClRankTypeQuartDec as i16,
+            enums::ClTieBreak::ClTieBreakStable,
+            &[],
+            enums::ClNanRankPolicy::ClNanRankLast,
+            &mut res,
+        );
+        MPTCalculator::from_v(&valid)
+            .rank(enums::ClRankType::ClRankTypeQuartDec as i16, &mut expected);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && res[1] == 4.0
+                && res[3] == 4.0
+                && [res[0], res[2], res[4], res[5]] == expected,
+            true
+        );
+
+        let err = mpt.rank_by_nan_policy(
+            enums::ClRankType::ClRankTypeAsc as i16,
+            enums::ClTieBreak::ClTieBreakShared,
+            &[],
+            enums::ClNanRankPolicy::ClNanError,
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidValue && res.iter().all(|x| x.is_nan()),
+            true
+        );
+
+        let empty = vec![f64::NAN; 2];
+        let mut res = [0.0; 2];
+        let err = MPTCalculator::from_v(&empty).rank_by_nan_policy(
+            enums::ClRankType::ClRankTypeAsc as i16,
+            enums::ClTieBreak::ClTieBreakShared,
+            &[],
+            enums::ClNanRankPolicy::ClNanRankLast,
+            &mut res,
+        );
+        assert_eq!(err == Errors::ClErrorCodeNoError && res == [1.0, 1.0], true);
+    }
 }
@@ -0,0 +1,277 @@
+use crate::{
+    common::{get_annual_multiplier, is_valid_frequency},
+    enums::{self, Errors},
+    MPTCalculator,
+};
+
+///the variance of one period in log price of the range based estimators, NAN if any price is not positive.
+fn range_variance(
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+    method: enums::ClVolatilityMethod,
+) -> f64 {
+    if !(open > 0.0 && high > 0.0 && low > 0.0 && close > 0.0) {
+        return f64::NAN;
+    }
+    match method {
+        enums::ClVolatilityMethod::ClVolatilityParkinson => {
+            (high / low).ln().powi(2) / (4.0 * 2.0_f64.ln())
+        }
+        enums::ClVolatilityMethod::ClVolatilityGarmanKlass => {
+            0.5 * (high / low).ln().powi(2)
+                - (2.0 * 2.0_f64.ln() - 1.0) * (close / open).ln().powi(2)
+        }
+        enums::ClVolatilityMethod::ClVolatilityRogersSatchell => {
+            (high / close).ln() * (high / open).ln() + (low / close).ln() * (low / open).ln()
+        }
+        enums::ClVolatilityMethod::ClVolatilityCloseToClose => f64::NAN,
+    }
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///calculate the annualized realized volatility of the close prices in values, if any price has NAN/INF values,
+    ///the result will be NAN.
+    ///the close to close method is the same as volatility and ignores the open, high and low prices,
+    ///the range based methods use the open, high and low prices of each period as well.
+    ///# Arguments
+    ///open: the open price of each period, the same size as the values, can be empty for close to close.
+    ///
+    ///high: the high price of each period, the same size as the values, can be empty for close to close.
+    ///
+    ///low: the low price of each period, the same size as the values, can be empty for close to close.
+    ///
+    ///freq: the frequence of source data.
+    ///
+    ///method: the volatility estimator.
+    ///
+    ///result: the annualized volatility, in percent.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let open = vec![100.0, 101.5, 100.8, 102.2, 103.0, 101.9];
+    ///let high = vec![102.0, 102.3, 102.9, 103.8, 103.5, 103.1];
+    ///let low = vec![99.5, 100.6, 100.1, 101.7, 101.2, 101.0];
+    ///let close = vec![101.4, 100.9, 102.5, 102.8, 101.6, 102.7];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&close);
+    ///let err = mpt.realized_volatility(
+    ///    &open,
+    ///    &high,
+    ///    &low,
+    ///    enums::ClFrequency::ClFrequencyDaily,
+    ///    enums::ClVolatilityMethod::ClVolatilityParkinson,
+    ///    &mut res,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 21.247045),
+    ///    true
+    ///);
+    ///```
+    pub fn realized_volatility(
+        &self,
+        open: &[f64],
+        high: &[f64],
+        low: &[f64],
+        freq: enums::ClFrequency,
+        method: enums::ClVolatilityMethod,
+        result: &mut f64,
+    ) -> Errors {
+        if method == enums::ClVolatilityMethod::ClVolatilityCloseToClose {
+            return self.volatility(freq, result);
+        }
+        if self.values.len() == 0
+            || !is_valid_frequency(freq)
+            || open.len() != self.values.len()
+            || high.len() != self.values.len()
+            || low.len() != self.values.len()
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *result = f64::NAN;
+
+        let mut sum = 0.0;
+        for i in 0..self.values.len() {
+            let variance = range_variance(open[i], high[i], low[i], self.values[i], method);
+            if !variance.is_finite() {
+                return Errors::ClErrorCodeNoError;
+            }
+            sum += variance;
+        }
+        let variance = sum / self.values.len() as f64 * get_annual_multiplier(freq, true);
+        if variance >= 0.0 {
+            *result = variance.sqrt() * 100.0;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the annualized realized volatility from the intraday returns in values, if the values has NAN/INF values,
+    ///the result will be NAN.
+    ///the values are split into days of periods_per_day returns, the realized variance of one day is the sum of
+    ///the squared returns aggregated over sampling periods, it is averaged over the sampling offsets to reduce
+    ///the microstructure noise and scaled to the full day when the first or last block is not complete,
+    ///the offsets without any complete block are skipped.
+    ///the daily realized variance is averaged over the days and annualized by days_per_year.
+    ///# Arguments
+    ///periods_per_day: the number of intraday returns of one day, the size of values should be a multiple of it.
+    ///
+    ///sampling: the number of intraday returns aggregated into one subsampled return, 1 means no subsampling.
+    ///
+    ///days_per_year: the number of trading days of one year, it can be from business_days_in_year.
+    ///
+    ///result: the annualized volatility, in percent.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///use mpt_lib::{business_days_in_year, HolidayList};
+    ///
+    ///let data = vec![
+    ///    0.12, -0.08, 0.05, 0.10, -0.15, 0.07, -0.04, 0.09, -0.11, 0.06, 0.03, -0.02,
+    ///];
+    ///let holidays = HolidayList::from(&[45292, 45306, 45341, 45439, 45477, 45537, 45624, 45651]);
+    ///let days_per_year = business_days_in_year(2024, Some(&holidays)) as f64;
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.realized_volatility_intraday(6, 1, days_per_year, &mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 3.331636),
+    ///    true
+    ///);
+    ///```
+    pub fn realized_volatility_intraday(
+        &self,
+        periods_per_day: usize,
+        sampling: usize,
+        days_per_year: f64,
+        result: &mut f64,
+    ) -> Errors {
+        if self.values.len() == 0
+            || periods_per_day == 0
+            || self.values.len() % periods_per_day != 0
+            || sampling == 0
+            || sampling > periods_per_day
+            || !(days_per_year > 0.0)
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *result = f64::NAN;
+        if self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let days = self.values.chunks(periods_per_day);
+        let count = days.len() as f64;
+        let variance = days
+            .map(|day| {
+                let offsets = sampling.min(periods_per_day - sampling + 1);
+                (0..offsets)
+                    .map(|offset| {
+                        let blocks: Vec<f64> = day[offset..]
+                            .chunks_exact(sampling)
+                            .map(|x| x.iter().sum::<f64>())
+                            .collect();
+                        blocks.iter().map(|x| x * x).sum::<f64>() * periods_per_day as f64
+                            / (blocks.len() * sampling) as f64
+                    })
+                    .sum::<f64>()
+                    / offsets as f64
+            })
+            .sum::<f64>()
+            / count;
+        *result = (variance * days_per_year).sqrt();
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        enums::{self, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_realized_volatility() {
+        let open = vec![100.0, 101.5, 100.8, 102.2, 103.0, 101.9];
+        let high = vec![102.0, 102.3, 102.9, 103.8, 103.5, 103.1];
+        let low = vec![99.5, 100.6, 100.1, 101.7, 101.2, 101.0];
+        let close = vec![101.4, 100.9, 102.5, 102.8, 101.6, 102.7];
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v(&close);
+        let err = mpt.realized_volatility(
+            &[],
+            &[],
+            &[],
+            enums::ClFrequency::ClFrequencyDaily,
+            enums::ClVolatilityMethod::ClVolatilityCloseToClose,
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 17.710793),
+            true
+        );
+
+        let err = mpt.realized_volatility(
+            &open,
+            &high,
+            &low,
+            enums::ClFrequency::ClFrequencyDaily,
+            enums::ClVolatilityMethod::ClVolatilityGarmanKlass,
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 22.327798),
+            true
+        );
+
+        let err = mpt.realized_volatility(
+            &open,
+            &high,
+            &low,
+            enums::ClFrequency::ClFrequencyDaily,
+            enums::ClVolatilityMethod::ClVolatilityRogersSatchell,
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 21.868641),
+            true
+        );
+
+        let err = mpt.realized_volatility(
+            &open[1..],
+            &high,
+            &low,
+            enums::ClFrequency::ClFrequencyDaily,
+            enums::ClVolatilityMethod::ClVolatilityParkinson,
+            &mut res,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+
+    #[test]
+    fn should_correct_realized_volatility_intraday() {
+        let data = vec![
+            0.12, -0.08, 0.05, 0.10, -0.15, 0.07, -0.04, 0.09, -0.11, 0.06, 0.03, -0.02,
+        ];
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.realized_volatility_intraday(6, 2, 252.0, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 1.835034),
+            true
+        );
+
+        let err = mpt.realized_volatility_intraday(6, 4, 252.0, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 1.842743),
+            true
+        );
+
+        let err = mpt.realized_volatility_intraday(5, 1, 252.0, &mut res);
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+}
@@ -0,0 +1,520 @@
+use crate::{
+    enums::{self, Errors},
+    kernels::check_sample_size,
+    MPTCalculator,
+};
+use std::f64::consts::PI;
+
+///the 2 state gaussian hidden markov model of the returns, the state 0 is the calm state with the lower volatility
+///and the state 1 is the turbulent state.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegimeModel {
+    ///the mean return of each state.
+    pub means: Vec<f64>,
+    ///the standard deviation of the returns of each state, it is not annualized.
+    pub volatilities: Vec<f64>,
+    ///transition[i][j] is the probability of the state j of the next period if the state of this period is i.
+    pub transition: Vec<Vec<f64>>,
+    ///probabilities[t][i] is the smoothed probability of the state i of the period t given all the returns.
+    pub probabilities: Vec<Vec<f64>>,
+    ///the log likelihood of the returns.
+    pub log_likelihood: f64,
+    ///the number of the em iterations.
+    pub iterations: usize,
+}
+
+impl RegimeModel {
+    pub fn new() -> RegimeModel {
+        RegimeModel {
+            means: Vec::new(),
+            volatilities: Vec::new(),
+            transition: Vec::new(),
+            probabilities: Vec::new(),
+            log_likelihood: f64::NAN,
+            iterations: 0,
+        }
+    }
+}
+
+impl Default for RegimeModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn normal_density(x: f64, mean: f64, volatility: f64) -> f64 {
+    let z = (x - mean) / volatility;
+    (-0.5 * z * z).exp() / (volatility * (2.0 * PI).sqrt())
+}
+
+///the scaled forward backward algorithm, return the smoothed state probabilities, the expected transitions and the
+///log likelihood.
+fn forward_backward(
+    values: &[f64],
+    initial: &[f64; 2],
+    means: &[f64; 2],
+    volatilities: &[f64; 2],
+    transition: &[[f64; 2]; 2],
+) -> (Vec<[f64; 2]>, [[f64; 2]; 2], f64) {
+    let n = values.len();
+    let densities: Vec<[f64; 2]> = values
+        .iter()
+        .map(|x| {
+            [
+                normal_density(*x, means[0], volatilities[0]).max(f64::MIN_POSITIVE),
+                normal_density(*x, means[1], volatilities[1]).max(f64::MIN_POSITIVE),
+            ]
+        })
+        .collect();
+
+    let mut alpha = vec![[0.0; 2]; n];
+    let mut scales = vec![0.0; n];
+    for t in 0..n {
+        for j in 0..2 {
+            let prior = if t == 0 {
+                initial[j]
+            } else {
+                alpha[t - 1][0] * transition[0][j] + alpha[t - 1][1] * transition[1][j]
+            };
+            alpha[t][j] = prior * densities[t][j];
+        }
+        scales[t] = alpha[t][0] + alpha[t][1];
+        alpha[t][0] /= scales[t];
+        alpha[t][1] /= scales[t];
+    }
+
+    let mut beta = vec![[1.0; 2]; n];
+    for t in (0..n - 1).rev() {
+        for i in 0..2 {
+            beta[t][i] = (0..2)
+                .map(|j| transition[i][j] * densities[t + 1][j] * beta[t + 1][j])
+                .sum::<f64>()
+                / scales[t + 1];
+        }
+    }
+
+    let probabilities: Vec<[f64; 2]> = (0..n)
+        .map(|t| {
+            let p = [alpha[t][0] * beta[t][0], alpha[t][1] * beta[t][1]];
+            let sum = p[0] + p[1];
+            [p[0] / sum, p[1] / sum]
+        })
+        .collect();
+    let mut transitions = [[0.0; 2]; 2];
+    for t in 0..n - 1 {
+        for i in 0..2 {
+            for j in 0..2 {
+                transitions[i][j] +=
+                    alpha[t][i] * transition[i][j] * densities[t + 1][j] * beta[t + 1][j]
+                        / scales[t + 1];
+            }
+        }
+    }
+    (
+        probabilities,
+        transitions,
+        scales.iter().map(|x| x.ln()).sum(),
+    )
+}
+
+///the minimum number of the periods of a volatility regime.
+const MIN_SEGMENT: usize = 5;
+
+///the cost of the segments of the returns, it is the negative gaussian log likelihood of the variance of a segment
+///around the mean of all the returns, without the constant.
+struct VarianceCost {
+    sums: Vec<f64>,
+    floor: f64,
+}
+
+impl VarianceCost {
+    fn new(values: &[f64]) -> VarianceCost {
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let mut sums = vec![0.0; values.len() + 1];
+        for (t, x) in values.iter().enumerate() {
+            sums[t + 1] = sums[t] + (x - mean) * (x - mean);
+        }
+        let floor = sums[values.len()] / values.len() as f64 * 1e-6;
+        VarianceCost { sums, floor }
+    }
+
+    ///the cost of the periods from begin to end, end is exclusive.
+    fn cost(&self, begin: usize, end: usize) -> f64 {
+        let count = (end - begin) as f64;
+        let variance = (self.sums[end] - self.sums[begin]) / count;
+        count * variance.max(self.floor).ln()
+    }
+}
+
+///split the segment from begin to end at the best changepoint while the cost decreases more than the penalty.
+fn binary_segmentation(
+    cost: &VarianceCost,
+    begin: usize,
+    end: usize,
+    penalty: f64,
+    changepoints: &mut Vec<usize>,
+) {
+    if end - begin < 2 * MIN_SEGMENT {
+        return;
+    }
+    let (split, split_cost) = (begin + MIN_SEGMENT..=end - MIN_SEGMENT)
+        .map(|k| (k, cost.cost(begin, k) + cost.cost(k, end)))
+        .fold(
+            (0, f64::INFINITY),
+            |acc, x| if x.1 < acc.1 { x } else { acc },
+        );
+    if cost.cost(begin, end) - split_cost > penalty {
+        binary_segmentation(cost, begin, split, penalty, changepoints);
+        changepoints.push(split);
+        binary_segmentation(cost, split, end, penalty, changepoints);
+    }
+}
+
+///the optimal partition by the pruned exact linear time method.
+fn pelt(cost: &VarianceCost, n: usize, penalty: f64) -> Vec<usize> {
+    let mut best = vec![f64::INFINITY; n + 1];
+    let mut previous = vec![0; n + 1];
+    best[0] = -penalty;
+    let mut candidates = vec![0];
+    for t in MIN_SEGMENT..=n {
+        let costs: Vec<(usize, f64)> = candidates
+            .iter()
+            .filter(|s| t - **s >= MIN_SEGMENT)
+            .map(|s| (*s, best[*s] + cost.cost(*s, t)))
+            .collect();
+        for (s, value) in costs.iter() {
+            if value + penalty < best[t] {
+                best[t] = value + penalty;
+                previous[t] = *s;
+            }
+        }
+        candidates.retain(|s| {
+            costs
+                .iter()
+                .find(|x| x.0 == *s)
+                .map_or(true, |x| x.1 <= best[t])
+        });
+        if best[t].is_finite() {
+            candidates.push(t);
+        }
+    }
+
+    let mut changepoints = Vec::new();
+    if best[n].is_finite() {
+        let mut t = previous[n];
+        while t > 0 {
+            changepoints.push(t);
+            t = previous[t];
+        }
+    }
+    changepoints.reverse();
+    changepoints
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///fit a 2 state gaussian hidden markov model to the returns by the expectation maximization (baum welch)
+    ///algorithm, the probabilities of the states of each period and the mean and the volatility of each state allow
+    ///the statistics conditioned on the calm or the turbulent regime. the model starts from the states of the same
+    ///mean with the half and the one and a half standard deviation, and the volatility of a state is at least 1e-6
+    ///of the standard deviation of the returns. if the array has NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///max_iterations: the maximum number of the em iterations.
+    ///
+    ///tolerance: the iterations stop if the log likelihood improves less than the tolerance.
+    ///
+    ///model: the fitted model.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///use mpt_lib::regime::RegimeModel;
+    ///
+    ///let data = vec![
+    ///    0.5, 0.6, 0.4, 0.5, 0.7, 0.3, 0.5, 0.6, -6.0, 5.0, -4.0, 7.0, -5.0, 0.4, 0.6, 0.5, 0.4, 0.6,
+    ///];
+    ///let mut model = RegimeModel::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.fit_regimes(200, 1e-8, &mut model);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && model.volatilities[0] < model.volatilities[1]
+    ///        && model.probabilities[0][0] > 0.9
+    ///        && model.probabilities[9][1] > 0.9,
+    ///    true
+    ///);
+    ///```
+    pub fn fit_regimes(
+        &self,
+        max_iterations: usize,
+        tolerance: f64,
+        model: &mut RegimeModel,
+    ) -> Errors {
+        *model = RegimeModel::new();
+        let ret = check_sample_size(self.values.len(), 4);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        if max_iterations == 0 || !(tolerance >= 0.0) {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        instrument!("fit_regimes", rows = self.values.len());
+        let n = self.values.len();
+        model.means = vec![f64::NAN; 2];
+        model.volatilities = vec![f64::NAN; 2];
+        model.transition = vec![vec![f64::NAN; 2]; 2];
+        model.probabilities = vec![vec![f64::NAN; 2]; n];
+        if self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let mean = self.values.iter().sum::<f64>() / n as f64;
+        let deviation = (self
+            .values
+            .iter()
+            .map(|x| (x - mean) * (x - mean))
+            .sum::<f64>()
+            / n as f64)
+            .sqrt();
+        if deviation <= 0.0 {
+            return Errors::ClErrorCodeNoError;
+        }
+        let floor = deviation * 1e-6;
+        let mut initial = [0.5, 0.5];
+        let mut means = [mean, mean];
+        let mut volatilities = [deviation * 0.5, deviation * 1.5];
+        let mut transition = [[0.9, 0.1], [0.1, 0.9]];
+        let mut log_likelihood = f64::NEG_INFINITY;
+        for iteration in 1..=max_iterations {
+            let (probabilities, transitions, likelihood) =
+                forward_backward(self.values, &initial, &means, &volatilities, &transition);
+            model.iterations = iteration;
+            let improvement = likelihood - log_likelihood;
+            log_likelihood = likelihood;
+
+            initial = probabilities[0];
+            for i in 0..2 {
+                let weight: f64 = probabilities.iter().map(|x| x[i]).sum();
+                if weight <= 0.0 {
+                    continue;
+                }
+                means[i] = probabilities
+                    .iter()
+                    .zip(self.values.iter())
+                    .map(|(p, x)| p[i] * x)
+                    .sum::<f64>()
+                    / weight;
+                volatilities[i] = (probabilities
+                    .iter()
+                    .zip(self.values.iter())
+                    .map(|(p, x)| p[i] * (x - means[i]) * (x - means[i]))
+                    .sum::<f64>()
+                    / weight)
+                    .sqrt()
+                    .max(floor);
+                let row = transitions[i][0] + transitions[i][1];
+                if row > 0.0 {
+                    transition[i] = [transitions[i][0] / row, transitions[i][1] / row];
+                }
+            }
+            if improvement.abs() < tolerance {
+                break;
+            }
+        }
+        let (probabilities, _, log_likelihood) =
+            forward_backward(self.values, &initial, &means, &volatilities, &transition);
+
+        let order = if volatilities[0] <= volatilities[1] {
+            [0, 1]
+        } else {
+            [1, 0]
+        };
+        model.means = order.iter().map(|i| means[*i]).collect();
+        model.volatilities = order.iter().map(|i| volatilities[*i]).collect();
+        model.transition = order
+            .iter()
+            .map(|i| order.iter().map(|j| transition[*i][*j]).collect())
+            .collect();
+        model.probabilities = probabilities
+            .iter()
+            .map(|p| order.iter().map(|i| p[*i]).collect())
+            .collect();
+        model.log_likelihood = log_likelihood;
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///detect the changepoints of the volatility of the returns, the returns of each regime are normal around the
+    ///mean of all the returns with the variance of the regime, and a changepoint is added if it decreases the cost,
+    ///the negative log likelihood, more than the penalty. the binary segmentation splits the returns at the best
+    ///changepoint recursively, and the pelt finds the optimal changepoints exactly. a regime has 5 periods at least.
+    ///the changepoints can annotate the charts or restrict the statistic windows to the current regime. the input data
+    ///should sort by date. if the array has NAN/INF values, there is no changepoint.
+    ///
+    ///# Arguments
+    ///dates: the date of value
+    ///
+    ///method: the changepoint detection method.
+    ///
+    ///penalty: the penalty of a changepoint, it should be positive, e.g. 2 * ln(n) of the bic.
+    ///
+    ///changepoints: the first date of each regime after the first regime.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{ClChangepointMethod, Errors};
+    ///
+    ///let data = vec![
+    ///    0.5, -0.4, 0.3, -0.6, 0.4, -0.5, 0.6, -0.3, 5.0, -6.0, 4.0, -5.5, 6.5, -4.5, 5.5, -5.0,
+    ///];
+    ///let dates: Vec<i32> = (0..16).map(|x| 45000 + x * 30).collect();
+    ///let mut changepoints = Vec::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.detect_vol_changepoints(
+    ///    &dates,
+    ///    ClChangepointMethod::ClChangepointPelt,
+    ///    2.0 * 16.0_f64.ln(),
+    ///    &mut changepoints,
+    ///);
+    ///assert_eq!(err == Errors::ClErrorCodeNoError && changepoints == vec![45240], true);
+    ///```
+    pub fn detect_vol_changepoints(
+        &self,
+        dates: &[i32],
+        method: enums::ClChangepointMethod,
+        penalty: f64,
+        changepoints: &mut Vec<i32>,
+    ) -> Errors {
+        changepoints.clear();
+        if self.values.len() == 0
+            || dates.len() != self.values.len()
+            || !penalty.is_finite()
+            || penalty <= 0.0
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        instrument!("detect_vol_changepoints", rows = self.values.len());
+        if self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let n = self.values.len();
+        let cost = VarianceCost::new(self.values);
+        let indexes = match method {
+            enums::ClChangepointMethod::ClChangepointBinarySegmentation => {
+                let mut indexes = Vec::new();
+                binary_segmentation(&cost, 0, n, penalty, &mut indexes);
+                indexes
+            }
+            enums::ClChangepointMethod::ClChangepointPelt => pelt(&cost, n, penalty),
+        };
+        *changepoints = indexes.iter().map(|x| dates[*x]).collect();
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RegimeModel;
+    use crate::{
+        enums::{ClChangepointMethod, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_fit_regimes() {
+        let calm = vec![
+            0.27133, 1.24475, 1.34278, 0.13557, 0.61685, 0.9016, 1.40278, 0.51232, 0.4709, 0.77481,
+        ];
+        let turbulent = vec![
+            -9.68658, 4.22095, -6.7, -15.27331, 8.46123, 12.5, -10.32347, 6.5, -4.8, 9.9,
+        ];
+        let data: Vec<f64> = calm
+            .iter()
+            .chain(turbulent.iter())
+            .chain(calm.iter())
+            .copied()
+            .collect();
+        let mut model = RegimeModel::new();
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.fit_regimes(500, 1e-10, &mut model);
+        let calm_mean = calm.iter().sum::<f64>() / calm.len() as f64;
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && model.probabilities.len() == data.len()
+                && model.probabilities[..10].iter().all(|x| x[0] > 0.9)
+                && model.probabilities[10..20].iter().all(|x| x[1] > 0.9)
+                && model.probabilities[20..].iter().all(|x| x[0] > 0.9)
+                && model
+                    .probabilities
+                    .iter()
+                    .all(|x| MPTCalculator::is_eq_double(x[0] + x[1], 1.0))
+                && model
+                    .transition
+                    .iter()
+                    .all(|x| MPTCalculator::is_eq_double(x[0] + x[1], 1.0))
+                && model.transition[0][0] > 0.8
+                && (model.means[0] - calm_mean).abs() < 0.1
+                && model.volatilities[1] > model.volatilities[0] * 5.0
+                && model.log_likelihood.is_finite(),
+            true
+        );
+
+        let mut missing = data.clone();
+        missing[3] = f64::NAN;
+        let err = MPTCalculator::from_v(&missing).fit_regimes(500, 1e-10, &mut model);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && model.means[0].is_nan(),
+            true
+        );
+        let err = MPTCalculator::from_v(&data[..3]).fit_regimes(500, 1e-10, &mut model);
+        assert_eq!(err, Errors::ClErrorCodeInsufficientData);
+    }
+
+    #[test]
+    fn should_correct_detect_vol_changepoints() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502,
+        ];
+        let returns: Vec<f64> = data
+            .iter()
+            .map(|x| x * 0.2)
+            .chain(data.iter().rev().map(|x| x * 3.0))
+            .chain(data.iter().map(|x| x * 0.5))
+            .collect();
+        let dates: Vec<i32> = (0..returns.len() as i32).map(|x| 40000 + x).collect();
+        let penalty = 2.0 * (returns.len() as f64).ln();
+        let mpt = MPTCalculator::from_v(&returns);
+        for method in [
+            ClChangepointMethod::ClChangepointBinarySegmentation,
+            ClChangepointMethod::ClChangepointPelt,
+        ] {
+            let mut changepoints = Vec::new();
+            let err = mpt.detect_vol_changepoints(&dates, method, penalty, &mut changepoints);
+            assert_eq!(
+                err == Errors::ClErrorCodeNoError && changepoints == vec![40020, 40040],
+                true
+            );
+        }
+
+        let mut changepoints = vec![1];
+        let err = MPTCalculator::from_v(&data).detect_vol_changepoints(
+            &dates[..20],
+            ClChangepointMethod::ClChangepointPelt,
+            penalty,
+            &mut changepoints,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && changepoints.len() == 0,
+            true
+        );
+        let err = mpt.detect_vol_changepoints(
+            &dates,
+            ClChangepointMethod::ClChangepointPelt,
+            0.0,
+            &mut changepoints,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+}
@@ -0,0 +1,1055 @@
+use crate::enums::ClStatisticUnit;
+
+///the metadata of a statistic, so the front-ends can build the configuration screens from the registry.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatisticMetadata {
+    ///the name of the statistic, it is the name in StandardPack::values if the statistic is in the standard pack,
+    ///e.g. max_draw_down_peek_date, otherwise the name of the method of MPTCalculator.
+    pub name: &'static str,
+    pub display_name: &'static str,
+    ///the statistic needs the benchmark of the calculator.
+    pub requires_benchmark: bool,
+    ///the statistic needs the risk free of the calculator.
+    pub requires_riskfree: bool,
+    ///the statistic needs the dates of the values.
+    pub requires_dates: bool,
+    ///the statistic has the is_annu flag.
+    pub annualizable: bool,
+    pub unit: ClStatisticUnit,
+}
+
+///the metadata of the statistics, they are the names of StandardPack::values and the public statistics of
+///MPTCalculator in the absolute and relative statistics with a f64 result. the helpers with a comparator or a
+///statistic function and the deprecated names are not registered, nor the analytics of the other modules such as
+///the optimizer, the episodes and the array utilities.
+pub const STATISTICS: [StatisticMetadata; 107] = [
+    StatisticMetadata {
+        name: "average",
+        display_name: "Average",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "mean_geometric",
+        display_name: "Geometric Mean",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "median",
+        display_name: "Median",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "cumulative_return",
+        display_name: "Cumulative Return",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "annualized_return",
+        display_name: "Annualized Return",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "standard_deviation",
+        display_name: "Standard Deviation",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "downside_deviation",
+        display_name: "Downside Deviation",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "skewness",
+        display_name: "Skewness",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "kurtosis",
+        display_name: "Kurtosis",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "jarque_bera",
+        display_name: "Jarque-Bera",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "gain_loss_ratio",
+        display_name: "Gain/Loss Ratio",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "up_month_percent",
+        display_name: "Up Period Percent",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "sharpe_ratio",
+        display_name: "Sharpe Ratio",
+        requires_benchmark: false,
+        requires_riskfree: true,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "sortino_ratio",
+        display_name: "Sortino Ratio",
+        requires_benchmark: false,
+        requires_riskfree: true,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "omega",
+        display_name: "Omega",
+        requires_benchmark: false,
+        requires_riskfree: true,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "max_draw_down",
+        display_name: "Max Drawdown",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "max_draw_down_peek_date",
+        display_name: "Max Drawdown Peak Date",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitDate,
+    },
+    StatisticMetadata {
+        name: "max_draw_down_valley_date",
+        display_name: "Max Drawdown Valley Date",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitDate,
+    },
+    StatisticMetadata {
+        name: "average_draw_down",
+        display_name: "Average Drawdown",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "calmar_ratio",
+        display_name: "Calmar Ratio",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "sterling_ratio",
+        display_name: "Sterling Ratio",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "standard_deviation_by_mode",
+        display_name: "Standard Deviation by Variance Mode",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "mean_harmonic",
+        display_name: "Harmonic Mean",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "weighted_mean_arithmetic",
+        display_name: "Weighted Arithmetic Mean",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "weighted_mean_geometric",
+        display_name: "Weighted Geometric Mean",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "weighted_mean_harmonic",
+        display_name: "Weighted Harmonic Mean",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "mean_arithmetic",
+        display_name: "Arithmetic Mean",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "mean_arithmetic_annu",
+        display_name: "Annualized Arithmetic Mean",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "annualized_return_by_dates",
+        display_name: "Annualized Return by Dates",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "standard_deviation_by_dates",
+        display_name: "Standard Deviation by Dates",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "standard_deviation_by_calendar",
+        display_name: "Standard Deviation by Calendar",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "gain_standard_deviation",
+        display_name: "Gain Standard Deviation",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "loss_standard_deviation",
+        display_name: "Loss Standard Deviation",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "semi_standard_deviation",
+        display_name: "Semi Standard Deviation",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "semi_standard_deviation_by_mode",
+        display_name: "Semi Standard Deviation by Variance Mode",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "semi_standard_deviation_by_target",
+        display_name: "Semi Standard Deviation by Target",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "weighted_standard_deviation",
+        display_name: "Weighted Standard Deviation",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "weighted_standard_deviation_by_mode",
+        display_name: "Weighted Standard Deviation by Variance Mode",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "upside_deviation",
+        display_name: "Upside Deviation",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "sharpe_ratio_arithmetic",
+        display_name: "Arithmetic Sharpe Ratio",
+        requires_benchmark: false,
+        requires_riskfree: true,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "sharpe_ratio_geometric",
+        display_name: "Geometric Sharpe Ratio",
+        requires_benchmark: false,
+        requires_riskfree: true,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "sortino_ratio_arithmetic",
+        display_name: "Arithmetic Sortino Ratio",
+        requires_benchmark: false,
+        requires_riskfree: true,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "sortino_ratio_geometric",
+        display_name: "Geometric Sortino Ratio",
+        requires_benchmark: false,
+        requires_riskfree: true,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "kappa3",
+        display_name: "Kappa 3",
+        requires_benchmark: false,
+        requires_riskfree: true,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "gain_to_pain_ratio",
+        display_name: "Gain to Pain Ratio",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "tail_ratio",
+        display_name: "Tail Ratio",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "common_sense_ratio",
+        display_name: "Common Sense Ratio",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "coefficient_variation",
+        display_name: "Coefficient of Variation",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "coefficient_variation_annualized",
+        display_name: "Annualized Coefficient of Variation",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "efficiency_ratio_arithmetic",
+        display_name: "Efficiency Ratio",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "median_weighted",
+        display_name: "Weighted Median",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "down_month_percent",
+        display_name: "Down Period Percent",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "average_gain_loss",
+        display_name: "Average Gain/Loss",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "volatility",
+        display_name: "Volatility",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "zscore",
+        display_name: "Z-Score",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "max_gain",
+        display_name: "Max Gain",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "average_draw_down_by_block",
+        display_name: "Average Drawdown by Block",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "sterling_ratio_by_constant",
+        display_name: "Sterling Ratio by Constant",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "best_rolling_month",
+        display_name: "Best Rolling Month",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "worst_rolling_month",
+        display_name: "Worst Rolling Month",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "rolling_period_quantile",
+        display_name: "Rolling Period Quantile",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "rolling_period_quantile_by_frequency",
+        display_name: "Rolling Period Quantile by Frequency",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "best_rolling_period",
+        display_name: "Best Rolling Period",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "worst_rolling_period",
+        display_name: "Worst Rolling Period",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "longest_up_streak",
+        display_name: "Longest Up Streak",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "longest_down_streak",
+        display_name: "Longest Down Streak",
+        requires_benchmark: false,
+        requires_riskfree: false,
+        requires_dates: true,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "alpha",
+        display_name: "Alpha",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "beta",
+        display_name: "Beta",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "r_squared",
+        display_name: "R-Squared",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "correlation",
+        display_name: "Correlation",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "covariance",
+        display_name: "Covariance",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "batting_average",
+        display_name: "Batting Average",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "appraisal_ratio",
+        display_name: "Appraisal Ratio",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "tracking_error",
+        display_name: "Tracking Error",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "information_ratio_arithmetic",
+        display_name: "Information Ratio",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "excess_return_arithmetic",
+        display_name: "Excess Return",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "upside_capture",
+        display_name: "Upside Capture Ratio",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "downside_capture",
+        display_name: "Downside Capture Ratio",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "treynor_ratio_arithmetic",
+        display_name: "Treynor Ratio",
+        requires_benchmark: true,
+        requires_riskfree: true,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "m_squared",
+        display_name: "M-Squared",
+        requires_benchmark: true,
+        requires_riskfree: true,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "adjusted_beta",
+        display_name: "Adjusted Beta",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "vasicek_beta",
+        display_name: "Vasicek Beta",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "beta_by_method",
+        display_name: "Beta by Regression Method",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "alpha_by_method",
+        display_name: "Alpha by Regression Method",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "standard_error_alpha",
+        display_name: "Standard Error of Alpha",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "standard_error_beta",
+        display_name: "Standard Error of Beta",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "bear_bull_beta",
+        display_name: "Bear/Bull Beta",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "quantile_beta",
+        display_name: "Quantile Beta",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "downside_upside_beta",
+        display_name: "Downside/Upside Beta",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "bear_bull_colleation",
+        display_name: "Bear/Bull Correlation",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "tracking_error_by_mode",
+        display_name: "Tracking Error by Variance Mode",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "information_ratio_geometric",
+        display_name: "Geometric Information Ratio",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "excess_return_geometric",
+        display_name: "Geometric Excess Return",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "excess_return_relative_percentage",
+        display_name: "Relative Excess Return",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "excess_return_t_test",
+        display_name: "Excess Return T-Test",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "downside_standard_deviation",
+        display_name: "Downside Standard Deviation",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "upside_standard_deviation",
+        display_name: "Upside Standard Deviation",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "upside_capture_by_method",
+        display_name: "Upside Capture Ratio by Method",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "downside_capture_by_method",
+        display_name: "Downside Capture Ratio by Method",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "treynor_ratio_geometric",
+        display_name: "Geometric Treynor Ratio",
+        requires_benchmark: true,
+        requires_riskfree: true,
+        requires_dates: false,
+        annualizable: true,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "relative_risk",
+        display_name: "Relative Risk",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "market_risk",
+        display_name: "Market Risk",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "stock_risk",
+        display_name: "Stock Risk",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitPercent,
+    },
+    StatisticMetadata {
+        name: "up_number_ratio",
+        display_name: "Up Number Ratio",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "down_number_ratio",
+        display_name: "Down Number Ratio",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "up_percent",
+        display_name: "Up Percent",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+    StatisticMetadata {
+        name: "down_percent",
+        display_name: "Down Percent",
+        requires_benchmark: true,
+        requires_riskfree: false,
+        requires_dates: false,
+        annualizable: false,
+        unit: ClStatisticUnit::ClUnitRatio,
+    },
+];
+
+///return the metadata of the statistic, or None if the statistic is not registered.
+///# Examples
+///```
+///use mpt_lib::enums::ClStatisticUnit;
+///use mpt_lib::registry;
+///
+///let metadata = registry::find("sharpe_ratio").unwrap();
+///assert_eq!(
+///    metadata.requires_riskfree
+///        && !metadata.requires_benchmark
+///        && metadata.annualizable
+///        && metadata.unit == ClStatisticUnit::ClUnitRatio,
+///    true
+///);
+///assert_eq!(registry::find("unknown").is_none(), true);
+///```
+pub fn find(name: &str) -> Option<&'static StatisticMetadata> {
+    return STATISTICS.iter().find(|x| x.name == name);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find, STATISTICS};
+    use crate::{report::StandardPack, test_vectors::TEST_VECTORS};
+
+    #[test]
+    fn should_correct_registry() {
+        for (name, _) in StandardPack::new().values() {
+            assert_eq!(find(name).is_some(), true, "{}", name);
+        }
+        for vector in TEST_VECTORS.iter() {
+            assert_eq!(find(vector.name).is_some(), true, "{}", vector.name);
+        }
+        for source in [
+            include_str!("absolute_statistics.rs"),
+            include_str!("relative_statistics.rs"),
+        ] {
+            let chunks: Vec<&str> = source.split("\n    pub fn ").collect();
+            for i in 1..chunks.len() {
+                let signature = &chunks[i][..chunks[i].find('{').unwrap()];
+                let name = &signature[..signature.find(|c| c == '(' || c == '<').unwrap()];
+                let deprecated = chunks[i - 1]
+                    .lines()
+                    .last()
+                    .unwrap()
+                    .contains("#[deprecated");
+                if signature.contains("&self")
+                    && signature.contains("&mut f64")
+                    && !signature.contains("fn(")
+                    && !signature[name.len()..].starts_with('<')
+                    && !deprecated
+                {
+                    assert_eq!(find(name).is_some(), true, "{}", name);
+                }
+            }
+        }
+        for (i, metadata) in STATISTICS.iter().enumerate() {
+            assert_eq!(
+                STATISTICS[..i].iter().all(|x| x.name != metadata.name)
+                    && metadata.display_name.len() > 0,
+                true
+            );
+        }
+    }
+}
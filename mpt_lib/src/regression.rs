@@ -0,0 +1,774 @@
+use crate::{
+    common::normal_inverse_cdf,
+    date_util,
+    enums::{self, ClRegressionMethod, Errors},
+    goals::SplitMix64,
+    kernels,
+    linalg::{invert_matrix, mat_vec_mul},
+    MPTCalculator,
+};
+
+///the result of an ordinary least squares regression, the first coefficient is the intercept if it is included.
+pub(crate) struct OlsResult {
+    pub coefficients: Vec<f64>,
+    pub residual_std: f64,
+    pub r_squared: f64,
+}
+
+///ordinary least squares regression of y on the x columns, the rows which have NAN/INF values are skipped.
+///return None if there are not enough rows or the columns are collinear.
+pub(crate) fn ols(x_columns: &[&[f64]], y: &[f64], with_intercept: bool) -> Option<OlsResult> {
+    if x_columns.iter().any(|x| x.len() != y.len()) {
+        return None;
+    }
+    let k = x_columns.len() + if with_intercept { 1 } else { 0 };
+    let rows: Vec<Vec<f64>> = (0..y.len())
+        .filter(|i| y[*i].is_finite() && x_columns.iter().all(|x| x[*i].is_finite()))
+        .map(|i| {
+            let mut row = Vec::with_capacity(k + 1);
+            if with_intercept {
+                row.push(1.0);
+            }
+            x_columns.iter().for_each(|x| row.push(x[i]));
+            row.push(y[i]);
+            row
+        })
+        .collect();
+    let count = rows.len();
+    if k == 0 || count <= k {
+        return None;
+    }
+
+    let mut xtx = vec![vec![0.0; k]; k];
+    let mut xty = vec![0.0; k];
+    rows.iter().for_each(|row| {
+        for i in 0..k {
+            for j in 0..k {
+                xtx[i][j] += row[i] * row[j];
+            }
+            xty[i] += row[i] * row[k];
+        }
+    });
+    let xtx_inv = invert_matrix(&xtx)?;
+    let coefficients = mat_vec_mul(&xtx_inv, &xty);
+
+    let y_mean = rows.iter().map(|row| row[k]).sum::<f64>() / count as f64;
+    let mut sse = 0.0;
+    let mut sst = 0.0;
+    rows.iter().for_each(|row| {
+        let fitted: f64 = (0..k).map(|i| row[i] * coefficients[i]).sum();
+        sse += (row[k] - fitted) * (row[k] - fitted);
+        sst += (row[k] - y_mean) * (row[k] - y_mean);
+    });
+    let variance = sse / (count - k) as f64;
+
+    Some(OlsResult {
+        coefficients,
+        residual_std: variance.sqrt(),
+        r_squared: if sst > 0.0 { 1.0 - sse / sst } else { f64::NAN },
+    })
+}
+
+fn median(values: &[f64]) -> f64 {
+    let mut result = f64::NAN;
+    kernels::percentile(values, 50, &mut result);
+    result
+}
+
+///weighted least squares line of y on x, return (intercept, slope).
+fn weighted_line(x: &[f64], y: &[f64], weights: &[f64]) -> Option<(f64, f64)> {
+    let (mut w_sum, mut x_sum, mut y_sum, mut xx_sum, mut xy_sum) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for i in 0..x.len() {
+        w_sum += weights[i];
+        x_sum += weights[i] * x[i];
+        y_sum += weights[i] * y[i];
+        xx_sum += weights[i] * x[i] * x[i];
+        xy_sum += weights[i] * x[i] * y[i];
+    }
+    let denominator = w_sum * xx_sum - x_sum * x_sum;
+    if w_sum <= 0.0 || denominator <= 0.0 {
+        return None;
+    }
+    let slope = (w_sum * xy_sum - x_sum * y_sum) / denominator;
+    Some(((y_sum - slope * x_sum) / w_sum, slope))
+}
+
+///the max number of the pairs of the theil-sen slopes, the pairs are sampled above it.
+const THEIL_SEN_MAX_PAIRS: usize = 100_000;
+
+///the seed of the sampled theil-sen pairs, so the slope is the same between runs.
+const THEIL_SEN_SEED: u64 = 0x2545_F491_4F6C_DD1D;
+
+///robust regression line of y on x, the pairs which have NAN/INF values are skipped. return (intercept, slope),
+///or None if there are less than 2 pairs, x is constant or the parameter of the method is invalid.
+///the theil-sen slope is the median of the slopes of all pairs, or of THEIL_SEN_MAX_PAIRS seeded random pairs if
+///there are more pairs.
+pub(crate) fn robust_line(x: &[f64], y: &[f64], method: ClRegressionMethod) -> Option<(f64, f64)> {
+    let (mut x, mut y): (Vec<f64>, Vec<f64>) = x
+        .iter()
+        .zip(y.iter())
+        .filter(|(a, b)| a.is_finite() && b.is_finite())
+        .map(|(a, b)| (*a, *b))
+        .unzip();
+    if x.len() < 2 {
+        return None;
+    }
+    match method {
+        ClRegressionMethod::ClRegressionOls => weighted_line(&x, &y, &vec![1.0; x.len()]),
+        ClRegressionMethod::ClRegressionTheilSen => {
+            let pairs = x.len() * (x.len() - 1) / 2;
+            let mut slopes = Vec::with_capacity(pairs.min(THEIL_SEN_MAX_PAIRS));
+            if pairs <= THEIL_SEN_MAX_PAIRS {
+                for i in 0..x.len() {
+                    for j in i + 1..x.len() {
+                        if x[i] != x[j] {
+                            slopes.push((y[j] - y[i]) / (x[j] - x[i]));
+                        }
+                    }
+                }
+            } else {
+                let mut generator = SplitMix64 {
+                    state: THEIL_SEN_SEED,
+                };
+                for _ in 0..THEIL_SEN_MAX_PAIRS {
+                    let (i, j) = (generator.index(x.len()), generator.index(x.len()));
+                    if x[i] != x[j] {
+                        slopes.push((y[j] - y[i]) / (x[j] - x[i]));
+                    }
+                }
+            }
+            if slopes.len() == 0 {
+                return None;
+            }
+            let slope = median(&slopes);
+            let intercepts: Vec<f64> = x.iter().zip(y.iter()).map(|(a, b)| b - slope * a).collect();
+            Some((median(&intercepts), slope))
+        }
+        ClRegressionMethod::ClRegressionHuber(k) => {
+            if !k.is_finite() || k <= 0.0 {
+                return None;
+            }
+            let mut weights = vec![1.0; x.len()];
+            let mut line = weighted_line(&x, &y, &weights)?;
+            for _ in 0..50 {
+                let residuals: Vec<f64> = x
+                    .iter()
+                    .zip(y.iter())
+                    .map(|(a, b)| b - line.0 - line.1 * a)
+                    .collect();
+                let center = median(&residuals);
+                let deviations: Vec<f64> = residuals.iter().map(|r| (r - center).abs()).collect();
+                let scale = median(&deviations) / 0.6745;
+                if scale <= 0.0 {
+                    break;
+                }
+                weights
+                    .iter_mut()
+                    .zip(residuals.iter())
+                    .for_each(|(w, r)| *w = (k * scale / r.abs()).min(1.0));
+                let next = weighted_line(&x, &y, &weights)?;
+                let change = (next.0 - line.0).abs() + (next.1 - line.1).abs();
+                line = next;
+                if change <= 1e-10 * (1.0 + line.0.abs() + line.1.abs()) {
+                    break;
+                }
+            }
+            Some(line)
+        }
+        ClRegressionMethod::ClRegressionWinsorized(pct) => {
+            if pct < 0 || pct >= 50 {
+                return None;
+            }
+            for values in [&mut x, &mut y] {
+                let (mut low, mut high) = (f64::NAN, f64::NAN);
+                kernels::percentile(values, pct, &mut low);
+                kernels::percentile(values, 100 - pct, &mut high);
+                values.iter_mut().for_each(|v| *v = v.clamp(low, high));
+            }
+            weighted_line(&x, &y, &vec![1.0; x.len()])
+        }
+    }
+}
+
+///the log of the cumulative growth of the values at the begin and the end of each period, the first value is 0 at
+///the begin. return None if the values have NAN/INF values.
+fn log_equity_curve(values: &[f64]) -> Option<Vec<f64>> {
+    if values.iter().any(|x| !x.is_finite() || *x <= -100.0) {
+        return None;
+    }
+    let mut curve = vec![0.0];
+    for value in values.iter() {
+        curve.push(curve[curve.len() - 1] + (1.0 + value / 100.0).ln());
+    }
+    Some(curve)
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///calculate the multiple factor regression of the values on the factor returns by ordinary least squares,
+    ///the periods which have NAN/INF values are skipped.
+    ///# Arguments
+    ///factors: the return array of each factor, should have the same length as the values.
+    ///
+    ///alpha: the intercept of one period.
+    ///
+    ///betas: the beta of each factor.
+    ///
+    ///residual_std: the standard deviation of the residuals.
+    ///
+    ///r_squared: the r squared of the regression.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let equity = vec![
+    ///    0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+    ///    1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+    ///    3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+    ///    -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+    ///];
+    ///let rates = vec![
+    ///    1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016, 1.40278, 1.51232,
+    ///    -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901, 3.73988, 1.59068,
+    ///    -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526, -8.43036, -0.84062,
+    ///    1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864, -10.64778, 8.75952,
+    ///];
+    ///let mut alpha = 0.0;
+    ///let mut betas = Vec::new();
+    ///let mut residual_std = 0.0;
+    ///let mut r_squared = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.factor_regression(
+    ///    &[&equity, &rates],
+    ///    &mut alpha,
+    ///    &mut betas,
+    ///    &mut residual_std,
+    ///    &mut r_squared,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(alpha, -0.004501)
+    ///        && MPTCalculator::is_eq_double_array(&betas, &[0.966168, -0.051444])
+    ///        && MPTCalculator::is_eq_double(residual_std, 1.265392)
+    ///        && MPTCalculator::is_eq_double(r_squared, 0.929171),
+    ///    true
+    ///);
+    ///```
+    pub fn factor_regression(
+        &self,
+        factors: &[&[f64]],
+        alpha: &mut f64,
+        betas: &mut Vec<f64>,
+        residual_std: &mut f64,
+        r_squared: &mut f64,
+    ) -> Errors {
+        instrument!(
+            "factor_regression",
+            rows = self.values.len(),
+            cols = factors.len()
+        );
+        if self.values.len() == 0
+            || factors.len() == 0
+            || factors.iter().any(|x| x.len() != self.values.len())
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *alpha = f64::NAN;
+        *residual_std = f64::NAN;
+        *r_squared = f64::NAN;
+        betas.clear();
+        betas.resize(factors.len(), f64::NAN);
+
+        if let Some(result) = ols(factors, self.values, true) {
+            *alpha = result.coefficients[0];
+            betas.copy_from_slice(&result.coefficients[1..]);
+            *residual_std = result.residual_std;
+            *r_squared = result.r_squared;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///estimate the profit and loss of one period under the factor shocks by the factor betas of the regression,
+    ///the confidence band is given by the residual volatility of the regression.
+    ///# Arguments
+    ///factors: the return array of each factor, should have the same length as the values.
+    ///
+    ///shocks: the shock of each factor, in the same unit as the factor returns.
+    ///
+    ///confidence: the confidence level of the band, e.g. 0.95.
+    ///
+    ///pnl: the implied return of the shocks.
+    ///
+    ///lower: the lower bound of the confidence band.
+    ///
+    ///upper: the upper bound of the confidence band.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let equity = vec![
+    ///    0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+    ///    1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+    ///    3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+    ///    -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+    ///];
+    ///let rates = vec![
+    ///    1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016, 1.40278, 1.51232,
+    ///    -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901, 3.73988, 1.59068,
+    ///    -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526, -8.43036, -0.84062,
+    ///    1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864, -10.64778, 8.75952,
+    ///];
+    ///let mut pnl = 0.0;
+    ///let mut lower = 0.0;
+    ///let mut upper = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.factor_shock(&[&equity, &rates], &[-20.0, 1.0], 0.95, &mut pnl, &mut lower, &mut upper);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(pnl, -19.374812)
+    ///        && MPTCalculator::is_eq_double(lower, -21.854934)
+    ///        && MPTCalculator::is_eq_double(upper, -16.894689),
+    ///    true
+    ///);
+    ///```
+    pub fn factor_shock(
+        &self,
+        factors: &[&[f64]],
+        shocks: &[f64],
+        confidence: f64,
+        pnl: &mut f64,
+        lower: &mut f64,
+        upper: &mut f64,
+    ) -> Errors {
+        if shocks.len() != factors.len() || !(confidence > 0.0 && confidence < 1.0) {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *pnl = f64::NAN;
+        *lower = f64::NAN;
+        *upper = f64::NAN;
+
+        let mut alpha = f64::NAN;
+        let mut betas = Vec::new();
+        let mut residual_std = f64::NAN;
+        let mut r_squared = f64::NAN;
+        let ret = self.factor_regression(
+            factors,
+            &mut alpha,
+            &mut betas,
+            &mut residual_std,
+            &mut r_squared,
+        );
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+
+        *pnl = betas.iter().zip(shocks.iter()).map(|x| x.0 * x.1).sum();
+        let z = normal_inverse_cdf(0.5 + confidence / 2.0);
+        *lower = *pnl - z * residual_std;
+        *upper = *pnl + z * residual_std;
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///map the size and value factor betas of the factor regression to the style box coordinates and cell.
+    ///the x coordinate is the negative value beta clamped in [-1, 1], -1 is deep value and 1 is deep growth,
+    ///the y coordinate is the negative size beta clamped in [-1, 1], -1 is small cap and 1 is large cap.
+    ///the coordinates within the band are blend or mid cap, the cell is row * 3 + column from 0 as large value
+    ///to 8 as small growth, it is -1 if any beta is NAN/INF.
+    ///# Arguments
+    ///size_beta: the beta of the small minus big factor.
+    ///
+    ///value_beta: the beta of the high minus low factor.
+    ///
+    ///band: the half width of the blend and mid cap band, in (0, 1).
+    ///
+    ///x: the value growth coordinate.
+    ///
+    ///y: the size coordinate.
+    ///
+    ///cell: the style box cell.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let mut x = 0.0;
+    ///let mut y = 0.0;
+    ///let mut cell = 0;
+    ///let err = MPTCalculator::style_box(0.4, -0.2, 0.25, &mut x, &mut y, &mut cell);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(x, 0.2)
+    ///        && MPTCalculator::is_eq_double(y, -0.4)
+    ///        && cell == 7,
+    ///    true
+    ///);
+    ///```
+    pub fn style_box(
+        size_beta: f64,
+        value_beta: f64,
+        band: f64,
+        x: &mut f64,
+        y: &mut f64,
+        cell: &mut i32,
+    ) -> Errors {
+        if !(band > 0.0 && band < 1.0) {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *x = f64::NAN;
+        *y = f64::NAN;
+        *cell = -1;
+        if !size_beta.is_finite() || !value_beta.is_finite() {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        *x = (-value_beta).clamp(-1.0, 1.0);
+        *y = (-size_beta).clamp(-1.0, 1.0);
+        let column = if *x < -band {
+            0
+        } else if *x > band {
+            2
+        } else {
+            1
+        };
+        let row = if *y > band {
+            0
+        } else if *y < -band {
+            2
+        } else {
+            1
+        };
+        *cell = row * 3 + column;
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the k ratio of an array, it is the slope of the regression of the log cumulative return on the time
+    ///divided by the standard error of the slope, a measure of the consistency of the return trend. the time is the
+    ///number of the periods by the dates, so a missing period is a gap of the time. the input data should sort by
+    ///date. if the array has NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///dates: the date of value
+    ///
+    ///freq: the frequence of source data.
+    ///
+    ///res: the k ratio.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![1.0, 2.0, -1.0, 1.5, 0.5];
+    ///let dates = vec![44957, 44985, 45016, 45046, 45077];
+    ///let mut res = f64::NAN;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.k_ratio(&dates, enums::ClFrequency::ClFrequencyMonthly, &mut res);
+    ///assert_eq!(err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 4.627636), true);
+    ///```
+    pub fn k_ratio(&self, dates: &[i32], freq: enums::ClFrequency, res: &mut f64) -> Errors {
+        *res = f64::NAN;
+        if self.values.len() < 2 || dates.len() != self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let y = match log_equity_curve(self.values) {
+            Some(curve) => curve,
+            None => return Errors::ClErrorCodeNoError,
+        };
+        let x: Vec<f64> = [0.0]
+            .into_iter()
+            .chain(dates.iter().map(|date| {
+                (date_util::periods_between(freq, dates[0] as u64, *date as u64) + 1) as f64
+            }))
+            .collect();
+        let result = match ols(&[&x], &y, true) {
+            Some(result) => result,
+            None => return Errors::ClErrorCodeNoError,
+        };
+        let x_mean = x.iter().sum::<f64>() / x.len() as f64;
+        let sxx: f64 = x.iter().map(|t| (t - x_mean) * (t - x_mean)).sum();
+        let standard_error = result.residual_std / sxx.sqrt();
+        if standard_error > 0.0 {
+            *res = result.coefficients[1] / standard_error;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the r squared of the regression of the log cumulative return on the time, the equity curve stability,
+    ///it is 1 for a constant return and it complements the k ratio for the evaluation of the trend followers. the
+    ///periods are evenly spaced. if the array has NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///res: the r squared.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let data = vec![1.0, 2.0, -1.0, 1.5, 0.5];
+    ///let mut res = f64::NAN;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.equity_curve_r_squared(&mut res);
+    ///assert_eq!(err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 0.842613), true);
+    ///```
+    pub fn equity_curve_r_squared(&self, res: &mut f64) -> Errors {
+        *res = f64::NAN;
+        if self.values.len() < 2 {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let y = match log_equity_curve(self.values) {
+            Some(curve) => curve,
+            None => return Errors::ClErrorCodeNoError,
+        };
+        let x: Vec<f64> = (0..y.len()).map(|t| t as f64).collect();
+        if let Some(result) = ols(&[&x], &y, true) {
+            *res = result.r_squared;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ols, robust_line, THEIL_SEN_MAX_PAIRS};
+    use crate::{
+        enums::{self, Errors},
+        MPTCalculator,
+    };
+
+    const DATA: [f64; 36] = [
+        -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+        1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713, 1.88831,
+        1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807, 1.66744,
+        -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ];
+    const EQUITY: [f64; 36] = [
+        0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+        1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901, 3.73988,
+        1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526, -8.43036,
+        -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+    ];
+    const RATES: [f64; 36] = [
+        1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016, 1.40278, 1.51232,
+        -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901, 3.73988, 1.59068,
+        -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526, -8.43036, -0.84062,
+        1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864, -10.64778, 8.75952,
+    ];
+
+    #[test]
+    fn should_correct_ols() {
+        let result = ols(&[&EQUITY, &RATES], &DATA, true).unwrap();
+        assert_eq!(
+            MPTCalculator::is_eq_double_array(
+                &result.coefficients,
+                &[-0.004501, 0.966168, -0.051444]
+            ),
+            true
+        );
+        assert_eq!(ols(&[&EQUITY, &EQUITY], &DATA, true).is_none(), true);
+    }
+
+    #[test]
+    fn should_correct_factor_regression() {
+        let mut alpha = 0.0;
+        let mut betas = Vec::new();
+        let mut residual_std = 0.0;
+        let mut r_squared = 0.0;
+        let mpt = MPTCalculator::from_v(&DATA);
+        let err = mpt.factor_regression(
+            &[&EQUITY, &RATES],
+            &mut alpha,
+            &mut betas,
+            &mut residual_std,
+            &mut r_squared,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(alpha, -0.004501)
+                && MPTCalculator::is_eq_double_array(&betas, &[0.966168, -0.051444])
+                && MPTCalculator::is_eq_double(residual_std, 1.265392)
+                && MPTCalculator::is_eq_double(r_squared, 0.929171),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_factor_shock() {
+        let mut pnl = 0.0;
+        let mut lower = 0.0;
+        let mut upper = 0.0;
+        let mpt = MPTCalculator::from_v(&DATA);
+        let err = mpt.factor_shock(
+            &[&EQUITY, &RATES],
+            &[-20.0, 1.0],
+            0.95,
+            &mut pnl,
+            &mut lower,
+            &mut upper,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(pnl, -19.374812)
+                && MPTCalculator::is_eq_double(lower, -21.854934)
+                && MPTCalculator::is_eq_double(upper, -16.894689),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_style_box() {
+        let small = vec![
+            1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016, 1.40278, 1.51232,
+            -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901, 3.73988, 1.59068,
+            -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526, -8.43036, -0.84062,
+            1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864, -10.64778, 8.75952,
+        ];
+        let mut alpha = 0.0;
+        let mut betas = Vec::new();
+        let mut residual_std = 0.0;
+        let mut r_squared = 0.0;
+        let mpt = MPTCalculator::from_v(&DATA);
+        mpt.factor_regression(
+            &[&small, &EQUITY],
+            &mut alpha,
+            &mut betas,
+            &mut residual_std,
+            &mut r_squared,
+        );
+
+        let mut x = 0.0;
+        let mut y = 0.0;
+        let mut cell = 0;
+        let err = MPTCalculator::style_box(betas[0], betas[1], 0.25, &mut x, &mut y, &mut cell);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(x, -0.966168)
+                && MPTCalculator::is_eq_double(y, 0.051444)
+                && cell == 3,
+            true
+        );
+
+        let err = MPTCalculator::style_box(-1.5, 0.3, 0.25, &mut x, &mut y, &mut cell);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(x, -0.3)
+                && MPTCalculator::is_eq_double(y, 1.0)
+                && cell == 0,
+            true
+        );
+
+        let err = MPTCalculator::style_box(f64::NAN, 0.3, 0.25, &mut x, &mut y, &mut cell);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && x.is_nan() && cell == -1,
+            true
+        );
+        let err = MPTCalculator::style_box(0.1, 0.3, 1.0, &mut x, &mut y, &mut cell);
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+
+    #[test]
+    fn should_correct_k_ratio() {
+        let dates: Vec<i32> = vec![
+            38776, 38807, 38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082, 39113,
+            39141, 39172, 39202, 39233, 39263, 39294, 39325, 39355, 39386, 39416, 39447, 39478,
+            39507, 39538, 39568, 39599, 39629, 39660, 39691, 39721, 39752, 39782, 39813, 39844,
+        ];
+        let mpt = MPTCalculator::from_v(&DATA);
+        let mut res = f64::NAN;
+        let err = mpt.k_ratio(&dates, enums::ClFrequency::ClFrequencyMonthly, &mut res);
+
+        let mut y = vec![0.0];
+        DATA.iter()
+            .for_each(|r| y.push(y[y.len() - 1] + (1.0 + r / 100.0).ln()));
+        let x: Vec<f64> = (0..37).map(|t| t as f64).collect();
+        let x_mean = 18.0;
+        let y_mean = y.iter().sum::<f64>() / 37.0;
+        let sxx: f64 = x.iter().map(|t| (t - x_mean).powi(2)).sum();
+        let slope = x
+            .iter()
+            .zip(y.iter())
+            .map(|(a, b)| (a - x_mean) * (b - y_mean))
+            .sum::<f64>()
+            / sxx;
+        let sse: f64 = x
+            .iter()
+            .zip(y.iter())
+            .map(|(a, b)| (b - y_mean - slope * (a - x_mean)).powi(2))
+            .sum();
+        let expected = slope / ((sse / 35.0).sqrt() / sxx.sqrt());
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, expected),
+            true
+        );
+
+        //a missing month is a gap of the time
+        let mut gap_dates = dates.clone();
+        gap_dates[35] = 39903;
+        mpt.k_ratio(&gap_dates, enums::ClFrequency::ClFrequencyMonthly, &mut res);
+        assert_eq!(
+            res.is_finite() && !MPTCalculator::is_eq_double(res, expected),
+            true
+        );
+
+        let mut missing = DATA.to_vec();
+        missing[3] = f64::NAN;
+        let err = MPTCalculator::from_v(&missing).k_ratio(
+            &dates,
+            enums::ClFrequency::ClFrequencyMonthly,
+            &mut res,
+        );
+        assert_eq!(err == Errors::ClErrorCodeNoError && res.is_nan(), true);
+    }
+
+    #[test]
+    fn should_correct_equity_curve_r_squared() {
+        let mpt = MPTCalculator::from_v(&DATA);
+        let mut res = f64::NAN;
+        let err = mpt.equity_curve_r_squared(&mut res);
+        let mut y = vec![0.0];
+        DATA.iter()
+            .for_each(|r| y.push(y[y.len() - 1] + (1.0 + r / 100.0).ln()));
+        let x: Vec<f64> = (0..37).map(|t| t as f64).collect();
+        let mut correlation = f64::NAN;
+        MPTCalculator::from_v_b(&x, &y).correlation(&mut correlation);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(res, correlation * correlation),
+            true
+        );
+
+        let constant = vec![1.0; 12];
+        MPTCalculator::from_v(&constant).equity_curve_r_squared(&mut res);
+        assert_eq!(MPTCalculator::is_eq_double(res, 1.0), true);
+
+        let mut missing = DATA.to_vec();
+        missing[3] = f64::NAN;
+        let err = MPTCalculator::from_v(&missing).equity_curve_r_squared(&mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res.is_nan(), true);
+    }
+
+    #[test]
+    fn should_correct_sampled_theil_sen() {
+        let x: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+        let mut y: Vec<f64> = x.iter().map(|v| 1.0 + 2.0 * v).collect();
+        (0..1000).step_by(50).for_each(|i| y[i] += 500.0);
+        assert_eq!(x.len() * (x.len() - 1) / 2 > THEIL_SEN_MAX_PAIRS, true);
+        let line = robust_line(&x, &y, enums::ClRegressionMethod::ClRegressionTheilSen);
+        assert_eq!(line, Some((1.0, 2.0)));
+        assert_eq!(
+            robust_line(&x, &y, enums::ClRegressionMethod::ClRegressionTheilSen),
+            line
+        );
+    }
+}
@@ -2,10 +2,12 @@ use std::ops::ControlFlow;
 
 use crate::{
     common::{
-        annualize_return, get_annual_multiplier, CaptureData, InformationRatioData, RatioData,
-        TreynorRatioData,
+        annualize_return, check_sample_size, get_annual_multiplier, student_t_cdf, CaptureData,
+        InformationRatioData, RatioData, TreynorRatioData,
     },
-    enums::{self, ClFrequency, Errors},
+    enums::{self, ClFrequency, ClRegressionMethod, Errors},
+    kernels,
+    regression::robust_line,
     MPTCalculator,
 };
 struct XYData {
@@ -139,17 +141,103 @@ impl<'a> MPTCalculator<'a> {
     ///);
     ///```
     pub fn beta(&self, beta: &mut f64) -> Errors {
+        *beta = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
         let xy_data = gather_xy(self.values, self.benchmark, self.values.len());
 
-        let stdev = xy_data.xx_sum - xy_data.x_sum * xy_data.x_sum / xy_data.count as f64;
-        if xy_data.count > 0 && stdev != 0.0 {
-            *beta = (xy_data.xy_sum - xy_data.x_sum * xy_data.y_sum / xy_data.count as f64) / stdev;
+        let sxx = xy_data.xx_sum - xy_data.x_sum * xy_data.x_sum / xy_data.count as f64;
+        if xy_data.count > 0 && sxx != 0.0 {
+            *beta = (xy_data.xy_sum - xy_data.x_sum * xy_data.y_sum / xy_data.count as f64) / sxx;
         } else {
             *beta = f64::NAN;
         }
 
         return Errors::ClErrorCodeNoError;
     }
+    ///calculate the beta value of the array against each of the benchmarks in one pass over the data,
+    ///the periods which have NAN/INF values in the array or one benchmark are skipped for that benchmark only.
+    ///
+    ///# Arguments
+    ///benchmarks: the benchmark arrays, each should have the same size as the values.
+    ///
+    ///betas: the beta value against each benchmark.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let bmk_data = vec![
+    ///    0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+    ///    1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+    ///    3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+    ///    -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+    ///];
+    ///let mut res = Vec::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.betas(&[&bmk_data, &data], &mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double_array(&res, &[0.97364, 1.0]),
+    ///    true
+    ///);
+    ///```
+    pub fn betas(&self, benchmarks: &[&[f64]], betas: &mut Vec<f64>) -> Errors {
+        if self.values.len() == 0
+            || benchmarks.len() == 0
+            || benchmarks.iter().any(|x| x.len() != self.values.len())
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        betas.clear();
+
+        let mut xy_datas: Vec<XYData> = benchmarks
+            .iter()
+            .map(|_| XYData {
+                x_sum: 0.0,
+                y_sum: 0.0,
+                xx_sum: 0.0,
+                yy_sum: 0.0,
+                xy_sum: 0.0,
+                count: 0,
+            })
+            .collect();
+        for i in 0..self.values.len() {
+            if !self.values[i].is_finite() {
+                continue;
+            }
+            for (xy_data, benchmark) in xy_datas.iter_mut().zip(benchmarks.iter()) {
+                if benchmark[i].is_finite() {
+                    xy_data.xy_sum += self.values[i] * benchmark[i];
+                    xy_data.xx_sum += benchmark[i] * benchmark[i];
+                    xy_data.yy_sum += self.values[i] * self.values[i];
+                    xy_data.y_sum += self.values[i];
+                    xy_data.x_sum += benchmark[i];
+                    xy_data.count += 1;
+                }
+            }
+        }
+
+        for xy_data in xy_datas.iter() {
+            let sxx = xy_data.xx_sum - xy_data.x_sum * xy_data.x_sum / xy_data.count as f64;
+            if xy_data.count > 0 && sxx != 0.0 {
+                betas.push(
+                    (xy_data.xy_sum - xy_data.x_sum * xy_data.y_sum / xy_data.count as f64) / sxx,
+                );
+            } else {
+                betas.push(f64::NAN);
+            }
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
     ///calculate the alpha value of an array if the array has NAN/INF values,the result will be NAN.
     ///
     ///# Arguments
@@ -187,6 +275,10 @@ impl<'a> MPTCalculator<'a> {
     ///```
     pub fn alpha(&self, freq: enums::ClFrequency, is_annu: bool, alpha_result: &mut f64) -> Errors {
         *alpha_result = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
         let xy_data = gather_xy(self.values, self.benchmark, self.values.len());
 
         if xy_data.count > 0 {
@@ -204,6 +296,164 @@ impl<'a> MPTCalculator<'a> {
         return Errors::ClErrorCodeNoError;
     }
 
+    ///calculate the blume adjusted beta value, it is 2/3 of the beta plus 1/3, which moves the beta toward the
+    ///market beta of 1.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let data = vec![2.1, -0.9, 3.0, 1.1, -2.0, 0.9, 2.0, -1.1];
+    ///let bmk_data = vec![1.0, -0.5, 1.5, 0.5, -1.0, 0.5, 1.0, -0.5];
+    ///let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+    ///let (mut beta, mut res) = (f64::NAN, f64::NAN);
+    ///mpt.beta(&mut beta);
+    ///let err = mpt.adjusted_beta(&mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(res, beta * 2.0 / 3.0 + 1.0 / 3.0),
+    ///    true
+    ///);
+    ///```
+    pub fn adjusted_beta(&self, adjusted_beta: &mut f64) -> Errors {
+        *adjusted_beta = f64::NAN;
+        let mut beta = f64::NAN;
+        let ret = self.beta(&mut beta);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        *adjusted_beta = beta * 2.0 / 3.0 + 1.0 / 3.0;
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the vasicek shrunk beta value, it is the average of the beta and the prior mean weighted by the
+    ///inverse of their variances, so a noisy beta is shrunk more toward the prior mean.
+    ///
+    ///# Arguments
+    ///prior_mean: the cross sectional mean of the betas, e.g. 1.0.
+    ///
+    ///prior_variance: the cross sectional variance of the betas, it should be positive.
+    ///
+    ///shrunk_beta: the shrunk beta value.
+    pub fn vasicek_beta(
+        &self,
+        prior_mean: f64,
+        prior_variance: f64,
+        shrunk_beta: &mut f64,
+    ) -> Errors {
+        *shrunk_beta = f64::NAN;
+        if !prior_mean.is_finite() || !prior_variance.is_finite() || prior_variance <= 0.0 {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let ret = check_sample_size(self.values.len(), 3);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        let xy_data = gather_xy(self.values, self.benchmark, self.values.len());
+        if xy_data.count < 3 {
+            return Errors::ClErrorCodeNoError;
+        }
+        let count = xy_data.count as f64;
+        let sxx = xy_data.xx_sum - xy_data.x_sum * xy_data.x_sum / count;
+        let sxy = xy_data.xy_sum - xy_data.x_sum * xy_data.y_sum / count;
+        let syy = xy_data.yy_sum - xy_data.y_sum * xy_data.y_sum / count;
+        if sxx <= 0.0 {
+            return Errors::ClErrorCodeNoError;
+        }
+        let beta = sxy / sxx;
+        let beta_variance = ((syy - sxy * beta) / (count - 2.0)).max(0.0) / sxx;
+        *shrunk_beta =
+            (prior_variance * beta + beta_variance * prior_mean) / (prior_variance + beta_variance);
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the beta value by the regression method, the robust methods resist the outlier periods, e.g. one
+    ///erroneous data point. the periods which have NAN/INF values are skipped.
+    ///
+    ///# Arguments
+    ///method: the regression method, ClRegressionOls is the same as the beta.
+    ///
+    ///beta: the beta value.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{ClRegressionMethod, Errors};
+    ///
+    ///let data = vec![2.1, -0.9, 3.0, 1.1, -2.0, 0.9, 35.0, -1.1];
+    ///let bmk_data = vec![1.0, -0.5, 1.5, 0.5, -1.0, 0.5, 1.0, -0.5];
+    ///let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+    ///let mut res = f64::NAN;
+    ///let err = mpt.beta_by_method(ClRegressionMethod::ClRegressionTheilSen, &mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 2.05),
+    ///    true
+    ///);
+    ///```
+    pub fn beta_by_method(&self, method: ClRegressionMethod, beta: &mut f64) -> Errors {
+        *beta = f64::NAN;
+        if method == ClRegressionMethod::ClRegressionOls {
+            return self.beta(beta);
+        }
+        let ret = self.check_regression_method(method);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        if let Some((_, slope)) = robust_line(self.benchmark, self.values, method) {
+            *beta = slope;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    fn check_regression_method(&self, method: ClRegressionMethod) -> Errors {
+        match method {
+            ClRegressionMethod::ClRegressionHuber(k) if !k.is_finite() || k <= 0.0 => {
+                return Errors::ClErrorCodeInvalidPara
+            }
+            ClRegressionMethod::ClRegressionWinsorized(pct) if pct < 0 || pct >= 50 => {
+                return Errors::ClErrorCodeInvalidPara
+            }
+            _ => {}
+        }
+        return check_sample_size(self.values.len(), 2);
+    }
+
+    ///calculate the alpha value by the regression method, it is the intercept of the regression line. the periods
+    ///which have NAN/INF values are skipped.
+    ///
+    ///# Arguments
+    ///freq: the frequence of source data
+    ///
+    ///is_annu: the flag of annualize.
+    ///
+    ///method: the regression method, ClRegressionOls is the same as the alpha.
+    ///
+    ///alpha_result: the alpha value.
+    pub fn alpha_by_method(
+        &self,
+        freq: enums::ClFrequency,
+        is_annu: bool,
+        method: ClRegressionMethod,
+        alpha_result: &mut f64,
+    ) -> Errors {
+        *alpha_result = f64::NAN;
+        if method == ClRegressionMethod::ClRegressionOls {
+            return self.alpha(freq, is_annu, alpha_result);
+        }
+        let ret = self.check_regression_method(method);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        if let Some((intercept, _)) = robust_line(self.benchmark, self.values, method) {
+            *alpha_result = intercept;
+            if is_annu {
+                *alpha_result *= get_annual_multiplier(freq, false);
+            }
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
     ///calculate the tracking value of an array if the array has NAN/INF values,the result will be NAN.
     ///
     ///# Arguments
@@ -242,16 +492,76 @@ impl<'a> MPTCalculator<'a> {
         is_annu: bool,
         tracking_error_result: &mut f64,
     ) -> Errors {
+        return self.tracking_error_by_mode(
+            freq,
+            is_annu,
+            enums::ClVarianceMode::ClVarianceSample,
+            tracking_error_result,
+        );
+    }
+
+    ///calculate the tracking value of an array by the sample or population variance, if the array has NAN/INF values,
+    ///the result will be NAN.
+    ///
+    ///# Arguments
+    ///freq: the frequence of source data
+    ///
+    ///is_annu: the flag of annualize.
+    ///
+    ///mode: the sample variance divides by n - 1 and the population variance divides by n.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let bmk_data = vec![
+    ///    0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+    ///    1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+    ///    3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+    ///    -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+    ///];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+    ///let err = mpt.tracking_error_by_mode(
+    ///    enums::ClFrequency::ClFrequencyMonthly,
+    ///    true,
+    ///    enums::ClVarianceMode::ClVariancePopulation,
+    ///    &mut res,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 4.309504),
+    ///    true
+    ///);
+    ///```
+    pub fn tracking_error_by_mode(
+        &self,
+        freq: enums::ClFrequency,
+        is_annu: bool,
+        mode: enums::ClVarianceMode,
+        tracking_error_result: &mut f64,
+    ) -> Errors {
+        *tracking_error_result = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
         let mut excess_vec = vec![f64::NAN; self.values.len()];
         let ret = Self::array_subtraction_internal(self.values, self.benchmark, &mut excess_vec);
         if ret != Errors::ClErrorCodeNoError {
             return ret;
         }
 
-        return Self::standard_deviation_internal(
+        return Self::standard_deviation_by_mode_internal(
             &excess_vec,
             freq,
             is_annu,
+            mode,
             tracking_error_result,
         );
     }
@@ -296,6 +606,60 @@ impl<'a> MPTCalculator<'a> {
         return Errors::ClErrorCodeNoError;
     }
 
+    ///calculate a relative statistic against the peer composite of a peer universe instead of the benchmark, the
+    ///calculator with the peer composite as the benchmark and the same risk free is passed to statistic.
+    ///
+    ///# Arguments
+    ///peers: the returns of the peers, each peer should have the same length as the values.
+    ///
+    ///method: the average or the median of the peers.
+    ///
+    ///result: the value of the statistic.
+    ///
+    ///statistic: the relative statistic.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![1.6, -0.4, 1.9, 0.8, -1.2, 1.1];
+    ///let peer1 = vec![1.0, -1.0, 1.5, 0.5, -1.5, 0.9];
+    ///let peer2 = vec![2.0, 0.0, 2.5, 1.0, -0.5, 1.4];
+    ///let mut result = f64::NAN;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.peer_relative(
+    ///    &[&peer1, &peer2],
+    ///    enums::ClPeerComposite::ClPeerAverage,
+    ///    &mut result,
+    ///    |calc, res| calc.batting_average(res),
+    ///);
+    ///assert_eq!(err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(result, 50.0), true);
+    ///```
+    pub fn peer_relative<F>(
+        &self,
+        peers: &[&[f64]],
+        method: enums::ClPeerComposite,
+        result: &mut f64,
+        statistic: F,
+    ) -> Errors
+    where
+        F: FnOnce(&MPTCalculator, &mut f64) -> Errors,
+    {
+        *result = f64::NAN;
+        if peers.iter().any(|x| x.len() != self.values.len()) {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let mut composite = Vec::new();
+        let ret = MPTCalculator::peer_composite(peers, method, &mut composite);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        return statistic(
+            &MPTCalculator::from(self.values, &composite, self.riskfree),
+            result,
+        );
+    }
+
     ///calculate the information ratio arithmetic value of an array if the array has NAN/INF values,the result will be NAN.
     ///
     ///# Arguments
@@ -339,6 +703,10 @@ impl<'a> MPTCalculator<'a> {
         information_ratio_arithmetic_res: &mut f64,
     ) -> Errors {
         *information_ratio_arithmetic_res = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
 
         let mut information_ratio_data = InformationRatioData {
             total_return: 1.0,
@@ -423,6 +791,11 @@ impl<'a> MPTCalculator<'a> {
         is_annu: bool,
         information_ratio_geometric_res: &mut f64,
     ) -> Errors {
+        *information_ratio_geometric_res = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
         let mut information_ratio_data = InformationRatioData {
             total_return: 1.0,
             bmk_total_return: 1.0,
@@ -688,6 +1061,10 @@ impl<'a> MPTCalculator<'a> {
         up_downside_standard_deviation: &mut f64,
     ) -> Errors {
         *up_downside_standard_deviation = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
         let mut excess_return: Vec<f64> = Vec::with_capacity(self.values.len());
         if self
             .values
@@ -810,6 +1187,11 @@ impl<'a> MPTCalculator<'a> {
     }
 
     fn get_s(&self, s_result: &mut f64) -> Errors {
+        *s_result = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 3);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
         let mut alpha_result = f64::NAN;
         let mut ret = self.alpha(ClFrequency::ClFrequencyMonthly, false, &mut alpha_result);
 
@@ -842,6 +1224,8 @@ impl<'a> MPTCalculator<'a> {
         standard_error_alpha_result: &mut f64,
         standard_error_beta_result: &mut f64,
     ) -> Errors {
+        *standard_error_alpha_result = f64::NAN;
+        *standard_error_beta_result = f64::NAN;
         let mut avg_result = f64::NAN;
         let mut s_result = f64::NAN;
         let mut ret = MPTCalculator::from_v(self.benchmark).average(&mut avg_result);
@@ -1056,6 +1440,10 @@ impl<'a> MPTCalculator<'a> {
         treynor_ratio_arithmetic_result: &mut f64,
     ) -> Errors {
         *treynor_ratio_arithmetic_result = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
         let mut treynor_ratio_data = TreynorRatioData {
             total_return: 1.0,
             rf_total_return: 1.0,
@@ -1136,6 +1524,10 @@ impl<'a> MPTCalculator<'a> {
         treynor_ratio_geometric_result: &mut f64,
     ) -> Errors {
         *treynor_ratio_geometric_result = 0.0;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
         let mut treynor_ratio_data = TreynorRatioData {
             total_return: 1.0,
             rf_total_return: 1.0,
@@ -1180,6 +1572,21 @@ impl<'a> MPTCalculator<'a> {
         cmp_fn: fn(f64, f64) -> bool,
         upside_capture_ratio: &mut f64,
         upside_capture_return: &mut f64,
+    ) -> Errors {
+        return self.up_down_side_capture_by_method(
+            cmp_fn,
+            enums::ClCaptureMethod::ClCaptureGeometricAverage,
+            upside_capture_ratio,
+            upside_capture_return,
+        );
+    }
+
+    fn up_down_side_capture_by_method(
+        &self,
+        cmp_fn: fn(f64, f64) -> bool,
+        method: enums::ClCaptureMethod,
+        upside_capture_ratio: &mut f64,
+        upside_capture_return: &mut f64,
     ) -> Errors {
         let mut capture_data = CaptureData {
             count: 0,
@@ -1206,7 +1613,11 @@ impl<'a> MPTCalculator<'a> {
             return Errors::ClErrorCodeNoError;
         }
 
-        if capture_data.count > 0 {
+        if capture_data.count > 0 && method == enums::ClCaptureMethod::ClCaptureCumulative {
+            *upside_capture_return = (capture_data.accu_y - 1.0) * 100.0;
+            *upside_capture_ratio =
+                (capture_data.accu_y - 1.0) / (capture_data.accu_x - 1.0) * 100.0;
+        } else if capture_data.count > 0 {
             *upside_capture_return =
                 (capture_data.accu_y.powf(1.0 / capture_data.count as f64) - 1.0) * 100.0;
 
@@ -1312,12 +1723,12 @@ impl<'a> MPTCalculator<'a> {
     ) -> Errors {
         return self.up_down_side_capture(|a, b| a < b, down_capture_ratio, down_capture_return);
     }
-    ///calculate the bear bull beta value of an array if the array has NAN/INF values,the result will be NAN.
+    ///calculate the upside capture ratio value of an array by the selected method, the geometric average method
+    ///compares the geometric average returns of the upside market periods, the cumulative method compares the
+    ///compound returns of the upside market periods. if the array has NAN/INF values,the result will be NAN.
     ///
     ///# Arguments
-    ///freq: the frequence of source data
-    ///
-    ///is_annu: the flag of annualize.
+    ///method: the capture ratio methodology.
     ///
     ///# Examples
     ///```
@@ -1330,36 +1741,138 @@ impl<'a> MPTCalculator<'a> {
     ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
     ///];
     ///let bmk_data = vec![
-    ///    0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
-    ///    1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
-    ///    3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
-    ///    -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+    ///    -0.34902, 4.72157, -0.07781, -5.69315, 0.50715, -3.32714, 2.85004, 0.70329, 5.68503,
+    ///    2.51328, 0.19667, 1.60986, -0.88035, 0.93436, 1.73095, 3.99893, -1.58709, -6.90567,
+    ///    2.15374, 1.58778, 2.80202, -7.2765, -0.22549, -6.8847, -3.80168, 0.26042, 4.1003,
+    ///    4.48299, -7.8344, 3.60549, 3.49499, -8.10192, -20.90433, -11.9778, 5.56181, -11.19773,
     ///];
-    ///let mut bear_beta = f64::NAN;
-    ///let mut bull_beta = f64::NAN;
+    ///let mut capture_ratio = f64::NAN;
+    ///let mut capture_return = f64::NAN;
     ///let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
-    ///let err = mpt.bear_bull_beta(&mut bear_beta, &mut bull_beta);
+    ///let err = mpt.upside_capture_by_method(
+    ///    enums::ClCaptureMethod::ClCaptureCumulative,
+    ///    &mut capture_ratio,
+    ///    &mut capture_return,
+    ///);
     ///assert_eq!(
     ///    err == Errors::ClErrorCodeNoError
-    ///        && MPTCalculator::is_eq_double(bear_beta, 0.97732)
-    ///        && MPTCalculator::is_eq_double(bull_beta, 1.07004),
+    ///        && MPTCalculator::is_eq_double(capture_ratio, 70.44719)
+    ///        && MPTCalculator::is_eq_double(capture_return, 48.67209),
     ///    true
     ///);
     ///```
-    pub fn bear_bull_beta(&self, bear_beta: &mut f64, bull_beta: &mut f64) -> Errors {
-        let xy_data = gather_bear_bull_xy(self.values, self.benchmark, self.values.len());
-
-        *bear_beta = f64::NAN;
-        let bear_divisor = xy_data.bear_count as f64 * xy_data.bear_xx_sum
-            - xy_data.bear_x_sum * xy_data.bear_x_sum;
-        if bear_divisor != 0.0 {
-            *bear_beta = (xy_data.bear_count as f64 * xy_data.bear_xy_sum
-                - xy_data.bear_x_sum * xy_data.bear_y_sum)
-                / bear_divisor
-        }
-
-        *bull_beta = f64::NAN;
-        let bull_divisor = xy_data.bull_count as f64 * xy_data.bull_xx_sum
+    pub fn upside_capture_by_method(
+        &self,
+        method: enums::ClCaptureMethod,
+        capture_ratio: &mut f64,
+        capture_return: &mut f64,
+    ) -> Errors {
+        return self.up_down_side_capture_by_method(
+            |a, b| a >= b,
+            method,
+            capture_ratio,
+            capture_return,
+        );
+    }
+    ///calculate the downside capture ratio value of an array by the selected method, the geometric average method
+    ///compares the geometric average returns of the downside market periods, the cumulative method compares the
+    ///compound returns of the downside market periods. if the array has NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///method: the capture ratio methodology.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let bmk_data = vec![
+    ///    -0.34902, 4.72157, -0.07781, -5.69315, 0.50715, -3.32714, 2.85004, 0.70329, 5.68503,
+    ///    2.51328, 0.19667, 1.60986, -0.88035, 0.93436, 1.73095, 3.99893, -1.58709, -6.90567,
+    ///    2.15374, 1.58778, 2.80202, -7.2765, -0.22549, -6.8847, -3.80168, 0.26042, 4.1003,
+    ///    4.48299, -7.8344, 3.60549, 3.49499, -8.10192, -20.90433, -11.9778, 5.56181, -11.19773,
+    ///];
+    ///let mut capture_ratio = f64::NAN;
+    ///let mut capture_return = f64::NAN;
+    ///let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+    ///let err = mpt.downside_capture_by_method(
+    ///    enums::ClCaptureMethod::ClCaptureCumulative,
+    ///    &mut capture_ratio,
+    ///    &mut capture_return,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(capture_ratio, 81.72396)
+    ///        && MPTCalculator::is_eq_double(capture_return, -52.48796),
+    ///    true
+    ///);
+    ///```
+    pub fn downside_capture_by_method(
+        &self,
+        method: enums::ClCaptureMethod,
+        capture_ratio: &mut f64,
+        capture_return: &mut f64,
+    ) -> Errors {
+        return self.up_down_side_capture_by_method(
+            |a, b| a < b,
+            method,
+            capture_ratio,
+            capture_return,
+        );
+    }
+    ///calculate the bear bull beta value of an array if the array has NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///freq: the frequence of source data
+    ///
+    ///is_annu: the flag of annualize.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let bmk_data = vec![
+    ///    0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+    ///    1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+    ///    3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+    ///    -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+    ///];
+    ///let mut bear_beta = f64::NAN;
+    ///let mut bull_beta = f64::NAN;
+    ///let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+    ///let err = mpt.bear_bull_beta(&mut bear_beta, &mut bull_beta);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(bear_beta, 0.97732)
+    ///        && MPTCalculator::is_eq_double(bull_beta, 1.07004),
+    ///    true
+    ///);
+    ///```
+    pub fn bear_bull_beta(&self, bear_beta: &mut f64, bull_beta: &mut f64) -> Errors {
+        let xy_data = gather_bear_bull_xy(self.values, self.benchmark, self.values.len());
+
+        *bear_beta = f64::NAN;
+        let bear_divisor = xy_data.bear_count as f64 * xy_data.bear_xx_sum
+            - xy_data.bear_x_sum * xy_data.bear_x_sum;
+        if bear_divisor != 0.0 {
+            *bear_beta = (xy_data.bear_count as f64 * xy_data.bear_xy_sum
+                - xy_data.bear_x_sum * xy_data.bear_y_sum)
+                / bear_divisor
+        }
+
+        *bull_beta = f64::NAN;
+        let bull_divisor = xy_data.bull_count as f64 * xy_data.bull_xx_sum
             - xy_data.bull_x_sum * xy_data.bull_x_sum;
         if bull_divisor != 0.0 {
             *bull_beta = (xy_data.bull_count as f64 * xy_data.bull_xy_sum
@@ -1369,6 +1882,186 @@ impl<'a> MPTCalculator<'a> {
 
         return Errors::ClErrorCodeNoError;
     }
+    ///calculate the beta value over the periods which the benchmark return is between the lower and upper quantiles
+    ///of the benchmark returns, the quantiles are linear interpolated and the band includes its bounds.
+    ///the periods which have NAN/INF values are skipped.
+    ///
+    ///# Arguments
+    ///lower_q: the lower quantile of the band, between 0 and 1.
+    ///
+    ///upper_q: the upper quantile of the band, between lower_q and 1.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let bmk_data = vec![
+    ///    0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+    ///    1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+    ///    3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+    ///    -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+    ///];
+    ///let mut res = f64::NAN;
+    ///let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+    ///let err = mpt.quantile_beta(0.0, 0.25, &mut res);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 0.920677),
+    ///    true
+    ///);
+    ///```
+    pub fn quantile_beta(&self, lower_q: f64, upper_q: f64, beta: &mut f64) -> Errors {
+        if self.values.len() == 0
+            || self.benchmark.len() != self.values.len()
+            || !(lower_q >= 0.0 && lower_q <= upper_q && upper_q <= 1.0)
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *beta = f64::NAN;
+
+        let pairs: Vec<(f64, f64)> = self
+            .values
+            .iter()
+            .zip(self.benchmark.iter())
+            .filter(|x| x.0.is_finite() && x.1.is_finite())
+            .map(|x| (*x.0, *x.1))
+            .collect();
+        if pairs.len() == 0 {
+            return Errors::ClErrorCodeNoError;
+        }
+        let mut sorted: Vec<f64> = pairs.iter().map(|x| x.1).collect();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let lower = kernels::sorted_quantile(&sorted, lower_q);
+        let upper = kernels::sorted_quantile(&sorted, upper_q);
+
+        let (values, benchmark): (Vec<f64>, Vec<f64>) = pairs
+            .iter()
+            .filter(|x| x.1 >= lower && x.1 <= upper)
+            .cloned()
+            .unzip();
+        let xy_data = gather_xy(&values, &benchmark, values.len());
+        let divisor = xy_data.count as f64 * xy_data.xx_sum - xy_data.x_sum * xy_data.x_sum;
+        if xy_data.count > 1 && divisor != 0.0 {
+            *beta =
+                (xy_data.count as f64 * xy_data.xy_sum - xy_data.x_sum * xy_data.y_sum) / divisor;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the downside and upside beta value of an array by the method, the periods which have NAN/INF values
+    ///are skipped.
+    ///ClDownsideBetaBearBull is the same as bear_bull_beta, it is the regression slope over the periods which the
+    ///benchmark return is below or above zero.
+    ///ClDownsideBetaBelowMean is the harlow rao beta, it is the co-moment about the means of all periods divided by
+    ///the benchmark moment about its mean over the periods which the benchmark return is below or above its mean.
+    ///ClDownsideBetaBelowRiskFree is the bawa lindenberg beta, it is the co-moment about the riskfree divided by
+    ///the benchmark moment about the riskfree over the periods which the benchmark return is below or above the riskfree.
+    ///
+    ///# Arguments
+    ///method: the downside beta definition.
+    ///
+    ///downside_beta: the downside beta.
+    ///
+    ///upside_beta: the upside beta.
+    ///
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let bmk_data = vec![
+    ///    0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+    ///    1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+    ///    3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+    ///    -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+    ///];
+    ///let rf_data = vec![0.3; 36];
+    ///let mut downside_beta = f64::NAN;
+    ///let mut upside_beta = f64::NAN;
+    ///let mpt = MPTCalculator::from(&data, &bmk_data, &rf_data);
+    ///let err = mpt.downside_upside_beta(
+    ///    enums::ClDownsideBetaMethod::ClDownsideBetaBelowRiskFree,
+    ///    &mut downside_beta,
+    ///    &mut upside_beta,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(downside_beta, 0.965202)
+    ///        && MPTCalculator::is_eq_double(upside_beta, 1.014137),
+    ///    true
+    ///);
+    ///```
+    pub fn downside_upside_beta(
+        &self,
+        method: enums::ClDownsideBetaMethod,
+        downside_beta: &mut f64,
+        upside_beta: &mut f64,
+    ) -> Errors {
+        if method == enums::ClDownsideBetaMethod::ClDownsideBetaBearBull {
+            return self.bear_bull_beta(downside_beta, upside_beta);
+        }
+        let use_riskfree = method == enums::ClDownsideBetaMethod::ClDownsideBetaBelowRiskFree;
+        if self.values.len() == 0
+            || self.benchmark.len() != self.values.len()
+            || (use_riskfree && self.riskfree.len() != self.values.len())
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *downside_beta = f64::NAN;
+        *upside_beta = f64::NAN;
+
+        let valid: Vec<usize> = (0..self.values.len())
+            .filter(|i| {
+                self.values[*i].is_finite()
+                    && self.benchmark[*i].is_finite()
+                    && (!use_riskfree || self.riskfree[*i].is_finite())
+            })
+            .collect();
+        if valid.len() == 0 {
+            return Errors::ClErrorCodeNoError;
+        }
+        let value_mean = valid.iter().map(|i| self.values[*i]).sum::<f64>() / valid.len() as f64;
+        let bmk_mean = valid.iter().map(|i| self.benchmark[*i]).sum::<f64>() / valid.len() as f64;
+
+        let mut down_co_moment = 0.0;
+        let mut down_moment = 0.0;
+        let mut up_co_moment = 0.0;
+        let mut up_moment = 0.0;
+        for i in valid {
+            let (value_target, bmk_target) = if use_riskfree {
+                (self.riskfree[i], self.riskfree[i])
+            } else {
+                (value_mean, bmk_mean)
+            };
+            let x = self.benchmark[i] - bmk_target;
+            let y = self.values[i] - value_target;
+            if x < 0.0 {
+                down_co_moment += x * y;
+                down_moment += x * x;
+            } else if x > 0.0 {
+                up_co_moment += x * y;
+                up_moment += x * x;
+            }
+        }
+        if down_moment > 0.0 {
+            *downside_beta = down_co_moment / down_moment;
+        }
+        if up_moment > 0.0 {
+            *upside_beta = up_co_moment / up_moment;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
     ///calculate the bear bull colleation value of an array if the array has NAN/INF values,the result will be NAN.
     ///
     ///# Arguments
@@ -1496,11 +2189,25 @@ impl<'a> MPTCalculator<'a> {
     ///);
     ///```
     pub fn r_squared(&self, r_squard_result: &mut f64) -> Errors {
+        *r_squard_result = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
         let xy_data = gather_xy(self.values, self.benchmark, self.values.len());
 
-        let cov_xy = xy_data.xy_sum - xy_data.x_sum * xy_data.y_sum / xy_data.count as f64;
-        let y_std = xy_data.yy_sum - xy_data.y_sum * xy_data.y_sum / xy_data.count as f64;
-        let x_std = xy_data.xx_sum - xy_data.x_sum * xy_data.x_sum / xy_data.count as f64;
+        //the sums are centered by the means, the raw sums cancel out when the values are nearly constant and the
+        //result can be out of 0 to 100.
+        let x_mean = xy_data.x_sum / xy_data.count as f64;
+        let y_mean = xy_data.y_sum / xy_data.count as f64;
+        let (mut cov_xy, mut x_std, mut y_std) = (0.0, 0.0, 0.0);
+        for i in 0..self.values.len() {
+            if self.values[i].is_finite() && self.benchmark[i].is_finite() {
+                cov_xy += (self.benchmark[i] - x_mean) * (self.values[i] - y_mean);
+                x_std += (self.benchmark[i] - x_mean) * (self.benchmark[i] - x_mean);
+                y_std += (self.values[i] - y_mean) * (self.values[i] - y_mean);
+            }
+        }
         if x_std != 0.0 && y_std != 0.0 {
             *r_squard_result = cov_xy * cov_xy / (y_std * x_std) * 100.0;
         } else {
@@ -1612,6 +2319,11 @@ impl<'a> MPTCalculator<'a> {
     ///);
     ///```
     pub fn correlation(&self, correlation_result: &mut f64) -> Errors {
+        *correlation_result = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
         let xy_data = gather_xy(self.values, self.benchmark, self.values.len());
 
         if xy_data.count > 0 {
@@ -1667,6 +2379,7 @@ impl<'a> MPTCalculator<'a> {
     ///);
     ///```
     pub fn appraisal_ratio(&self, appraisal_ratio_result: &mut f64) -> Errors {
+        *appraisal_ratio_result = f64::NAN;
         let mut alpha_result = f64::NAN;
         let mut s_result = f64::NAN;
 
@@ -2040,6 +2753,10 @@ impl<'a> MPTCalculator<'a> {
         m_squared_res: &mut f64,
     ) -> Errors {
         *m_squared_res = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
         let mut rf_mean = f64::NAN;
         MPTCalculator::from_v(self.riskfree).mean_arithmetic(&mut rf_mean);
         if !rf_mean.is_finite() {
@@ -2224,6 +2941,11 @@ impl<'a> MPTCalculator<'a> {
     ///);
     ///```
     pub fn covariance(&self, covariance: &mut f64) -> Errors {
+        *covariance = f64::NAN;
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
         let xy_data = gather_xy(self.values, self.benchmark, self.values.len());
 
         if xy_data.count > 0 {
@@ -2235,43 +2957,190 @@ impl<'a> MPTCalculator<'a> {
 
         return Errors::ClErrorCodeNoError;
     }
-}
-
-#[cfg(test)]
-mod test {
-    use crate::{
-        enums::{self, Errors},
-        MPTCalculator,
-    };
 
-    #[test]
-    fn should_correct_alpha() {
-        let data = vec![
-            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
-            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
-            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
-            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
-        ];
-        let bmk_data = vec![
-            0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
-            1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
-            3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
-            -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
-        ];
-        let mut res = 0.0;
-        let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
-        let err = mpt.alpha(enums::ClFrequency::ClFrequencyMonthly, true, &mut res);
-        assert_eq!(
-            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 0.66313),
-            true
+    ///calculate the information ratio arithmetic of each rolling window, the value of position i is the ratio of
+    ///the window which ends at i, the first window - 1 values are NAN.
+    ///if the window has NAN/INF values, the value of the window will be NAN.
+    ///
+    ///# Arguments
+    ///window: the number of periods of each window.
+    ///
+    ///freq: the frequence of source data
+    ///
+    ///is_annu: the flag of annualize.
+    ///
+    ///output: the rolling information ratio, the same size as the values.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let bmk_data = vec![
+    ///    0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+    ///    1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+    ///    3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+    ///    -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+    ///];
+    ///let mut res = vec![0.0; 36];
+    ///let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+    ///let err = mpt.rolling_information_ratio(
+    ///    12,
+    ///    enums::ClFrequency::ClFrequencyMonthly,
+    ///    true,
+    ///    &mut res,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && res[10].is_nan()
+    ///        && MPTCalculator::is_eq_double_array(&res[11..14], &[0.319728, 0.759975, 1.04146]),
+    ///    true
+    ///);
+    ///```
+    pub fn rolling_information_ratio(
+        &self,
+        window: usize,
+        freq: enums::ClFrequency,
+        is_annu: bool,
+        output: &mut [f64],
+    ) -> Errors {
+        instrument!(
+            "rolling_information_ratio",
+            rows = self.values.len(),
+            window = window
         );
+        if window < 2
+            || self.benchmark.len() != self.values.len()
+            || output.len() < self.values.len()
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        for i in 0..self.values.len() {
+            output[i] = f64::NAN;
+            if i + 1 < window {
+                continue;
+            }
+            let ret = MPTCalculator::from_v_b(
+                &self.values[i + 1 - window..=i],
+                &self.benchmark[i + 1 - window..=i],
+            )
+            .information_ratio_arithmetic(freq, is_annu, &mut output[i]);
+            if ret != Errors::ClErrorCodeNoError {
+                return ret;
+            }
+        }
+        return Errors::ClErrorCodeNoError;
     }
 
-    #[test]
-    fn should_correct_beta() {
-        let data = vec![
-            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
-            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///test whether the average excess return over the benchmark differs from zero by the one sample t-test.
+    ///if the array has NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///t_stat: the t statistic of the average excess return.
+    ///
+    ///p_value: the two sided p value of the t statistic with n - 1 degrees of freedom.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let bmk_data = vec![
+    ///    0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+    ///    1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+    ///    3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+    ///    -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+    ///];
+    ///let mut t_stat = 0.0;
+    ///let mut p_value = 0.0;
+    ///let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+    ///let err = mpt.excess_return_t_test(&mut t_stat, &mut p_value);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(t_stat, 0.379585)
+    ///        && MPTCalculator::is_eq_double(p_value, 0.706547),
+    ///    true
+    ///);
+    ///```
+    pub fn excess_return_t_test(&self, t_stat: &mut f64, p_value: &mut f64) -> Errors {
+        *t_stat = f64::NAN;
+        *p_value = f64::NAN;
+        if self.benchmark.len() != self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let ret = check_sample_size(self.values.len(), 2);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        let mut tracking_error = f64::NAN;
+        let ret = self.tracking_error(
+            enums::ClFrequency::ClFrequencyMonthly,
+            false,
+            &mut tracking_error,
+        );
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        if !tracking_error.is_finite() || tracking_error == 0.0 {
+            return Errors::ClErrorCodeNoError;
+        }
+        let count = self.values.len() as f64;
+        let mean = self
+            .values
+            .iter()
+            .zip(self.benchmark.iter())
+            .map(|x| x.0 - x.1)
+            .sum::<f64>()
+            / count;
+        *t_stat = mean / (tracking_error / count.sqrt());
+        *p_value = 2.0 * (1.0 - student_t_cdf(t_stat.abs(), count - 1.0));
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        enums::{self, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_alpha() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let bmk_data = vec![
+            0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+            1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+            3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+            -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+        ];
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+        let err = mpt.alpha(enums::ClFrequency::ClFrequencyMonthly, true, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 0.66313),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_beta() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
             1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
             1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
         ];
@@ -2290,6 +3159,149 @@ mod test {
         );
     }
 
+    #[test]
+    fn should_correct_adjusted_beta() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let bmk_data = vec![
+            0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+            1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+            3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+            -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+        ];
+        let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+        let mut res = f64::NAN;
+        let err = mpt.adjusted_beta(&mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 0.98243),
+            true
+        );
+
+        let (mut shrunk, mut noisy_shrunk) = (f64::NAN, f64::NAN);
+        let err = mpt.vasicek_beta(1.5, 0.04, &mut shrunk);
+        MPTCalculator::from_v_b(&data[..6], &bmk_data[..6]).vasicek_beta(
+            1.5,
+            0.04,
+            &mut noisy_shrunk,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(shrunk, 1.00142)
+                && (noisy_shrunk - 1.5).abs() < (shrunk - 1.5).abs(),
+            true
+        );
+        let err = mpt.vasicek_beta(1.0, 0.0, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeInvalidPara && res.is_nan(), true);
+    }
+
+    #[test]
+    fn should_correct_beta_by_method() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let bmk_data = vec![
+            0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+            1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+            3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+            -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+        ];
+        let mut outlier = data.clone();
+        outlier[32] = 20.0;
+        let methods = [
+            (enums::ClRegressionMethod::ClRegressionTheilSen, 0.1),
+            (enums::ClRegressionMethod::ClRegressionHuber(1.345), 0.1),
+            (enums::ClRegressionMethod::ClRegressionWinsorized(5), 0.3),
+        ];
+
+        let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+        let outlier_mpt = MPTCalculator::from_v_b(&outlier, &bmk_data);
+        let (mut res, mut expected) = (f64::NAN, f64::NAN);
+        mpt.beta(&mut expected);
+        let err = mpt.beta_by_method(enums::ClRegressionMethod::ClRegressionOls, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res == expected, true);
+        mpt.alpha(enums::ClFrequency::ClFrequencyMonthly, true, &mut expected);
+        let err = mpt.alpha_by_method(
+            enums::ClFrequency::ClFrequencyMonthly,
+            true,
+            enums::ClRegressionMethod::ClRegressionOls,
+            &mut res,
+        );
+        assert_eq!(err == Errors::ClErrorCodeNoError && res == expected, true);
+
+        outlier_mpt.beta(&mut res);
+        assert_eq!((res - 0.97364).abs() > 0.3, true);
+        for (method, tolerance) in methods {
+            let (mut beta, mut robust_beta, mut alpha) = (f64::NAN, f64::NAN, f64::NAN);
+            mpt.beta_by_method(method, &mut beta);
+            let err = outlier_mpt.beta_by_method(method, &mut robust_beta);
+            outlier_mpt.alpha_by_method(
+                enums::ClFrequency::ClFrequencyMonthly,
+                false,
+                method,
+                &mut alpha,
+            );
+            assert_eq!(
+                err == Errors::ClErrorCodeNoError
+                    && (beta - 0.97364).abs() < 0.1
+                    && (robust_beta - beta).abs() < tolerance
+                    && alpha.abs() < 1.0,
+                true
+            );
+        }
+
+        let err = mpt.beta_by_method(enums::ClRegressionMethod::ClRegressionHuber(0.0), &mut res);
+        assert_eq!(err == Errors::ClErrorCodeInvalidPara && res.is_nan(), true);
+        let err = mpt.beta_by_method(
+            enums::ClRegressionMethod::ClRegressionWinsorized(50),
+            &mut res,
+        );
+        assert_eq!(err == Errors::ClErrorCodeInvalidPara && res.is_nan(), true);
+        let constant = MPTCalculator::from_v_b(&data[..3], &[1.0, 1.0, 1.0]);
+        let err =
+            constant.beta_by_method(enums::ClRegressionMethod::ClRegressionTheilSen, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res.is_nan(), true);
+    }
+
+    #[test]
+    fn should_correct_betas() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let bmk_data = vec![
+            0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+            1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+            3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+            -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+        ];
+        let mut gap_bmk_data = bmk_data.clone();
+        gap_bmk_data[3] = f64::NAN;
+        let mut expected = 0.0;
+        MPTCalculator::from_v_b(&data, &gap_bmk_data).beta(&mut expected);
+
+        let mut res = Vec::new();
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.betas(&[&bmk_data, &gap_bmk_data, &[1.0; 36]], &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(&res[0..2], &[0.97364, expected])
+                && res[2].is_nan(),
+            true
+        );
+
+        let err = mpt.betas(&[&bmk_data[1..]], &mut res);
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+
     #[test]
     fn should_correct_tracking_error() {
         let data = vec![
@@ -2313,6 +3325,44 @@ mod test {
         );
     }
 
+    #[test]
+    fn should_correct_tracking_error_by_mode() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let bmk_data = vec![
+            0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+            1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+            3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+            -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+        ];
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+        let err = mpt.tracking_error_by_mode(
+            enums::ClFrequency::ClFrequencyMonthly,
+            true,
+            enums::ClVarianceMode::ClVarianceSample,
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 4.37063),
+            true
+        );
+        let err = mpt.tracking_error_by_mode(
+            enums::ClFrequency::ClFrequencyMonthly,
+            true,
+            enums::ClVarianceMode::ClVariancePopulation,
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 4.309504),
+            true
+        );
+    }
+
     #[test]
     fn should_correct_information_ratio_arithmetic() {
         let data = vec![
@@ -2680,6 +3730,104 @@ mod test {
         );
     }
 
+    #[test]
+    fn should_correct_quantile_beta() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let bmk_data = vec![
+            0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+            1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+            3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+            -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+        ];
+        let mut res = f64::NAN;
+        let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+        let err = mpt.quantile_beta(0.75, 1.0, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 0.552493),
+            true
+        );
+
+        let err = mpt.quantile_beta(0.0, 1.0, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 0.97364),
+            true
+        );
+
+        let err = mpt.quantile_beta(0.5, 0.5, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res.is_nan(), true);
+
+        let err = mpt.quantile_beta(0.6, 0.4, &mut res);
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+
+    #[test]
+    fn should_correct_downside_upside_beta() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let bmk_data = vec![
+            0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+            1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+            3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+            -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+        ];
+        let rf_data = vec![0.3; 36];
+        let mut downside_beta = f64::NAN;
+        let mut upside_beta = f64::NAN;
+        let mpt = MPTCalculator::from(&data, &bmk_data, &rf_data);
+        let err = mpt.downside_upside_beta(
+            enums::ClDownsideBetaMethod::ClDownsideBetaBearBull,
+            &mut downside_beta,
+            &mut upside_beta,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(downside_beta, 0.977318)
+                && MPTCalculator::is_eq_double(upside_beta, 1.070039),
+            true
+        );
+
+        let err = mpt.downside_upside_beta(
+            enums::ClDownsideBetaMethod::ClDownsideBetaBelowMean,
+            &mut downside_beta,
+            &mut upside_beta,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(downside_beta, 0.971022)
+                && MPTCalculator::is_eq_double(upside_beta, 0.9801),
+            true
+        );
+
+        let err = mpt.downside_upside_beta(
+            enums::ClDownsideBetaMethod::ClDownsideBetaBelowRiskFree,
+            &mut downside_beta,
+            &mut upside_beta,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(downside_beta, 0.965202)
+                && MPTCalculator::is_eq_double(upside_beta, 1.014137),
+            true
+        );
+
+        let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+        let err = mpt.downside_upside_beta(
+            enums::ClDownsideBetaMethod::ClDownsideBetaBelowRiskFree,
+            &mut downside_beta,
+            &mut upside_beta,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+
     #[test]
     fn should_correct_bear_bull_colleation() {
         let data = vec![
@@ -2729,6 +3877,16 @@ mod test {
             err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(result, 92.59959),
             true
         );
+
+        //a nearly constant benchmark, the raw sums gave 100.00000016
+        let data = vec![0.0, 4.990407196366245];
+        let bmk_data = vec![-21.88564853538616, -21.873454747051984];
+        let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+        let err = mpt.r_squared(&mut result);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && (result - 100.0).abs() < 1e-9,
+            true
+        );
     }
 
     #[test]
@@ -3028,4 +4186,131 @@ mod test {
             true
         );
     }
+
+    #[test]
+    fn should_correct_rolling_information_ratio() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let bmk_data = vec![
+            0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+            1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+            3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+            -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+        ];
+        let mut res = vec![0.0; 36];
+        let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+        let err = mpt.rolling_information_ratio(
+            12,
+            enums::ClFrequency::ClFrequencyMonthly,
+            true,
+            &mut res,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && res[0..11].iter().all(|x| x.is_nan())
+                && MPTCalculator::is_eq_double_array(
+                    &res[31..],
+                    &[0.750267, 0.940768, 0.538942, 0.743595, 0.32261]
+                ),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_excess_return_t_test() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let bmk_data = vec![
+            0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+            1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+            3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+            -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+        ];
+        let mut t_stat = 0.0;
+        let mut p_value = 0.0;
+        let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+        let err = mpt.excess_return_t_test(&mut t_stat, &mut p_value);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(t_stat, 0.379585)
+                && MPTCalculator::is_eq_double(p_value, 0.706547),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_capture_by_method() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let bmk_data = vec![
+            -0.34902, 4.72157, -0.07781, -5.69315, 0.50715, -3.32714, 2.85004, 0.70329, 5.68503,
+            2.51328, 0.19667, 1.60986, -0.88035, 0.93436, 1.73095, 3.99893, -1.58709, -6.90567,
+            2.15374, 1.58778, 2.80202, -7.2765, -0.22549, -6.8847, -3.80168, 0.26042, 4.1003,
+            4.48299, -7.8344, 3.60549, 3.49499, -8.10192, -20.90433, -11.9778, 5.56181, -11.19773,
+        ];
+        let mut capture_ratio = f64::NAN;
+        let mut capture_return = f64::NAN;
+        let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+        let err = mpt.upside_capture_by_method(
+            enums::ClCaptureMethod::ClCaptureGeometricAverage,
+            &mut capture_ratio,
+            &mut capture_return,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(capture_ratio, 75.25659)
+                && MPTCalculator::is_eq_double(capture_return, 2.00265),
+            true
+        );
+
+        let err = mpt.downside_capture_by_method(
+            enums::ClCaptureMethod::ClCaptureCumulative,
+            &mut capture_ratio,
+            &mut capture_return,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(capture_ratio, 81.72396)
+                && MPTCalculator::is_eq_double(capture_return, -52.48796),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_peer_relative() {
+        let data = vec![1.6, -0.4, 1.9, 0.8, -1.2, 1.1];
+        let peer1 = vec![1.0, -1.0, 1.5, 0.5, -1.5, 0.9];
+        let peer2 = vec![2.0, 0.0, 2.5, 1.0, -0.5, 1.4];
+        let peer3 = vec![9.0, 5.0, 8.0, 7.0, 6.0, 9.0];
+        let mpt = MPTCalculator::from_v(&data);
+        let (mut res, mut expected) = (f64::NAN, f64::NAN);
+        let err = mpt.peer_relative(
+            &[&peer1, &peer2, &peer3],
+            enums::ClPeerComposite::ClPeerMedian,
+            &mut res,
+            |calc, res| calc.beta(res),
+        );
+        MPTCalculator::from_v_b(&data, &peer2).beta(&mut expected);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res == expected, true);
+
+        let err = mpt.peer_relative(
+            &[&peer1, &peer2[1..]],
+            enums::ClPeerComposite::ClPeerAverage,
+            &mut res,
+            |calc, res| calc.beta(res),
+        );
+        assert_eq!(err == Errors::ClErrorCodeInvalidPara && res.is_nan(), true);
+    }
 }
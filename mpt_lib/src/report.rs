@@ -0,0 +1,412 @@
+use crate::{
+    enums::{self, Errors},
+    methodology::Methodology,
+    MPTCalculator,
+};
+
+///the usual fact sheet statistics of a fund, the returns and the ratios are annualized by the frequency.
+///the relative statistics are NAN if the calculator has no benchmark and the risk adjusted ratios are NAN if
+///the calculator has no risk free.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StandardPack {
+    pub cumulative_return: f64,
+    pub annualized_return: f64,
+    pub standard_deviation: f64,
+    pub sharpe_ratio: f64,
+    pub sortino_ratio: f64,
+    pub alpha: f64,
+    pub beta: f64,
+    pub r_squared: f64,
+    pub upside_capture: f64,
+    pub downside_capture: f64,
+    pub sterling_ratio: f64,
+    pub max_draw_down: f64,
+    pub max_draw_down_peek_date: i32,
+    pub max_draw_down_valley_date: i32,
+}
+
+impl StandardPack {
+    pub fn new() -> StandardPack {
+        StandardPack {
+            cumulative_return: f64::NAN,
+            annualized_return: f64::NAN,
+            standard_deviation: f64::NAN,
+            sharpe_ratio: f64::NAN,
+            sortino_ratio: f64::NAN,
+            alpha: f64::NAN,
+            beta: f64::NAN,
+            r_squared: f64::NAN,
+            upside_capture: f64::NAN,
+            downside_capture: f64::NAN,
+            sterling_ratio: f64::NAN,
+            max_draw_down: f64::NAN,
+            max_draw_down_peek_date: 0,
+            max_draw_down_valley_date: 0,
+        }
+    }
+
+    ///the name and the value of each statistic, the dates are the integer dates.
+    pub fn values(&self) -> Vec<(&'static str, f64)> {
+        return vec![
+            ("cumulative_return", self.cumulative_return),
+            ("annualized_return", self.annualized_return),
+            ("standard_deviation", self.standard_deviation),
+            ("sharpe_ratio", self.sharpe_ratio),
+            ("sortino_ratio", self.sortino_ratio),
+            ("alpha", self.alpha),
+            ("beta", self.beta),
+            ("r_squared", self.r_squared),
+            ("upside_capture", self.upside_capture),
+            ("downside_capture", self.downside_capture),
+            ("sterling_ratio", self.sterling_ratio),
+            ("max_draw_down", self.max_draw_down),
+            (
+                "max_draw_down_peek_date",
+                self.max_draw_down_peek_date as f64,
+            ),
+            (
+                "max_draw_down_valley_date",
+                self.max_draw_down_valley_date as f64,
+            ),
+        ];
+    }
+
+    ///the json object of the statistics, the NAN/INF values are null.
+    pub fn to_json(&self) -> String {
+        return values_to_json(&self.values());
+    }
+}
+
+impl Default for StandardPack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///serialize the named values as a json object, the value which is not finite is null.
+pub fn values_to_json(values: &[(&str, f64)]) -> String {
+    let body: Vec<String> = values
+        .iter()
+        .map(|(name, v)| {
+            if v.is_finite() {
+                format!("\"{}\":{}", name, v)
+            } else {
+                format!("\"{}\":null", name)
+            }
+        })
+        .collect();
+    return format!("{{{}}}", body.join(","));
+}
+
+///calculate the standard statistics pack of the calculator in one call.
+///
+///# Arguments
+///calc: the calculator of the fund, the benchmark and the risk free can be empty.
+///
+///dates: the date of value, should be ascending.
+///
+///freq: the frequence of source data.
+///
+///pack: the statistics.
+///# Examples
+///```
+///use mpt_lib::MPTCalculator;
+///use mpt_lib::enums::{self, Errors};
+///use mpt_lib::report::{self, StandardPack};
+///
+///let data = vec![
+///    1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016, 1.40278, 1.51232,
+///    -1.95588, 1.1185,
+///];
+///let bmk_data = vec![
+///    1.22072, -2.0668, 0.20588, 0.91563, 1.76766, 2.21429, 2.43456, 1.99825, 0.89481, 1.59564,
+///    -1.86793, 0.41477,
+///];
+///let rf_data = vec![0.3; 12];
+///let dates = vec![
+///    38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082, 39113, 39141, 39172,
+///];
+///let mut pack = StandardPack::new();
+///let mpt = MPTCalculator::from(&data, &bmk_data, &rf_data);
+///let err = report::standard_pack(&mpt, &dates, enums::ClFrequency::ClFrequencyMonthly, &mut pack);
+///assert_eq!(
+///    err == Errors::ClErrorCodeNoError
+///        && MPTCalculator::is_eq_double(pack.cumulative_return, 11.830213)
+///        && MPTCalculator::is_eq_double(pack.beta, 1.196013)
+///        && MPTCalculator::is_eq_double(pack.max_draw_down, -2.87814),
+///    true
+///);
+///```
+pub fn standard_pack(
+    calc: &MPTCalculator,
+    dates: &[i32],
+    freq: enums::ClFrequency,
+    pack: &mut StandardPack,
+) -> Errors {
+    return standard_pack_by_methodology(calc, dates, freq, &Methodology::current(), pack);
+}
+
+///calculate the standard statistics pack of the calculator by the formula variants of the methodology, so a
+///historical report can be reproduced by the methodology version it was generated with.
+///
+///# Arguments
+///calc: the calculator of the fund, the benchmark and the risk free can be empty.
+///
+///dates: the date of value, should be ascending.
+///
+///freq: the frequence of source data.
+///
+///methodology: the formula variants of the statistics.
+///
+///pack: the statistics.
+///# Examples
+///```
+///use mpt_lib::MPTCalculator;
+///use mpt_lib::enums::{self, Errors};
+///use mpt_lib::methodology::Methodology;
+///use mpt_lib::report::{self, StandardPack};
+///
+///let data = vec![1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577];
+///let bmk_data = vec![1.22072, -2.0668, 0.20588, 0.91563, 1.76766, 2.21429];
+///let dates = vec![38837, 38868, 38898, 38929, 38960, 38990];
+///let freq = enums::ClFrequency::ClFrequencyMonthly;
+///let (mut pack, mut expected) = (StandardPack::new(), StandardPack::new());
+///let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+///let methodology = Methodology::from_version(enums::ClMethodologyVersion::ClMethodologyV1);
+///let err = report::standard_pack_by_methodology(&mpt, &dates, freq, &methodology, &mut pack);
+///report::standard_pack(&mpt, &dates, freq, &mut expected);
+///assert_eq!(err == Errors::ClErrorCodeNoError && pack.to_json() == expected.to_json(), true);
+///```
+pub fn standard_pack_by_methodology(
+    calc: &MPTCalculator,
+    dates: &[i32],
+    freq: enums::ClFrequency,
+    methodology: &Methodology,
+    pack: &mut StandardPack,
+) -> Errors {
+    if calc.values.len() < 2 || dates.len() != calc.values.len() {
+        return Errors::ClErrorCodeInvalidPara;
+    }
+    *pack = StandardPack::new();
+    if dates.windows(2).any(|x| x[0] >= x[1]) {
+        return Errors::ClErrorCodeUnsortedByDate;
+    }
+
+    if calc.values.iter().all(|x| x.is_finite()) {
+        pack.cumulative_return = (calc
+            .values
+            .iter()
+            .fold(1.0, |acc, v| acc * (1.0 + v / 100.0))
+            - 1.0)
+            * 100.0;
+    }
+    let ret = calc.annualized_return_by_dates(
+        dates,
+        methodology.geometric_annualization,
+        &mut pack.annualized_return,
+    );
+    if ret != Errors::ClErrorCodeNoError {
+        return ret;
+    }
+    let ret = calc.standard_deviation_by_mode(
+        freq,
+        true,
+        methodology.variance_mode,
+        &mut pack.standard_deviation,
+    );
+    if ret != Errors::ClErrorCodeNoError {
+        return ret;
+    }
+    let (mut months, mut recovery_months, mut recovery_date) = (0, 0, 0);
+    let ret = calc.max_draw_down(
+        dates,
+        freq,
+        &mut pack.max_draw_down,
+        &mut pack.max_draw_down_peek_date,
+        &mut pack.max_draw_down_valley_date,
+        &mut months,
+        &mut recovery_months,
+        &mut recovery_date,
+    );
+    if ret != Errors::ClErrorCodeNoError {
+        return ret;
+    }
+    let ret = calc.sterling_ratio_by_constant(
+        dates,
+        freq,
+        methodology.sterling_constant,
+        &mut pack.sterling_ratio,
+    );
+    if ret != Errors::ClErrorCodeNoError {
+        return ret;
+    }
+
+    if calc.riskfree.len() == calc.values.len() {
+        let ret = calc.sharpe_ratio(freq, true, &mut pack.sharpe_ratio);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        let ret = calc.sortino_ratio(freq, true, &mut pack.sortino_ratio);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+    }
+
+    if calc.benchmark.len() == calc.values.len() {
+        let ret = calc.alpha(freq, true, &mut pack.alpha);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        let ret = calc.beta(&mut pack.beta);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        let ret = calc.r_squared(&mut pack.r_squared);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        let mut capture_return = f64::NAN;
+        let ret = calc.upside_capture_by_method(
+            methodology.capture_method,
+            &mut pack.upside_capture,
+            &mut capture_return,
+        );
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        let ret = calc.downside_capture_by_method(
+            methodology.capture_method,
+            &mut pack.downside_capture,
+            &mut capture_return,
+        );
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+    }
+    return Errors::ClErrorCodeNoError;
+}
+
+#[cfg(test)]
+mod test {
+    use super::{standard_pack, standard_pack_by_methodology, StandardPack};
+    use crate::{
+        enums::{self, Errors},
+        methodology::Methodology,
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_standard_pack() {
+        let data = vec![
+            1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016, 1.40278, 1.51232,
+            -1.95588, 1.1185,
+        ];
+        let bmk_data = vec![
+            1.22072, -2.0668, 0.20588, 0.91563, 1.76766, 2.21429, 2.43456, 1.99825, 0.89481,
+            1.59564, -1.86793, 0.41477,
+        ];
+        let rf_data = vec![0.3; 12];
+        let dates = vec![
+            38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082, 39113, 39141, 39172,
+        ];
+        let freq = enums::ClFrequency::ClFrequencyMonthly;
+        let mut pack = StandardPack::new();
+        let mpt = MPTCalculator::from(&data, &bmk_data, &rf_data);
+        let err = standard_pack(&mpt, &dates, freq, &mut pack);
+        let mut sharpe_ratio = f64::NAN;
+        mpt.sharpe_ratio(freq, true, &mut sharpe_ratio);
+        let mut sterling_ratio = f64::NAN;
+        mpt.sterling_ratio(&dates, freq, &mut sterling_ratio);
+        let (mut upside_capture, mut downside_capture, mut capture_return) =
+            (f64::NAN, f64::NAN, f64::NAN);
+        mpt.upside_capture(&mut upside_capture, &mut capture_return);
+        mpt.downside_capture(&mut downside_capture, &mut capture_return);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(pack.annualized_return, 11.814662)
+                && pack.sharpe_ratio == sharpe_ratio
+                && pack.sterling_ratio == sterling_ratio
+                && pack.upside_capture == upside_capture
+                && pack.downside_capture == downside_capture
+                && pack.max_draw_down_peek_date == 38838
+                && pack.max_draw_down_valley_date == 38868,
+            true
+        );
+
+        let mpt = MPTCalculator::from_v(&data);
+        let err = standard_pack(&mpt, &dates, freq, &mut pack);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && pack.standard_deviation.is_finite()
+                && pack.sharpe_ratio.is_nan()
+                && pack.beta.is_nan(),
+            true
+        );
+        let json = pack.to_json();
+        assert_eq!(
+            json.starts_with("{\"cumulative_return\":11.830212")
+                && json.contains("\"beta\":null,")
+                && json.ends_with("\"max_draw_down_valley_date\":38868}"),
+            true
+        );
+
+        let err = standard_pack(&mpt, &dates[1..], freq, &mut pack);
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+
+    #[test]
+    fn should_correct_standard_pack_by_methodology() {
+        let data = vec![
+            1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016, 1.40278, 1.51232,
+            -1.95588, 1.1185,
+        ];
+        let bmk_data = vec![
+            1.22072, -2.0668, 0.20588, 0.91563, 1.76766, 2.21429, 2.43456, 1.99825, 0.89481,
+            1.59564, -1.86793, 0.41477,
+        ];
+        let dates = vec![
+            38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082, 39113, 39141, 39172,
+        ];
+        let freq = enums::ClFrequency::ClFrequencyMonthly;
+        let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+        let (mut pack, mut expected) = (StandardPack::new(), StandardPack::new());
+        standard_pack(&mpt, &dates, freq, &mut expected);
+        let mut methodology = Methodology::current();
+        standard_pack_by_methodology(&mpt, &dates, freq, &methodology, &mut pack);
+        assert_eq!(pack.to_json(), expected.to_json());
+
+        methodology.capture_method = enums::ClCaptureMethod::ClCaptureCumulative;
+        methodology.variance_mode = enums::ClVarianceMode::ClVariancePopulation;
+        methodology.geometric_annualization = false;
+        methodology.sterling_constant = 0.0;
+        let err = standard_pack_by_methodology(&mpt, &dates, freq, &methodology, &mut pack);
+        let (mut upside_capture, mut capture_return, mut standard_deviation, mut annu_return) =
+            (f64::NAN, f64::NAN, f64::NAN, f64::NAN);
+        mpt.upside_capture_by_method(
+            enums::ClCaptureMethod::ClCaptureCumulative,
+            &mut upside_capture,
+            &mut capture_return,
+        );
+        mpt.standard_deviation_by_mode(
+            freq,
+            true,
+            enums::ClVarianceMode::ClVariancePopulation,
+            &mut standard_deviation,
+        );
+        mpt.annualized_return_by_dates(&dates, false, &mut annu_return);
+        let mut sterling_ratio = f64::NAN;
+        mpt.sterling_ratio_by_constant(&dates, freq, 0.0, &mut sterling_ratio);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && pack.upside_capture == upside_capture
+                && pack.upside_capture != expected.upside_capture
+                && pack.standard_deviation == standard_deviation
+                && pack.standard_deviation < expected.standard_deviation
+                && pack.annualized_return == annu_return
+                && pack.sterling_ratio == sterling_ratio
+                && pack.sterling_ratio != expected.sterling_ratio
+                && pack.beta == expected.beta,
+            true
+        );
+    }
+}
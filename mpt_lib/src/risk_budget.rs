@@ -0,0 +1,171 @@
+use crate::{enums::Errors, linalg::mat_vec_mul, MPTCalculator};
+
+///the risk contributions of the assets against their target risk budgets, the contribution ratios, the budgets and
+///the deviations are fractions of the portfolio risk.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RiskBudgetReport {
+    ///the standard deviation of the portfolio.
+    pub portfolio_risk: f64,
+    ///the contribution of each asset to the standard deviation of the portfolio, the sum is the portfolio risk.
+    pub contributions: Vec<f64>,
+    ///the contribution of each asset divided by the portfolio risk, the sum is 1.
+    pub contribution_ratios: Vec<f64>,
+    ///the target risk budget of each asset, the sum is 1.
+    pub budgets: Vec<f64>,
+    ///the contribution ratio minus the budget of each asset.
+    pub deviations: Vec<f64>,
+    ///the max absolute deviation.
+    pub max_deviation: f64,
+}
+
+impl RiskBudgetReport {
+    pub fn new() -> RiskBudgetReport {
+        RiskBudgetReport {
+            portfolio_risk: f64::NAN,
+            contributions: Vec::new(),
+            contribution_ratios: Vec::new(),
+            budgets: Vec::new(),
+            deviations: Vec::new(),
+            max_deviation: f64::NAN,
+        }
+    }
+}
+
+impl Default for RiskBudgetReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///compare the risk contribution of each asset, weight * (covariance * weights) / portfolio risk, against its
+    ///target risk budget, e.g. to verify the equal risk contributions of a risk parity portfolio.
+    ///# Arguments
+    ///weights: the weight of each asset.
+    ///
+    ///covariance: the covariance matrix of the assets.
+    ///
+    ///budgets: the target risk budget of each asset, they are normalized to the sum of 1. it is empty for the equal
+    ///risk contributions.
+    ///
+    ///report: the risk contributions and their deviations from the budgets.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///use mpt_lib::risk_budget::RiskBudgetReport;
+    ///
+    ///let covariance = vec![vec![4.0, 0.0], vec![0.0, 16.0]];
+    ///let mut report = RiskBudgetReport::new();
+    ///let err = MPTCalculator::risk_budget_report(&[2.0 / 3.0, 1.0 / 3.0], &covariance, &[], &mut report);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(&report.contribution_ratios, &[0.5, 0.5])
+    ///        && MPTCalculator::is_eq_double(report.max_deviation, 0.0),
+    ///    true
+    ///);
+    ///```
+    pub fn risk_budget_report(
+        weights: &[f64],
+        covariance: &[Vec<f64>],
+        budgets: &[f64],
+        report: &mut RiskBudgetReport,
+    ) -> Errors {
+        *report = RiskBudgetReport::new();
+        let n = weights.len();
+        if n == 0
+            || covariance.len() != n
+            || covariance.iter().any(|x| x.len() != n)
+            || (budgets.len() != 0 && budgets.len() != n)
+            || weights.iter().any(|x| !x.is_finite())
+            || covariance.iter().any(|x| x.iter().any(|v| !v.is_finite()))
+            || budgets.iter().any(|x| !x.is_finite() || *x < 0.0)
+            || (budgets.len() != 0 && budgets.iter().sum::<f64>() <= 0.0)
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+
+        let marginal = mat_vec_mul(covariance, weights);
+        let variance: f64 = weights.iter().zip(marginal.iter()).map(|x| x.0 * x.1).sum();
+        if !(variance > 0.0) {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        report.portfolio_risk = variance.sqrt();
+        report.contributions = weights
+            .iter()
+            .zip(marginal.iter())
+            .map(|x| x.0 * x.1 / report.portfolio_risk)
+            .collect();
+        report.contribution_ratios = weights
+            .iter()
+            .zip(marginal.iter())
+            .map(|x| x.0 * x.1 / variance)
+            .collect();
+        report.budgets = if budgets.len() == 0 {
+            vec![1.0 / n as f64; n]
+        } else {
+            let sum: f64 = budgets.iter().sum();
+            budgets.iter().map(|x| x / sum).collect()
+        };
+        report.deviations = report
+            .contribution_ratios
+            .iter()
+            .zip(report.budgets.iter())
+            .map(|x| x.0 - x.1)
+            .collect();
+        report.max_deviation = report
+            .deviations
+            .iter()
+            .fold(0.0, |acc, x| f64::max(acc, x.abs()));
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RiskBudgetReport;
+    use crate::{enums::Errors, MPTCalculator};
+
+    #[test]
+    fn should_correct_risk_budget_report() {
+        let covariance = vec![
+            vec![4.0, 1.2, 0.0],
+            vec![1.2, 9.0, -1.5],
+            vec![0.0, -1.5, 25.0],
+        ];
+        let weights = [0.5, 0.3, 0.2];
+        let mut report = RiskBudgetReport::new();
+        let err =
+            MPTCalculator::risk_budget_report(&weights, &covariance, &[2.0, 1.0, 1.0], &mut report);
+        //the variance is 1.0 + 0.81 + 1.0 + 2 * (0.18 - 0.09) = 2.99
+        let ratios = [
+            0.5 * (2.0 + 0.36) / 2.99,
+            0.3 * (0.6 + 2.7 - 0.3) / 2.99,
+            0.2 * (-0.45 + 5.0) / 2.99,
+        ];
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(report.portfolio_risk, 2.99f64.sqrt())
+                && MPTCalculator::is_eq_double_array(&report.contribution_ratios, &ratios)
+                && MPTCalculator::is_eq_double(
+                    report.contributions.iter().sum::<f64>(),
+                    2.99f64.sqrt()
+                )
+                && MPTCalculator::is_eq_double_array(&report.budgets, &[0.5, 0.25, 0.25])
+                && MPTCalculator::is_eq_double(report.deviations[0], ratios[0] - 0.5)
+                && MPTCalculator::is_eq_double(report.max_deviation, 0.5 - ratios[0]),
+            true
+        );
+
+        let err = MPTCalculator::risk_budget_report(
+            &weights,
+            &covariance,
+            &[1.0, -1.0, 1.0],
+            &mut report,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && report.contributions.len() == 0,
+            true
+        );
+    }
+}
@@ -0,0 +1,290 @@
+use crate::{common::is_sorted_array, enums::Errors, kernels, MPTCalculator};
+
+///a historical window scenario, e.g. the global financial crisis or the covid crash.
+pub struct HistoricalScenario {
+    pub name: String,
+    pub start_date: i32,
+    pub end_date: i32,
+}
+
+///a hypothetical scenario given by the benchmark return of each period.
+pub struct ShockScenario {
+    pub name: String,
+    pub benchmark_shocks: Vec<f64>,
+}
+
+///the performance of the fund in one scenario, the return and draw down are in percent.
+#[derive(Clone, Debug)]
+pub struct ScenarioReport {
+    pub name: String,
+    pub periods: i32,
+    pub total_return: f64,
+    pub benchmark_return: f64,
+    pub excess_return: f64,
+    pub max_draw_down: f64,
+    pub beta: f64,
+}
+
+impl ScenarioReport {
+    fn new(name: &str) -> ScenarioReport {
+        ScenarioReport {
+            name: name.to_string(),
+            periods: 0,
+            total_return: f64::NAN,
+            benchmark_return: f64::NAN,
+            excess_return: f64::NAN,
+            max_draw_down: f64::NAN,
+            beta: f64::NAN,
+        }
+    }
+}
+
+fn fill_report(values: &[f64], benchmark: &[f64], beta: f64, report: &mut ScenarioReport) {
+    report.periods = values.len() as i32;
+    if values.len() == 0 {
+        return;
+    }
+    MPTCalculator::total_return_accumulat(values, &mut report.total_return);
+    MPTCalculator::total_return_accumulat(benchmark, &mut report.benchmark_return);
+    report.excess_return = report.total_return - report.benchmark_return;
+    report.beta = beta;
+    if !report.total_return.is_finite() {
+        return;
+    }
+
+    report.max_draw_down = 0.0
+        - kernels::draw_down_depths(values)
+            .into_iter()
+            .fold(0.0, f64::max);
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///calculate the performance, max draw down and beta of the fund in each historical scenario window,
+    ///the values and benchmark should sort by date. if the data in a window has NAN/INF values, the result of
+    ///the window will be NAN
+    ///# Arguments
+    ///dates: the date of value
+    ///
+    ///scenarios: the historical windows, the start date and the end date are included.
+    ///
+    ///reports: the report of each scenario.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///use mpt_lib::scenarios::HistoricalScenario;
+    ///
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let bmk_data = vec![
+    ///    0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+    ///    1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+    ///    3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+    ///    -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+    ///];
+    ///let dates = vec![
+    ///    38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082, 39113, 39141, 39172,
+    ///    39202, 39233, 39263, 39294, 39325, 39355, 39386, 39416, 39447, 39478, 39507, 39538,
+    ///    39568, 39599, 39629, 39660, 39691, 39721, 39752, 39782, 39813, 39844, 39872, 39903,
+    ///];
+    ///let scenarios = vec![HistoricalScenario {
+    ///    name: "GFC".to_string(),
+    ///    start_date: 39600,
+    ///    end_date: 39903,
+    ///}];
+    ///let mut reports = Vec::new();
+    ///let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+    ///let err = mpt.historical_scenarios(&dates, &scenarios, &mut reports);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && reports[0].periods == 10
+    ///        && MPTCalculator::is_eq_double(reports[0].total_return, -35.230250)
+    ///        && MPTCalculator::is_eq_double(reports[0].max_draw_down, -38.741684)
+    ///        && MPTCalculator::is_eq_double(reports[0].beta, 0.992746),
+    ///    true
+    ///);
+    ///```
+    pub fn historical_scenarios(
+        &self,
+        dates: &[i32],
+        scenarios: &[HistoricalScenario],
+        reports: &mut Vec<ScenarioReport>,
+    ) -> Errors {
+        if self.values.len() == 0
+            || dates.len() != self.values.len()
+            || self.benchmark.len() != self.values.len()
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        reports.clear();
+        if dates.len() > 1 && !is_sorted_array(dates) {
+            return Errors::ClErrorCodeUnsortedByDate;
+        }
+
+        for scenario in scenarios {
+            let mut report = ScenarioReport::new(&scenario.name);
+            let start = dates.partition_point(|x| *x < scenario.start_date);
+            let end = dates.partition_point(|x| *x <= scenario.end_date);
+            if start < end {
+                let mut beta = f64::NAN;
+                MPTCalculator::from_v_b(&self.values[start..end], &self.benchmark[start..end])
+                    .beta(&mut beta);
+                fill_report(
+                    &self.values[start..end],
+                    &self.benchmark[start..end],
+                    beta,
+                    &mut report,
+                );
+            }
+            reports.push(report);
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///estimate the performance and max draw down of the fund in each shock scenario, the fund return of each
+    ///period is the benchmark shock multiplied by the beta of the whole history.
+    ///if the array has NAN/INF values,the result will be NAN
+    ///# Arguments
+    ///scenarios: the benchmark shocks of each scenario.
+    ///
+    ///reports: the report of each scenario.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///use mpt_lib::scenarios::ShockScenario;
+    ///
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let bmk_data = vec![
+    ///    0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+    ///    1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901,
+    ///    3.73988, 1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526,
+    ///    -8.43036, -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+    ///];
+    ///let scenarios = vec![ShockScenario {
+    ///    name: "equity crash".to_string(),
+    ///    benchmark_shocks: vec![-10.0, -5.0, 3.0],
+    ///}];
+    ///let mut reports = Vec::new();
+    ///let mpt = MPTCalculator::from_v_b(&data, &bmk_data);
+    ///let err = mpt.shock_scenarios(&scenarios, &mut reports);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(reports[0].total_return, -11.622466)
+    ///        && MPTCalculator::is_eq_double(reports[0].benchmark_return, -11.935)
+    ///        && MPTCalculator::is_eq_double(reports[0].max_draw_down, -14.130647),
+    ///    true
+    ///);
+    ///```
+    pub fn shock_scenarios(
+        &self,
+        scenarios: &[ShockScenario],
+        reports: &mut Vec<ScenarioReport>,
+    ) -> Errors {
+        if self.values.len() == 0 || self.benchmark.len() != self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        reports.clear();
+
+        let mut beta = f64::NAN;
+        if self.values.iter().all(|x| x.is_finite()) && self.benchmark.iter().all(|x| x.is_finite())
+        {
+            self.beta(&mut beta);
+        }
+
+        for scenario in scenarios {
+            let mut report = ScenarioReport::new(&scenario.name);
+            let values: Vec<f64> = scenario.benchmark_shocks.iter().map(|x| x * beta).collect();
+            fill_report(&values, &scenario.benchmark_shocks, beta, &mut report);
+            reports.push(report);
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{enums::Errors, MPTCalculator};
+
+    use super::{HistoricalScenario, ShockScenario};
+
+    const DATA: [f64; 36] = [
+        -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+        1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713, 1.88831,
+        1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807, 1.66744,
+        -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ];
+    const BMK_DATA: [f64; 36] = [
+        0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+        1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901, 3.73988,
+        1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526, -8.43036,
+        -0.84062, 1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+    ];
+    const DATES: [i32; 36] = [
+        38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082, 39113, 39141, 39172, 39202,
+        39233, 39263, 39294, 39325, 39355, 39386, 39416, 39447, 39478, 39507, 39538, 39568, 39599,
+        39629, 39660, 39691, 39721, 39752, 39782, 39813, 39844, 39872, 39903,
+    ];
+
+    #[test]
+    fn should_correct_historical_scenarios() {
+        let scenarios = vec![
+            HistoricalScenario {
+                name: "GFC".to_string(),
+                start_date: 39600,
+                end_date: 39903,
+            },
+            HistoricalScenario {
+                name: "empty".to_string(),
+                start_date: 30000,
+                end_date: 30100,
+            },
+        ];
+        let mut reports = Vec::new();
+        let mpt = MPTCalculator::from_v_b(&DATA, &BMK_DATA);
+        let err = mpt.historical_scenarios(&DATES, &scenarios, &mut reports);
+        assert_eq!(err, Errors::ClErrorCodeNoError);
+        assert_eq!(reports.len(), 2);
+        assert_eq!(
+            reports[0].periods == 10
+                && MPTCalculator::is_eq_double(reports[0].total_return, -35.230250)
+                && MPTCalculator::is_eq_double(reports[0].benchmark_return, -36.291006)
+                && MPTCalculator::is_eq_double(reports[0].excess_return, 1.060756)
+                && MPTCalculator::is_eq_double(reports[0].max_draw_down, -38.741684)
+                && MPTCalculator::is_eq_double(reports[0].beta, 0.992746),
+            true
+        );
+        assert_eq!(
+            reports[1].periods == 0 && reports[1].total_return.is_nan(),
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_shock_scenarios() {
+        let scenarios = vec![ShockScenario {
+            name: "equity crash".to_string(),
+            benchmark_shocks: vec![-10.0, -5.0, 3.0],
+        }];
+        let mut reports = Vec::new();
+        let mpt = MPTCalculator::from_v_b(&DATA, &BMK_DATA);
+        let err = mpt.shock_scenarios(&scenarios, &mut reports);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(reports[0].beta, 0.973642)
+                && MPTCalculator::is_eq_double(reports[0].total_return, -11.622466)
+                && MPTCalculator::is_eq_double(reports[0].benchmark_return, -11.935)
+                && MPTCalculator::is_eq_double(reports[0].max_draw_down, -14.130647),
+            true
+        );
+    }
+}
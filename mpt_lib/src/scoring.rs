@@ -0,0 +1,536 @@
+use crate::{
+    common::{get_annual_multiplier, is_valid_frequency},
+    enums::{self, ClScoreTransform, Errors},
+    kernels, MPTCalculator,
+};
+
+///a statistic of the composite score, the transform makes the higher values the better values, e.g. ClScoreNegate
+///for the standard deviation, and ClScoreNegateAbsolute for the statistics the closer to 0 the better.
+#[derive(Clone, Copy, Debug)]
+pub struct ScoreComponent {
+    pub name: &'static str,
+    pub statistic: fn(&MPTCalculator, &mut f64) -> Errors,
+    pub transform: ClScoreTransform,
+    pub weight: f64,
+}
+
+///the composite scores of a universe.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompositeScores {
+    ///statistics[i][j] is the statistic of the component i of the fund j.
+    pub statistics: Vec<Vec<f64>>,
+    ///normalized[i][j] is the normalized transformed statistic of the component i of the fund j.
+    pub normalized: Vec<Vec<f64>>,
+    ///the weighted average of the normalized statistics of each fund.
+    pub scores: Vec<f64>,
+    ///the rank of the score of each fund, 1 is the highest score.
+    pub ranks: Vec<f64>,
+}
+
+impl CompositeScores {
+    pub fn new() -> CompositeScores {
+        CompositeScores {
+            statistics: Vec::new(),
+            normalized: Vec::new(),
+            scores: Vec::new(),
+            ranks: Vec::new(),
+        }
+    }
+}
+
+impl Default for CompositeScores {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///the weights and the reference levels of the composite risk score. the default weights are 0.4 for the annualized
+///volatility, 0.3 for the max draw down, 0.2 for the value at risk and 0.1 for the skewness, the default reference
+///levels are 15% annualized volatility, 30% max draw down and 25% annualized value at risk, they are about the risk
+///of a global equity fund.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RiskScoreWeights {
+    pub volatility: f64,
+    pub max_draw_down: f64,
+    pub value_at_risk: f64,
+    pub skewness: f64,
+    pub volatility_reference: f64,
+    pub max_draw_down_reference: f64,
+    pub value_at_risk_reference: f64,
+}
+
+impl RiskScoreWeights {
+    pub fn new() -> RiskScoreWeights {
+        RiskScoreWeights {
+            volatility: 0.4,
+            max_draw_down: 0.3,
+            value_at_risk: 0.2,
+            skewness: 0.1,
+            volatility_reference: 15.0,
+            max_draw_down_reference: 30.0,
+            value_at_risk_reference: 25.0,
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        let weights = [
+            self.volatility,
+            self.max_draw_down,
+            self.value_at_risk,
+            self.skewness,
+        ];
+        return weights.iter().all(|x| x.is_finite() && *x >= 0.0)
+            && weights.iter().sum::<f64>() > 0.0
+            && [
+                self.volatility_reference,
+                self.max_draw_down_reference,
+                self.value_at_risk_reference,
+            ]
+            .iter()
+            .all(|x| x.is_finite() && *x > 0.0);
+    }
+}
+
+impl Default for RiskScoreWeights {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///the composite risk score and its components.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RiskScore {
+    ///the annualized standard deviation in percent.
+    pub volatility: f64,
+    ///the max draw down of the compound returns, it is a positive loss in percent.
+    pub max_draw_down: f64,
+    ///the historical 95% value at risk of a period scaled to a year by the square root of the annual multiplier, it
+    ///is a positive loss in percent.
+    pub value_at_risk: f64,
+    pub skewness: f64,
+    ///the weighted average of the components divided by their reference levels, the skewness component is 1 minus
+    ///the skewness. it is 1 for a fund at the reference levels with a symmetric distribution, the higher the riskier.
+    pub score: f64,
+}
+
+impl RiskScore {
+    pub fn new() -> RiskScore {
+        RiskScore {
+            volatility: f64::NAN,
+            max_draw_down: f64::NAN,
+            value_at_risk: f64::NAN,
+            skewness: f64::NAN,
+            score: f64::NAN,
+        }
+    }
+}
+
+impl Default for RiskScore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn transform(value: f64, transform: ClScoreTransform) -> f64 {
+    match transform {
+        ClScoreTransform::ClScoreIdentity => value,
+        ClScoreTransform::ClScoreNegate => -value,
+        ClScoreTransform::ClScoreAbsolute => value.abs(),
+        ClScoreTransform::ClScoreNegateAbsolute => -value.abs(),
+    }
+}
+
+///calculate the composite score and the rank of each fund of a universe. the statistics of the components are
+///calculated by the batch engine, transformed, normalized across the universe by the method, and averaged by the
+///weights. a fund without the statistic of a component, e.g. a fund with insufficient history, is scored by the
+///weights of its other components, and a fund without any statistic has a NAN score and a NAN rank. the equal
+///scores are ranked by the fund order.
+///
+///# Arguments
+///funds: the returns of each fund, all the funds should have the same length.
+///
+///benchmark: the benchmark of the funds, it can be empty if no component needs it.
+///
+///riskfree: the risk free of the funds, it can be empty if no component needs it.
+///
+///components: the statistics of the score, the weights should not be negative and their sum should be positive.
+///
+///method: the normalization of the statistics.
+///
+///scores: the composite scores.
+///# Examples
+///```
+///use mpt_lib::enums::{ClFrequency, ClNormalizeMethod, ClScoreTransform, Errors};
+///use mpt_lib::scoring::{self, CompositeScores, ScoreComponent};
+///
+///let fund1 = vec![1.0, 2.0, 3.0, 2.0];
+///let fund2 = vec![2.0, -2.0, 4.0, 0.0];
+///let fund3 = vec![0.5, 0.5, 1.0, 1.0];
+///let components = [
+///    ScoreComponent {
+///        name: "average",
+///        statistic: |calc, res| calc.average(res),
+///        transform: ClScoreTransform::ClScoreIdentity,
+///        weight: 1.0,
+///    },
+///    ScoreComponent {
+///        name: "standard_deviation",
+///        statistic: |calc, res| calc.standard_deviation(ClFrequency::ClFrequencyMonthly, true, res),
+///        transform: ClScoreTransform::ClScoreNegate,
+///        weight: 1.0,
+///    },
+///];
+///let mut scores = CompositeScores::new();
+///let err = scoring::composite_scores(
+///    &[&fund1, &fund2, &fund3],
+///    &[],
+///    &[],
+///    &components,
+///    ClNormalizeMethod::ClNormalizePercentile,
+///    &mut scores,
+///);
+///assert_eq!(
+///    err == Errors::ClErrorCodeNoError && scores.ranks == vec![1.0, 3.0, 2.0],
+///    true
+///);
+///```
+pub fn composite_scores(
+    funds: &[&[f64]],
+    benchmark: &[f64],
+    riskfree: &[f64],
+    components: &[ScoreComponent],
+    method: enums::ClNormalizeMethod,
+    scores: &mut CompositeScores,
+) -> Errors {
+    *scores = CompositeScores::new();
+    if components.len() == 0
+        || components
+            .iter()
+            .any(|x| !x.weight.is_finite() || x.weight < 0.0)
+        || components.iter().map(|x| x.weight).sum::<f64>() <= 0.0
+    {
+        return Errors::ClErrorCodeInvalidPara;
+    }
+    instrument!(
+        "composite_scores",
+        funds = funds.len(),
+        components = components.len()
+    );
+
+    let statistics: Vec<fn(&MPTCalculator, &mut f64) -> Errors> =
+        components.iter().map(|x| x.statistic).collect();
+    let ret = MPTCalculator::statistics_batch(
+        funds,
+        benchmark,
+        riskfree,
+        &statistics,
+        &mut scores.statistics,
+    );
+    if ret != Errors::ClErrorCodeNoError {
+        return ret;
+    }
+
+    for (component, values) in components.iter().zip(scores.statistics.iter()) {
+        let transformed: Vec<f64> = values
+            .iter()
+            .map(|x| transform(*x, component.transform))
+            .collect();
+        let mut normalized = vec![f64::NAN; transformed.len()];
+        let ret = MPTCalculator::from_v(&transformed).normalize(method, &mut normalized);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        scores.normalized.push(normalized);
+    }
+
+    scores.scores = (0..funds.len())
+        .map(|j| {
+            let (sum, weights) = components
+                .iter()
+                .zip(scores.normalized.iter())
+                .filter(|x| x.1[j].is_finite() && x.0.weight > 0.0)
+                .fold((0.0, 0.0), |acc, x| {
+                    (acc.0 + x.0.weight * x.1[j], acc.1 + x.0.weight)
+                });
+            if weights > 0.0 {
+                sum / weights
+            } else {
+                f64::NAN
+            }
+        })
+        .collect();
+
+    scores.ranks = vec![f64::NAN; funds.len()];
+    return MPTCalculator::from_v(&scores.scores).rank_by_nan_policy(
+        enums::ClRankType::ClRankTypeDec as i16,
+        enums::ClTieBreak::ClTieBreakStable,
+        &[],
+        enums::ClNanRankPolicy::ClNanExcludeAndRenumber,
+        &mut scores.ranks,
+    );
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///calculate the composite risk score of an array, it combines the annualized volatility, the max draw down, the
+    ///value at risk and the skewness into a single number for the risk rating of the suitability tools. each
+    ///component is divided by its reference level, and the negative skewness increases the score. if the array has
+    ///NAN/INF values,the result will be NAN
+    ///
+    ///# Arguments
+    ///freq: the frequence of source data.
+    ///
+    ///weights: the weights and the reference levels of the components, RiskScoreWeights::new() is the default.
+    ///
+    ///risk_score: the score and its components.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///use mpt_lib::scoring::{RiskScore, RiskScoreWeights};
+    ///let data = vec![
+    ///    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+    ///    1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+    ///    1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+    ///    1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+    ///];
+    ///let mut risk_score = RiskScore::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.composite_risk_score(
+    ///    enums::ClFrequency::ClFrequencyMonthly,
+    ///    &RiskScoreWeights::new(),
+    ///    &mut risk_score,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(risk_score.score, 1.368203),
+    ///    true
+    ///);
+    ///```
+    pub fn composite_risk_score(
+        &self,
+        freq: enums::ClFrequency,
+        weights: &RiskScoreWeights,
+        risk_score: &mut RiskScore,
+    ) -> Errors {
+        *risk_score = RiskScore::new();
+        if !is_valid_frequency(freq) || !weights.is_valid() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let ret = kernels::check_sample_size(self.values.len(), 3);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        if self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        self.standard_deviation(freq, true, &mut risk_score.volatility);
+        self.skewness(&mut risk_score.skewness);
+        risk_score.max_draw_down = kernels::draw_down_depths(self.values)
+            .into_iter()
+            .fold(0.0, f64::max);
+        let mut quantile = f64::NAN;
+        kernels::percentile(self.values, 5, &mut quantile);
+        risk_score.value_at_risk =
+            f64::max(-quantile, 0.0) * get_annual_multiplier(freq, false).sqrt();
+
+        let components = [
+            (
+                weights.volatility,
+                risk_score.volatility / weights.volatility_reference,
+            ),
+            (
+                weights.max_draw_down,
+                risk_score.max_draw_down / weights.max_draw_down_reference,
+            ),
+            (
+                weights.value_at_risk,
+                risk_score.value_at_risk / weights.value_at_risk_reference,
+            ),
+            (weights.skewness, 1.0 - risk_score.skewness),
+        ];
+        if components.iter().any(|x| !x.1.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+        risk_score.score = components.iter().map(|x| x.0 * x.1).sum::<f64>()
+            / components.iter().map(|x| x.0).sum::<f64>();
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{composite_scores, CompositeScores, RiskScore, RiskScoreWeights, ScoreComponent};
+    use crate::{
+        enums::{ClFrequency, ClNormalizeMethod, ClScoreTransform, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_composite_scores() {
+        let fund1 = vec![1.0, 2.0, 3.0, 2.0, -1.0, 0.5];
+        let fund2 = vec![2.0, -2.0, 4.0, 0.0, 1.0, -0.5];
+        let fund3 = vec![f64::NAN; 6];
+        let fund4 = vec![0.5, 0.5, 1.0, 1.0, 0.2, 0.4];
+        let index = vec![0.8, 1.5, 2.5, 1.0, -0.5, 0.2];
+        let components = [
+            ScoreComponent {
+                name: "average",
+                statistic: |calc, res| calc.average(res),
+                transform: ClScoreTransform::ClScoreIdentity,
+                weight: 1.0,
+            },
+            ScoreComponent {
+                name: "standard_deviation",
+                statistic: |calc, res| {
+                    calc.standard_deviation(ClFrequency::ClFrequencyMonthly, true, res)
+                },
+                transform: ClScoreTransform::ClScoreNegate,
+                weight: 1.0,
+            },
+            ScoreComponent {
+                name: "beta",
+                statistic: |calc, res| calc.beta(res),
+                transform: ClScoreTransform::ClScoreNegateAbsolute,
+                weight: 0.0,
+            },
+        ];
+        let mut scores = CompositeScores::new();
+        let err = composite_scores(
+            &[&fund1, &fund2, &fund3, &fund4],
+            &index,
+            &[],
+            &components,
+            ClNormalizeMethod::ClNormalizeZScore,
+            &mut scores,
+        );
+
+        let funds = [&fund1, &fund2, &fund4];
+        let mut expected = [0.0; 3];
+        for (k, component) in components[..2].iter().enumerate() {
+            let values: Vec<f64> = funds
+                .iter()
+                .map(|x| {
+                    let mut res = f64::NAN;
+                    (component.statistic)(&MPTCalculator::from_v(x), &mut res);
+                    if k == 1 {
+                        -res
+                    } else {
+                        res
+                    }
+                })
+                .collect();
+            let mut normalized = [f64::NAN; 3];
+            MPTCalculator::from_v(&values)
+                .normalize(ClNormalizeMethod::ClNormalizeZScore, &mut normalized);
+            expected
+                .iter_mut()
+                .zip(normalized.iter())
+                .for_each(|x| *x.0 += x.1 / 2.0);
+        }
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double_array(
+                    &[scores.scores[0], scores.scores[1], scores.scores[3]],
+                    &expected
+                )
+                && scores.scores[2].is_nan()
+                && scores.ranks[2].is_nan()
+                && scores.statistics.len() == 3
+                && scores.normalized.len() == 3,
+            true
+        );
+        assert_eq!(
+            [scores.ranks[0], scores.ranks[1], scores.ranks[3]]
+                .iter()
+                .all(|x| *x >= 1.0 && *x <= 3.0),
+            true
+        );
+
+        let mut invalid = components;
+        invalid[0].weight = -1.0;
+        let err = composite_scores(
+            &[&fund1],
+            &[],
+            &[],
+            &invalid,
+            ClNormalizeMethod::ClNormalizeZScore,
+            &mut scores,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && scores.scores.len() == 0,
+            true
+        );
+    }
+
+    #[test]
+    fn should_correct_composite_risk_score() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        let mpt = MPTCalculator::from_v(&data);
+        let weights = RiskScoreWeights::new();
+        let mut risk_score = RiskScore::new();
+        let err =
+            mpt.composite_risk_score(ClFrequency::ClFrequencyMonthly, &weights, &mut risk_score);
+        let mut volatility = f64::NAN;
+        mpt.standard_deviation(ClFrequency::ClFrequencyMonthly, true, &mut volatility);
+        let mut skewness = f64::NAN;
+        mpt.skewness(&mut skewness);
+        let expected = 0.4 * volatility / 15.0
+            + 0.3 * risk_score.max_draw_down / 30.0
+            + 0.2 * risk_score.value_at_risk / 25.0
+            + 0.1 * (1.0 - skewness);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(risk_score.volatility, volatility)
+                && MPTCalculator::is_eq_double(risk_score.skewness, skewness)
+                && MPTCalculator::is_eq_double(risk_score.max_draw_down, 43.725947)
+                && MPTCalculator::is_eq_double(risk_score.value_at_risk, 34.10686)
+                && MPTCalculator::is_eq_double(risk_score.score, expected),
+            true
+        );
+
+        //only the volatility
+        let only_volatility = RiskScoreWeights {
+            max_draw_down: 0.0,
+            value_at_risk: 0.0,
+            skewness: 0.0,
+            ..weights
+        };
+        mpt.composite_risk_score(
+            ClFrequency::ClFrequencyMonthly,
+            &only_volatility,
+            &mut risk_score,
+        );
+        assert_eq!(
+            MPTCalculator::is_eq_double(risk_score.score, volatility / 15.0),
+            true
+        );
+
+        let mut nan_data = data.clone();
+        nan_data[3] = f64::NAN;
+        let err = MPTCalculator::from_v(&nan_data).composite_risk_score(
+            ClFrequency::ClFrequencyMonthly,
+            &weights,
+            &mut risk_score,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && risk_score.score.is_nan(),
+            true
+        );
+
+        let invalid = RiskScoreWeights {
+            volatility_reference: 0.0,
+            ..weights
+        };
+        let err =
+            mpt.composite_risk_score(ClFrequency::ClFrequencyMonthly, &invalid, &mut risk_score);
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && risk_score.volatility.is_nan(),
+            true
+        );
+    }
+}
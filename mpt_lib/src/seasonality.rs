@@ -0,0 +1,240 @@
+use chrono::{Datelike, NaiveDate};
+
+use crate::{
+    common::student_t_cdf,
+    date_util,
+    enums::{self, Errors},
+    MPTCalculator,
+};
+
+///the returns grouped by the calendar month, or by the weekday for the daily data.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeasonalityProfile {
+    ///the calendar month from 1 to 12, or the weekday from 1 (monday) to 7 (sunday).
+    pub labels: Vec<u32>,
+    ///the number of the returns of each label.
+    pub counts: Vec<usize>,
+    ///the average return of each label.
+    pub averages: Vec<f64>,
+    ///the fraction of the positive returns of each label.
+    pub hit_rates: Vec<f64>,
+    ///the welch t statistic of the average return of each label against the average return of the other labels.
+    pub t_stats: Vec<f64>,
+    ///the two sided p value of the t statistic.
+    pub p_values: Vec<f64>,
+}
+
+impl SeasonalityProfile {
+    pub fn new() -> SeasonalityProfile {
+        SeasonalityProfile {
+            labels: Vec::new(),
+            counts: Vec::new(),
+            averages: Vec::new(),
+            hit_rates: Vec::new(),
+            t_stats: Vec::new(),
+            p_values: Vec::new(),
+        }
+    }
+}
+
+impl Default for SeasonalityProfile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///the count, mean and sample variance of the values.
+fn moments(values: &[f64]) -> (f64, f64, f64) {
+    let count = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / count;
+    let variance = values.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (count - 1.0);
+    (count, mean, variance)
+}
+
+///the welch t statistic and the two sided p value of the means of the two samples.
+fn welch_test(sample: &[f64], others: &[f64]) -> (f64, f64) {
+    if sample.len() < 2 || others.len() < 2 {
+        return (f64::NAN, f64::NAN);
+    }
+    let (n1, m1, v1) = moments(sample);
+    let (n2, m2, v2) = moments(others);
+    let error = v1 / n1 + v2 / n2;
+    if error <= 0.0 {
+        return (f64::NAN, f64::NAN);
+    }
+    let t = (m1 - m2) / error.sqrt();
+    let df = error * error / ((v1 / n1).powi(2) / (n1 - 1.0) + (v2 / n2).powi(2) / (n2 - 1.0));
+    (t, 2.0 * (1.0 - student_t_cdf(t.abs(), df)))
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///calculate the seasonality profile of the returns, the returns are grouped by the calendar month of the dates,
+    ///or by the weekday for the daily data, for the best month and the worst month commentary. the average return of
+    ///each group is tested against the average return of the other groups by the welch t test. the NAN/INF values
+    ///are skipped, and the statistics of a group without returns are NAN.
+    ///
+    ///# Arguments
+    ///dates: the date of value
+    ///
+    ///freq: the frequence of source data.
+    ///
+    ///profile: the statistics of each month or weekday.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///use mpt_lib::seasonality::SeasonalityProfile;
+    ///
+    ///let data = vec![1.0, -0.5, 2.0, 0.5, 1.5, -1.0, 3.0, 0.0];
+    ///let dates = vec![44957, 44985, 45322, 45351, 45688, 45716, 46053, 46081];
+    ///let mut profile = SeasonalityProfile::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.seasonality_profile(&dates, enums::ClFrequency::ClFrequencyMonthly, &mut profile);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && profile.labels.len() == 12
+    ///        && profile.counts[0] == 4
+    ///        && MPTCalculator::is_eq_double(profile.averages[0], 1.875)
+    ///        && MPTCalculator::is_eq_double(profile.hit_rates[1], 0.25)
+    ///        && profile.averages[2].is_nan(),
+    ///    true
+    ///);
+    ///```
+    pub fn seasonality_profile(
+        &self,
+        dates: &[i32],
+        freq: enums::ClFrequency,
+        profile: &mut SeasonalityProfile,
+    ) -> Errors {
+        *profile = SeasonalityProfile::new();
+        if self.values.len() == 0
+            || dates.len() != self.values.len()
+            || dates.iter().any(|x| *x < 2)
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        instrument!("seasonality_profile", rows = self.values.len());
+
+        let is_daily = freq == enums::ClFrequency::ClFrequencyDaily;
+        let label_count = if is_daily { 7 } else { 12 };
+        let mut groups: Vec<Vec<f64>> = vec![Vec::new(); label_count];
+        for (value, n_date) in self.values.iter().zip(dates.iter()) {
+            if !value.is_finite() {
+                continue;
+            }
+            let mut date = NaiveDate::default();
+            if !date_util::from_int(*n_date as u64, &mut date) {
+                return Errors::ClErrorCodeInvalidPara;
+            }
+            let label = if is_daily {
+                date.weekday().number_from_monday()
+            } else {
+                date.month()
+            };
+            groups[label as usize - 1].push(*value);
+        }
+
+        for (k, group) in groups.iter().enumerate() {
+            let others: Vec<f64> = groups
+                .iter()
+                .enumerate()
+                .filter(|x| x.0 != k)
+                .flat_map(|x| x.1.iter().copied())
+                .collect();
+            let (t_stat, p_value) = welch_test(group, &others);
+            profile.labels.push(k as u32 + 1);
+            profile.counts.push(group.len());
+            if group.len() > 0 {
+                profile
+                    .averages
+                    .push(group.iter().sum::<f64>() / group.len() as f64);
+                profile
+                    .hit_rates
+                    .push(group.iter().filter(|x| **x > 0.0).count() as f64 / group.len() as f64);
+            } else {
+                profile.averages.push(f64::NAN);
+                profile.hit_rates.push(f64::NAN);
+            }
+            profile.t_stats.push(t_stat);
+            profile.p_values.push(p_value);
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SeasonalityProfile;
+    use crate::{
+        enums::{ClFrequency, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_seasonality_profile() {
+        //the month ends from 2019-01-31, the returns of december are higher
+        let dates: Vec<i32> = vec![
+            43496, 43524, 43555, 43585, 43616, 43646, 43677, 43708, 43738, 43769, 43799, 43830,
+            43861, 43890, 43921, 43951, 43982, 44012, 44043, 44074, 44104, 44135, 44165, 44196,
+            44227, 44255, 44286, 44316, 44347, 44377, 44408, 44439, 44469, 44500, 44530, 44561,
+        ];
+        let mut data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        data[11] = 8.0;
+        data[23] = 9.0;
+        data[35] = 10.0;
+        data[12] = f64::NAN;
+        let mut profile = SeasonalityProfile::new();
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.seasonality_profile(&dates, ClFrequency::ClFrequencyMonthly, &mut profile);
+        let others: Vec<f64> = data
+            .iter()
+            .enumerate()
+            .filter(|x| x.0 % 12 != 11 && x.1.is_finite())
+            .map(|x| *x.1)
+            .collect();
+        let other_mean = others.iter().sum::<f64>() / others.len() as f64;
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && profile.labels == (1..=12).collect::<Vec<u32>>()
+                && profile.counts[0] == 2
+                && profile.counts[11] == 3
+                && MPTCalculator::is_eq_double(profile.averages[11], 9.0)
+                && MPTCalculator::is_eq_double(profile.hit_rates[0], 0.0)
+                && MPTCalculator::is_eq_double(profile.hit_rates[11], 1.0)
+                && profile.t_stats[11] > 0.0
+                && profile.p_values[11] < 0.01
+                && profile.p_values[11] < profile.p_values[5]
+                && other_mean < 0.0,
+            true
+        );
+
+        //the daily returns from 2024-01-01, a monday
+        let daily = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 1.5, 2.5];
+        let days: Vec<i32> = (0..9).map(|x| 45292 + x).collect();
+        let err = MPTCalculator::from_v(&daily).seasonality_profile(
+            &days,
+            ClFrequency::ClFrequencyDaily,
+            &mut profile,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && profile.labels.len() == 7
+                && MPTCalculator::is_eq_double(profile.averages[0], 1.25)
+                && profile.counts[6] == 1
+                && profile.t_stats[6].is_nan(),
+            true
+        );
+
+        let err =
+            mpt.seasonality_profile(&dates[1..], ClFrequency::ClFrequencyMonthly, &mut profile);
+        assert_eq!(
+            err == Errors::ClErrorCodeInvalidPara && profile.labels.len() == 0,
+            true
+        );
+    }
+}
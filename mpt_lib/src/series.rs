@@ -0,0 +1,626 @@
+use crate::{
+    date_util,
+    enums::{self, Errors},
+    MPTCalculator,
+};
+
+///the data quality summary of a dated series, the missing values are the NAN/INF values.
+///the min and max dates are 0 and the min and max are NAN if there is no valid value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SeriesProfile {
+    pub count: usize,
+    pub missing_count: usize,
+    pub min: f64,
+    pub min_date: i32,
+    pub max: f64,
+    pub max_date: i32,
+    pub longest_missing_periods: usize,
+    pub longest_missing_start_date: i32,
+    pub frequency: enums::ClFrequency,
+}
+
+impl SeriesProfile {
+    pub fn new() -> SeriesProfile {
+        SeriesProfile {
+            count: 0,
+            missing_count: 0,
+            min: f64::NAN,
+            min_date: 0,
+            max: f64::NAN,
+            max_date: 0,
+            longest_missing_periods: 0,
+            longest_missing_start_date: 0,
+            frequency: enums::ClFrequency::ClFrequencyUnknown,
+        }
+    }
+}
+
+impl Default for SeriesProfile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///fill the NAN/INF values of the dated series by the policy, the values before the first valid value are kept
+///as NAN by the forward fill, and the values out of the first and last valid values are kept as NAN by the linear
+///interpolation. the drop policy removes the missing values and their dates.
+fn fill_series(
+    dates: &[i32],
+    values: &[f64],
+    policy: enums::ClFillPolicy,
+    filled_dates: &mut Vec<i32>,
+    filled_values: &mut Vec<f64>,
+) {
+    filled_dates.clear();
+    filled_values.clear();
+    match policy {
+        enums::ClFillPolicy::ClFillDrop => {
+            for i in 0..values.len() {
+                if values[i].is_finite() {
+                    filled_dates.push(dates[i]);
+                    filled_values.push(values[i]);
+                }
+            }
+            return;
+        }
+        enums::ClFillPolicy::ClFillZero => {
+            filled_values.extend(values.iter().map(|x| if x.is_finite() { *x } else { 0.0 }));
+        }
+        enums::ClFillPolicy::ClFillForward => {
+            let mut last = f64::NAN;
+            values.iter().for_each(|x| {
+                if x.is_finite() {
+                    last = *x;
+                }
+                filled_values.push(last);
+            });
+        }
+        enums::ClFillPolicy::ClFillLinear => {
+            let mut previous: Option<usize> = None;
+            filled_values.extend(
+                values
+                    .iter()
+                    .map(|x| if x.is_finite() { *x } else { f64::NAN }),
+            );
+            for i in 0..values.len() {
+                if !values[i].is_finite() {
+                    continue;
+                }
+                if let Some(p) = previous {
+                    let span = (dates[i] - dates[p]) as f64;
+                    for j in p + 1..i {
+                        let t = (dates[j] - dates[p]) as f64 / span;
+                        filled_values[j] = values[p] + t * (values[i] - values[p]);
+                    }
+                }
+                previous = Some(i);
+            }
+        }
+    }
+    filled_dates.extend_from_slice(dates);
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///summarize the data quality of the values in one call, it includes the count, the missing count, the min and max
+    ///with their dates, the longest run of missing values and the frequency detected from the dates.
+    ///
+    ///# Arguments
+    ///dates: the date of value, should be ascending.
+    ///
+    ///profile: the data quality summary.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///use mpt_lib::series::SeriesProfile;
+    ///
+    ///let data = vec![1.5, f64::NAN, -2.0, 0.5, f64::NAN, f64::NAN, 3.0, -0.5];
+    ///let dates = vec![44957, 44985, 45016, 45046, 45077, 45107, 45138, 45169];
+    ///let mut profile = SeriesProfile::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.series_profile(&dates, &mut profile);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && profile.missing_count == 3
+    ///        && profile.min_date == 45016
+    ///        && profile.max_date == 45138
+    ///        && profile.longest_missing_periods == 2
+    ///        && profile.longest_missing_start_date == 45077
+    ///        && profile.frequency == enums::ClFrequency::ClFrequencyMonthly,
+    ///    true
+    ///);
+    ///```
+    pub fn series_profile(&self, dates: &[i32], profile: &mut SeriesProfile) -> Errors {
+        if self.values.len() == 0 || dates.len() != self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *profile = SeriesProfile::new();
+        if dates.windows(2).any(|x| x[0] >= x[1]) {
+            return Errors::ClErrorCodeUnsortedByDate;
+        }
+
+        profile.count = self.values.len();
+        let mut missing_periods = 0;
+        for i in 0..self.values.len() {
+            let value = self.values[i];
+            if !value.is_finite() {
+                profile.missing_count += 1;
+                missing_periods += 1;
+                if missing_periods > profile.longest_missing_periods {
+                    profile.longest_missing_periods = missing_periods;
+                    profile.longest_missing_start_date = dates[i + 1 - missing_periods];
+                }
+                continue;
+            }
+            missing_periods = 0;
+            if !(value >= profile.min) {
+                profile.min = value;
+                profile.min_date = dates[i];
+            }
+            if !(value <= profile.max) {
+                profile.max = value;
+                profile.max_date = dates[i];
+            }
+        }
+        profile.frequency = date_util::infer_frequency(dates);
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///fill the NAN/INF values of the dated series by the policy so the output can be used by the calculator directly.
+    ///ClFillForward uses the last valid value, ClFillZero uses 0, ClFillLinear interpolates by the dates between the
+    ///previous and the next valid values and ClFillDrop removes the missing values and their dates.
+    ///the leading missing values of ClFillForward and the leading and trailing missing values of ClFillLinear are
+    ///kept as NAN.
+    ///
+    ///# Arguments
+    ///dates: the date of value, should be ascending.
+    ///
+    ///policy: the fill policy.
+    ///
+    ///filled_dates: the dates of the filled values.
+    ///
+    ///filled_values: the filled values.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![1.0, f64::NAN, f64::NAN, 4.0, f64::NAN];
+    ///let dates = vec![45292, 45293, 45294, 45295, 45296];
+    ///let mut filled_dates = Vec::new();
+    ///let mut filled_values = Vec::new();
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.fill_missing(
+    ///    &dates,
+    ///    enums::ClFillPolicy::ClFillLinear,
+    ///    &mut filled_dates,
+    ///    &mut filled_values,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && filled_dates == dates
+    ///        && MPTCalculator::is_eq_double_array(&filled_values[..4], &[1.0, 2.0, 3.0, 4.0])
+    ///        && filled_values[4].is_nan(),
+    ///    true
+    ///);
+    ///```
+    pub fn fill_missing(
+        &self,
+        dates: &[i32],
+        policy: enums::ClFillPolicy,
+        filled_dates: &mut Vec<i32>,
+        filled_values: &mut Vec<f64>,
+    ) -> Errors {
+        if self.values.len() == 0 || dates.len() != self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        filled_dates.clear();
+        filled_values.clear();
+        if dates.windows(2).any(|x| x[0] >= x[1]) {
+            return Errors::ClErrorCodeUnsortedByDate;
+        }
+        fill_series(dates, self.values, policy, filled_dates, filled_values);
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///map several dated series onto the master dates, e.g. the calendar of the benchmark, so the aligned values can be
+    ///used as the returns matrix of the batch and relative statistics.
+    ///the value of a master date is the value of the series at the same date, or filled by the policy of the series
+    ///if the series has no valid value at that date. ClFillForward uses the last valid value on or before the date,
+    ///ClFillZero uses 0, ClFillLinear interpolates by the dates between the valid values around the date and
+    ///ClFillDrop removes the master date from the output.
+    ///
+    ///# Arguments
+    ///master_dates: the master dates, should be ascending.
+    ///
+    ///dates_matrix: the dates of each series, should be ascending.
+    ///
+    ///values_matrix: the values of each series, the same size as its dates.
+    ///
+    ///policies: the fill policy of each series.
+    ///
+    ///aligned_dates: the master dates kept in the output.
+    ///
+    ///aligned_matrix: the aligned values of each series, the same size as the aligned dates.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let master_dates = vec![45322, 45351, 45382, 45412];
+    ///let fund_dates = vec![45322, 45382, 45412];
+    ///let fund = vec![1.2, 0.8, -0.4];
+    ///let index_dates = vec![45322, 45351, 45412];
+    ///let index = vec![0.9, 1.1, -0.2];
+    ///let mut aligned_dates = Vec::new();
+    ///let mut aligned_matrix = Vec::new();
+    ///let err = MPTCalculator::align_series(
+    ///    &master_dates,
+    ///    &[&fund_dates, &index_dates],
+    ///    &[&fund, &index],
+    ///    &[enums::ClFillPolicy::ClFillZero, enums::ClFillPolicy::ClFillDrop],
+    ///    &mut aligned_dates,
+    ///    &mut aligned_matrix,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && aligned_dates == vec![45322, 45351, 45412]
+    ///        && aligned_matrix == vec![vec![1.2, 0.0, -0.4], vec![0.9, 1.1, -0.2]],
+    ///    true
+    ///);
+    ///```
+    pub fn align_series(
+        master_dates: &[i32],
+        dates_matrix: &[&[i32]],
+        values_matrix: &[&[f64]],
+        policies: &[enums::ClFillPolicy],
+        aligned_dates: &mut Vec<i32>,
+        aligned_matrix: &mut Vec<Vec<f64>>,
+    ) -> Errors {
+        if master_dates.len() == 0
+            || dates_matrix.len() == 0
+            || values_matrix.len() != dates_matrix.len()
+            || policies.len() != dates_matrix.len()
+            || (0..dates_matrix.len()).any(|i| dates_matrix[i].len() != values_matrix[i].len())
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        aligned_dates.clear();
+        aligned_matrix.clear();
+        if dates_matrix
+            .iter()
+            .chain([master_dates].iter())
+            .any(|dates| dates.windows(2).any(|x| x[0] >= x[1]))
+        {
+            return Errors::ClErrorCodeUnsortedByDate;
+        }
+
+        let columns: Vec<Vec<f64>> = (0..dates_matrix.len())
+            .map(|i| {
+                let mut dates: Vec<i32> = dates_matrix[i]
+                    .iter()
+                    .chain(master_dates.iter())
+                    .copied()
+                    .collect();
+                dates.sort();
+                dates.dedup();
+                let values: Vec<f64> = dates
+                    .iter()
+                    .map(|d| match dates_matrix[i].binary_search(d) {
+                        Ok(k) => values_matrix[i][k],
+                        Err(_) => f64::NAN,
+                    })
+                    .collect();
+                let (mut filled_dates, mut filled_values) = (Vec::new(), Vec::new());
+                fill_series(
+                    &dates,
+                    &values,
+                    policies[i],
+                    &mut filled_dates,
+                    &mut filled_values,
+                );
+                master_dates
+                    .iter()
+                    .map(|d| match filled_dates.binary_search(d) {
+                        Ok(k) => filled_values[k],
+                        Err(_) => f64::NAN,
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let rows: Vec<usize> = (0..master_dates.len())
+            .filter(|t| {
+                (0..columns.len()).all(|i| {
+                    policies[i] != enums::ClFillPolicy::ClFillDrop || columns[i][*t].is_finite()
+                })
+            })
+            .collect();
+        aligned_dates.extend(rows.iter().map(|t| master_dates[*t]));
+        aligned_matrix.extend(
+            columns
+                .iter()
+                .map(|x| rows.iter().map(|t| x[*t]).collect::<Vec<f64>>()),
+        );
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///align the series like align_series, the master dates are the business days of the calendar from the earliest
+    ///to the latest date of the series. the weekends and the holidays are not in the aligned dates, the values on
+    ///them only fill the following business days by the forward fill and the linear interpolation.
+    ///
+    ///```
+    ///use mpt_lib::{HolidayList, MPTCalculator};
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let holidays = HolidayList::from(&[44921]);
+    ///let fund_dates = vec![44918, 44921, 44922];
+    ///let fund = vec![1.0, 2.0, 3.0];
+    ///let index_dates = vec![44918, 44922];
+    ///let index = vec![0.5, 0.6];
+    ///let mut aligned_dates = Vec::new();
+    ///let mut aligned_matrix = Vec::new();
+    ///let err = MPTCalculator::align_series_by_calendar(
+    ///    &[&fund_dates, &index_dates],
+    ///    &[&fund, &index],
+    ///    &[enums::ClFillPolicy::ClFillForward, enums::ClFillPolicy::ClFillDrop],
+    ///    Some(&holidays),
+    ///    &mut aligned_dates,
+    ///    &mut aligned_matrix,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && aligned_dates == vec![44918, 44922]
+    ///        && aligned_matrix == vec![vec![1.0, 3.0], vec![0.5, 0.6]],
+    ///    true
+    ///);
+    ///```
+    pub fn align_series_by_calendar(
+        dates_matrix: &[&[i32]],
+        values_matrix: &[&[f64]],
+        policies: &[enums::ClFillPolicy],
+        calendar: Option<&dyn date_util::HolidayCalendar>,
+        aligned_dates: &mut Vec<i32>,
+        aligned_matrix: &mut Vec<Vec<f64>>,
+    ) -> Errors {
+        let (start, end) = match (
+            dates_matrix.iter().flat_map(|x| x.iter()).min(),
+            dates_matrix.iter().flat_map(|x| x.iter()).max(),
+        ) {
+            (Some(start), Some(end)) => (*start, *end),
+            _ => return Errors::ClErrorCodeInvalidPara,
+        };
+        if start < 2 {
+            return Errors::ClErrorCodeInvalidDate;
+        }
+        let master_dates: Vec<i32> = (start..end + 1)
+            .filter(|d| date_util::is_business_day(*d as u64, calendar))
+            .collect();
+        return Self::align_series(
+            &master_dates,
+            dates_matrix,
+            values_matrix,
+            policies,
+            aligned_dates,
+            aligned_matrix,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SeriesProfile;
+    use crate::{
+        enums::{self, Errors},
+        HolidayList, MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_series_profile() {
+        let data = vec![f64::NAN, 2.0, -1.0, 2.0, -1.0, f64::INFINITY];
+        let dates = vec![45292, 45293, 45294, 45295, 45296, 45299];
+        let mut profile = SeriesProfile::new();
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.series_profile(&dates, &mut profile);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && profile.count == 6
+                && profile.missing_count == 2
+                && profile.min == -1.0
+                && profile.min_date == 45294
+                && profile.max == 2.0
+                && profile.max_date == 45293
+                && profile.longest_missing_periods == 1
+                && profile.longest_missing_start_date == 45292
+                && profile.frequency == enums::ClFrequency::ClFrequencyDaily,
+            true
+        );
+
+        let data = vec![f64::NAN, f64::NAN];
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.series_profile(&[44926, 45291], &mut profile);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && profile.min.is_nan()
+                && profile.max_date == 0
+                && profile.longest_missing_periods == 2
+                && profile.frequency == enums::ClFrequency::ClFrequencyAnnually,
+            true
+        );
+
+        let err = mpt.series_profile(&[45291, 44926], &mut profile);
+        assert_eq!(err, Errors::ClErrorCodeUnsortedByDate);
+    }
+
+    #[test]
+    fn should_correct_fill_missing() {
+        let data = vec![f64::NAN, 2.0, f64::NAN, 8.0, f64::INFINITY];
+        let dates = vec![45291, 45322, 45351, 45382, 45412];
+        let mut filled_dates = Vec::new();
+        let mut filled_values = Vec::new();
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.fill_missing(
+            &dates,
+            enums::ClFillPolicy::ClFillForward,
+            &mut filled_dates,
+            &mut filled_values,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && filled_values[0].is_nan()
+                && filled_values[1..] == [2.0, 2.0, 8.0, 8.0],
+            true
+        );
+
+        mpt.fill_missing(
+            &dates,
+            enums::ClFillPolicy::ClFillZero,
+            &mut filled_dates,
+            &mut filled_values,
+        );
+        assert_eq!(filled_values, vec![0.0, 2.0, 0.0, 8.0, 0.0]);
+
+        mpt.fill_missing(
+            &dates,
+            enums::ClFillPolicy::ClFillLinear,
+            &mut filled_dates,
+            &mut filled_values,
+        );
+        assert_eq!(
+            MPTCalculator::is_eq_double(filled_values[2], 2.0 + 6.0 * 29.0 / 60.0)
+                && filled_values[4].is_nan(),
+            true
+        );
+
+        mpt.fill_missing(
+            &dates,
+            enums::ClFillPolicy::ClFillDrop,
+            &mut filled_dates,
+            &mut filled_values,
+        );
+        assert_eq!(
+            filled_dates == vec![45322, 45382] && filled_values == vec![2.0, 8.0],
+            true
+        );
+
+        let err = mpt.fill_missing(
+            &dates[1..],
+            enums::ClFillPolicy::ClFillDrop,
+            &mut filled_dates,
+            &mut filled_values,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+
+    #[test]
+    fn should_correct_align_series() {
+        let master_dates = vec![45291, 45322, 45351, 45382, 45412];
+        let fund_dates = vec![45322, 45335, 45382];
+        let fund = vec![2.0, 5.0, 8.0];
+        let index_dates = vec![45291, 45322, 45351, 45382, 45412];
+        let index = vec![1.0, f64::NAN, 3.0, 4.0, 5.0];
+        let mut aligned_dates = Vec::new();
+        let mut aligned_matrix = Vec::new();
+        let err = MPTCalculator::align_series(
+            &master_dates,
+            &[&fund_dates, &fund_dates, &index_dates],
+            &[&fund, &fund, &index],
+            &[
+                enums::ClFillPolicy::ClFillForward,
+                enums::ClFillPolicy::ClFillLinear,
+                enums::ClFillPolicy::ClFillDrop,
+            ],
+            &mut aligned_dates,
+            &mut aligned_matrix,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && aligned_dates == vec![45291, 45351, 45382, 45412]
+                && aligned_matrix[0][0].is_nan()
+                && aligned_matrix[0][1..] == [5.0, 8.0, 8.0]
+                && aligned_matrix[1][0].is_nan()
+                && MPTCalculator::is_eq_double(aligned_matrix[1][1], 5.0 + 3.0 * 16.0 / 47.0)
+                && aligned_matrix[1][2] == 8.0
+                && aligned_matrix[1][3].is_nan()
+                && aligned_matrix[2] == vec![1.0, 3.0, 4.0, 5.0],
+            true
+        );
+
+        let err = MPTCalculator::align_series(
+            &master_dates,
+            &[&fund_dates],
+            &[&fund],
+            &[],
+            &mut aligned_dates,
+            &mut aligned_matrix,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+
+        let err = MPTCalculator::align_series(
+            &[45322, 45291],
+            &[&fund_dates],
+            &[&fund],
+            &[enums::ClFillPolicy::ClFillZero],
+            &mut aligned_dates,
+            &mut aligned_matrix,
+        );
+        assert_eq!(err, Errors::ClErrorCodeUnsortedByDate);
+    }
+
+    #[test]
+    fn should_correct_align_series_by_calendar() {
+        //2022-12-26 and 2022-12-30 are the holidays, 2022-12-24 and 2022-12-25 are the weekend
+        let holidays = HolidayList::from(&[44921, 44925]);
+        let fund_dates = vec![44918, 44921, 44922, 44924];
+        let fund = vec![1.0, 2.0, 3.0, 4.0];
+        let index_dates = vec![44918, 44922, 44923, 44924, 44925];
+        let index = vec![0.5, 0.6, 0.7, 0.8, 0.9];
+        let policies = [
+            enums::ClFillPolicy::ClFillForward,
+            enums::ClFillPolicy::ClFillDrop,
+        ];
+        let mut aligned_dates = Vec::new();
+        let mut aligned_matrix = Vec::new();
+        let err = MPTCalculator::align_series_by_calendar(
+            &[&fund_dates, &index_dates],
+            &[&fund, &index],
+            &policies,
+            Some(&holidays),
+            &mut aligned_dates,
+            &mut aligned_matrix,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && aligned_dates == vec![44918, 44922, 44923, 44924]
+                && aligned_matrix == vec![vec![1.0, 3.0, 3.0, 4.0], vec![0.5, 0.6, 0.7, 0.8]],
+            true
+        );
+
+        //without the calendar the holidays are business days, 2022-12-26 is dropped by the index
+        let err = MPTCalculator::align_series_by_calendar(
+            &[&fund_dates, &index_dates],
+            &[&fund, &index],
+            &policies,
+            None,
+            &mut aligned_dates,
+            &mut aligned_matrix,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && aligned_dates == vec![44918, 44922, 44923, 44924, 44925]
+                && aligned_matrix
+                    == vec![vec![1.0, 3.0, 3.0, 4.0, 4.0], vec![0.5, 0.6, 0.7, 0.8, 0.9]],
+            true
+        );
+
+        let err = MPTCalculator::align_series_by_calendar(
+            &[],
+            &[],
+            &[],
+            Some(&holidays),
+            &mut aligned_dates,
+            &mut aligned_matrix,
+        );
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+}
@@ -0,0 +1,347 @@
+use crate::{
+    common::{get_annual_multiplier, normal_cdf},
+    enums::{self, Errors},
+    MPTCalculator,
+};
+
+///the sample autocorrelation of the values at the lag, the values should have no NAN/INF values.
+///return NAN if there are not more values than the lag or the values are constant.
+pub(crate) fn autocorrelation(values: &[f64], lag: usize) -> f64 {
+    if lag == 0 || values.len() <= lag {
+        return f64::NAN;
+    }
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let variance: f64 = values.iter().map(|x| (x - mean) * (x - mean)).sum();
+    if variance <= 0.0 {
+        return f64::NAN;
+    }
+    let covariance: f64 = (lag..values.len())
+        .map(|t| (values[t] - mean) * (values[t - lag] - mean))
+        .sum();
+    covariance / variance
+}
+
+///remove the autocorrelation at the lag, r(t) = (r*(t) - c * r*(t - lag)) / (1 - c), the leading values
+///without the lagged value are NAN.
+fn remove_lag(values: &[f64], lag: usize, c: f64) -> Vec<f64> {
+    (0..values.len())
+        .map(|t| {
+            if t < lag {
+                f64::NAN
+            } else {
+                (values[t] - c * values[t - lag]) / (1.0 - c)
+            }
+        })
+        .collect()
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///detect the return smoothing by the first order autocorrelation, the smoothed returns of the illiquid assets,
+    ///e.g. the private assets and the hedge funds, are positively autocorrelated. the p value is of the one sided
+    ///test of the autocorrelation > 0, the autocorrelation * sqrt(n) is standard normal without the smoothing.
+    ///if the array has NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///autocorrelation: the first order autocorrelation.
+    ///
+    ///p_value: the p value of the test, a small p value indicates the smoothing.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::Errors;
+    ///
+    ///let data = vec![1.0, 1.2, 1.1, 0.6, 0.2, 0.3, 0.8, 1.3, 1.5, 1.0, 0.4, 0.1];
+    ///let (mut autocorrelation, mut p_value) = (f64::NAN, f64::NAN);
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.detect_smoothing(&mut autocorrelation, &mut p_value);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(autocorrelation, 0.528989)
+    ///        && p_value < 0.05,
+    ///    true
+    ///);
+    ///```
+    pub fn detect_smoothing(&self, autocorrelation: &mut f64, p_value: &mut f64) -> Errors {
+        *autocorrelation = f64::NAN;
+        *p_value = f64::NAN;
+        if self.values.len() < 3 {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        if self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+        *autocorrelation = crate::smoothing::autocorrelation(self.values, 1);
+        if autocorrelation.is_finite() {
+            *p_value = 1.0 - normal_cdf(*autocorrelation * (self.values.len() as f64).sqrt());
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///unsmooth the returns to recover the volatility understated by the smoothing. the geltner method removes the
+    ///first order autocorrelation rho, r(t) = (r*(t) - rho * r*(t - 1)) / (1 - rho). the okunev white method
+    ///removes the autocorrelation of each lag from 1 to the lags in turn, the coefficient of each lag makes the
+    ///autocorrelation of the lag 0 with the autocorrelation of the double lag. the leading values without the
+    ///lagged values are NAN. if the array has NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///method: the unsmoothing method.
+    ///
+    ///output: the unsmoothed returns, the same size as the values.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{ClUnsmoothMethod, Errors};
+    ///
+    ///let data = vec![1.0, 1.2, 1.1, 0.6, 0.2, 0.3, 0.8, 1.3, 1.5, 1.0, 0.4, 0.1];
+    ///let mut output = vec![f64::NAN; data.len()];
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.unsmooth_returns(ClUnsmoothMethod::ClUnsmoothGeltner, &mut output);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && output[0].is_nan()
+    ///        && MPTCalculator::is_eq_double(output[1], (1.2 - 0.528989 * 1.0) / (1.0 - 0.528989)),
+    ///    true
+    ///);
+    ///```
+    pub fn unsmooth_returns(&self, method: enums::ClUnsmoothMethod, output: &mut [f64]) -> Errors {
+        if self.values.len() < 3
+            || output.len() < self.values.len()
+            || method == enums::ClUnsmoothMethod::ClUnsmoothOkunevWhite(0)
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        output.iter_mut().for_each(|x| *x = f64::NAN);
+        if self.values.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let unsmoothed = match method {
+            enums::ClUnsmoothMethod::ClUnsmoothGeltner => {
+                let rho = autocorrelation(self.values, 1);
+                if !(rho < 1.0) {
+                    return Errors::ClErrorCodeNoError;
+                }
+                remove_lag(self.values, 1, rho)
+            }
+            enums::ClUnsmoothMethod::ClUnsmoothOkunevWhite(lags) => {
+                let mut series = self.values.to_vec();
+                let mut begin = 0;
+                for lag in 1..=lags {
+                    let valid = &series[begin..];
+                    let a = autocorrelation(valid, lag);
+                    let a2 = autocorrelation(valid, 2 * lag);
+                    if !a.is_finite() || !a2.is_finite() {
+                        return Errors::ClErrorCodeNoError;
+                    }
+                    let c = if a == 0.0 {
+                        0.0
+                    } else {
+                        let discriminant = (1.0 + a2) * (1.0 + a2) - 4.0 * a * a;
+                        if discriminant < 0.0 {
+                            return Errors::ClErrorCodeNoError;
+                        }
+                        ((1.0 + a2) - discriminant.sqrt()) / (2.0 * a)
+                    };
+                    let next = remove_lag(valid, lag, c);
+                    series[begin..].copy_from_slice(&next);
+                    begin += lag;
+                }
+                series
+            }
+        };
+        output[..unsmoothed.len()].copy_from_slice(&unsmoothed);
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the sharpe ratio annualized by the lo adjustment of the autocorrelation, it need riskfree data. the
+    ///sqrt(m) annualization of the periodic sharpe ratio overstates the sharpe ratio of the positively autocorrelated
+    ///returns, e.g. the smoothed returns, the lo adjustment annualizes it by m / sqrt(m + 2 * sum((m - k) * rho(k))),
+    ///m is the periods per year and rho(k) is the autocorrelation of the excess returns at the lag k from 1 to q.
+    ///if the array and riskfree have NAN/INF values,the result will be NAN.
+    ///
+    ///# Arguments
+    ///freq: the frequence of source data.
+    ///
+    ///q: the number of the autocorrelation lags, the lags are not more than m - 1, 0 is the sqrt(m) annualization.
+    ///
+    ///res: the adjusted annual sharpe ratio.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![1.0, 1.2, 1.1, 0.6, 0.2, 0.3, 0.8, 1.3, 1.5, 1.0, 0.4, 0.1];
+    ///let rf_data = vec![0.1; 12];
+    ///let (mut res, mut sharpe) = (f64::NAN, f64::NAN);
+    ///let mpt = MPTCalculator::from_v_r(&data, &rf_data);
+    ///let err = mpt.sharpe_ratio_lo_adjusted(enums::ClFrequency::ClFrequencyMonthly, 1, &mut res);
+    ///mpt.sharpe_ratio(enums::ClFrequency::ClFrequencyMonthly, true, &mut sharpe);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double(res, 3.67852)
+    ///        && res < sharpe,
+    ///    true
+    ///);
+    ///```
+    pub fn sharpe_ratio_lo_adjusted(
+        &self,
+        freq: enums::ClFrequency,
+        q: usize,
+        res: &mut f64,
+    ) -> Errors {
+        *res = f64::NAN;
+        let mut sharpe = f64::NAN;
+        let ret = self.sharpe_ratio(freq, false, &mut sharpe);
+        if ret != Errors::ClErrorCodeNoError {
+            return ret;
+        }
+        if self.riskfree.len() < self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        let excess: Vec<f64> = self
+            .values
+            .iter()
+            .zip(self.riskfree.iter())
+            .map(|(r, rf)| r - rf)
+            .collect();
+        if !sharpe.is_finite() || excess.iter().any(|x| !x.is_finite()) {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let periods = get_annual_multiplier(freq, false);
+        if !(periods >= 1.0) {
+            return Errors::ClErrorCodeNoError;
+        }
+        let lags = q.min(periods as usize - 1);
+        let mut denominator = periods;
+        for lag in 1..=lags {
+            let rho = autocorrelation(&excess, lag);
+            if !rho.is_finite() {
+                return Errors::ClErrorCodeNoError;
+            }
+            denominator += 2.0 * (periods - lag as f64) * rho;
+        }
+        if denominator > 0.0 {
+            *res = sharpe * periods / denominator.sqrt();
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::autocorrelation;
+    use crate::{
+        enums::{ClFrequency, ClUnsmoothMethod, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_unsmooth_returns() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713,
+            1.88831, 1.73502, 1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807,
+            1.66744, -9.68658, -0.46681, 4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+        ];
+        //the appraisal smoothing of the returns
+        let mut smoothed = vec![data[0]];
+        for t in 1..data.len() {
+            smoothed.push(0.6 * smoothed[t - 1] + 0.4 * data[t]);
+        }
+        let mpt = MPTCalculator::from_v(&smoothed);
+        let (mut rho, mut p_value) = (f64::NAN, f64::NAN);
+        let err = mpt.detect_smoothing(&mut rho, &mut p_value);
+        let (mut raw_rho, mut raw_p_value) = (f64::NAN, f64::NAN);
+        MPTCalculator::from_v(&data).detect_smoothing(&mut raw_rho, &mut raw_p_value);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && rho > 0.5
+                && p_value < 0.01
+                && raw_p_value > p_value,
+            true
+        );
+
+        let mut output = vec![f64::NAN; smoothed.len()];
+        let err = mpt.unsmooth_returns(ClUnsmoothMethod::ClUnsmoothGeltner, &mut output);
+        let expected: Vec<f64> = (1..smoothed.len())
+            .map(|t| (smoothed[t] - rho * smoothed[t - 1]) / (1.0 - rho))
+            .collect();
+        let (mut smoothed_std, mut unsmoothed_std) = (f64::NAN, f64::NAN);
+        MPTCalculator::from_v(&smoothed[1..]).standard_deviation(
+            crate::enums::ClFrequency::ClFrequencyMonthly,
+            false,
+            &mut smoothed_std,
+        );
+        MPTCalculator::from_v(&output[1..]).standard_deviation(
+            crate::enums::ClFrequency::ClFrequencyMonthly,
+            false,
+            &mut unsmoothed_std,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && output[0].is_nan()
+                && MPTCalculator::is_eq_double_array(&output[1..], &expected)
+                && unsmoothed_std > smoothed_std * 1.5
+                && autocorrelation(&output[1..], 1).abs() < rho.abs(),
+            true
+        );
+
+        let err = mpt.unsmooth_returns(ClUnsmoothMethod::ClUnsmoothOkunevWhite(2), &mut output);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && output[..3].iter().all(|x| x.is_nan())
+                && output[3..].iter().all(|x| x.is_finite())
+                && autocorrelation(&output[3..], 1).abs() < rho / 2.0,
+            true
+        );
+
+        let err = mpt.unsmooth_returns(ClUnsmoothMethod::ClUnsmoothOkunevWhite(0), &mut output);
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+
+    #[test]
+    fn should_correct_sharpe_ratio_lo_adjusted() {
+        let data = vec![
+            -1.76334, -3.7317, -0.49068, 11.83432, 9.08289, 3.39531, 0.70368, 0.89286, -0.76953,
+            6.39783, 1.38484, 2.33645, 2.80998, 0.5808, -0.61141, -0.20506, -0.47945, -0.13765,
+            -3.4459, -0.85653, 1.83585, 0.84836, 3.61024, 3.99188, -1.7892, 2.02054, -0.81169,
+            -1.40753, 3.02125, -0.67676, 1.07073, -2.21509, 0.29547, -2.65139, 2.62273, -0.65557,
+        ];
+        let rf_data = vec![0.1; data.len()];
+        let mut smoothed = vec![data[0]];
+        for t in 1..data.len() {
+            smoothed.push(0.6 * smoothed[t - 1] + 0.4 * data[t]);
+        }
+        let mpt = MPTCalculator::from_v_r(&smoothed, &rf_data);
+        let (mut res, mut sharpe) = (f64::NAN, f64::NAN);
+        mpt.sharpe_ratio(ClFrequency::ClFrequencyMonthly, true, &mut sharpe);
+        let err = mpt.sharpe_ratio_lo_adjusted(ClFrequency::ClFrequencyMonthly, 0, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, sharpe),
+            true
+        );
+
+        let excess: Vec<f64> = smoothed.iter().map(|x| x - 0.1).collect();
+        let denominator = 12.0
+            + (1..12)
+                .map(|k| 2.0 * (12 - k) as f64 * autocorrelation(&excess, k))
+                .sum::<f64>();
+        let expected = sharpe / 12.0_f64.sqrt() * 12.0 / denominator.sqrt();
+        let err = mpt.sharpe_ratio_lo_adjusted(ClFrequency::ClFrequencyMonthly, 100, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(res, expected)
+                && res < sharpe,
+            true
+        );
+
+        let err = mpt.sharpe_ratio_lo_adjusted(ClFrequency::ClFrequencyAnnually, 3, &mut res);
+        let mut annual = f64::NAN;
+        mpt.sharpe_ratio(ClFrequency::ClFrequencyAnnually, true, &mut annual);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, annual),
+            true
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{
+    common::check_sample_size,
+    enums::{self, Errors},
+    kernels, MPTCalculator,
+};
+
+///calculate the average value of a slice not include NAN/INF values, it is the same as MPTCalculator::average.
+///# Examples
+///```
+///use mpt_lib::stats;
+///use mpt_lib::MPTCalculator;
+///use mpt_lib::enums::Errors;
+///let data = vec![10.0, 20.0, f64::NAN, 30.0];
+///let mut res = f64::NAN;
+///let err = stats::mean(&data, &mut res);
+///assert_eq!(err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 20.0), true);
+///```
+pub fn mean(values: &[f64], result: &mut f64) -> Errors {
+    return MPTCalculator::from_v(values).average(result);
+}
+
+///calculate the standard deviation value of a slice, if the slice has NAN/INF values,the result will be NAN.
+///it is the same as MPTCalculator::standard_deviation.
+///
+///# Arguments
+///values: the source data.
+///
+///freq: the frequence of source data.
+///
+///is_annu: the flag of annualize.
+///# Examples
+///```
+///use mpt_lib::stats;
+///use mpt_lib::MPTCalculator;
+///use mpt_lib::enums::{self, Errors};
+///let data = vec![1.0, -2.0, 3.0, 0.5, -1.5, 2.0];
+///let mut res = f64::NAN;
+///let err = stats::standard_deviation(&data, enums::ClFrequency::ClFrequencyMonthly, true, &mut res);
+///assert_eq!(err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 6.752777), true);
+///```
+pub fn standard_deviation(
+    values: &[f64],
+    freq: enums::ClFrequency,
+    is_annu: bool,
+    result: &mut f64,
+) -> Errors {
+    *result = f64::NAN;
+    let ret = check_sample_size(values.len(), 2);
+    if ret != Errors::ClErrorCodeNoError {
+        return ret;
+    }
+    return MPTCalculator::standard_deviation_internal(values, freq, is_annu, result);
+}
+
+///calculate the linear interpolated percentile value of a slice not include NAN/INF values,
+///it is the same as MPTCalculator::percentile.
+///
+///# Arguments
+///values: the source data.
+///
+///nth: the percent between 0 and 100.
+///# Examples
+///```
+///use mpt_lib::stats;
+///use mpt_lib::MPTCalculator;
+///use mpt_lib::enums::Errors;
+///let data = vec![4.0, 1.0, 3.0, 2.0, f64::NAN];
+///let mut res = f64::NAN;
+///let err = stats::percentile(&data, 75, &mut res);
+///assert_eq!(err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 3.25), true);
+///```
+pub fn percentile(values: &[f64], nth: i32, result: &mut f64) -> Errors {
+    return kernels::percentile(values, nth, result);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        enums::{self, Errors},
+        stats, MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_stats() {
+        let data = vec![
+            -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481,
+            1.59564, 0.86793, 2.41477,
+        ];
+        let mpt = MPTCalculator::from_v(&data);
+
+        let mut expected = f64::NAN;
+        let mut res = f64::NAN;
+        mpt.standard_deviation(enums::ClFrequency::ClFrequencyMonthly, true, &mut expected);
+        let err = stats::standard_deviation(
+            &data,
+            enums::ClFrequency::ClFrequencyMonthly,
+            true,
+            &mut res,
+        );
+        assert_eq!(err == Errors::ClErrorCodeNoError && res == expected, true);
+
+        mpt.average(&mut expected);
+        let err = stats::mean(&data, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res == expected, true);
+
+        mpt.percentile(25, &mut expected);
+        let err = stats::percentile(&data, 25, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res == expected, true);
+
+        let err = stats::percentile(&[f64::NAN], 25, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeNoError && res.is_nan(), true);
+        let err = stats::percentile(&data, 101, &mut res);
+        assert_eq!(err, Errors::ClErrorCodeInvalidPara);
+    }
+}
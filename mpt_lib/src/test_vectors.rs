@@ -0,0 +1,192 @@
+//! the canonical datasets and the expected values of the statistics, so the bindings of other languages can verify
+//! they produce the same numbers as this lib. the returns are monthly returns in percent from 2021-01 to 2023-12.
+use crate::{
+    enums::{self, Errors},
+    MPTCalculator,
+};
+
+///the monthly returns of the fund.
+pub const FUND: [f64; 36] = [
+    -1.22072, -0.0668, 2.20588, -0.91563, -0.76766, -1.21429, 3.43456, 4.99825, 3.89481, 1.59564,
+    0.86793, 2.41477, -1.80305, 0.6709, 3.57769, 4.77481, -0.37317, -3.52713, 1.88831, 1.73502,
+    1.20155, -3.36542, -2.03551, -5.6145, -2.71663, -0.04815, 3.99807, 1.66744, -9.68658, -0.46681,
+    4.22095, -6.7, -15.27331, -8.46123, 0.76369, -10.32347,
+];
+
+///the monthly returns of the benchmark.
+pub const BENCHMARK: [f64; 36] = [
+    0.27133, 1.24475, 1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016,
+    1.40278, 1.51232, -1.95588, 1.1185, 4.42953, 3.48951, -1.66133, -3.10048, 1.49901, 3.73988,
+    1.59068, -4.18066, -0.69376, -5.99816, -3.24858, -0.4318, 4.87031, 1.29526, -8.43036, -0.84062,
+    1.44647, -8.91073, -16.79479, -7.17546, 1.06403, -8.42864,
+];
+
+///the monthly returns of the risk free.
+pub const RISKFREE: [f64; 36] = [
+    0.1, 0.1, 0.1, 0.1, 0.1, 0.1, 0.1, 0.1, 0.1, 0.1, 0.1, 0.1, 0.2, 0.2, 0.2, 0.2, 0.2, 0.2, 0.2,
+    0.2, 0.2, 0.2, 0.2, 0.2, 0.4, 0.4, 0.4, 0.4, 0.4, 0.4, 0.4, 0.4, 0.4, 0.4, 0.4, 0.4,
+];
+
+///the month end dates of the returns.
+pub const DATES: [i32; 36] = [
+    44227, 44255, 44286, 44316, 44347, 44377, 44408, 44439, 44469, 44500, 44530, 44561, 44592,
+    44620, 44651, 44681, 44712, 44742, 44773, 44804, 44834, 44865, 44895, 44926, 44957, 44985,
+    45016, 45046, 45077, 45107, 45138, 45169, 45199, 45230, 45260, 45291,
+];
+
+///the expected value of one statistic of the canonical datasets.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TestVector {
+    ///the name of the MPTCalculator method.
+    pub name: &'static str,
+    ///the arguments of the method besides the datasets.
+    pub arguments: &'static str,
+    pub expected: f64,
+    pub tolerance: f64,
+}
+
+///the expected values of the statistics of the canonical datasets, the calculator is
+///MPTCalculator::from(&FUND, &BENCHMARK, &RISKFREE) with monthly frequency.
+pub const TEST_VECTORS: [TestVector; 14] = [
+    TestVector {
+        name: "average",
+        arguments: "",
+        expected: -0.85193861,
+        tolerance: 1e-6,
+    },
+    TestVector {
+        name: "standard_deviation",
+        arguments: "freq=monthly, is_annu=true",
+        expected: 15.9931683,
+        tolerance: 1e-6,
+    },
+    TestVector {
+        name: "skewness",
+        arguments: "",
+        expected: -1.31603805,
+        tolerance: 1e-6,
+    },
+    TestVector {
+        name: "kurtosis",
+        arguments: "",
+        expected: 1.76946199,
+        tolerance: 1e-6,
+    },
+    TestVector {
+        name: "sharpe_ratio",
+        arguments: "freq=monthly, is_annu=true",
+        expected: -0.80424654,
+        tolerance: 1e-6,
+    },
+    TestVector {
+        name: "sortino_ratio",
+        arguments: "freq=monthly, is_annu=true",
+        expected: -0.8669241,
+        tolerance: 1e-6,
+    },
+    TestVector {
+        name: "alpha",
+        arguments: "freq=monthly, is_annu=true",
+        expected: 0.66313379,
+        tolerance: 1e-6,
+    },
+    TestVector {
+        name: "beta",
+        arguments: "",
+        expected: 0.97364248,
+        tolerance: 1e-6,
+    },
+    TestVector {
+        name: "r_squared",
+        arguments: "",
+        expected: 92.59959212,
+        tolerance: 1e-6,
+    },
+    TestVector {
+        name: "tracking_error",
+        arguments: "freq=monthly, is_annu=true",
+        expected: 4.37063436,
+        tolerance: 1e-6,
+    },
+    TestVector {
+        name: "information_ratio_arithmetic",
+        arguments: "freq=monthly, is_annu=true",
+        expected: 0.19228186,
+        tolerance: 1e-6,
+    },
+    TestVector {
+        name: "upside_capture",
+        arguments: "",
+        expected: 98.2149111,
+        tolerance: 1e-6,
+    },
+    TestVector {
+        name: "downside_capture",
+        arguments: "",
+        expected: 95.55831206,
+        tolerance: 1e-6,
+    },
+    TestVector {
+        name: "max_draw_down",
+        arguments: "dates=DATES, freq=monthly",
+        expected: -43.72594749,
+        tolerance: 1e-6,
+    },
+];
+
+///calculate the statistic of the test vector by this lib, it is used to check the test vectors.
+pub fn calculate(vector: &TestVector, result: &mut f64) -> Errors {
+    let mpt = MPTCalculator::from(&FUND, &BENCHMARK, &RISKFREE);
+    let freq = enums::ClFrequency::ClFrequencyMonthly;
+    let mut other = f64::NAN;
+    let (mut peek_date, mut valley_date, mut months, mut recovery_months, mut recovery_date) =
+        (0, 0, 0, 0, 0);
+    *result = f64::NAN;
+    match vector.name {
+        "average" => mpt.average(result),
+        "standard_deviation" => mpt.standard_deviation(freq, true, result),
+        "skewness" => mpt.skewness(result),
+        "kurtosis" => mpt.kurtosis(result),
+        "sharpe_ratio" => mpt.sharpe_ratio(freq, true, result),
+        "sortino_ratio" => mpt.sortino_ratio(freq, true, result),
+        "alpha" => mpt.alpha(freq, true, result),
+        "beta" => mpt.beta(result),
+        "r_squared" => mpt.r_squared(result),
+        "tracking_error" => mpt.tracking_error(freq, true, result),
+        "information_ratio_arithmetic" => mpt.information_ratio_arithmetic(freq, true, result),
+        "upside_capture" => mpt.upside_capture(result, &mut other),
+        "downside_capture" => mpt.downside_capture(result, &mut other),
+        "max_draw_down" => mpt.max_draw_down(
+            &DATES,
+            freq,
+            result,
+            &mut peek_date,
+            &mut valley_date,
+            &mut months,
+            &mut recovery_months,
+            &mut recovery_date,
+        ),
+        _ => Errors::ClErrorCodeInvalidPara,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{calculate, TEST_VECTORS};
+    use crate::{enums::Errors, MPTCalculator};
+
+    #[test]
+    fn should_correct_test_vectors() {
+        for vector in TEST_VECTORS.iter() {
+            let mut res = f64::NAN;
+            let err = calculate(vector, &mut res);
+            assert_eq!(
+                err == Errors::ClErrorCodeNoError
+                    && MPTCalculator::approx_eq(res, vector.expected, vector.tolerance),
+                true,
+                "{}",
+                vector.name
+            );
+        }
+    }
+}
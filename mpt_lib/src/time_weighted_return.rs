@@ -0,0 +1,161 @@
+use crate::{common::is_sorted_array, enums::Errors, MPTCalculator};
+
+impl<'a> MPTCalculator<'a> {
+    ///calculate the modified dietz return of one period, the values are the external cash flows in the period,
+    ///a withdrawal is negative. each cash flow is weighted by the remaining days of the period.
+    ///if the array has NAN/INF values,the result will be NAN
+    ///# Arguments
+    ///dates: the date of cash flows, should be sorted and in the period.
+    ///
+    ///begin_date: the begin date of the period.
+    ///
+    ///end_date: the end date of the period.
+    ///
+    ///begin_value: the market value at the begin date.
+    ///
+    ///end_value: the market value at the end date.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![100.0, -50.0];
+    ///let dates = vec![44206, 44216];
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.modified_dietz(&dates, 44196, 44227, 1000.0, 1200.0, &mut res);
+    ///assert_eq!(
+    ///   err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 14.285714),
+    ///   true
+    ///);
+    ///```
+    pub fn modified_dietz(
+        &self,
+        dates: &[i32],
+        begin_date: i32,
+        end_date: i32,
+        begin_value: f64,
+        end_value: f64,
+        dietz_res: &mut f64,
+    ) -> Errors {
+        if dates.len() != self.values.len() || end_date <= begin_date {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *dietz_res = f64::NAN;
+        if dates.len() > 1 && !is_sorted_array(dates) {
+            return Errors::ClErrorCodeUnsortedByDate;
+        }
+        if dates.iter().any(|x| *x < begin_date || *x > end_date) {
+            return Errors::ClErrorCodeInvalidDate;
+        }
+        if !begin_value.is_finite()
+            || !end_value.is_finite()
+            || self.values.iter().any(|x| !x.is_finite())
+        {
+            return Errors::ClErrorCodeNoError;
+        }
+
+        let period_days = (end_date - begin_date) as f64;
+        let mut sum_flow = 0.0;
+        let mut weighted_flow = 0.0;
+        self.values.iter().zip(dates.iter()).for_each(|v| {
+            sum_flow += v.0;
+            weighted_flow += v.0 * (end_date - v.1) as f64 / period_days;
+        });
+
+        let average_capital = begin_value + weighted_flow;
+        if average_capital != 0.0 {
+            *dietz_res = (end_value - begin_value - sum_flow) / average_capital * 100.0;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+
+    ///calculate the true time weighted return from the sub period valuations, the values are the market values
+    ///valued just before each external cash flow, the first value is the begin market value and the last value is
+    ///the end market value. the return of each sub period is linked geometrically.
+    ///if the array has NAN/INF values,the result will be NAN
+    ///# Arguments
+    ///cash_flows: the external cash flow after each valuation, a withdrawal is negative, the last one is ignored.
+    ///
+    ///sub_period_returns: the return of each sub period.
+    ///
+    ///twr_res: the linked time weighted return.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![1000.0, 1080.0, 1150.0, 1200.0];
+    ///let cash_flows = vec![0.0, 100.0, -50.0, 0.0];
+    ///let mut sub_period_returns = Vec::new();
+    ///let mut res = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.true_time_weighted_return(&cash_flows, &mut sub_period_returns, &mut res);
+    ///assert_eq!(
+    ///   err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 14.822804),
+    ///   true
+    ///);
+    ///```
+    pub fn true_time_weighted_return(
+        &self,
+        cash_flows: &[f64],
+        sub_period_returns: &mut Vec<f64>,
+        twr_res: &mut f64,
+    ) -> Errors {
+        if self.values.len() < 2 || cash_flows.len() != self.values.len() {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *twr_res = f64::NAN;
+        sub_period_returns.clear();
+        sub_period_returns.resize(self.values.len() - 1, f64::NAN);
+
+        for i in 1..self.values.len() {
+            let begin_value = self.values[i - 1] + cash_flows[i - 1];
+            if self.values[i].is_finite() && begin_value.is_finite() && begin_value != 0.0 {
+                sub_period_returns[i - 1] = (self.values[i] / begin_value - 1.0) * 100.0;
+            }
+        }
+
+        Self::total_return_accumulat(sub_period_returns, twr_res);
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{enums::Errors, MPTCalculator};
+
+    #[test]
+    fn should_correct_modified_dietz() {
+        let data = vec![100.0, -50.0];
+        let dates = vec![44206, 44216];
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.modified_dietz(&dates, 44196, 44227, 1000.0, 1200.0, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError && MPTCalculator::is_eq_double(res, 14.285714),
+            true
+        );
+        let err = mpt.modified_dietz(&dates, 44207, 44227, 1000.0, 1200.0, &mut res);
+        assert_eq!(err == Errors::ClErrorCodeInvalidDate, true);
+    }
+
+    #[test]
+    fn should_correct_true_time_weighted_return() {
+        let data = vec![1000.0, 1080.0, 1150.0, 1200.0];
+        let cash_flows = vec![0.0, 100.0, -50.0, 0.0];
+        let mut sub_period_returns = Vec::new();
+        let mut res = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.true_time_weighted_return(&cash_flows, &mut sub_period_returns, &mut res);
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && MPTCalculator::is_eq_double(res, 14.822804)
+                && MPTCalculator::is_eq_double_array(
+                    &sub_period_returns,
+                    &[8.0, -2.542373, 9.090909]
+                ),
+            true
+        );
+    }
+}
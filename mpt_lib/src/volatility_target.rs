@@ -0,0 +1,179 @@
+use crate::{
+    common::{get_annual_multiplier, is_valid_frequency},
+    enums::{self, Errors},
+    MPTCalculator,
+};
+
+///the exponentially weighted standard deviation of the values, the latest value has the largest weight,
+///decay 1.0 is the sample standard deviation.
+fn weighted_standard_deviation(values: &[f64], decay: f64) -> f64 {
+    let mut sum_weight = 0.0;
+    let mut sum_weight_square = 0.0;
+    let mut sum = 0.0;
+    let mut weight = 1.0;
+    let mut points = Vec::with_capacity(values.len());
+    values.iter().rev().for_each(|x| {
+        if x.is_finite() {
+            sum_weight += weight;
+            sum_weight_square += weight * weight;
+            sum += weight * x;
+            points.push((weight, *x));
+        }
+        weight *= decay;
+    });
+    if points.len() < 2 {
+        return f64::NAN;
+    }
+    let mean = sum / sum_weight;
+    let variance = points
+        .iter()
+        .map(|x| x.0 * (x.1 - mean) * (x.1 - mean))
+        .sum::<f64>()
+        / (sum_weight - sum_weight_square / sum_weight);
+    return variance.sqrt();
+}
+
+impl<'a> MPTCalculator<'a> {
+    ///simulate the volatility targeting overlay, the exposure of each period is the target volatility divided by the
+    ///annualized volatility estimated from the previous window periods, capped by the max leverage.
+    ///the first window periods and the periods which have not enough valid history are NAN.
+    ///# Arguments
+    ///target_volatility: the annualized target volatility, in percent.
+    ///
+    ///freq: the frequency of the values.
+    ///
+    ///window: the number of trailing periods used to estimate the volatility, at least 2.
+    ///
+    ///decay: the ewma decay factor in (0, 1], 1.0 is the equal weighted trailing volatility.
+    ///
+    ///max_leverage: the max exposure of the overlay.
+    ///
+    ///managed: the return of the managed series, the same size as the values.
+    ///
+    ///leverages: the exposure of each period, the same size as the values.
+    ///
+    ///turnover: the average absolute change of the exposure of one period.
+    ///# Examples
+    ///```
+    ///use mpt_lib::MPTCalculator;
+    ///use mpt_lib::enums::{self, Errors};
+    ///
+    ///let data = vec![1.0, -2.0, 3.0, 0.5, -1.5, 2.0, -0.5, 1.0];
+    ///let mut managed = [0.0; 8];
+    ///let mut leverages = [0.0; 8];
+    ///let mut turnover = 0.0;
+    ///let mpt = MPTCalculator::from_v(&data);
+    ///let err = mpt.volatility_target(
+    ///    10.0,
+    ///    enums::ClFrequency::ClFrequencyMonthly,
+    ///    3,
+    ///    1.0,
+    ///    1.5,
+    ///    &mut managed,
+    ///    &mut leverages,
+    ///    &mut turnover,
+    ///);
+    ///assert_eq!(
+    ///    err == Errors::ClErrorCodeNoError
+    ///        && MPTCalculator::is_eq_double_array(
+    ///            &managed[3..],
+    ///            &[0.573539, -1.732051, 2.560738, -0.75, 1.5]
+    ///        )
+    ///        && MPTCalculator::is_eq_double(turnover, 0.08823),
+    ///    true
+    ///);
+    ///```
+    pub fn volatility_target(
+        &self,
+        target_volatility: f64,
+        freq: enums::ClFrequency,
+        window: usize,
+        decay: f64,
+        max_leverage: f64,
+        managed: &mut [f64],
+        leverages: &mut [f64],
+        turnover: &mut f64,
+    ) -> Errors {
+        if self.values.len() == 0
+            || !is_valid_frequency(freq)
+            || window < 2
+            || !(target_volatility > 0.0)
+            || !(decay > 0.0 && decay <= 1.0)
+            || !(max_leverage > 0.0)
+            || managed.len() < self.values.len()
+            || leverages.len() < self.values.len()
+        {
+            return Errors::ClErrorCodeInvalidPara;
+        }
+        *turnover = f64::NAN;
+        let multiplier = get_annual_multiplier(freq, false).sqrt();
+
+        for i in 0..self.values.len() {
+            leverages[i] = f64::NAN;
+            managed[i] = f64::NAN;
+            if i < window {
+                continue;
+            }
+            let volatility =
+                weighted_standard_deviation(&self.values[i - window..i], decay) * multiplier;
+            if volatility.is_finite() && volatility > 0.0 {
+                leverages[i] = (target_volatility / volatility).min(max_leverage);
+            } else if volatility == 0.0 {
+                leverages[i] = max_leverage;
+            }
+            if leverages[i].is_finite() && self.values[i].is_finite() {
+                managed[i] = leverages[i] * self.values[i];
+            }
+        }
+
+        let changes: Vec<f64> = (1..self.values.len())
+            .filter(|i| leverages[*i].is_finite() && leverages[*i - 1].is_finite())
+            .map(|i| (leverages[i] - leverages[i - 1]).abs())
+            .collect();
+        if changes.len() > 0 {
+            *turnover = changes.iter().sum::<f64>() / changes.len() as f64;
+        }
+        return Errors::ClErrorCodeNoError;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        enums::{self, Errors},
+        MPTCalculator,
+    };
+
+    #[test]
+    fn should_correct_volatility_target() {
+        let data = vec![1.0, -2.0, 3.0, 0.5, -1.5, 2.0, -0.5, 1.0];
+        let mut managed = [0.0; 8];
+        let mut leverages = [0.0; 8];
+        let mut turnover = 0.0;
+        let mpt = MPTCalculator::from_v(&data);
+        let err = mpt.volatility_target(
+            10.0,
+            enums::ClFrequency::ClFrequencyMonthly,
+            3,
+            0.9,
+            1.5,
+            &mut managed,
+            &mut leverages,
+            &mut turnover,
+        );
+        assert_eq!(
+            err == Errors::ClErrorCodeNoError
+                && leverages[0..3].iter().all(|x| x.is_nan())
+                && MPTCalculator::is_eq_double_array(
+                    &leverages[3..],
+                    &[1.121818, 1.185734, 1.286551, 1.5, 1.5]
+                )
+                && MPTCalculator::is_eq_double_array(
+                    &managed[3..],
+                    &[0.560909, -1.778602, 2.573103, -0.75, 1.5]
+                )
+                && MPTCalculator::is_eq_double(turnover, 0.094546),
+            true
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use mpt_lib::enums::Errors;
+use tokio::{sync::Semaphore, task};
+
+use crate::{standard_pack, StatisticsPackRequest, StatisticsPackResponse};
+
+///run the statistics packs on the blocking threads of tokio with a bounded concurrency, so the async handlers do
+///not block the runtime and a burst of requests does not occupy all the blocking threads. the clones share the
+///same bound.
+///# Examples
+///```
+///use mpt_lib::enums::Errors;
+///use mpt_service::executor::PackExecutor;
+///use mpt_service::StatisticsPackRequest;
+///
+///let runtime = tokio::runtime::Builder::new_multi_thread().build().unwrap();
+///let executor = PackExecutor::new(4);
+///let request = StatisticsPackRequest {
+///    dates: vec![38837, 38868, 38898, 38929, 38960, 38990],
+///    fund: vec![1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577],
+///    benchmark: vec![],
+///    riskfree: vec![],
+///    frequency: -1,
+///};
+///let response = runtime.block_on(executor.compute_pack_async(request));
+///assert_eq!(
+///    response.error_code == Errors::ClErrorCodeNoError as u32 && response.standard_deviation.is_some(),
+///    true
+///);
+///```
+#[derive(Clone, Debug)]
+pub struct PackExecutor {
+    permits: Arc<Semaphore>,
+}
+
+impl PackExecutor {
+    ///the executor which runs at most max_concurrency packs at the same time, it is 1 at least.
+    pub fn new(max_concurrency: usize) -> PackExecutor {
+        PackExecutor {
+            permits: Arc::new(Semaphore::new(max_concurrency.max(1))),
+        }
+    }
+
+    ///the number of the packs which can start now.
+    pub fn available(&self) -> usize {
+        self.permits.available_permits()
+    }
+
+    ///calculate the standard statistics pack of the request on a blocking thread, it waits for a permit if
+    ///max_concurrency packs are running. the error code is ClErrorCodeUnknown if the calculation panics.
+    pub async fn compute_pack_async(
+        &self,
+        request: StatisticsPackRequest,
+    ) -> StatisticsPackResponse {
+        let permit = self.permits.clone().acquire_owned().await;
+        let result = task::spawn_blocking(move || {
+            let _permit = permit;
+            standard_pack(&request)
+        })
+        .await;
+        return result.unwrap_or_else(|_| StatisticsPackResponse {
+            error_code: Errors::ClErrorCodeUnknown as u32,
+            ..Default::default()
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PackExecutor;
+    use crate::{standard_pack, StatisticsPackRequest};
+    use mpt_lib::enums::{ClFrequency, Errors};
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn should_correct_compute_pack_async() {
+        let request = StatisticsPackRequest {
+            dates: vec![
+                38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082, 39113, 39141, 39172,
+            ],
+            fund: vec![
+                1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016, 1.40278,
+                1.51232, -1.95588, 1.1185,
+            ],
+            benchmark: vec![
+                1.22072, -2.0668, 0.20588, 0.91563, 1.76766, 2.21429, 2.43456, 1.99825, 0.89481,
+                1.59564, -1.86793, 0.41477,
+            ],
+            riskfree: vec![0.3; 12],
+            frequency: ClFrequency::ClFrequencyMonthly as i32,
+        };
+        let executor = PackExecutor::new(2);
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let executor = executor.clone();
+                let request = request.clone();
+                tokio::spawn(async move { executor.compute_pack_async(request).await })
+            })
+            .collect();
+        let expected = standard_pack(&request);
+        for handle in handles {
+            assert_eq!(handle.await.unwrap(), expected);
+        }
+        assert_eq!(
+            expected.error_code == Errors::ClErrorCodeNoError as u32 && executor.available() == 2,
+            true
+        );
+        assert_eq!(PackExecutor::new(0).available(), 1);
+    }
+}
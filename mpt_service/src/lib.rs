@@ -0,0 +1,238 @@
+//! The request and response models of the statistics service, so all the services which compute the statistics
+//! packs over the wire share the same payload schema.
+//!
+//! The models derive both serde for the REST/JSON payload and prost for the gRPC/protobuf payload, the protobuf
+//! schema is
+//!
+//! ```text
+//! message StatisticsPackRequest {
+//!   repeated int32 dates = 1;
+//!   repeated double fund = 2;
+//!   repeated double benchmark = 3;
+//!   repeated double riskfree = 4;
+//!   int32 frequency = 5;
+//! }
+//!
+//! message StatisticsPackResponse {
+//!   uint32 error_code = 1;
+//!   int32 frequency = 2;
+//!   optional double cumulative_return = 3;
+//!   optional double annualized_return = 4;
+//!   optional double standard_deviation = 5;
+//!   optional double sharpe_ratio = 6;
+//!   optional double sortino_ratio = 7;
+//!   optional double alpha = 8;
+//!   optional double beta = 9;
+//!   optional double r_squared = 10;
+//!   optional double upside_capture = 11;
+//!   optional double downside_capture = 12;
+//!   optional double max_draw_down = 13;
+//!   int32 max_draw_down_peek_date = 14;
+//!   int32 max_draw_down_valley_date = 15;
+//! }
+//! ```
+//!
+//! the transport is left to the service, it only needs to decode the request, call standard_pack and encode the
+//! response. with the async feature the PackExecutor runs standard_pack on the blocking threads of tokio.
+use mpt_lib::{
+    date_util,
+    enums::{ClFrequency, Errors},
+    report::{self, StandardPack},
+    MPTCalculator,
+};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "async")]
+pub mod executor;
+
+///the request of the standard statistics pack, the returns are in percent.
+///the benchmark and the risk free can be empty, the frequency is the value of ClFrequency, -1 means the frequency is
+///detected from the dates.
+#[derive(Clone, PartialEq, prost::Message, Serialize, Deserialize)]
+pub struct StatisticsPackRequest {
+    #[prost(int32, repeated, tag = "1")]
+    pub dates: Vec<i32>,
+    #[prost(double, repeated, tag = "2")]
+    pub fund: Vec<f64>,
+    #[prost(double, repeated, tag = "3")]
+    #[serde(default)]
+    pub benchmark: Vec<f64>,
+    #[prost(double, repeated, tag = "4")]
+    #[serde(default)]
+    pub riskfree: Vec<f64>,
+    #[prost(int32, tag = "5")]
+    pub frequency: i32,
+}
+
+///the response of the standard statistics pack, the error code is the value of Errors and the frequency is the
+///frequency used by the calculation. the statistics which can not be calculated are empty.
+#[derive(Clone, PartialEq, prost::Message, Serialize, Deserialize)]
+pub struct StatisticsPackResponse {
+    #[prost(uint32, tag = "1")]
+    pub error_code: u32,
+    #[prost(int32, tag = "2")]
+    pub frequency: i32,
+    #[prost(double, optional, tag = "3")]
+    pub cumulative_return: Option<f64>,
+    #[prost(double, optional, tag = "4")]
+    pub annualized_return: Option<f64>,
+    #[prost(double, optional, tag = "5")]
+    pub standard_deviation: Option<f64>,
+    #[prost(double, optional, tag = "6")]
+    pub sharpe_ratio: Option<f64>,
+    #[prost(double, optional, tag = "7")]
+    pub sortino_ratio: Option<f64>,
+    #[prost(double, optional, tag = "8")]
+    pub alpha: Option<f64>,
+    #[prost(double, optional, tag = "9")]
+    pub beta: Option<f64>,
+    #[prost(double, optional, tag = "10")]
+    pub r_squared: Option<f64>,
+    #[prost(double, optional, tag = "11")]
+    pub upside_capture: Option<f64>,
+    #[prost(double, optional, tag = "12")]
+    pub downside_capture: Option<f64>,
+    #[prost(double, optional, tag = "13")]
+    pub max_draw_down: Option<f64>,
+    #[prost(int32, tag = "14")]
+    pub max_draw_down_peek_date: i32,
+    #[prost(int32, tag = "15")]
+    pub max_draw_down_valley_date: i32,
+}
+
+impl StatisticsPackResponse {
+    fn from_pack(err: Errors, freq: ClFrequency, pack: &StandardPack) -> StatisticsPackResponse {
+        let value = |v: f64| if v.is_finite() { Some(v) } else { None };
+        StatisticsPackResponse {
+            error_code: err as u32,
+            frequency: freq as i32,
+            cumulative_return: value(pack.cumulative_return),
+            annualized_return: value(pack.annualized_return),
+            standard_deviation: value(pack.standard_deviation),
+            sharpe_ratio: value(pack.sharpe_ratio),
+            sortino_ratio: value(pack.sortino_ratio),
+            alpha: value(pack.alpha),
+            beta: value(pack.beta),
+            r_squared: value(pack.r_squared),
+            upside_capture: value(pack.upside_capture),
+            downside_capture: value(pack.downside_capture),
+            max_draw_down: value(pack.max_draw_down),
+            max_draw_down_peek_date: pack.max_draw_down_peek_date,
+            max_draw_down_valley_date: pack.max_draw_down_valley_date,
+        }
+    }
+}
+
+///calculate the standard statistics pack of the request, it is the same as mpt_lib::report::standard_pack.
+///# Examples
+///```
+///use mpt_lib::enums::Errors;
+///use mpt_service::{standard_pack, StatisticsPackRequest};
+///
+///let request = StatisticsPackRequest {
+///    dates: vec![38837, 38868, 38898, 38929, 38960, 38990],
+///    fund: vec![1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577],
+///    benchmark: vec![1.22072, -2.0668, 0.20588, 0.91563, 1.76766, 2.21429],
+///    riskfree: vec![],
+///    frequency: -1,
+///};
+///let response = standard_pack(&request);
+///assert_eq!(
+///    response.error_code == Errors::ClErrorCodeNoError as u32
+///        && response.frequency == 2
+///        && response.beta.is_some()
+///        && response.sharpe_ratio.is_none(),
+///    true
+///);
+///```
+pub fn standard_pack(request: &StatisticsPackRequest) -> StatisticsPackResponse {
+    let mut pack = StandardPack::new();
+    let freq = match i16::try_from(request.frequency)
+        .ok()
+        .and_then(|x| ClFrequency::try_from(x).ok())
+    {
+        Some(ClFrequency::ClFrequencyUnknown) => date_util::infer_frequency(&request.dates),
+        Some(freq) => freq,
+        None => ClFrequency::ClFrequencyUnknown,
+    };
+    if freq == ClFrequency::ClFrequencyUnknown
+        || request.benchmark.len() != 0 && request.benchmark.len() != request.fund.len()
+        || request.riskfree.len() != 0 && request.riskfree.len() != request.fund.len()
+    {
+        return StatisticsPackResponse::from_pack(Errors::ClErrorCodeInvalidPara, freq, &pack);
+    }
+    let calc = MPTCalculator::from(&request.fund, &request.benchmark, &request.riskfree);
+    let err = report::standard_pack(&calc, &request.dates, freq, &mut pack);
+    return StatisticsPackResponse::from_pack(err, freq, &pack);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{standard_pack, StatisticsPackRequest, StatisticsPackResponse};
+    use mpt_lib::{
+        enums::{ClFrequency, Errors},
+        report::{self, StandardPack},
+        MPTCalculator,
+    };
+    use prost::Message;
+
+    #[test]
+    fn should_correct_standard_pack() {
+        let request = StatisticsPackRequest {
+            dates: vec![
+                38837, 38868, 38898, 38929, 38960, 38990, 39021, 39051, 39082, 39113, 39141, 39172,
+            ],
+            fund: vec![
+                1.34278, -2.87814, 0.13557, 0.61685, 2.37931, 2.577, 3.25861, 1.9016, 1.40278,
+                1.51232, -1.95588, 1.1185,
+            ],
+            benchmark: vec![
+                1.22072, -2.0668, 0.20588, 0.91563, 1.76766, 2.21429, 2.43456, 1.99825, 0.89481,
+                1.59564, -1.86793, 0.41477,
+            ],
+            riskfree: vec![0.3; 12],
+            frequency: ClFrequency::ClFrequencyMonthly as i32,
+        };
+        let response = standard_pack(&request);
+        let mut pack = StandardPack::new();
+        let calc = MPTCalculator::from(&request.fund, &request.benchmark, &request.riskfree);
+        report::standard_pack(
+            &calc,
+            &request.dates,
+            ClFrequency::ClFrequencyMonthly,
+            &mut pack,
+        );
+        assert_eq!(
+            response.error_code == Errors::ClErrorCodeNoError as u32
+                && response.sharpe_ratio == Some(pack.sharpe_ratio)
+                && response.downside_capture == Some(pack.downside_capture)
+                && response.max_draw_down_valley_date == 38868,
+            true
+        );
+
+        let decoded = StatisticsPackResponse::decode(response.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded, response);
+
+        let json = r#"{"dates":[38837,38868,38898],"fund":[1.0,-2.0,3.0],"frequency":2}"#;
+        let request: StatisticsPackRequest = serde_json::from_str(json).unwrap();
+        let response = standard_pack(&request);
+        let json = serde_json::to_string(&response).unwrap();
+        let decoded: StatisticsPackResponse = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            decoded == response
+                && response.beta.is_none()
+                && json.contains("\"beta\":null")
+                && response.standard_deviation.is_some(),
+            true
+        );
+
+        let request = StatisticsPackRequest {
+            frequency: 100,
+            ..request
+        };
+        assert_eq!(
+            standard_pack(&request).error_code,
+            Errors::ClErrorCodeInvalidPara as u32
+        );
+    }
+}